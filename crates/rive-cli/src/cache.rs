@@ -0,0 +1,159 @@
+//! Persistent build cache.
+//!
+//! Generated Rust is compiled in a Cargo project under `target/build` that is
+//! kept between builds, so Cargo can reuse its own artifacts. A fingerprint of
+//! the Rive sources and of the generated crate lets unchanged projects skip
+//! the Rive pipeline and rustc entirely.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Name of the fingerprint file inside the cache directory.
+const FINGERPRINT_FILE: &str = "fingerprint.toml";
+
+/// Hashes recorded by the last successful stages of a build.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Fingerprint {
+    /// Hash of the Rive sources and the compiler that generated `src/main.rs`
+    source: Option<String>,
+    /// Hash of the generated crate for each profile it was compiled with
+    #[serde(default)]
+    profiles: BTreeMap<String, String>,
+}
+
+/// The generated Cargo project of a Rive project and its fingerprint.
+pub struct BuildCache {
+    dir: PathBuf,
+    fingerprint: Fingerprint,
+}
+
+impl BuildCache {
+    /// Opens the cache under the given target directory.
+    ///
+    /// A missing or unreadable fingerprint is treated as an empty cache.
+    pub fn open(target_dir: &Path) -> Self {
+        let dir = target_dir.join("build");
+        let fingerprint = fs::read_to_string(dir.join(FINGERPRINT_FILE))
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { dir, fingerprint }
+    }
+
+    /// Returns the directory of the generated Cargo project.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the generated Rust code if it was generated from sources with
+    /// the given hash.
+    pub fn cached_rust(&self, source_hash: &str) -> Option<String> {
+        if self.fingerprint.source.as_deref() != Some(source_hash) {
+            return None;
+        }
+        fs::read_to_string(self.dir.join("src/main.rs")).ok()
+    }
+
+    /// Returns true if the crate with the given hash was already compiled
+    /// with `profile`.
+    pub fn is_fresh(&self, profile: &str, crate_hash: &str) -> bool {
+        self.fingerprint.profiles.get(profile).map(String::as_str) == Some(crate_hash)
+    }
+
+    /// Writes the generated Cargo project.
+    ///
+    /// Files whose contents are unchanged are left untouched, so Cargo's own
+    /// freshness checks still see them as up to date.
+    ///
+    /// # Errors
+    /// Returns an error if a file cannot be written.
+    pub fn write_crate(&self, cargo_toml: &str, rust_code: &str) -> Result<()> {
+        let src_dir = self.dir.join("src");
+        fs::create_dir_all(&src_dir)
+            .with_context(|| format!("Failed to create {}", src_dir.display()))?;
+
+        write_if_changed(&self.dir.join("Cargo.toml"), cargo_toml)?;
+        write_if_changed(&src_dir.join("main.rs"), rust_code)
+    }
+
+    /// Records the hash of the sources the crate was generated from.
+    pub fn record_source(&mut self, source_hash: String) {
+        self.fingerprint.source = Some(source_hash);
+    }
+
+    /// Records the hash of the crate compiled with `profile`.
+    pub fn record_profile(&mut self, profile: &str, crate_hash: String) {
+        self.fingerprint
+            .profiles
+            .insert(profile.to_string(), crate_hash);
+    }
+
+    /// Saves the fingerprint.
+    ///
+    /// # Errors
+    /// Returns an error if the fingerprint cannot be written.
+    pub fn save(&self) -> Result<()> {
+        let contents = toml::to_string_pretty(&self.fingerprint)
+            .with_context(|| "Failed to serialize build fingerprint")?;
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        fs::write(self.dir.join(FINGERPRINT_FILE), contents)
+            .with_context(|| "Failed to save build fingerprint")
+    }
+}
+
+/// Hashes a sequence of byte strings with 64-bit FNV-1a.
+///
+/// The hash is stable across platforms and Rust versions, unlike
+/// `DefaultHasher`, so fingerprints stay valid between compiler toolchains.
+pub fn content_hash(parts: &[&[u8]]) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    for part in parts {
+        // Hash the length first so ("ab", "c") and ("a", "bc") differ
+        for byte in (part.len() as u64).to_le_bytes().iter().chain(*part) {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    format!("{hash:016x}")
+}
+
+/// Identifies the running compiler, so rebuilding or upgrading `rive`
+/// invalidates code generated by the previous one.
+pub fn compiler_stamp() -> String {
+    let modified = std::env::current_exe()
+        .and_then(fs::metadata)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    format!("{}-{modified}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Returns the directory standalone scripts are built in.
+///
+/// This is `rive/scripts` under `$XDG_CACHE_HOME`, falling back to
+/// `~/.cache` and then to the system temporary directory.
+pub fn script_cache_root() -> PathBuf {
+    let cache_home = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir);
+    cache_home.join("rive").join("scripts")
+}
+
+/// Writes `contents` to `path` unless the file already holds exactly that.
+fn write_if_changed(path: &Path, contents: &str) -> Result<()> {
+    if fs::read(path).is_ok_and(|existing| existing == contents.as_bytes()) {
+        return Ok(());
+    }
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
@@ -3,14 +3,45 @@
 use crate::compiler::Compiler;
 use crate::utils::find_project;
 use anyhow::Result;
+use clap::Args;
+
+/// Options shared by the commands that build the project.
+#[derive(Args, Debug)]
+pub struct BuildArgs {
+    /// Build with the `release` profile
+    #[arg(long)]
+    pub release: bool,
+
+    /// Build with the named profile from rive.toml
+    #[arg(long, value_name = "NAME", conflicts_with = "release")]
+    pub profile: Option<String>,
+
+    /// Report which build stages ran and how long each took
+    #[arg(long)]
+    pub timings: bool,
+}
+
+impl BuildArgs {
+    /// Returns the name of the profile to build with.
+    pub fn profile_name(&self) -> &str {
+        match &self.profile {
+            Some(profile) => profile,
+            None if self.release => "release",
+            None => "dev",
+        }
+    }
+}
 
 /// Executes the `build` command to compile the Rive project.
 ///
 /// # Errors
 /// Returns an error if the project cannot be built.
-pub fn execute() -> Result<()> {
+pub fn execute(args: &BuildArgs) -> Result<()> {
     let (_config, project_root) = find_project()?;
     let compiler = Compiler::new(project_root)?;
-    compiler.build(false)?;
+    let build = compiler.build(args.profile_name())?;
+    if args.timings {
+        build.timings.print();
+    }
     Ok(())
 }
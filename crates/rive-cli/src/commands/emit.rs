@@ -0,0 +1,47 @@
+//! Implementation of the `rive emit` command.
+
+use crate::emit::{Stage, emit};
+use crate::pipeline::SourceFile;
+use crate::utils::find_project;
+use anyhow::{Context, Result};
+use clap::Args;
+use std::fs;
+use std::path::PathBuf;
+
+/// Options of the `emit` command.
+#[derive(Args, Debug)]
+pub struct EmitArgs {
+    /// Representation to print
+    #[arg(long, value_enum)]
+    pub stage: Stage,
+
+    /// Print JSON for tools instead of text
+    #[arg(long)]
+    pub json: bool,
+
+    /// Standalone script to read instead of the current project
+    #[arg(value_name = "SCRIPT")]
+    pub script: Option<PathBuf>,
+}
+
+/// Executes the `emit` command to print a stage of the pipeline.
+///
+/// # Errors
+/// Returns an error if the source cannot be read or a stage fails.
+pub fn execute(args: &EmitArgs) -> Result<()> {
+    let (source_path, name) = match &args.script {
+        Some(script) => (script.clone(), script.display().to_string()),
+        None => (
+            find_project()?.1.join("src/main.rive"),
+            "main.rive".to_string(),
+        ),
+    };
+    let text = fs::read_to_string(&source_path)
+        .with_context(|| format!("Failed to read {}", source_path.display()))?;
+
+    print!(
+        "{}",
+        emit(&SourceFile { name, text }, args.stage, args.json)?
+    );
+    Ok(())
+}
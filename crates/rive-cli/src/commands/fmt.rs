@@ -0,0 +1,50 @@
+//! Implementation of the `rive fmt` command.
+
+use crate::format::{format_file, project_files};
+use crate::utils::{find_project, print_status};
+use anyhow::Result;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Options of the `fmt` command.
+#[derive(Args, Debug)]
+pub struct FmtArgs {
+    /// Only report files that are not formatted, failing if there are any
+    #[arg(long)]
+    pub check: bool,
+
+    /// Files to format instead of the current project
+    #[arg(value_name = "FILES")]
+    pub files: Vec<PathBuf>,
+}
+
+/// Executes the `fmt` command to format Rive source files.
+///
+/// # Errors
+/// Returns an error if a file cannot be formatted, or with `--check`, if a
+/// file is not formatted.
+pub fn execute(args: &FmtArgs) -> Result<()> {
+    let files = if args.files.is_empty() {
+        project_files(&find_project()?.1)?
+    } else {
+        args.files.clone()
+    };
+
+    let mut unformatted = 0;
+    for file in &files {
+        if !format_file(file, args.check)? {
+            continue;
+        }
+        unformatted += 1;
+        if args.check {
+            println!("Diff in {}", file.display());
+        } else {
+            print_status("Formatted", &file.display().to_string());
+        }
+    }
+
+    if args.check && unformatted > 0 {
+        anyhow::bail!("{unformatted} file(s) are not formatted; run `rive fmt` to format them");
+    }
+    Ok(())
+}
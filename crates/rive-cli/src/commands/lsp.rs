@@ -0,0 +1,18 @@
+//! Implementation of the `rive lsp` command.
+
+use anyhow::Result;
+use std::io;
+
+/// Executes the `lsp` command, serving the language server protocol over
+/// stdin and stdout until the editor disconnects.
+///
+/// # Errors
+/// Returns an error if the streams fail, or if the editor exits without
+/// shutting the server down first.
+pub fn execute() -> Result<()> {
+    let clean_exit = rive_lsp::serve(io::stdin().lock(), io::stdout().lock())?;
+    if !clean_exit {
+        anyhow::bail!("The editor exited without shutting down the language server");
+    }
+    Ok(())
+}
@@ -3,6 +3,11 @@
 pub mod build;
 pub mod check;
 pub mod clean;
+pub mod emit;
+pub mod fmt;
 pub mod init;
+pub mod lsp;
 pub mod new;
+pub mod repl;
 pub mod run;
+pub mod test;
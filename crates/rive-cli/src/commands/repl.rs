@@ -0,0 +1,102 @@
+//! Implementation of the `rive repl` command.
+
+use crate::commands::run::INTERPRETER_STACK_SIZE;
+use crate::repl::{Session, is_complete};
+use anyhow::{Context, Result};
+use std::io::{self, BufRead, Write};
+use std::thread;
+
+const HELP: &str = "\
+Enter statements, expressions or declarations. Input continues on the next
+line while a bracket is left open.
+
+Commands:
+  :type <expr>   Show the type of an expression
+  :rir <expr>    Show the RIR an expression lowers to
+  :help          Show this message
+  :quit          Leave the REPL";
+
+/// Executes the `repl` command, reading inputs from stdin until it ends or
+/// `:quit` is entered.
+///
+/// # Errors
+/// Returns an error if stdin or stdout fail.
+pub fn execute() -> Result<()> {
+    println!(
+        "Rive {} REPL. Enter :help for help.",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let status = thread::Builder::new()
+        .stack_size(INTERPRETER_STACK_SIZE)
+        .spawn(|| read_eval_print(&mut Session::new()))
+        .context("Failed to start the interpreter")?
+        .join()
+        .map_err(|_| anyhow::anyhow!("The interpreter crashed"))??;
+
+    if let Some(status) = status {
+        std::process::exit(status);
+    }
+    Ok(())
+}
+
+/// Reads and evaluates inputs until stdin ends, `:quit` is entered or an
+/// input calls `exit`, returning the status passed to `exit`.
+fn read_eval_print(session: &mut Session) -> Result<Option<i32>> {
+    let mut stdin = io::stdin().lock();
+    let mut input = String::new();
+
+    loop {
+        print!("{}", if input.is_empty() { "> " } else { ". " });
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if stdin.read_line(&mut line)? == 0 {
+            println!();
+            return Ok(None);
+        }
+        input.push_str(&line);
+        if !is_complete(&input) {
+            continue;
+        }
+
+        let entry = std::mem::take(&mut input);
+        let entry = entry.trim();
+        let result = match entry.strip_prefix(':') {
+            Some(command) => {
+                let (name, argument) = command.split_once(' ').unwrap_or((command, ""));
+                match name {
+                    "type" | "t" => session
+                        .type_of(argument)
+                        .map(|type_name| println!("{type_name}")),
+                    "rir" => session.rir_of(argument).map(|rir| println!("{rir}")),
+                    "help" | "h" => {
+                        println!("{HELP}");
+                        Ok(())
+                    }
+                    "quit" | "q" => return Ok(None),
+                    _ => Err(anyhow::anyhow!(
+                        "Unknown command ':{name}'; enter :help for the list of commands"
+                    )),
+                }
+            }
+            None => match session.eval(entry) {
+                Ok(evaluation) => {
+                    print!("{}", evaluation.output);
+                    if let Some(value) = evaluation.value {
+                        println!("{value}");
+                    }
+                    if let Some(status) = evaluation.exit_status {
+                        return Ok(Some(status));
+                    }
+                    Ok(())
+                }
+                Err(error) => Err(error),
+            },
+        };
+
+        if let Err(error) = result {
+            eprintln!("{error}");
+        }
+    }
+}
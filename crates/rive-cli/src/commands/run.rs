@@ -1,30 +1,161 @@
 //! Implementation of the `rive run` command.
 
+use crate::cache::script_cache_root;
+use crate::commands::build::BuildArgs;
 use crate::compiler::Compiler;
 use crate::utils::{find_project, print_status};
 use anyhow::{Context, Result};
-use std::process::Command;
+use clap::Args;
+use rive_interpreter::{Interpreter, RuntimeError};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use std::thread;
 
-/// Executes the `run` command to build and run the Rive project.
+/// Stack size of the thread the interpreter runs on. Each call of the
+/// interpreted program takes several frames of the interpreter's own, so
+/// deep recursion needs more than the default main thread stack.
+pub(crate) const INTERPRETER_STACK_SIZE: usize = 256 * 1024 * 1024;
+
+/// Options of the `run` command.
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    /// Standalone script to run instead of the current project
+    #[arg(value_name = "SCRIPT")]
+    pub script: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub build: BuildArgs,
+
+    /// Run the program with the interpreter instead of compiling it
+    #[arg(long)]
+    pub interpret: bool,
+
+    /// Arguments passed to the program, after `--`
+    #[arg(last = true, value_name = "ARGS")]
+    pub args: Vec<String>,
+}
+
+/// Executes the `run` command to build and run the Rive project, or a
+/// standalone script if one is given.
+///
+/// The program's exit status becomes the exit status of `rive`.
 ///
 /// # Errors
-/// Returns an error if the project cannot be built or run.
-pub fn execute() -> Result<()> {
-    let (_config, project_root) = find_project()?;
-    let compiler = Compiler::new(project_root)?;
-    let (binary_path, _duration) = compiler.build(false)?;
+/// Returns an error if the program cannot be built or run.
+pub fn execute(args: &RunArgs) -> Result<()> {
+    // Scripts run in the caller's directory, projects in their root
+    let (compiler, run_dir) = match &args.script {
+        Some(script) => (Compiler::script(script, &script_cache_root())?, None),
+        None => {
+            let (_config, project_root) = find_project()?;
+            let compiler = Compiler::new(project_root)?;
+            let run_dir = compiler.project_root().to_path_buf();
+            (compiler, Some(run_dir))
+        }
+    };
+
+    if args.interpret {
+        return interpret(&compiler, args.build.profile_name(), &args.args);
+    }
+
+    let build = compiler.build(args.build.profile_name())?;
+    if args.build.timings {
+        build.timings.print();
+    }
 
-    print_status("Running", &binary_path.display().to_string());
+    print_status("Running", &build.binary.display().to_string());
     println!();
 
-    let status = Command::new(&binary_path)
-        .current_dir(compiler.project_root())
+    run_binary(&build.binary, &args.args, run_dir.as_deref())
+}
+
+/// Runs a script invoked as `rive script.rive [ARGS]...`, e.g. through a
+/// `#!/usr/bin/env rive` line.
+///
+/// Only the program's own output is printed, so scripts can be used in
+/// pipelines.
+///
+/// # Errors
+/// Returns an error if the script cannot be built or run.
+pub fn execute_script(script: &Path, args: &[String]) -> Result<()> {
+    let compiler = Compiler::script(script, &script_cache_root())?.quiet(true);
+    let build = compiler.build("dev")?;
+    run_binary(&build.binary, args, None)
+}
+
+/// Runs the program with the RIR interpreter, exiting with its status if it
+/// fails.
+///
+/// Panics are reported like the compiled program reports them, with exit
+/// status 101. Integer overflow panics unless the profile turns overflow
+/// checks off.
+fn interpret(compiler: &Compiler, profile: &str, args: &[String]) -> Result<()> {
+    let overflow_checks = compiler.profile(profile)?.overflow_checks.unwrap_or(true);
+    let module = compiler.lower()?;
+    print_status("Running", "interpreter");
+    println!();
+
+    let args = args.to_vec();
+    let source_name = compiler.source_name();
+    let result = thread::Builder::new()
+        .stack_size(INTERPRETER_STACK_SIZE)
+        .spawn(move || {
+            Interpreter::new(&module, &mut io::stdout().lock())
+                .with_args(args)
+                .with_source_name(source_name)
+                .with_overflow_checks(overflow_checks)
+                .run()
+        })
+        .context("Failed to start the interpreter")?
+        .join()
+        .map_err(|_| anyhow::anyhow!("The interpreter crashed"))?;
+
+    match result {
+        Ok(0) => Ok(()),
+        Ok(status) => std::process::exit(status),
+        Err(RuntimeError::Panic(message)) => {
+            eprintln!("error: {message}");
+            std::process::exit(101)
+        }
+        Err(error @ RuntimeError::Failed(_)) => {
+            eprintln!("{error}");
+            std::process::exit(1)
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Runs a built program, exiting with its status if it fails.
+pub fn run_binary(binary_path: &Path, args: &[String], run_dir: Option<&Path>) -> Result<()> {
+    let mut command = Command::new(binary_path);
+    command.args(args);
+    if let Some(run_dir) = run_dir {
+        command.current_dir(run_dir);
+    }
+
+    let status = command
         .status()
         .with_context(|| format!("Failed to execute {}", binary_path.display()))?;
 
     if !status.success() {
-        anyhow::bail!("Program exited with status: {status}");
+        std::process::exit(exit_code(status));
     }
 
     Ok(())
 }
+
+/// Returns the exit code to report for a program's status.
+///
+/// A program killed by a signal has no exit code; like a shell, this reports
+/// it as 128 plus the signal number.
+fn exit_code(status: ExitStatus) -> i32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
+    }
+    status.code().unwrap_or(1)
+}
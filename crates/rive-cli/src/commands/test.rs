@@ -0,0 +1,38 @@
+//! Implementation of the `rive test` command.
+
+use crate::commands::run::run_binary;
+use crate::compiler::Compiler;
+use crate::utils::{find_project, print_status};
+use anyhow::Result;
+use clap::Args;
+
+/// Options of the `test` command.
+#[derive(Args, Debug)]
+pub struct TestArgs {
+    /// Only run tests whose name contains this text
+    pub filter: Option<String>,
+
+    /// Report which build stages ran and how long each took
+    #[arg(long)]
+    pub timings: bool,
+}
+
+/// Executes the `test` command to build and run the project's tests.
+///
+/// Exits with a non-zero status if any test fails.
+///
+/// # Errors
+/// Returns an error if the tests cannot be built or run.
+pub fn execute(args: &TestArgs) -> Result<()> {
+    let (_config, project_root) = find_project()?;
+    let compiler = Compiler::new(project_root)?;
+    let build = compiler.build_tests()?;
+    if args.timings {
+        build.timings.print();
+    }
+
+    print_status("Running", &build.binary.display().to_string());
+
+    let filter = args.filter.iter().cloned().collect::<Vec<_>>();
+    run_binary(&build.binary, &filter, Some(compiler.project_root()))
+}
@@ -2,21 +2,37 @@
 //!
 //! Pipeline: Source → Lexer → Parser → AST → Semantic → RIR → CodeGen → Rust
 
-use crate::pipeline;
+use crate::cache::{BuildCache, compiler_stamp, content_hash};
+use crate::pipeline::{self, SourceFile};
+use crate::timings::Timings;
 use crate::utils::{binary_name, print_project_status};
 use anyhow::{Context, Result};
-use rive_utils::Config;
+use rive_ir::RirModule;
+use rive_utils::{Config, Lto, OptLevel, Profile};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::{Duration, Instant};
-use tempfile::TempDir;
 use which::which;
 
+/// Result of a successful build.
+pub struct Build {
+    /// Path of the executable in the project's target directory
+    pub binary: PathBuf,
+    /// Which stages ran and how long each one took
+    pub timings: Timings,
+}
+
 /// Compiler for Rive programs.
 pub struct Compiler {
     project_root: PathBuf,
     config: Config,
+    /// Rive source file to compile
+    source_path: PathBuf,
+    /// Directory build output is written to
+    target_dir: PathBuf,
+    /// Whether to suppress status messages
+    quiet: bool,
 }
 
 impl Compiler {
@@ -27,30 +43,172 @@ impl Compiler {
     pub fn new(project_root: PathBuf) -> Result<Self> {
         let config = Config::load(project_root.join("rive.toml"))?;
         Ok(Self {
+            source_path: project_root.join("src/main.rive"),
+            target_dir: project_root.join("target"),
             project_root,
             config,
+            quiet: false,
         })
     }
 
-    /// Compiles the Rive project to a binary executable.
+    /// Creates a compiler for a standalone script outside of any project.
+    ///
+    /// The script is built with a default configuration named after the
+    /// file. Its build output goes to a directory under `cache_root` keyed by
+    /// the script's content hash, so rerunning an unchanged script reuses the
+    /// previous build.
     ///
     /// # Errors
-    /// Returns an error if compilation fails at any stage.
-    pub fn build(&self, release: bool) -> Result<(PathBuf, Duration)> {
-        let start = Instant::now();
+    /// Returns an error if the script cannot be read.
+    pub fn script(script_path: &Path, cache_root: &Path) -> Result<Self> {
+        let source = fs::read(script_path)
+            .with_context(|| format!("Failed to read {}", script_path.display()))?;
+        let project_root = script_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
 
-        print_project_status("Compiling", &self.config, &self.project_root);
+        Ok(Self {
+            project_root,
+            config: Config::new(script_package_name(script_path)),
+            source_path: script_path.to_path_buf(),
+            target_dir: cache_root.join(content_hash(&[&source])),
+            quiet: false,
+        })
+    }
+
+    /// Suppresses the status messages printed while compiling.
+    #[must_use]
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
 
+    /// Compiles the Rive project to a binary executable with the named profile.
+    ///
+    /// Stages whose inputs are unchanged since the last build are skipped:
+    /// the Rive pipeline when the sources are unchanged, and rustc when the
+    /// generated crate was already compiled with the same profile.
+    ///
+    /// # Errors
+    /// Returns an error if the profile is not defined or compilation fails at
+    /// any stage.
+    pub fn build(&self, profile: &str) -> Result<Build> {
+        self.print_status("Compiling");
         let source = self.read_main_source()?;
-        let rust_code = pipeline::build_pipeline(&source)?;
+        self.build_cached(
+            &self.target_dir(),
+            profile,
+            &[source.name.as_bytes(), source.text.as_bytes()],
+            |timings| {
+                let rust_code = pipeline::build_pipeline(&source, timings)?;
+                self.save_generated_code(&rust_code)?;
+                Ok(rust_code)
+            },
+        )
+    }
+
+    /// Compiles the project's `@test` functions into a test harness.
+    ///
+    /// Tests may be written in `src/main.rive` and in any `.rive` file of the
+    /// `tests` directory; all files are compiled together as one program.
+    /// The harness is built with the `dev` profile under `target/test`.
+    ///
+    /// # Errors
+    /// Returns an error if a test file cannot be read or compilation fails.
+    pub fn build_tests(&self) -> Result<Build> {
+        self.print_status("Compiling");
+        let files = self.test_sources()?;
+        let texts = files
+            .iter()
+            .flat_map(|file| [file.name.as_bytes(), file.text.as_bytes()])
+            .collect::<Vec<_>>();
+        self.build_cached(&self.target_dir().join("test"), "dev", &texts, |timings| {
+            pipeline::test_pipeline(&files, timings)
+        })
+    }
 
-        self.save_generated_code(&rust_code)?;
-        let binary_path = self.compile_to_binary(&rust_code, release)?;
+    /// Returns the settings of the named profile, with unset fields filled in.
+    ///
+    /// # Errors
+    /// Returns an error if the profile is not defined or is invalid.
+    pub fn profile(&self, name: &str) -> Result<Profile> {
+        self.config.resolve_profile(name)
+    }
+
+    /// Checks the Rive project and lowers it to RIR, for running it with the
+    /// interpreter instead of compiling it.
+    ///
+    /// # Errors
+    /// Returns an error if the source cannot be read or fails to compile.
+    pub fn lower(&self) -> Result<RirModule> {
+        self.print_status("Checking");
+        let source = self.read_main_source()?;
+        pipeline::lower_pipeline(&source)
+    }
+
+    /// Builds a binary in `target_dir`, skipping the stages whose inputs are
+    /// unchanged since the last build there.
+    ///
+    /// `generate` runs the Rive pipeline on `sources` and returns Rust code.
+    fn build_cached(
+        &self,
+        target_dir: &Path,
+        profile: &str,
+        sources: &[&[u8]],
+        generate: impl FnOnce(&mut Timings) -> Result<String>,
+    ) -> Result<Build> {
+        let start = Instant::now();
+        let mut timings = Timings::new();
+        let settings = self.config.resolve_profile(profile)?;
+
+        let mut cache = BuildCache::open(target_dir);
+        let cargo_toml = self.cargo_toml();
+
+        let stamp = compiler_stamp();
+        let source_hash = content_hash(&[&[stamp.as_bytes()], sources].concat());
+        let rust_code = match cache.cached_rust(&source_hash) {
+            Some(rust_code) => {
+                for stage in pipeline::STAGES {
+                    timings.skip(stage);
+                }
+                rust_code
+            }
+            None => {
+                let rust_code = generate(&mut timings)?;
+                cache.write_crate(&cargo_toml, &rust_code)?;
+                cache.record_source(source_hash);
+                cache.save()?;
+                rust_code
+            }
+        };
+
+        let crate_hash = content_hash(&[
+            cargo_toml.as_bytes(),
+            rust_code.as_bytes(),
+            profile.as_bytes(),
+        ]);
+        let built_path = self.built_binary_path(cache.dir(), profile);
+
+        if cache.is_fresh(profile, &crate_hash) && built_path.exists() {
+            timings.skip("rustc");
+        } else {
+            cache.write_crate(&cargo_toml, &rust_code)?;
+            timings.record("rustc", || {
+                self.compile_rust(cache.dir(), profile, &settings)
+            })?;
+            cache.record_profile(profile, crate_hash);
+            cache.save()?;
+        }
+
+        let binary = target_dir.join(binary_name(&self.config.package.name));
+        fs::copy(&built_path, &binary)
+            .with_context(|| "Failed to copy binary to target directory")?;
 
         let duration = start.elapsed();
-        self.print_build_success(release, duration);
+        self.print_build_success(profile, duration);
 
-        Ok((binary_path, duration))
+        Ok(Build { binary, timings })
     }
 
     /// Checks the project for errors without building.
@@ -60,7 +218,7 @@ impl Compiler {
     pub fn check(&self) -> Result<Duration> {
         let start = Instant::now();
 
-        print_project_status("Checking", &self.config, &self.project_root);
+        self.print_status("Checking");
 
         let source = self.read_main_source()?;
         pipeline::check_pipeline(&source)?;
@@ -76,16 +234,63 @@ impl Compiler {
         &self.project_root
     }
 
+    /// Returns the directory build output is written to.
+    fn target_dir(&self) -> PathBuf {
+        self.target_dir.clone()
+    }
+
+    /// Returns the name diagnostics and runtime failures give the main
+    /// source file: `main.rive` in a project, or the path of a script.
+    pub fn source_name(&self) -> String {
+        if self.source_path == self.project_root.join("src/main.rive") {
+            "main.rive".to_string()
+        } else {
+            self.source_path.display().to_string()
+        }
+    }
+
     /// Reads the main source file.
-    fn read_main_source(&self) -> Result<String> {
-        let source_path = self.project_root.join("src/main.rive");
-        fs::read_to_string(&source_path)
-            .with_context(|| format!("Failed to read {}", source_path.display()))
+    fn read_main_source(&self) -> Result<SourceFile> {
+        let text = fs::read_to_string(&self.source_path)
+            .with_context(|| format!("Failed to read {}", self.source_path.display()))?;
+        Ok(SourceFile {
+            name: self.source_name(),
+            text,
+        })
+    }
+
+    /// Reads the main source file followed by the files of the `tests`
+    /// directory, in name order.
+    fn test_sources(&self) -> Result<Vec<SourceFile>> {
+        let mut files = vec![self.read_main_source()?];
+
+        let tests_dir = self.project_root.join("tests");
+        if tests_dir.is_dir() {
+            let mut paths = fs::read_dir(&tests_dir)
+                .with_context(|| format!("Failed to read {}", tests_dir.display()))?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<std::io::Result<Vec<_>>>()
+                .with_context(|| format!("Failed to read {}", tests_dir.display()))?;
+            paths.retain(|path| path.extension().is_some_and(|ext| ext == "rive"));
+            paths.sort();
+
+            for path in paths {
+                let text = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                files.push(SourceFile {
+                    name: format!("tests/{file_name}"),
+                    text,
+                });
+            }
+        }
+
+        Ok(files)
     }
 
     /// Saves generated Rust code to target directory.
     fn save_generated_code(&self, rust_code: &str) -> Result<()> {
-        let target_dir = self.project_root.join("target");
+        let target_dir = self.target_dir();
         fs::create_dir_all(&target_dir).with_context(|| "Failed to create target directory")?;
 
         let rust_output_path = target_dir.join("main.rs");
@@ -95,45 +300,37 @@ impl Compiler {
         Ok(())
     }
 
-    /// Compiles generated Rust code to binary.
-    fn compile_to_binary(&self, rust_code: &str, release: bool) -> Result<PathBuf> {
-        let temp_dir = TempDir::new().with_context(|| "Failed to create temporary directory")?;
-        self.create_rust_project(&temp_dir, rust_code)?;
-
-        let binary_path = self.compile_rust(&temp_dir, release)?;
-        let target_dir = self.project_root.join("target");
-        let final_path = target_dir.join(binary_name(&self.config.package.name));
-
-        fs::copy(&binary_path, &final_path)
-            .with_context(|| "Failed to copy binary to target directory")?;
-
-        Ok(final_path)
-    }
-
-    /// Creates a temporary Rust project with the generated code.
-    fn create_rust_project(&self, temp_dir: &TempDir, rust_code: &str) -> Result<()> {
-        let cargo_toml = format!(
-            "[package]\nname = \"{}\"\nversion = \"{}\"\nedition = \"2024\"\n\n[dependencies]\n",
+    /// Returns the manifest of the generated Cargo project.
+    ///
+    /// The empty `[workspace]` table makes the crate its own workspace root,
+    /// which is where Cargo reads the translated `[profile.*]` tables from.
+    fn cargo_toml(&self) -> String {
+        let mut manifest = format!(
+            "[package]\nname = \"{}\"\nversion = \"{}\"\nedition = \"2024\"\n\n[dependencies]\n\n[workspace]\n",
             self.config.package.name, self.config.package.version
         );
+        for (name, profile) in &self.config.profile {
+            manifest.push_str(&cargo_profile_table(name, profile));
+        }
+        manifest
+    }
 
-        fs::write(temp_dir.path().join("Cargo.toml"), cargo_toml)
-            .with_context(|| "Failed to write Cargo.toml")?;
-
-        let src_dir = temp_dir.path().join("src");
-        fs::create_dir(&src_dir).with_context(|| "Failed to create src directory")?;
-
-        fs::write(src_dir.join("main.rs"), rust_code).with_context(|| "Failed to write main.rs")?;
-
-        Ok(())
+    /// Returns where compiling the generated crate with `profile` puts the binary.
+    fn built_binary_path(&self, crate_dir: &Path, profile: &str) -> PathBuf {
+        // Cargo names the output directory of the dev profile `debug`
+        let output_dir = if profile == "dev" { "debug" } else { profile };
+        crate_dir
+            .join("target")
+            .join(output_dir)
+            .join(binary_name(&self.config.package.name))
     }
 
-    /// Compiles the Rust project using rustc or cargo.
-    fn compile_rust(&self, temp_dir: &TempDir, release: bool) -> Result<PathBuf> {
+    /// Compiles the generated crate using cargo, or rustc if cargo is missing.
+    fn compile_rust(&self, crate_dir: &Path, profile: &str, settings: &Profile) -> Result<()> {
         if which("cargo").is_ok() {
-            self.compile_with_cargo(temp_dir, release)
+            self.compile_with_cargo(crate_dir, profile)
         } else if which("rustc").is_ok() {
-            self.compile_with_rustc(temp_dir)
+            self.compile_with_rustc(crate_dir, profile, settings)
         } else {
             anyhow::bail!(
                 "Neither cargo nor rustc found in PATH. Please install Rust from https://rustup.rs/"
@@ -142,13 +339,12 @@ impl Compiler {
     }
 
     /// Compiles using cargo.
-    fn compile_with_cargo(&self, temp_dir: &TempDir, release: bool) -> Result<PathBuf> {
+    fn compile_with_cargo(&self, crate_dir: &Path, profile: &str) -> Result<()> {
         let mut cmd = Command::new("cargo");
-        cmd.arg("build").current_dir(temp_dir.path());
-
-        if release {
-            cmd.arg("--release");
-        }
+        cmd.arg("build")
+            .arg("--profile")
+            .arg(profile)
+            .current_dir(crate_dir);
 
         let output = cmd
             .output()
@@ -159,24 +355,27 @@ impl Compiler {
             anyhow::bail!("Rust compilation failed:\n{stderr}");
         }
 
-        let profile = if release { "release" } else { "debug" };
-        let binary_path = temp_dir
-            .path()
-            .join("target")
-            .join(profile)
-            .join(binary_name(&self.config.package.name));
-
-        Ok(binary_path)
+        Ok(())
     }
 
-    /// Compiles using rustc directly.
-    fn compile_with_rustc(&self, temp_dir: &TempDir) -> Result<PathBuf> {
-        let output_path = temp_dir.path().join(binary_name(&self.config.package.name));
+    /// Compiles using rustc directly, mirroring cargo's output layout.
+    fn compile_with_rustc(
+        &self,
+        crate_dir: &Path,
+        profile: &str,
+        settings: &Profile,
+    ) -> Result<()> {
+        let output_path = self.built_binary_path(crate_dir, profile);
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
 
         let output = Command::new("rustc")
-            .arg(temp_dir.path().join("src/main.rs"))
+            .arg(crate_dir.join("src/main.rs"))
             .arg("-o")
             .arg(&output_path)
+            .args(rustc_flags(settings))
             .output()
             .with_context(|| "Failed to execute rustc")?;
 
@@ -185,13 +384,32 @@ impl Compiler {
             anyhow::bail!("Rust compilation failed:\n{stderr}");
         }
 
-        Ok(output_path)
+        Ok(())
+    }
+
+    /// Prints a status message naming the package and its source.
+    fn print_status(&self, status: &str) {
+        if !self.quiet {
+            print_project_status(status, &self.config, &self.source_root());
+        }
+    }
+
+    /// Returns the path shown in status messages: the project root, or the
+    /// script itself.
+    fn source_root(&self) -> PathBuf {
+        if self.source_path == self.project_root.join("src/main.rive") {
+            self.project_root.clone()
+        } else {
+            self.source_path.clone()
+        }
     }
 
     /// Prints build success message.
-    fn print_build_success(&self, release: bool, duration: Duration) {
+    fn print_build_success(&self, profile: &str, duration: Duration) {
         use colored::Colorize;
-        let profile = if release { "release" } else { "dev" };
+        if self.quiet {
+            return;
+        }
         println!(
             "    {} project built successfully with `{profile}` profile in {:.2}s",
             "Finished".green().bold(),
@@ -202,6 +420,9 @@ impl Compiler {
     /// Prints check success message.
     fn print_check_success(&self, duration: Duration) {
         use colored::Colorize;
+        if self.quiet {
+            return;
+        }
         println!(
             "    {} project checked successfully in {:.2}s",
             "Finished".green().bold(),
@@ -209,3 +430,88 @@ impl Compiler {
         );
     }
 }
+
+/// Derives a Cargo package name from a script's file name.
+///
+/// Characters Cargo rejects become `_`, and names that do not start with a
+/// letter are prefixed with `script_`.
+fn script_package_name(script_path: &Path) -> String {
+    let stem = script_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    let name: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("script_{name}")
+    }
+}
+
+/// Translates a `[profile.<name>]` table of rive.toml into Cargo's format.
+///
+/// Custom profiles always name the profile they inherit from, as Cargo
+/// requires.
+fn cargo_profile_table(name: &str, profile: &Profile) -> String {
+    let mut table = format!("\n[profile.{name}]\n");
+    if !Profile::BUILTIN.contains(&name) {
+        let inherits = profile.inherits.as_deref().unwrap_or("dev");
+        table.push_str(&format!("inherits = \"{inherits}\"\n"));
+    }
+    if let Some(level) = profile.opt_level {
+        match level {
+            OptLevel::Level(level) => table.push_str(&format!("opt-level = {level}\n")),
+            OptLevel::Size(_) => table.push_str(&format!("opt-level = \"{level}\"\n")),
+        }
+    }
+    if let Some(debug) = profile.debug {
+        table.push_str(&format!("debug = {debug}\n"));
+    }
+    if let Some(overflow_checks) = profile.overflow_checks {
+        table.push_str(&format!("overflow-checks = {overflow_checks}\n"));
+    }
+    if let Some(lto) = profile.lto {
+        match lto {
+            Lto::Enabled(_) => table.push_str(&format!("lto = {lto}\n")),
+            Lto::Mode(_) => table.push_str(&format!("lto = \"{lto}\"\n")),
+        }
+    }
+    if let Some(panic) = profile.panic {
+        table.push_str(&format!("panic = \"{panic}\"\n"));
+    }
+    table
+}
+
+/// Translates resolved profile settings into rustc flags.
+fn rustc_flags(settings: &Profile) -> Vec<String> {
+    let mut flags = Vec::new();
+    if let Some(level) = settings.opt_level {
+        flags.push(format!("-Copt-level={level}"));
+    }
+    if settings.debug == Some(true) {
+        flags.push("-g".to_string());
+    }
+    if let Some(overflow_checks) = settings.overflow_checks {
+        let value = if overflow_checks { "on" } else { "off" };
+        flags.push(format!("-Coverflow-checks={value}"));
+    }
+    match settings.lto {
+        Some(Lto::Enabled(true)) => flags.push("-Clto".to_string()),
+        Some(lto @ Lto::Mode(_)) => flags.push(format!("-Clto={lto}")),
+        Some(Lto::Enabled(false)) | None => {}
+    }
+    if let Some(panic) = settings.panic {
+        flags.push(format!("-Cpanic={panic}"));
+    }
+    flags
+}
@@ -0,0 +1,168 @@
+//! Printing of the intermediate representations of the pipeline.
+//!
+//! Each stage can be printed as text for people or as JSON for tools. The
+//! JSON form is an object naming the stage, with the stage's output under
+//! `tokens`, `tree` or `text`.
+
+use crate::pipeline::{self, SourceFile};
+use anyhow::Result;
+use clap::ValueEnum;
+use rive_core::Span;
+use rive_parser::{SyntaxNode, syntax_tree};
+use std::fmt::Write;
+
+/// A representation produced by the pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Stage {
+    /// Tokens produced by the lexer
+    Tokens,
+    /// Syntax tree produced by the parser
+    Ast,
+    /// Syntax tree with the type of every expression
+    TypedAst,
+    /// Rive Intermediate Representation
+    Rir,
+    /// Generated Rust code
+    Rust,
+}
+
+impl Stage {
+    /// Returns the name used on the command line.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Tokens => "tokens",
+            Self::Ast => "ast",
+            Self::TypedAst => "typed-ast",
+            Self::Rir => "rir",
+            Self::Rust => "rust",
+        }
+    }
+}
+
+/// Runs the pipeline up to `stage` and returns its output.
+///
+/// # Errors
+/// Returns an error if any stage up to `stage` fails.
+pub fn emit(file: &SourceFile, stage: Stage, json: bool) -> Result<String> {
+    let tokens = pipeline::lex(file)?;
+    if stage == Stage::Tokens {
+        return Ok(if json {
+            let tokens = tokens
+                .iter()
+                .map(|(token, span)| {
+                    format!(
+                        "{{\"kind\":{},\"text\":{},\"span\":{}}}",
+                        json_string(&format!("{:?}", token.kind)),
+                        json_string(&token.text),
+                        json_span(*span)
+                    )
+                })
+                .collect::<Vec<_>>();
+            stage_json(stage, "tokens", &format!("[{}]", tokens.join(",")))
+        } else {
+            tokens
+                .iter()
+                .fold(String::new(), |mut output, (token, span)| {
+                    let location = format!("{}:{}", span.start.line, span.start.column);
+                    let _ = writeln!(output, "{location:<8} {:?} {:?}", token.kind, token.text);
+                    output
+                })
+        });
+    }
+
+    let (ast, type_registry) = pipeline::parse_tokens(&tokens, file)?;
+    if stage == Stage::Ast {
+        return Ok(render_tree(stage, &syntax_tree(&ast, &type_registry), json));
+    }
+
+    let analysis = pipeline::analyze_program(&ast, type_registry, file)?;
+    if stage == Stage::TypedAst {
+        let registry = &analysis.type_registry;
+        let mut tree = syntax_tree(&ast, registry);
+        tree.annotate_types(&|span| {
+            analysis
+                .expression_types
+                .get(&span)
+                .map(|&type_id| registry.get_type_name(type_id))
+        });
+        return Ok(render_tree(stage, &tree, json));
+    }
+
+    let rir_module = pipeline::lower(&ast, analysis.type_registry, file)?;
+    let text = if stage == Stage::Rir {
+        rir_module.to_string()
+    } else {
+        pipeline::generate(&rir_module, file)?
+    };
+
+    Ok(if json {
+        stage_json(stage, "text", &json_string(&text))
+    } else {
+        text
+    })
+}
+
+/// Renders a syntax tree as indented text or JSON.
+fn render_tree(stage: Stage, tree: &SyntaxNode, json: bool) -> String {
+    if json {
+        let mut output = String::new();
+        write_node_json(&mut output, tree);
+        stage_json(stage, "tree", &output)
+    } else {
+        tree.to_string()
+    }
+}
+
+/// Wraps the JSON output of a stage in an object naming the stage.
+fn stage_json(stage: Stage, field: &str, value: &str) -> String {
+    format!(
+        "{{\"stage\":{},\"{field}\":{value}}}\n",
+        json_string(stage.name())
+    )
+}
+
+fn write_node_json(output: &mut String, node: &SyntaxNode) {
+    let _ = write!(output, "{{\"kind\":{}", json_string(node.kind));
+    if let Some(detail) = &node.detail {
+        let _ = write!(output, ",\"detail\":{}", json_string(detail));
+    }
+    if let Some(type_name) = &node.type_name {
+        let _ = write!(output, ",\"type\":{}", json_string(type_name));
+    }
+    let _ = write!(output, ",\"span\":{},\"children\":[", json_span(node.span));
+    for (i, child) in node.children.iter().enumerate() {
+        if i > 0 {
+            output.push(',');
+        }
+        write_node_json(output, child);
+    }
+    output.push_str("]}");
+}
+
+fn json_span(span: Span) -> String {
+    format!(
+        "{{\"start\":{{\"line\":{},\"column\":{}}},\"end\":{{\"line\":{},\"column\":{}}}}}",
+        span.start.line, span.start.column, span.end.line, span.end.column
+    )
+}
+
+/// Quotes and escapes a string as a JSON string literal.
+fn json_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(quoted, "\\u{:04x}", u32::from(c));
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,63 @@
+//! Formatting of Rive source files.
+
+use anyhow::{Context, Result};
+use miette::NamedSource;
+use rive_formatter::format_source;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directories of a project that hold Rive source files.
+const SOURCE_DIRS: [&str; 2] = ["src", "tests"];
+
+/// Returns the Rive source files of a project, in path order.
+///
+/// # Errors
+/// Returns an error if a source directory cannot be read.
+pub fn project_files(project_root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for dir in SOURCE_DIRS.map(|dir| project_root.join(dir)) {
+        if !dir.is_dir() {
+            continue;
+        }
+        let mut paths = fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read {}", dir.display()))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()
+            .with_context(|| format!("Failed to read {}", dir.display()))?;
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "rive"));
+        paths.sort();
+        files.extend(paths);
+    }
+
+    Ok(files)
+}
+
+/// Formats a source file in place, or only compares it with its formatted
+/// form if `check` is set.
+///
+/// Returns true if the file was not formatted.
+///
+/// # Errors
+/// Returns an error if the file cannot be read, written or formatted.
+pub fn format_file(path: &Path, check: bool) -> Result<bool> {
+    let source =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let formatted = format_source(&source).map_err(|error| {
+        let name = path.display().to_string();
+        let report =
+            miette::Report::new(error).with_source_code(NamedSource::new(name, source.clone()));
+        eprintln!("{report:?}");
+        anyhow::anyhow!("Failed to format {}", path.display())
+    })?;
+
+    if formatted == source {
+        return Ok(false);
+    }
+    if !check {
+        fs::write(path, formatted)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(true)
+}
@@ -1,8 +1,13 @@
 //! Rive CLI library for testing and reusability.
 
+pub mod cache;
 pub mod compiler;
+pub mod emit;
+pub mod format;
 pub mod pipeline;
+pub mod repl;
+pub mod timings;
 pub mod utils;
 
-pub use compiler::Compiler;
+pub use compiler::{Build, Compiler};
 pub use rive_utils::Config;
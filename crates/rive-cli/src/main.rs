@@ -1,12 +1,23 @@
 //! Command-line interface for the Rive compiler.
 
+mod cache;
 mod commands;
 mod compiler;
+mod emit;
+mod format;
 mod pipeline;
+mod repl;
+mod timings;
 mod utils;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use commands::build::BuildArgs;
+use commands::emit::EmitArgs;
+use commands::fmt::FmtArgs;
+use commands::run::RunArgs;
+use commands::test::TestArgs;
+use std::path::Path;
 
 #[derive(Parser)]
 #[command(name = "rive")]
@@ -31,17 +42,37 @@ enum Commands {
 
     /// Compile the current project
     #[command(visible_alias = "b")]
-    Build,
+    Build(BuildArgs),
 
-    /// Build and execute the current project
+    /// Build and execute the current project or a standalone script
     #[command(visible_alias = "r")]
-    Run,
+    Run(RunArgs),
 
     /// Check the current project for errors
     Check,
 
+    /// Run the `@test` functions of the current project
+    #[command(visible_alias = "t")]
+    Test(TestArgs),
+
     /// Remove the target directory
     Clean,
+
+    /// Print the tokens, syntax tree, RIR or Rust of the current project
+    Emit(EmitArgs),
+
+    /// Format the source files of the current project
+    Fmt(FmtArgs),
+
+    /// Run the language server for editors over stdin and stdout
+    Lsp,
+
+    /// Evaluate statements and expressions interactively
+    Repl,
+
+    /// Run a standalone script: `rive script.rive [ARGS]...`
+    #[command(external_subcommand)]
+    Script(Vec<String>),
 }
 
 fn main() -> Result<()> {
@@ -50,9 +81,27 @@ fn main() -> Result<()> {
     match cli.command {
         Commands::New { name } => commands::new::execute(&name),
         Commands::Init => commands::init::execute(),
-        Commands::Build => commands::build::execute(),
-        Commands::Run => commands::run::execute(),
+        Commands::Build(args) => commands::build::execute(&args),
+        Commands::Run(args) => commands::run::execute(&args),
         Commands::Check => commands::check::execute(),
+        Commands::Test(args) => commands::test::execute(&args),
         Commands::Clean => commands::clean::execute(),
+        Commands::Emit(args) => commands::emit::execute(&args),
+        Commands::Fmt(args) => commands::fmt::execute(&args),
+        Commands::Lsp => commands::lsp::execute(),
+        Commands::Repl => commands::repl::execute(),
+        Commands::Script(args) => run_script(&args),
+    }
+}
+
+/// Runs `rive <script> [ARGS]...`, rejecting names that are not files.
+fn run_script(args: &[String]) -> Result<()> {
+    let (script, script_args) = args
+        .split_first()
+        .expect("external subcommands have a name");
+    let script = Path::new(script);
+    if !script.is_file() {
+        anyhow::bail!("Unknown command or script '{}'", script.display());
     }
+    commands::run::execute_script(script, script_args)
 }
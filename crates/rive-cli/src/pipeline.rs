@@ -1,34 +1,47 @@
 //! Compilation pipeline stages.
 
+use crate::timings::Timings;
 use anyhow::{Context, Result};
 use miette::NamedSource;
 use rive_codegen::CodeGenerator;
-use rive_core::{Span, type_system::TypeRegistry};
+use rive_core::{Error, Span, type_system::TypeRegistry};
 use rive_ir::{AstLowering, RirModule};
-use rive_lexer::{Token, tokenize};
+use rive_lexer::{Token, tokenize_with_errors};
 use rive_parser::{Program, parse};
+use rive_semantic::Analysis;
 
-/// Runs lexical analysis on source code.
+/// Names of the pipeline stages, in the order they run.
+pub const STAGES: [&str; 5] = ["lex", "parse", "analyze", "lower", "codegen"];
+
+/// Runs lexical analysis on a source file.
+///
+/// Every invalid token is reported, not just the first one.
 ///
 /// # Errors
 /// Returns an error if lexical analysis fails.
-pub fn lex(source: &str) -> Result<Vec<(Token, Span)>> {
-    tokenize(source).map_err(|e| {
-        let report = miette::Report::new(e)
-            .with_source_code(NamedSource::new("main.rive", source.to_string()));
+pub fn lex(file: &SourceFile) -> Result<Vec<(Token, Span)>> {
+    let (tokens, errors) = tokenize_with_errors(&file.text);
+    if errors.is_empty() {
+        return Ok(tokens);
+    }
+
+    for error in errors {
+        let report = miette::Report::new(error).with_source_code(named_source(file));
         eprintln!("{report:?}");
-        anyhow::anyhow!("Lexical analysis failed")
-    })
+    }
+    Err(anyhow::anyhow!("Lexical analysis failed"))
 }
 
 /// Runs parsing on tokens to produce an AST and type registry.
 ///
 /// # Errors
 /// Returns an error if parsing fails.
-pub fn parse_tokens(tokens: &[(Token, Span)], source: &str) -> Result<(Program, TypeRegistry)> {
+pub fn parse_tokens(
+    tokens: &[(Token, Span)],
+    file: &SourceFile,
+) -> Result<(Program, TypeRegistry)> {
     parse(tokens).map_err(|e| {
-        let report = miette::Report::new(e)
-            .with_source_code(NamedSource::new("main.rive", source.to_string()));
+        let report = miette::Report::new(e).with_source_code(named_source(file));
         eprintln!("{report:?}");
         anyhow::anyhow!("Parsing failed")
     })
@@ -36,41 +49,68 @@ pub fn parse_tokens(tokens: &[(Token, Span)], source: &str) -> Result<(Program,
 
 /// Runs semantic analysis on the AST and returns the updated type registry.
 ///
+/// Warnings are reported but don't fail the analysis.
+///
 /// # Errors
 /// Returns an error if semantic analysis fails.
 pub fn analyze(
     program: &Program,
     type_registry: TypeRegistry,
-    source: &str,
+    file: &SourceFile,
 ) -> Result<TypeRegistry> {
-    rive_semantic::analyze_with_registry(program, type_registry).map_err(|e| {
-        let report = miette::Report::new(e)
-            .with_source_code(NamedSource::new("main.rive", source.to_string()));
+    analyze_program(program, type_registry, file).map(|analysis| analysis.type_registry)
+}
+
+/// Runs semantic analysis on the AST and returns everything it found.
+///
+/// Warnings are reported but don't fail the analysis.
+///
+/// # Errors
+/// Returns an error if semantic analysis fails.
+pub fn analyze_program(
+    program: &Program,
+    type_registry: TypeRegistry,
+    file: &SourceFile,
+) -> Result<Analysis> {
+    let mut analysis = rive_semantic::analyze_program(program, type_registry).map_err(|e| {
+        let report = miette::Report::new(e).with_source_code(named_source(file));
         eprintln!("{report:?}");
         anyhow::anyhow!("Semantic analysis failed")
-    })
+    })?;
+
+    for warning in std::mem::take(&mut analysis.warnings) {
+        let report = miette::Report::new(warning).with_source_code(named_source(file));
+        eprintln!("{report:?}");
+    }
+
+    Ok(analysis)
 }
 
 /// Lowers AST to RIR (Rive Intermediate Representation).
 ///
 /// # Errors
 /// Returns an error if lowering fails.
-pub fn lower(program: &Program, type_registry: TypeRegistry, source: &str) -> Result<RirModule> {
+pub fn lower(
+    program: &Program,
+    type_registry: TypeRegistry,
+    file: &SourceFile,
+) -> Result<RirModule> {
     let mut lowering = AstLowering::new(type_registry);
     lowering.lower_program(program).map_err(|e| {
-        let report = miette::Report::new(e)
-            .with_source_code(NamedSource::new("main.rive", source.to_string()));
+        let report = miette::Report::new(e).with_source_code(named_source(file));
         eprintln!("{report:?}");
         anyhow::anyhow!("RIR lowering failed")
     })
 }
 
-/// Generates Rust code from RIR.
+/// Generates Rust code from RIR compiled from `file`, whose name runtime
+/// failures report.
 ///
 /// # Errors
 /// Returns an error if code generation fails.
-pub fn generate(rir_module: &RirModule) -> Result<String> {
+pub fn generate(rir_module: &RirModule, file: &SourceFile) -> Result<String> {
     let mut codegen = CodeGenerator::new();
+    codegen.set_source_files(vec![(0, file.name.clone())]);
     codegen
         .generate(rir_module)
         .with_context(|| "Code generation failed")
@@ -80,23 +120,146 @@ pub fn generate(rir_module: &RirModule) -> Result<String> {
 ///
 /// # Errors
 /// Returns an error if any stage fails.
-pub fn check_pipeline(source: &str) -> Result<()> {
-    let tokens = lex(source)?;
-    let (ast, type_registry) = parse_tokens(&tokens, source)?;
-    let type_registry = analyze(&ast, type_registry, source)?;
-    let rir_module = lower(&ast, type_registry, source)?;
-    let _rust_code = generate(&rir_module)?;
+pub fn check_pipeline(file: &SourceFile) -> Result<()> {
+    let tokens = lex(file)?;
+    let (ast, type_registry) = parse_tokens(&tokens, file)?;
+    let type_registry = analyze(&ast, type_registry, file)?;
+    let rir_module = lower(&ast, type_registry, file)?;
+    let _rust_code = generate(&rir_module, file)?;
     Ok(())
 }
 
 /// Runs the complete compilation pipeline and returns generated code.
 ///
+/// The duration of each stage is recorded in `timings`.
+///
 /// # Errors
 /// Returns an error if any stage fails.
-pub fn build_pipeline(source: &str) -> Result<String> {
-    let tokens = lex(source)?;
-    let (ast, type_registry) = parse_tokens(&tokens, source)?;
-    let type_registry = analyze(&ast, type_registry, source)?;
-    let rir_module = lower(&ast, type_registry, source)?;
-    generate(&rir_module)
+pub fn build_pipeline(file: &SourceFile, timings: &mut Timings) -> Result<String> {
+    let tokens = timings.record("lex", || lex(file))?;
+    let (ast, type_registry) = timings.record("parse", || parse_tokens(&tokens, file))?;
+    let type_registry = timings.record("analyze", || analyze(&ast, type_registry, file))?;
+    let rir_module = timings.record("lower", || lower(&ast, type_registry, file))?;
+    timings.record("codegen", || generate(&rir_module, file))
+}
+
+/// Runs the pipeline up to RIR, for running the program with the interpreter.
+///
+/// # Errors
+/// Returns an error if any stage fails.
+pub fn lower_pipeline(file: &SourceFile) -> Result<RirModule> {
+    let tokens = lex(file)?;
+    let (ast, type_registry) = parse_tokens(&tokens, file)?;
+    let type_registry = analyze(&ast, type_registry, file)?;
+    lower(&ast, type_registry, file)
+}
+
+/// A Rive source file with the name diagnostics refer to it by.
+pub struct SourceFile {
+    /// Name used in diagnostics and failure locations, e.g. `main.rive`,
+    /// `tests/math.rive` or the path of a script
+    pub name: String,
+    pub text: String,
+}
+
+/// Runs the pipeline on several files compiled as one program and returns a
+/// test harness that runs their `@test` functions.
+///
+/// The files share one offset space: each file's spans are shifted by the
+/// total length of the files before it, so every span identifies its file.
+///
+/// # Errors
+/// Returns an error if any stage fails.
+pub fn test_pipeline(files: &[SourceFile], timings: &mut Timings) -> Result<String> {
+    let bases = files
+        .iter()
+        .scan(0, |next, file| {
+            let base = *next;
+            *next += file.text.len() + 1;
+            Some(base)
+        })
+        .collect::<Vec<_>>();
+
+    let tokens = timings.record("lex", || {
+        let mut tokens = Vec::new();
+        for (file, base) in files.iter().zip(&bases) {
+            let mut file_tokens = lex(file)?;
+            for (_, span) in &mut file_tokens {
+                span.start.offset += base;
+                span.end.offset += base;
+            }
+            tokens.extend(file_tokens);
+        }
+        Ok::<_, anyhow::Error>(tokens)
+    })?;
+
+    let (ast, type_registry) = timings.record("parse", || {
+        parse(&tokens).map_err(|e| {
+            report_in_files(e, files, &bases);
+            anyhow::anyhow!("Parsing failed")
+        })
+    })?;
+
+    let analysis = timings.record("analyze", || {
+        rive_semantic::analyze_program(&ast, type_registry).map_err(|e| {
+            report_in_files(e, files, &bases);
+            anyhow::anyhow!("Semantic analysis failed")
+        })
+    })?;
+    for mut warning in analysis.warnings {
+        let file = locate(files, &bases, &mut warning.span);
+        eprintln!(
+            "{:?}",
+            miette::Report::new(warning).with_source_code(named_source(file))
+        );
+    }
+
+    let rir_module = timings.record("lower", || {
+        let mut lowering = AstLowering::new(analysis.type_registry);
+        lowering.lower_program(&ast).map_err(|e| {
+            report_in_files(e, files, &bases);
+            anyhow::anyhow!("RIR lowering failed")
+        })
+    })?;
+
+    timings.record("codegen", || {
+        let mut codegen = CodeGenerator::new();
+        codegen.set_source_files(
+            bases
+                .iter()
+                .zip(files)
+                .map(|(base, file)| (*base, file.name.clone()))
+                .collect(),
+        );
+        codegen
+            .generate_tests(&rir_module)
+            .with_context(|| "Code generation failed")
+    })
+}
+
+/// Prints an error against the file its span points into.
+fn report_in_files(mut error: Error, files: &[SourceFile], bases: &[usize]) {
+    let file = match error.span_mut() {
+        Some(span) => locate(files, bases, span),
+        None => &files[0],
+    };
+    eprintln!(
+        "{:?}",
+        miette::Report::new(error).with_source_code(named_source(file))
+    );
+}
+
+/// Returns the file a span points into, making the span relative to it.
+fn locate<'a>(files: &'a [SourceFile], bases: &[usize], span: &mut Span) -> &'a SourceFile {
+    let index = bases
+        .iter()
+        .rposition(|base| *base <= span.start.offset)
+        .unwrap_or(0);
+    span.start.offset -= bases[index];
+    span.end.offset = span.end.offset.saturating_sub(bases[index]);
+    &files[index]
+}
+
+fn named_source(file: &SourceFile) -> NamedSource<String> {
+    NamedSource::new(&file.name, file.text.clone())
 }
@@ -0,0 +1,428 @@
+//! Interactive sessions for `rive repl`.
+//!
+//! A session is kept as a program: the declarations entered so far, and a
+//! `main` holding the statements entered so far. Each input is checked and
+//! lowered as part of that program, so bindings carry over from one input to
+//! the next, but only the input itself runs: the interpreter runs its
+//! statements with the variables the earlier inputs left behind, so nothing
+//! an earlier input did is done again.
+
+use anyhow::{Context, Result};
+use miette::NamedSource;
+use rive_core::type_system::{TypeId, TypeKind};
+use rive_core::{Error, Span};
+use rive_interpreter::{Interpreter, RuntimeError};
+use rive_ir::{AstLowering, RirModule, RirStatement};
+use rive_lexer::{TokenKind, tokenize_with_errors};
+use rive_parser::ast::{Block, FunctionBody, Item, Program, Statement};
+use rive_parser::parse;
+use rive_semantic::{Analysis, analyze_program};
+use std::collections::HashMap;
+use std::fmt;
+
+/// The value of an input ending in an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Value {
+    /// Name the value is bound to, so later inputs can use it
+    pub name: String,
+    pub type_name: String,
+    /// The value as `print` writes it
+    pub text: String,
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} = {}", self.name, self.type_name, self.text)
+    }
+}
+
+/// What evaluating an input produced.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Evaluation {
+    /// Everything the input printed
+    pub output: String,
+    pub value: Option<Value>,
+    /// Status the input passed to `exit`, which ends the session
+    pub exit_status: Option<i32>,
+}
+
+/// A statement input entered into the session.
+struct Input {
+    text: String,
+    /// Name the value of its trailing expression is bound to, if it has one
+    value_name: Option<String>,
+}
+
+/// A session program, with where the input being evaluated starts in it.
+struct Source {
+    text: String,
+    input_start: usize,
+    /// Where each earlier statement input starts and ends
+    input_ranges: Vec<(usize, usize)>,
+}
+
+/// A session program that passed semantic analysis.
+struct Checked {
+    program: Program,
+    analysis: Analysis,
+    /// Offset of the input in the session program
+    input_start: usize,
+    /// Line of the session program the input starts on
+    input_line: usize,
+}
+
+impl Checked {
+    /// Returns the span and type of the input's last statement, if it is an expression.
+    fn trailing_expression(&self) -> Option<(Span, TypeId)> {
+        let Some(Statement::Expression { expression, .. }) =
+            main_body(&self.program)?.statements.last()
+        else {
+            return None;
+        };
+        let span = expression.span();
+        if span.start.offset < self.input_start {
+            return None;
+        }
+        Some((span, *self.analysis.expression_types.get(&span)?))
+    }
+
+    /// Returns true if values of the type are worth showing: not `Unit`,
+    /// `Unit?` (the type of a loop without `break` values) or `Never`.
+    fn shows_value(&self, type_id: TypeId) -> bool {
+        let type_id = match &self.analysis.type_registry.get_type_metadata(type_id).kind {
+            TypeKind::Optional { inner } => *inner,
+            _ => type_id,
+        };
+        !matches!(type_id, TypeId::UNIT | TypeId::NEVER)
+    }
+
+    /// Lowers the program, returning it with the statements of `main` that
+    /// the input lowers to.
+    fn lower(self) -> Result<(RirModule, Vec<RirStatement>)> {
+        let mut lowering = AstLowering::new(self.analysis.type_registry);
+        let module = lowering
+            .lower_program(&self.program)
+            .map_err(|error| anyhow::anyhow!("{error}"))?;
+
+        let main = module.main_function().context("Missing main function")?;
+        let mut statements: Vec<_> = main
+            .body
+            .statements
+            .iter()
+            .filter(|statement| statement.span().start.offset >= self.input_start)
+            .cloned()
+            .collect();
+        if let Some(result) = &main.body.final_expr
+            && result.span().start.offset >= self.input_start
+        {
+            statements.push(RirStatement::Expression {
+                expr: result.clone(),
+                span: result.span(),
+            });
+        }
+        Ok((module, statements))
+    }
+}
+
+/// An interactive session.
+#[derive(Default)]
+pub struct Session {
+    /// Functions, type aliases and constants, in the order they were entered
+    items: Vec<String>,
+    /// Statement inputs, in the order they were entered
+    inputs: Vec<Input>,
+    /// Variables defined by the inputs run so far
+    variables: HashMap<String, rive_interpreter::Value>,
+    /// Number of values named so far
+    values: usize,
+}
+
+impl Session {
+    /// Starts an empty session.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evaluates an input.
+    ///
+    /// Declarations (`fun`, `type`, `newtype`, `const` and `@test`) are
+    /// added to the session. Anything else runs as statements of `main`; if
+    /// the input ends in an expression, its value is bound to a fresh name
+    /// and returned.
+    ///
+    /// # Errors
+    /// Returns an error if the input doesn't compile or panics. The session
+    /// is left as it was.
+    pub fn eval(&mut self, input: &str) -> Result<Evaluation> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(Evaluation::default());
+        }
+
+        if is_declaration(input) {
+            self.check(Some(input), input, None)?;
+            self.items.push(input.to_string());
+            return Ok(Evaluation::default());
+        }
+
+        let mut checked = self.check(None, input, None)?;
+        let mut value = None;
+        if let Some((_, type_id)) = checked.trailing_expression()
+            && checked.shows_value(type_id)
+        {
+            let name = self.value_name(input);
+            let type_name = checked.analysis.type_registry.get_type_name(type_id);
+            checked = self.check(None, input, Some(&name))?;
+            value = Some((name, type_name));
+        }
+
+        let input_line = checked.input_line;
+        let (module, statements) = checked.lower()?;
+        let mut output = Vec::new();
+        let result = Interpreter::new(&module, &mut output)
+            .with_source_name("repl")
+            .with_first_line(input_line)
+            .run_statements(&statements, self.variables.clone());
+        let output = String::from_utf8_lossy(&output).into_owned();
+
+        let variables = match result {
+            Ok(variables) => variables,
+            Err(RuntimeError::Exit(status)) => {
+                return Ok(Evaluation {
+                    output,
+                    value: None,
+                    exit_status: Some(status),
+                });
+            }
+            Err(RuntimeError::Panic(message)) => anyhow::bail!("{output}error: {message}"),
+            Err(error) => anyhow::bail!("{output}{error}"),
+        };
+
+        let value = value.map(|(name, type_name)| {
+            self.values += 1;
+            let text = variables
+                .get(&name)
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            Value {
+                name,
+                type_name,
+                text,
+            }
+        });
+        self.variables = variables;
+        self.inputs.push(Input {
+            text: input.to_string(),
+            value_name: value.as_ref().map(|value| value.name.clone()),
+        });
+        Ok(Evaluation {
+            output,
+            value,
+            exit_status: None,
+        })
+    }
+
+    /// Returns the type of an expression, without running it.
+    ///
+    /// # Errors
+    /// Returns an error if `expression` is not a valid expression.
+    pub fn type_of(&self, expression: &str) -> Result<String> {
+        let checked = self.check(None, expression, None)?;
+        let (_, type_id) = checked
+            .trailing_expression()
+            .context("Expected an expression")?;
+        Ok(checked.analysis.type_registry.get_type_name(type_id))
+    }
+
+    /// Returns the RIR an expression lowers to.
+    ///
+    /// # Errors
+    /// Returns an error if `expression` is not a valid expression.
+    pub fn rir_of(&self, expression: &str) -> Result<String> {
+        self.type_of(expression)?;
+
+        let name = self.value_name(expression);
+        let (_, statements) = self.check(None, expression, Some(&name))?.lower()?;
+        match statements.last() {
+            Some(RirStatement::Let { value, .. }) => Ok(value.to_string()),
+            _ => anyhow::bail!("Expected an expression"),
+        }
+    }
+
+    /// Returns the session program with `item` or `statements` added.
+    fn source(&self, item: Option<&str>, statements: Option<&str>) -> Source {
+        let mut text = String::new();
+        for existing in &self.items {
+            text.push_str(existing);
+            text.push_str("\n\n");
+        }
+        let mut input_start = text.len();
+        if let Some(item) = item {
+            text.push_str(item);
+            text.push_str("\n\n");
+        }
+
+        text.push_str("fun main() {\n");
+        let mut input_ranges = Vec::new();
+        for existing in &self.inputs {
+            input_ranges.push((text.len(), text.len() + existing.text.len()));
+            text.push_str(&existing.text);
+            text.push('\n');
+        }
+        if let Some(statements) = statements {
+            input_start = text.len();
+            text.push_str(statements);
+            text.push('\n');
+        }
+        text.push_str("}\n");
+        Source {
+            text,
+            input_start,
+            input_ranges,
+        }
+    }
+
+    /// Checks the session program with `input` added, as a declaration if
+    /// `item` is set and as statements otherwise. With a `value_name`, the
+    /// input's trailing expression is bound to it.
+    fn check(&self, item: Option<&str>, input: &str, value_name: Option<&str>) -> Result<Checked> {
+        let statements = item.is_none().then_some(input);
+        let source = self.source(item, statements);
+        let input_start = source.input_start;
+        let report = |error| report(error, input, input_start);
+
+        let (tokens, mut errors) = tokenize_with_errors(&source.text);
+        if !errors.is_empty() {
+            return Err(report(errors.swap_remove(0)));
+        }
+        let (mut program, type_registry) = parse(&tokens).map_err(report)?;
+
+        // Earlier values stay bound to their names
+        let earlier_values = self.inputs.iter().zip(&source.input_ranges);
+        for (input, &(start, end)) in earlier_values {
+            if let Some(name) = &input.value_name {
+                bind_trailing_expression(&mut program, start, end, name);
+            }
+        }
+        if let Some(name) = value_name {
+            let end = input_start + input.len();
+            bind_trailing_expression(&mut program, input_start, end, name);
+        }
+
+        let analysis = analyze_program(&program, type_registry).map_err(report)?;
+        let input_line = source.text[..input_start].matches('\n').count() + 1;
+        Ok(Checked {
+            program,
+            analysis,
+            input_start,
+            input_line,
+        })
+    }
+
+    /// Returns a name for the next value that the session doesn't use yet.
+    fn value_name(&self, input: &str) -> String {
+        let source = self.source(None, Some(input));
+        let (tokens, _) = tokenize_with_errors(&source.text);
+        let used = |name: &str| {
+            self.inputs
+                .iter()
+                .any(|input| input.value_name.as_deref() == Some(name))
+                || tokens
+                    .iter()
+                    .any(|(token, _)| token.kind == TokenKind::Identifier && token.text == name)
+        };
+        (self.values..)
+            .map(|n| format!("res{n}"))
+            .find(|name| !used(name))
+            .expect("some name is unused")
+    }
+}
+
+/// Returns the body of the session program's `main`.
+fn main_body(program: &Program) -> Option<&Block> {
+    program.items.iter().find_map(|item| match item {
+        Item::Function(function) if function.name == "main" => match &function.body {
+            FunctionBody::Block(body) => Some(body),
+            FunctionBody::Expression(_) => None,
+        },
+        _ => None,
+    })
+}
+
+/// Turns the last statement of `main` between `start` and `end` into a
+/// `let` binding its value to `name`, if it is an expression.
+fn bind_trailing_expression(program: &mut Program, start: usize, end: usize, name: &str) {
+    let body = program.items.iter_mut().find_map(|item| match item {
+        Item::Function(function) if function.name == "main" => match &mut function.body {
+            FunctionBody::Block(body) => Some(body),
+            FunctionBody::Expression(_) => None,
+        },
+        _ => None,
+    });
+    let Some(statement) = body.and_then(|body| {
+        body.statements.iter_mut().rev().find(|statement| {
+            let offset = statement.span().start.offset;
+            (start..end).contains(&offset)
+        })
+    }) else {
+        return;
+    };
+
+    if let Statement::Expression { expression, span } = statement {
+        *statement = Statement::Let {
+            name: name.to_string(),
+            mutable: false,
+            var_type: None,
+            infer_nullable: false,
+            initializer: expression.clone(),
+            span: *span,
+        };
+    }
+}
+
+/// Returns true if `input` declares a function, type or constant.
+fn is_declaration(input: &str) -> bool {
+    let (tokens, _) = tokenize_with_errors(input);
+    tokens.first().is_some_and(|(token, _)| {
+        matches!(
+            token.kind,
+            TokenKind::Fun
+                | TokenKind::Type
+                | TokenKind::Newtype
+                | TokenKind::Const
+                | TokenKind::At
+        )
+    })
+}
+
+/// Returns true if `input` has no unclosed delimiters, so it can be evaluated
+/// without reading more lines.
+pub fn is_complete(input: &str) -> bool {
+    let (tokens, _) = tokenize_with_errors(input);
+    let depth = tokens
+        .iter()
+        .fold(0i32, |depth, (token, _)| match token.kind {
+            TokenKind::LeftParen | TokenKind::LeftBracket | TokenKind::LeftBrace => depth + 1,
+            TokenKind::RightParen | TokenKind::RightBracket | TokenKind::RightBrace => depth - 1,
+            _ => depth,
+        });
+    depth <= 0
+}
+
+/// Turns an error in the session program into a report on the input it was
+/// found in.
+fn report(mut error: Error, input: &str, input_start: usize) -> anyhow::Error {
+    let Some(span) = error.span_mut() else {
+        return anyhow::anyhow!("{error}");
+    };
+    let input_end = input_start + input.len();
+    if span.start.offset < input_start || span.end.offset > input_end {
+        return anyhow::anyhow!("{error}");
+    }
+
+    span.start.offset -= input_start;
+    span.end.offset -= input_start;
+    let report =
+        miette::Report::new(error).with_source_code(NamedSource::new("repl", input.to_string()));
+    anyhow::anyhow!("{report:?}")
+}
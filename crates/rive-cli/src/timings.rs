@@ -0,0 +1,48 @@
+//! Per-stage timing of a build.
+
+use colored::Colorize;
+use std::time::{Duration, Instant};
+
+/// Records which build stages ran and how long each one took.
+#[derive(Debug, Default)]
+pub struct Timings {
+    stages: Vec<(&'static str, Option<Duration>)>,
+}
+
+impl Timings {
+    /// Creates an empty timing report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs a stage and records its duration.
+    pub fn record<T>(&mut self, stage: &'static str, run: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = run();
+        self.stages.push((stage, Some(start.elapsed())));
+        result
+    }
+
+    /// Records a stage that was skipped because its output was up to date.
+    pub fn skip(&mut self, stage: &'static str) {
+        self.stages.push((stage, None));
+    }
+
+    /// Returns each recorded stage with its duration, or `None` if it was skipped.
+    pub fn stages(&self) -> impl Iterator<Item = (&'static str, Option<Duration>)> + '_ {
+        self.stages.iter().copied()
+    }
+
+    /// Prints one line per stage, in the order the stages were recorded.
+    pub fn print(&self) {
+        println!("{}", "     Timings".green().bold());
+        for (stage, duration) in self.stages() {
+            match duration {
+                Some(duration) => {
+                    println!("{stage:>12} {:.2}ms", duration.as_secs_f64() * 1000.0);
+                }
+                None => println!("{stage:>12} {}", "fresh".dimmed()),
+            }
+        }
+    }
+}
@@ -53,3 +53,665 @@ fn test_new_command_structure() {
     assert!(project_path.join("src").exists());
     assert!(project_path.join("src/main.rive").exists());
 }
+
+#[test]
+fn test_content_hash() {
+    use rive_cli::cache::content_hash;
+
+    let hash = content_hash(&[b"fun main() {}"]);
+    assert_eq!(hash.len(), 16);
+    assert_eq!(hash, content_hash(&[b"fun main() {}"]));
+    assert_ne!(hash, content_hash(&[b"fun main() { }"]));
+    assert_ne!(content_hash(&[b"ab", b"c"]), content_hash(&[b"a", b"bc"]));
+}
+
+#[test]
+fn test_build_cache_fingerprint() {
+    use rive_cli::cache::BuildCache;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut cache = BuildCache::open(temp_dir.path());
+    assert!(cache.cached_rust("abc").is_none());
+
+    cache.write_crate("[package]\n", "fn main() {}\n").unwrap();
+    cache.record_source("abc".to_string());
+    cache.record_profile("debug", "def".to_string());
+    cache.save().unwrap();
+
+    let cache = BuildCache::open(temp_dir.path());
+    assert_eq!(cache.cached_rust("abc").as_deref(), Some("fn main() {}\n"));
+    assert!(cache.cached_rust("xyz").is_none());
+    assert!(cache.is_fresh("debug", "def"));
+    assert!(!cache.is_fresh("debug", "xyz"));
+    assert!(!cache.is_fresh("release", "def"));
+}
+
+#[test]
+fn test_build_skips_unchanged_stages() {
+    use rive_cli::Compiler;
+
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    rive_utils::Config::new("cached")
+        .save(project.join("rive.toml"))
+        .unwrap();
+    fs::write(
+        project.join("src/main.rive"),
+        "fun main() {\n    print(1)\n}\n",
+    )
+    .unwrap();
+
+    let skipped = |build: &rive_cli::Build| -> Vec<&str> {
+        build
+            .timings
+            .stages()
+            .filter(|(_, duration)| duration.is_none())
+            .map(|(stage, _)| stage)
+            .collect()
+    };
+
+    let compiler = Compiler::new(project.to_path_buf()).unwrap();
+    let first = compiler.build("dev").unwrap();
+    assert!(first.binary.exists());
+    assert!(skipped(&first).is_empty());
+
+    let second = compiler.build("dev").unwrap();
+    assert_eq!(
+        skipped(&second),
+        ["lex", "parse", "analyze", "lower", "codegen", "rustc"]
+    );
+
+    // A change that generates the same Rust still skips rustc
+    fs::write(project.join("src/main.rive"), "fun main() { print(1) }\n").unwrap();
+    let third = compiler.build("dev").unwrap();
+    assert_eq!(skipped(&third), ["rustc"]);
+
+    fs::write(project.join("src/main.rive"), "fun main() { print(2) }\n").unwrap();
+    let fourth = compiler.build("dev").unwrap();
+    assert!(skipped(&fourth).is_empty());
+}
+
+#[test]
+fn test_build_with_profiles() {
+    use rive_cli::Compiler;
+
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("rive.toml"),
+        "[package]\nname = \"profiled\"\nversion = \"0.1.0\"\n\n[profile.fast]\ninherits = \"release\"\nopt-level = 1\nlto = \"thin\"\n",
+    )
+    .unwrap();
+    fs::write(project.join("src/main.rive"), "fun main() { print(1) }\n").unwrap();
+
+    let compiler = Compiler::new(project.to_path_buf()).unwrap();
+    compiler.build("release").unwrap();
+    compiler.build("fast").unwrap();
+
+    let manifest = fs::read_to_string(project.join("target/build/Cargo.toml")).unwrap();
+    assert!(
+        manifest
+            .contains("[profile.fast]\ninherits = \"release\"\nopt-level = 1\nlto = \"thin\"\n")
+    );
+    assert!(project.join("target/build/target/release").exists());
+    assert!(project.join("target/build/target/fast").exists());
+
+    // Each profile is cached separately
+    let rebuilt = compiler.build("release").unwrap();
+    assert!(
+        rebuilt
+            .timings
+            .stages()
+            .any(|(stage, duration)| stage == "rustc" && duration.is_none())
+    );
+
+    let err = compiler.build("missing").err().unwrap();
+    assert!(err.to_string().contains("Profile 'missing' is not defined"));
+}
+
+#[test]
+fn test_build_script() {
+    use rive_cli::Compiler;
+
+    let temp_dir = TempDir::new().unwrap();
+    let cache_root = temp_dir.path().join("cache");
+    let script = temp_dir.path().join("2-greet.rive");
+    fs::write(
+        &script,
+        "#!/usr/bin/env rive\nfun main() {\n    print(\"hi\")\n}\n",
+    )
+    .unwrap();
+
+    let compiler = Compiler::script(&script, &cache_root).unwrap().quiet(true);
+    let build = compiler.build("dev").unwrap();
+    assert!(build.binary.exists());
+    assert!(build.binary.starts_with(&cache_root));
+    assert!(
+        build
+            .binary
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("script_2-greet")
+    );
+
+    // The build directory is keyed by the script's contents
+    let rebuilt = Compiler::script(&script, &cache_root).unwrap().build("dev");
+    assert_eq!(rebuilt.unwrap().binary, build.binary);
+
+    fs::write(&script, "fun main() {\n    print(\"bye\")\n}\n").unwrap();
+    let changed = Compiler::script(&script, &cache_root).unwrap().build("dev");
+    assert_ne!(changed.unwrap().binary, build.binary);
+}
+
+#[test]
+fn test_script_failures_name_the_script() {
+    use rive_cli::Compiler;
+    use rive_interpreter::{Interpreter, RuntimeError};
+    use std::process::Command;
+
+    let temp_dir = TempDir::new().unwrap();
+    let script = temp_dir.path().join("check.rive");
+    fs::write(&script, "fun main() {\n    panic(\"boom\")\n}\n").unwrap();
+    let location = format!("boom at {}:2:5", script.display());
+
+    let compiler = Compiler::script(&script, &temp_dir.path().join("cache"))
+        .unwrap()
+        .quiet(true);
+    assert_eq!(compiler.source_name(), script.display().to_string());
+
+    let build = compiler.build("dev").unwrap();
+    let compiled = Command::new(&build.binary).output().unwrap();
+    let stderr = String::from_utf8(compiled.stderr).unwrap();
+    assert!(stderr.contains(&location), "got: {stderr}");
+
+    let module = compiler.lower().unwrap();
+    let mut output = Vec::new();
+    let status = Interpreter::new(&module, &mut output)
+        .with_source_name(compiler.source_name())
+        .run();
+    assert_eq!(status, Err(RuntimeError::Panic(location)));
+}
+
+#[test]
+fn test_emit_stages() {
+    use rive_cli::emit::{Stage, emit};
+    use rive_cli::pipeline::SourceFile;
+
+    let source = &SourceFile {
+        name: "main.rive".to_string(),
+        text: "fun main() {\n    let x = 1 + 2\n    print(\"a\\\"b\")\n}\n".to_string(),
+    };
+
+    let tokens = emit(source, Stage::Tokens, false).unwrap();
+    assert!(tokens.starts_with("1:1      Fun \"fun\"\n"));
+
+    let typed = emit(source, Stage::TypedAst, false).unwrap();
+    assert!(typed.contains("      Let x\n        Binary + [Int]\n          Integer 1 [Int]\n"));
+
+    let rir = emit(source, Stage::Rir, false).unwrap();
+    assert!(rir.starts_with("RIR Module"));
+
+    let rust = emit(source, Stage::Rust, false).unwrap();
+    assert!(rust.contains("fn main()"));
+
+    let json = emit(source, Stage::Ast, true).unwrap();
+    assert!(json.starts_with("{\"stage\":\"ast\",\"tree\":{\"kind\":\"Program\""));
+    assert!(json.contains("{\"kind\":\"String\",\"detail\":\"\\\"a\\\\\\\"b\\\"\""));
+
+    let json = emit(source, Stage::Tokens, true).unwrap();
+    assert!(json.contains(
+        "{\"kind\":\"Let\",\"text\":\"let\",\"span\":{\"start\":{\"line\":2,\"column\":5},\"end\":{\"line\":2,\"column\":8}}}"
+    ));
+}
+
+#[test]
+fn test_build_and_run_tests() {
+    use rive_cli::Compiler;
+    use std::process::Command;
+
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path();
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::create_dir_all(project.join("tests")).unwrap();
+    fs::write(
+        project.join("rive.toml"),
+        "[package]\nname = \"tested\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("src/main.rive"),
+        "fun double(x: Int): Int = x * 2\n\nfun main() {}\n\n@test\nfun doubles() {\n    assert_eq(double(2), 4)\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("tests/more.rive"),
+        "@test\nfun fails() {\n    assert(double(1) == 3, \"wrong\")\n}\n\n@test(panics)\nfun panics() {\n    panic(\"boom\")\n}\n",
+    )
+    .unwrap();
+
+    let compiler = Compiler::new(project.to_path_buf()).unwrap();
+    let build = compiler.build_tests().unwrap();
+    assert!(build.binary.starts_with(project.join("target/test")));
+
+    let output = Command::new(&build.binary).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(101));
+    assert!(stdout.contains("test doubles ... ok"));
+    assert!(stdout.contains("test panics ... ok"));
+    assert!(stdout.contains("test fails ... FAILED"));
+    assert!(stdout.contains("---- fails at tests/more.rive:2:1 ----"));
+    assert!(stdout.contains("assertion failed: wrong at tests/more.rive:3:5"));
+    assert!(stdout.contains("test result: FAILED. 2 passed; 1 failed; 0 filtered out"));
+
+    let output = Command::new(&build.binary).arg("double").output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("test result: ok. 1 passed; 0 failed; 2 filtered out"));
+}
+
+#[test]
+fn test_format_project_files() {
+    use rive_cli::format::{format_file, project_files};
+
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::create_dir_all(root.join("tests")).unwrap();
+    fs::write(
+        root.join("src/main.rive"),
+        "fun main() {\n    print(1)\n}\n",
+    )
+    .unwrap();
+    fs::write(root.join("tests/b.rive"), "@test\nfun b(){assert(1==1)}\n").unwrap();
+    fs::write(root.join("tests/a.rive"), "fun a() {}\n").unwrap();
+    fs::write(root.join("tests/notes.txt"), "not rive").unwrap();
+
+    let files = project_files(root).unwrap();
+    assert_eq!(
+        files,
+        vec![
+            root.join("src/main.rive"),
+            root.join("tests/a.rive"),
+            root.join("tests/b.rive"),
+        ]
+    );
+
+    let unformatted = root.join("tests/b.rive");
+    assert!(!format_file(&root.join("src/main.rive"), true).unwrap());
+    assert!(format_file(&unformatted, true).unwrap());
+    assert_eq!(
+        fs::read_to_string(&unformatted).unwrap(),
+        "@test\nfun b(){assert(1==1)}\n",
+        "--check leaves files alone"
+    );
+
+    assert!(format_file(&unformatted, false).unwrap());
+    assert_eq!(
+        fs::read_to_string(&unformatted).unwrap(),
+        "@test\nfun b() { assert(1 == 1) }\n"
+    );
+    assert!(!format_file(&unformatted, true).unwrap());
+
+    fs::write(root.join("src/main.rive"), "fun main() {\n").unwrap();
+    assert!(format_file(&root.join("src/main.rive"), false).is_err());
+}
+
+#[test]
+fn test_repl_session() {
+    use rive_cli::repl::{Session, is_complete};
+
+    assert!(is_complete("let x = 1"));
+    assert!(!is_complete("fun f() {\n    print(1)"));
+    assert!(is_complete("fun f() {\n    print(1)\n}"));
+
+    let mut session = Session::new();
+
+    let evaluation = session.eval("let x = 10\nprint(\"hi\")").unwrap();
+    assert_eq!(evaluation.output, "hi\n");
+    assert_eq!(evaluation.value, None);
+
+    session
+        .eval("fun double(n: Int): Int {\n    n * 2\n}")
+        .unwrap();
+    let evaluation = session.eval("double(x) + 1").unwrap();
+    assert_eq!(evaluation.output, "", "earlier output is not repeated");
+    assert_eq!(evaluation.value.unwrap().to_string(), "res0: Int = 21");
+
+    let evaluation = session.eval("for i in 0..2 {\n    print(i)\n}").unwrap();
+    assert_eq!(evaluation.output, "0\n1\n");
+    assert_eq!(evaluation.value, None, "loops have no value to show");
+
+    let evaluation = session.eval("res0 * 2").unwrap();
+    assert_eq!(evaluation.value.unwrap().to_string(), "res1: Int = 42");
+
+    assert_eq!(session.type_of("\"a\" + \"b\"").unwrap(), "Text");
+    assert!(session.rir_of("x + 1").unwrap().contains("x"));
+
+    let error = session.eval("undefined + 1").unwrap_err().to_string();
+    assert!(error.contains("Undefined variable 'undefined'"));
+    assert!(error.contains("repl:1:1"));
+
+    let error = session.eval("panic(\"boom\")").unwrap_err().to_string();
+    assert!(error.contains("boom"));
+    let evaluation = session.eval("x").unwrap();
+    assert_eq!(
+        evaluation.value.unwrap().to_string(),
+        "res2: Int = 10",
+        "failed inputs leave the session as it was"
+    );
+
+    let evaluation = session.eval("exit(3)").unwrap();
+    assert_eq!(evaluation.exit_status, Some(3));
+}
+
+#[test]
+fn test_repl_runs_each_input_once() {
+    use rive_cli::repl::Session;
+
+    let mut session = Session::new();
+    session.eval("let mut count = 0").unwrap();
+    let evaluation = session.eval("count += 1\nprint(\"counted\")").unwrap();
+    assert_eq!(evaluation.output, "counted\n");
+    let evaluation = session.eval("count += 1\ncount").unwrap();
+    assert_eq!(evaluation.output, "", "earlier inputs don't run again");
+    assert_eq!(evaluation.value.unwrap().to_string(), "res0: Int = 2");
+
+    // The value is bound in the AST, not by editing the input's text
+    session.eval("let xs = List(1)").unwrap();
+    let evaluation = session.eval("let q = xs[5]").unwrap();
+    assert_eq!(evaluation.value.unwrap().text, "[5]");
+    let evaluation = session.eval("q").unwrap();
+    assert_eq!(
+        evaluation.value.unwrap().to_string(),
+        "res2: List<Int> = [1]"
+    );
+
+    let error = session.eval("let count = 5").unwrap_err().to_string();
+    assert!(error.contains("Symbol 'count' is already defined"));
+    assert!(error.contains("repl:1:1"), "got: {error}");
+}
+
+#[test]
+fn test_interpret_matches_compiled() {
+    use rive_cli::Compiler;
+    use std::process::Command;
+
+    let temp_dir = TempDir::new().unwrap();
+    let script = temp_dir.path().join("differential.rive");
+    fs::write(
+        &script,
+        r#"
+fun fib(n: Int): Int {
+    if n < 2 { return n }
+    return fib(n - 1) + fib(n - 2)
+}
+
+fun main(): Int {
+    let mut total = 0
+    outer: for i in 1..10 {
+        for j in 1..10 {
+            if j > i { continue outer }
+            if i * j > 20 { break outer }
+            total += i * j
+        }
+    }
+    let items = List(3, 1, 2)
+    items.sort()
+    items.append(total)
+    let x: Int? = 4
+    print(fib(15), " ", 7 / 2, " ", -7 % 3, " ", 1.5 * 2.0, " ", x ?: 0)
+    print(items, " ", items.contains(115), " ", List("a", "b"), " ", (1, "t"))
+    let code: Int = when total {
+        0 -> 1
+        in 1..=100 -> 2
+        _ -> 3
+    }
+    return code
+}
+"#,
+    )
+    .unwrap();
+
+    let compiler = Compiler::script(&script, &temp_dir.path().join("cache"))
+        .unwrap()
+        .quiet(true);
+    let build = compiler.build("dev").unwrap();
+    let compiled = Command::new(&build.binary).output().unwrap();
+
+    let module = compiler.lower().unwrap();
+    let mut output = Vec::new();
+    let status = rive_interpreter::run(&module, Vec::new(), &mut output);
+
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        String::from_utf8(compiled.stdout).unwrap()
+    );
+    assert_eq!(status, Ok(3));
+    assert_eq!(compiled.status.code(), Some(3));
+}
+
+#[test]
+fn test_interpret_unary_operands_match_compiled() {
+    use rive_cli::Compiler;
+    use std::process::Command;
+
+    let temp_dir = TempDir::new().unwrap();
+    let script = temp_dir.path().join("unary.rive");
+    fs::write(
+        &script,
+        r#"
+fun main() {
+    let x = 2
+    print(~(x | 1), " ", -(2 ^ 3), " ", -(x + 3), " ", !(x > 1 && x < 3))
+}
+"#,
+    )
+    .unwrap();
+
+    let compiler = Compiler::script(&script, &temp_dir.path().join("cache"))
+        .unwrap()
+        .quiet(true);
+    let build = compiler.build("dev").unwrap();
+    let compiled = Command::new(&build.binary).output().unwrap();
+
+    let module = compiler.lower().unwrap();
+    let mut output = Vec::new();
+    rive_interpreter::run(&module, Vec::new(), &mut output).unwrap();
+
+    let interpreted = String::from_utf8(output).unwrap();
+    assert_eq!(interpreted, "-4 -1 -5 false\n");
+    assert_eq!(interpreted, String::from_utf8(compiled.stdout).unwrap());
+}
+
+#[test]
+fn test_user_functions_shadow_process_builtins() {
+    use rive_cli::Compiler;
+    use std::process::Command;
+
+    let temp_dir = TempDir::new().unwrap();
+    let script = temp_dir.path().join("shadow.rive");
+    fs::write(
+        &script,
+        r#"
+fun exit(code: Int): Int {
+    return code + 1
+}
+
+fun args(): Int = 0
+
+fun main() {
+    print(exit(3), " ", args())
+}
+"#,
+    )
+    .unwrap();
+
+    let compiler = Compiler::script(&script, &temp_dir.path().join("cache"))
+        .unwrap()
+        .quiet(true);
+    let build = compiler.build("dev").unwrap();
+    let compiled = Command::new(&build.binary).arg("a").output().unwrap();
+
+    let module = compiler.lower().unwrap();
+    let mut output = Vec::new();
+    let status = rive_interpreter::run(&module, vec!["a".to_string()], &mut output);
+
+    assert_eq!(String::from_utf8(compiled.stdout).unwrap(), "4 0\n");
+    assert_eq!(compiled.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output).unwrap(), "4 0\n");
+    assert_eq!(status, Ok(0));
+}
+
+#[test]
+fn test_captured_collection_is_usable_after_call() {
+    use rive_cli::Compiler;
+    use std::process::Command;
+
+    let temp_dir = TempDir::new().unwrap();
+    let script = temp_dir.path().join("captures.rive");
+    fs::write(
+        &script,
+        r#"
+fun main() {
+    let xs = List(1, 2)
+    fun f(): Int {
+        return xs.len()
+    }
+    print(f())
+    print(xs)
+}
+"#,
+    )
+    .unwrap();
+
+    let compiler = Compiler::script(&script, &temp_dir.path().join("cache"))
+        .unwrap()
+        .quiet(true);
+    let build = compiler.build("dev").unwrap();
+    let compiled = Command::new(&build.binary).output().unwrap();
+
+    let module = compiler.lower().unwrap();
+    let mut output = Vec::new();
+    rive_interpreter::run(&module, Vec::new(), &mut output).unwrap();
+
+    assert_eq!(String::from_utf8(compiled.stdout).unwrap(), "2\n[1, 2]\n");
+    assert_eq!(String::from_utf8(output).unwrap(), "2\n[1, 2]\n");
+}
+
+#[test]
+fn test_globals_do_not_collide() {
+    use rive_cli::Compiler;
+    use std::process::Command;
+
+    let temp_dir = TempDir::new().unwrap();
+    let script = temp_dir.path().join("globals.rive");
+    fs::write(
+        &script,
+        r#"
+const G: Int = 5
+let a = 1
+let A = 2
+
+fun main() {
+    let G = 100
+    print(G, " ", a, " ", A)
+}
+"#,
+    )
+    .unwrap();
+
+    let compiler = Compiler::script(&script, &temp_dir.path().join("cache"))
+        .unwrap()
+        .quiet(true);
+    let build = compiler.build("dev").unwrap();
+    let compiled = Command::new(&build.binary).output().unwrap();
+
+    assert_eq!(String::from_utf8(compiled.stdout).unwrap(), "100 1 2\n");
+}
+
+#[test]
+fn test_nested_collections_print_the_same_in_both_backends() {
+    use rive_cli::Compiler;
+    use std::process::Command;
+
+    let temp_dir = TempDir::new().unwrap();
+    let script = temp_dir.path().join("nested.rive");
+    fs::write(
+        &script,
+        r#"
+fun main() {
+    let xs = List(List(1, 2), List(3))
+    print(xs)
+    print({"b": List(2), "a": List(1)})
+    print(List({"k": 1}), " ", (List(1), "a"))
+    assert_eq(xs, List(List(1, 2)))
+}
+"#,
+    )
+    .unwrap();
+
+    let compiler = Compiler::script(&script, &temp_dir.path().join("cache"))
+        .unwrap()
+        .quiet(true);
+    let build = compiler.build("dev").unwrap();
+    let compiled = Command::new(&build.binary).output().unwrap();
+
+    let module = compiler.lower().unwrap();
+    let mut output = Vec::new();
+    let error = rive_interpreter::run(&module, Vec::new(), &mut output).unwrap_err();
+
+    let expected = "[[1, 2], [3]]\n{a: [1], b: [2]}\n[{\"k\": 1}] ([1], a)\n";
+    assert_eq!(String::from_utf8(compiled.stdout).unwrap(), expected);
+    assert_eq!(String::from_utf8(output).unwrap(), expected);
+
+    let operands = "left: [[1, 2], [3]]\n right: [[1, 2]]";
+    assert!(
+        String::from_utf8(compiled.stderr)
+            .unwrap()
+            .contains(operands)
+    );
+    assert!(error.to_string().contains(operands), "got: {error}");
+}
+
+#[test]
+fn test_release_profile_wraps_overflow_in_both_backends() {
+    use rive_cli::Compiler;
+    use rive_interpreter::Interpreter;
+    use std::process::Command;
+
+    let temp_dir = TempDir::new().unwrap();
+    let script = temp_dir.path().join("overflow.rive");
+    fs::write(
+        &script,
+        r#"
+fun add(a: Int, b: Int): Int = a + b
+
+fun main() {
+    print(add(9223372036854775807, 1))
+}
+"#,
+    )
+    .unwrap();
+
+    let compiler = Compiler::script(&script, &temp_dir.path().join("cache"))
+        .unwrap()
+        .quiet(true);
+    let build = compiler.build("release").unwrap();
+    let compiled = Command::new(&build.binary).output().unwrap();
+
+    let overflow_checks = compiler.profile("release").unwrap().overflow_checks;
+    assert_eq!(overflow_checks, Some(false));
+    let module = compiler.lower().unwrap();
+    let mut output = Vec::new();
+    Interpreter::new(&module, &mut output)
+        .with_overflow_checks(false)
+        .run()
+        .unwrap();
+
+    let expected = "-9223372036854775808\n";
+    assert_eq!(String::from_utf8(compiled.stdout).unwrap(), expected);
+    assert_eq!(String::from_utf8(output).unwrap(), expected);
+}
@@ -3,15 +3,16 @@
 use super::{core::CodeGenerator, labels};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use rive_core::{Result, type_system::TypeId};
-use rive_ir::{RirBlock, RirExpression};
+use rive_core::{
+    Result,
+    type_system::{TypeId, TypeKind},
+};
+use rive_ir::{RirBlock, RirExpression, RirIterable};
 
 /// Parameters for generating a for loop.
 pub(crate) struct ForLoopParams<'a> {
     pub variable: &'a str,
-    pub start: &'a RirExpression,
-    pub end: &'a RirExpression,
-    pub inclusive: bool,
+    pub iterable: &'a RirIterable,
     pub body: &'a RirBlock,
     pub label: &'a Option<String>,
 }
@@ -64,7 +65,7 @@ impl CodeGenerator {
         label: &Option<String>,
     ) -> Result<TokenStream> {
         let cond = self.generate_expression(condition)?;
-        let body_stmts = self.generate_block(body)?;
+        let body_stmts = self.generate_statement_loop_body(body)?;
 
         Ok(labels::with_label(label, || {
             quote! {
@@ -78,27 +79,56 @@ impl CodeGenerator {
     /// Generates code for a for loop statement.
     pub(crate) fn generate_for(&mut self, params: ForLoopParams<'_>) -> Result<TokenStream> {
         let var = format_ident!("{}", params.variable);
-        let start_expr = self.generate_expression(params.start)?;
-        let end_expr = self.generate_expression(params.end)?;
-        let body_stmts = self.generate_block(params.body)?;
-        let range = labels::generate_range(&start_expr, &end_expr, params.inclusive);
+        let iterable = self.generate_iterable(params.iterable)?;
+        let body_stmts = self.generate_statement_loop_body(params.body)?;
 
         Ok(labels::with_label(params.label, || {
             quote! {
-                for #var in #range {
+                for #var in #iterable {
                     #body_stmts
                 }
             }
         }))
     }
 
+    /// Generates the sequence a for loop iterates over.
+    /// Collections are iterated over a snapshot, so the loop body may modify the original.
+    fn generate_iterable(&mut self, iterable: &RirIterable) -> Result<TokenStream> {
+        match iterable {
+            RirIterable::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let start_expr = self.generate_expression(start)?;
+                let end_expr = self.generate_expression(end)?;
+                Ok(labels::generate_range(&start_expr, &end_expr, *inclusive))
+            }
+            RirIterable::Collection { collection } => {
+                let collection_expr = self.generate_expression(collection)?;
+                let is_shared = self
+                    .type_registry
+                    .get(collection.type_id())
+                    .is_some_and(|meta| {
+                        matches!(meta.kind, TypeKind::List { .. } | TypeKind::Map { .. })
+                    });
+
+                if is_shared {
+                    Ok(quote! { (#collection_expr).borrow().clone() })
+                } else {
+                    Ok(quote! { (#collection_expr).clone() })
+                }
+            }
+        }
+    }
+
     /// Generates code for an infinite loop statement.
     pub(crate) fn generate_loop(
         &mut self,
         body: &RirBlock,
         label: &Option<String>,
     ) -> Result<TokenStream> {
-        let body_stmts = self.generate_block(body)?;
+        let body_stmts = self.generate_statement_loop_body(body)?;
 
         Ok(labels::with_label(label, || {
             quote! {
@@ -109,6 +139,14 @@ impl CodeGenerator {
         }))
     }
 
+    /// Generates the body of a loop used as a statement.
+    fn generate_statement_loop_body(&mut self, body: &RirBlock) -> Result<TokenStream> {
+        self.enter_loop_context(None);
+        let body_stmts = self.generate_block(body);
+        self.exit_loop_context();
+        body_stmts
+    }
+
     /// Generates code for a break statement.
     pub(crate) fn generate_break(
         &mut self,
@@ -133,6 +171,20 @@ impl CodeGenerator {
                 // No value, just break (result stays None)
                 Ok(labels::generate_break_stmt(label, &None))
             }
+        } else if self.in_statement_loop() {
+            // The loop's value is unused, so a break value is only evaluated
+            let break_stmt = labels::generate_break_stmt(label, &None);
+            if let Some(expr) = value {
+                let val_expr = self.generate_expression(expr)?;
+                Ok(quote! {
+                    {
+                        let _ = #val_expr;
+                        #break_stmt
+                    }
+                })
+            } else {
+                Ok(break_stmt)
+            }
         } else {
             // In loop expression context, wrap value in Some/None
             let value_token = if let Some(expr) = value {
@@ -207,15 +259,13 @@ impl CodeGenerator {
         _result_type: TypeId,
     ) -> Result<TokenStream> {
         let var = format_ident!("{}", params.variable);
-        let start_expr = self.generate_expression(params.start)?;
-        let end_expr = self.generate_expression(params.end)?;
-        let range = labels::generate_range(&start_expr, &end_expr, params.inclusive);
+        let iterable = self.generate_iterable(params.iterable)?;
         let label_clone = params.label.clone();
 
         self.wrap_loop_as_expression("__for_result", |generator| {
             let body_stmts = generator.generate_block(params.body)?;
             let for_loop = quote! {
-                for #var in #range {
+                for #var in #iterable {
                     #body_stmts
                 }
             };
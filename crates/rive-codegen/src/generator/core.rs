@@ -5,12 +5,14 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use rive_core::{Error, Result};
 use rive_ir::{RirBlock, RirFunction, RirModule};
+use std::collections::HashMap;
 
 /// Loop context for tracking result variables in loop expressions.
 #[derive(Debug, Clone)]
 struct LoopContext {
-    /// Name of the result variable for this loop (e.g., "__for_result")
-    result_var: String,
+    /// Name of the result variable for this loop (e.g., "__for_result"),
+    /// or `None` for a loop used as a statement
+    result_var: Option<String>,
 }
 
 /// Code generator for Rive programs.
@@ -19,6 +21,14 @@ pub struct CodeGenerator {
     loop_stack: Vec<LoopContext>,
     /// Type registry for type lookups during codegen
     pub(crate) type_registry: rive_core::type_system::TypeRegistry,
+    /// Source files by the byte offset their spans start at, used when
+    /// reporting runtime failures
+    source_files: Vec<(usize, String)>,
+    /// Whether any generated panic reports a Rive source location
+    reports_source_locations: bool,
+    /// Number of captured-variable parameters of each of the module's
+    /// functions, which also shadow builtins of the same name
+    pub(crate) functions: HashMap<String, usize>,
 }
 
 impl CodeGenerator {
@@ -27,14 +37,50 @@ impl CodeGenerator {
         Self {
             loop_stack: Vec::new(),
             type_registry: rive_core::type_system::TypeRegistry::new(),
+            source_files: vec![(0, "main.rive".to_string())],
+            reports_source_locations: false,
+            functions: HashMap::new(),
         }
     }
 
-    /// Enters a loop context with a result variable.
+    /// Returns true if `expr` is a variable whose type is not Copy.
+    ///
+    /// Such variables are cloned when consumed by a pattern or passed to a
+    /// local function that captures them, so they remain usable afterwards.
+    pub(crate) fn is_non_copy_variable(&self, expr: &rive_ir::RirExpression) -> bool {
+        matches!(expr, rive_ir::RirExpression::Variable { .. })
+            && self
+                .type_registry
+                .get(expr.type_id())
+                .is_some_and(|meta| !meta.is_copy())
+    }
+
+    /// Sets the source files the module was compiled from.
+    ///
+    /// Each file is given with the byte offset its spans start at, so
+    /// programs compiled from several files report the right file name.
+    pub fn set_source_files(&mut self, files: Vec<(usize, String)>) {
+        self.source_files = files;
+    }
+
+    /// Formats a span as a Rive source location (e.g., `main.rive:3:14`).
+    ///
+    /// Calling this marks the program as reporting its own panic locations,
+    /// so `main` installs a panic hook that omits the generated Rust location.
+    pub(crate) fn source_location(&mut self, span: rive_core::Span) -> String {
+        self.reports_source_locations = true;
+        let file = self
+            .source_files
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= span.start.offset)
+            .map_or("main.rive", |(_, name)| name.as_str());
+        format!("{file}:{}:{}", span.start.line, span.start.column)
+    }
+
+    /// Enters a loop context, with a result variable for loop expressions.
     pub(crate) fn enter_loop_context(&mut self, result_var: Option<String>) {
-        if let Some(var) = result_var {
-            self.loop_stack.push(LoopContext { result_var: var });
-        }
+        self.loop_stack.push(LoopContext { result_var });
     }
 
     /// Exits the current loop context.
@@ -44,7 +90,16 @@ impl CodeGenerator {
 
     /// Gets the current loop's result variable name, if in a loop context.
     pub(crate) fn current_loop_result_var(&self) -> Option<&str> {
-        self.loop_stack.last().map(|ctx| ctx.result_var.as_str())
+        self.loop_stack
+            .last()
+            .and_then(|ctx| ctx.result_var.as_deref())
+    }
+
+    /// Returns true if the innermost loop is a statement (has no result variable).
+    pub(crate) fn in_statement_loop(&self) -> bool {
+        self.loop_stack
+            .last()
+            .is_some_and(|ctx| ctx.result_var.is_none())
     }
 
     /// Generates a loop (for/while/loop) as a statement (no return value).
@@ -57,18 +112,14 @@ impl CodeGenerator {
         match expr {
             rive_ir::RirExpression::For {
                 variable,
-                start,
-                end,
-                inclusive,
+                iterable,
                 body,
                 label,
                 ..
             } => {
                 let params = ForLoopParams {
                     variable,
-                    start,
-                    end,
-                    inclusive: *inclusive,
+                    iterable,
                     body,
                     label,
                 };
@@ -89,31 +140,89 @@ impl CodeGenerator {
     pub fn generate(&mut self, module: &RirModule) -> Result<String> {
         // Copy the type registry from the module
         self.type_registry = module.type_registry.clone();
+        self.functions = function_captures(module);
 
-        let items: Result<Vec<_>> = module
+        // `main` is generated last so it knows whether any function reports
+        // Rive source locations, but keeps its position in the output
+        let (mains, others): (Vec<_>, Vec<_>) = module
             .functions
             .iter()
-            .map(|function| self.generate_function(function))
-            .collect();
+            .enumerate()
+            .partition(|(_, function)| function.name == "main");
+
+        let globals = module
+            .globals
+            .iter()
+            .map(|global| self.generate_global(global))
+            .collect::<Result<Vec<_>>>()?;
 
-        let items = items?;
+        let mut items = vec![TokenStream::new(); module.functions.len()];
+        for (index, function) in others.into_iter().chain(mains) {
+            items[index] = self.generate_function(function)?;
+        }
 
-        let tokens = quote! {
+        format_tokens(quote! {
+            #(#globals)*
             #(#items)*
-        };
+        })
+    }
+
+    /// Generates a test harness from a RIR module.
+    ///
+    /// The module's `@test` functions are run by a generated `main` that
+    /// replaces the program's own; see [`harness`](super::harness).
+    pub fn generate_tests(&mut self, module: &RirModule) -> Result<String> {
+        self.type_registry = module.type_registry.clone();
+        self.functions = function_captures(module);
 
-        let syntax_tree = syn::parse2::<syn::File>(tokens)
-            .map_err(|e| Error::Codegen(format!("Failed to parse generated code: {e}")))?;
+        let globals = module
+            .globals
+            .iter()
+            .map(|global| self.generate_global(global))
+            .collect::<Result<Vec<_>>>()?;
+        let functions = module
+            .functions
+            .iter()
+            .filter(|function| !function.is_main())
+            .map(|function| self.generate_function(function))
+            .collect::<Result<Vec<_>>>()?;
+        let harness = self.generate_harness(module);
 
-        Ok(prettyplease::unparse(&syntax_tree))
+        format_tokens(quote! {
+            #(#globals)*
+            #(#functions)*
+            #harness
+        })
     }
 
     /// Generates code for a RIR function.
     pub(crate) fn generate_function(&mut self, function: &RirFunction) -> Result<TokenStream> {
         let name = format_ident!("{}", function.name);
         let params = self.generate_parameters(&function.parameters)?;
-        let return_type = types::generate_return_type(function.return_type);
-        let body = self.generate_block(&function.body)?;
+        let mut return_type =
+            types::generate_return_type(function.return_type, &self.type_registry);
+        let mut body = self.generate_block(&function.body)?;
+
+        // Rust's `main` cannot return an integer, so an Int-returning `main`
+        // runs its body in a closure and exits with the result as status
+        if function.name == "main"
+            && self.type_registry.resolve(function.return_type)
+                == rive_core::type_system::TypeId::INT
+        {
+            return_type = quote! {};
+            body = quote! {
+                let status: i64 = (|| -> i64 { #body })();
+                std::process::exit(status as i32)
+            };
+        }
+
+        if function.name == "main" && self.reports_source_locations {
+            let hook = generate_panic_hook();
+            body = quote! {
+                #hook
+                #body
+            };
+        }
 
         if inline::should_inline_function(function) {
             Ok(quote! {
@@ -140,7 +249,8 @@ impl CodeGenerator {
             .iter()
             .map(|param| {
                 let name = format_ident!("{}", param.name);
-                let ty = types::rust_type(param.type_id, param.memory_strategy)?;
+                let ty =
+                    types::rust_type(param.type_id, param.memory_strategy, &self.type_registry)?;
                 Ok(quote! { #name: #ty })
             })
             .collect()
@@ -180,8 +290,43 @@ impl CodeGenerator {
     }
 }
 
+/// Parses generated tokens and formats them as Rust source.
+fn format_tokens(tokens: TokenStream) -> Result<String> {
+    let syntax_tree = syn::parse2::<syn::File>(tokens)
+        .map_err(|e| Error::Codegen(format!("Failed to parse generated code: {e}")))?;
+
+    Ok(prettyplease::unparse(&syntax_tree))
+}
+
+/// Generates a panic hook that prints only the panic message.
+///
+/// Rive panic messages already end with the Rive source location, so the
+/// default hook's location in the generated Rust would only be noise.
+fn generate_panic_hook() -> TokenStream {
+    quote! {
+        std::panic::set_hook(Box::new(|info| {
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| payload.downcast_ref::<&str>().copied())
+                .unwrap_or("explicit panic");
+            eprintln!("error: {}", message);
+        }));
+    }
+}
+
 impl Default for CodeGenerator {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// Returns the number of captured-variable parameters of each of a module's functions.
+fn function_captures(module: &RirModule) -> HashMap<String, usize> {
+    module
+        .functions
+        .iter()
+        .map(|f| (f.name.clone(), f.captures))
+        .collect()
+}
@@ -0,0 +1,140 @@
+//! Built-in function code generation.
+//!
+//! This module handles `panic`, `assert`, `assert_eq`, `todo` and `unreachable`,
+//! and the process functions `args` and `exit`.
+//! Every failure message ends with the Rive source location of the call
+//! (e.g. `main.rive:3:5`) instead of the location in the generated Rust.
+
+use super::super::core::CodeGenerator;
+use super::formatting::Style;
+use proc_macro2::TokenStream;
+use quote::quote;
+use rive_core::{Result, Span};
+use rive_ir::RirExpression;
+
+impl CodeGenerator {
+    /// Generates code for a call to a built-in function.
+    ///
+    /// Returns `None` if `function` is not a builtin.
+    ///
+    /// # Example
+    /// - `panic("boom")` → `panic!("{} at main.rive:3:5", "boom")`
+    /// - `assert(x > 0)` → `if !(x > 0) { panic!("assertion failed at main.rive:3:5") }`
+    pub(crate) fn generate_builtin_call(
+        &mut self,
+        function: &str,
+        arguments: &[RirExpression],
+        span: Span,
+    ) -> Result<Option<TokenStream>> {
+        match function {
+            "panic" | "assert" | "assert_eq" | "todo" | "unreachable" => {
+                self.generate_diagnostic_call(function, arguments, span)
+            }
+            "args" | "exit" => self.generate_process_call(function, arguments),
+            _ => Ok(None),
+        }
+    }
+
+    /// Generates a diagnostic builtin that panics with the Rive source location.
+    fn generate_diagnostic_call(
+        &mut self,
+        function: &str,
+        arguments: &[RirExpression],
+        span: Span,
+    ) -> Result<Option<TokenStream>> {
+        let location = self.source_location(span);
+        let args = self.generate_builtin_arguments(arguments)?;
+
+        let tokens = match (function, args.as_slice()) {
+            ("panic", [message]) => {
+                let format_str = format!("{{}} at {location}");
+                quote! { panic!(#format_str, #message) }
+            }
+            ("assert", [condition]) => {
+                let message = format!("assertion failed at {location}");
+                quote! {
+                    if !(#condition) {
+                        panic!(#message)
+                    }
+                }
+            }
+            ("assert", [condition, message]) => {
+                let format_str = format!("assertion failed: {{}} at {location}");
+                quote! {
+                    if !(#condition) {
+                        panic!(#format_str, #message)
+                    }
+                }
+            }
+            ("assert_eq", [left, right]) => {
+                let format_str = format!(
+                    "assertion `left == right` failed at {location}\n  left: {{}}\n right: {{}}"
+                );
+                let left_format =
+                    self.generate_format(quote! { left }, arguments[0].type_id(), Style::Debug);
+                let right_format =
+                    self.generate_format(quote! { right }, arguments[1].type_id(), Style::Debug);
+                quote! {
+                    match (&(#left), &(#right)) {
+                        (left, right) => {
+                            if !(*left == *right) {
+                                panic!(#format_str, #left_format, #right_format)
+                            }
+                        }
+                    }
+                }
+            }
+            ("todo", []) => {
+                let message = format!("not yet implemented at {location}");
+                quote! { panic!(#message) }
+            }
+            ("todo", [message]) => {
+                let format_str = format!("not yet implemented: {{}} at {location}");
+                quote! { panic!(#format_str, #message) }
+            }
+            ("unreachable", []) => {
+                let message = format!("entered unreachable code at {location}");
+                quote! { panic!(#message) }
+            }
+            ("unreachable", [message]) => {
+                let format_str = format!("entered unreachable code: {{}} at {location}");
+                quote! { panic!(#format_str, #message) }
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(tokens))
+    }
+
+    /// Generates the process builtins `args` and `exit`.
+    fn generate_process_call(
+        &mut self,
+        function: &str,
+        arguments: &[RirExpression],
+    ) -> Result<Option<TokenStream>> {
+        let args = self.generate_builtin_arguments(arguments)?;
+
+        let tokens = match (function, args.as_slice()) {
+            ("args", []) => quote! {
+                std::rc::Rc::new(std::cell::RefCell::new(
+                    std::env::args().skip(1).collect::<Vec<String>>()
+                ))
+            },
+            ("exit", [status]) => quote! { std::process::exit((#status) as i32) },
+            _ => return Ok(None),
+        };
+
+        Ok(Some(tokens))
+    }
+
+    /// Generates the argument expressions of a builtin call.
+    fn generate_builtin_arguments(
+        &mut self,
+        arguments: &[RirExpression],
+    ) -> Result<Vec<TokenStream>> {
+        arguments
+            .iter()
+            .map(|arg| self.generate_expression(arg))
+            .collect()
+    }
+}
@@ -3,11 +3,13 @@
 //! This module handles:
 //! - General function calls
 //! - Special `print()` function with custom formatting
+//! - Built-in diagnostic functions (see `builtins`)
 
 use super::super::core::CodeGenerator;
+use super::formatting::Style;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use rive_core::{Error, Result};
+use rive_core::{Error, Result, Span};
 use rive_ir::RirExpression;
 
 impl CodeGenerator {
@@ -18,17 +20,42 @@ impl CodeGenerator {
         &mut self,
         function: &str,
         arguments: &[RirExpression],
+        span: Span,
     ) -> Result<TokenStream> {
         // Special handling for print function
         if function == "print" {
             return self.generate_print_call(arguments);
         }
 
-        // General function call
+        // Built-in diagnostic functions report the Rive source location;
+        // a user function of the same name shadows them
+        if !self.functions.contains_key(function)
+            && let Some(tokens) = self.generate_builtin_call(function, arguments, span)?
+        {
+            return Ok(tokens);
+        }
+
+        // General function call. Captured variables are cloned so the
+        // enclosing function can still use them after the call.
         let func_name = format_ident!("{}", function);
+        let captures_from = arguments.len()
+            - self
+                .functions
+                .get(function)
+                .copied()
+                .unwrap_or(0)
+                .min(arguments.len());
         let args = arguments
             .iter()
-            .map(|arg| self.generate_expression(arg))
+            .enumerate()
+            .map(|(i, arg)| {
+                let tokens = self.generate_expression(arg)?;
+                if i >= captures_from && self.is_non_copy_variable(arg) {
+                    Ok(quote! { #tokens.clone() })
+                } else {
+                    Ok(tokens)
+                }
+            })
             .collect::<Result<Vec<_>>>()?;
 
         Ok(quote! { #func_name(#(#args),*) })
@@ -38,8 +65,9 @@ impl CodeGenerator {
     ///
     /// This function applies custom Rive formatting:
     /// - Optional types: prints value or "null"
+    /// - Results: prints `ok(value)` or `err(error)`
     /// - Lists: prints as `[x, x, x]`
-    /// - Maps: prints as `{k: v, k: v}`
+    /// - Maps: prints as `{k: v, k: v}` in key order
     /// - Tuples: prints as `(x, x, x)`
     /// - Strings: prints without quotes
     fn generate_print_call(&mut self, arguments: &[RirExpression]) -> Result<TokenStream> {
@@ -74,69 +102,37 @@ impl CodeGenerator {
         })
     }
 
-    /// Generates custom print format for special types.
+    /// Generates the print format of an argument.
     ///
-    /// Returns `(format_string, expression_tokens)`.
+    /// Returns `(format_string, expression_tokens)`. Primitives and text are
+    /// written with `{}`; composite values are formatted into a `String`
+    /// first (see `formatting`).
     ///
     /// # Examples
-    /// - Optional: `("null" or format!("{}", value))`
-    /// - List: `("[{}, {}]", list[0], list[1])`
-    /// - Tuple: `("({}, {})", t.0, t.1)`
-    /// - String: `("{}", str)`
+    /// - Int: `("{}", x)`
+    /// - List: `("{}", format!("{:?}", (&(xs)).borrow()))`
     fn generate_print_format(&mut self, arg: &RirExpression) -> Result<(String, Vec<TokenStream>)> {
         use rive_core::type_system::TypeKind;
 
-        let type_id = arg.type_id();
+        let type_id = self.type_registry.resolve(arg.type_id());
         let expr = self.generate_expression(arg)?;
 
-        // Get type info to determine formatting
-        if let Some(type_info) = self.type_registry.get(type_id) {
-            match &type_info.kind {
-                TypeKind::Optional { .. } => {
-                    // Format Optional: match expr { Some(v) => format!("{}", v), None => "null" }
-                    let format_expr = quote! {
-                        match &#expr {
-                            Some(v) => format!("{}", v),
-                            None => "null".to_string(),
-                        }
-                    };
-                    return Ok(("{}".to_string(), vec![format_expr]));
-                }
-                TypeKind::List { .. } => {
-                    // Format List: use Debug formatting
-                    return Ok(("{:?}".to_string(), vec![quote! { &#expr.borrow() }]));
-                }
-                TypeKind::Map { .. } => {
-                    // Format Map: custom format for readability
-                    let format_expr = quote! {
-                        {
-                            let dict = #expr.borrow();
-                            let items: Vec<String> = dict.iter()
-                                .map(|(k, v)| format!("{}: {}", k, v))
-                                .collect();
-                            format!("{{{}}}", items.join(", "))
-                        }
-                    };
-                    return Ok(("{}".to_string(), vec![format_expr]));
-                }
-                TypeKind::Tuple { elements } => {
-                    // Format Tuple: println!("({}, {})", t.0, t.1)
-                    let count = elements.len();
-                    let format_str = format!("({})", vec!["{}"; count].join(", "));
-                    let indices: Vec<syn::Index> = (0..count).map(syn::Index::from).collect();
-                    let field_exprs: Vec<TokenStream> =
-                        indices.iter().map(|idx| quote! { #expr.#idx }).collect();
-                    return Ok((format_str, field_exprs));
-                }
-                TypeKind::Text => {
-                    // Text: print without quotes
-                    return Ok(("{}".to_string(), vec![expr]));
-                }
-                _ => {}
-            }
+        let is_composite = self.type_registry.get(type_id).is_some_and(|info| {
+            matches!(
+                info.kind,
+                TypeKind::Optional { .. }
+                    | TypeKind::Result { .. }
+                    | TypeKind::Tuple { .. }
+                    | TypeKind::Array { .. }
+                    | TypeKind::List { .. }
+                    | TypeKind::Map { .. }
+            )
+        });
+        if is_composite || type_id == rive_core::type_system::TypeId::UNIT {
+            let format_expr = self.generate_format(quote! { &(#expr) }, type_id, Style::Display);
+            return Ok(("{}".to_string(), vec![format_expr]));
         }
 
-        // Default: use {} formatting
         Ok(("{}".to_string(), vec![expr]))
     }
 }
@@ -0,0 +1,158 @@
+//! Value formatting code generation.
+//!
+//! `print` writes values the way the interpreter's `Display` does, and the
+//! elements of printed lists, maps and `assert_eq` operands the way Rust's
+//! `{:?}` writes them. Lists and maps live behind a `RefCell` and maps are
+//! `HashMap`s, so values holding them are formatted element by element:
+//! nested lists print as `[...]` and map entries print in key order.
+
+use super::super::core::CodeGenerator;
+use proc_macro2::TokenStream;
+use quote::quote;
+use rive_core::type_system::{TypeId, TypeKind};
+
+/// How a value is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Style {
+    /// As `print` writes it: text unquoted, `null` for a missing value
+    Display,
+    /// As Rust's `{:?}` writes it: text quoted, `Some(..)`/`None`
+    Debug,
+}
+
+impl CodeGenerator {
+    /// Generates a `String` expression formatting `value`, an expression
+    /// that evaluates to a reference to a value of type `type_id`.
+    ///
+    /// # Example
+    /// - `List<List<Int>>` → `[[1, 2], [3]]`
+    /// - `Map<Text, Int?>` in `Debug` style → `{"a": Some(1), "b": None}`
+    pub(crate) fn generate_format(
+        &self,
+        value: TokenStream,
+        type_id: TypeId,
+        style: Style,
+    ) -> TokenStream {
+        let type_id = self.type_registry.resolve(type_id);
+        if style == Style::Debug && !self.holds_shared_collection(type_id) {
+            return quote! { format!("{:?}", #value) };
+        }
+
+        let Some(info) = self.type_registry.get(type_id) else {
+            return quote! { format!("{}", #value) };
+        };
+
+        match &info.kind {
+            TypeKind::Optional { inner } => {
+                let (some, none) = match style {
+                    Style::Display => (self.generate_format(quote! { v }, *inner, style), "null"),
+                    Style::Debug => {
+                        let inner = self.generate_format(quote! { v }, *inner, style);
+                        (quote! { format!("Some({})", #inner) }, "None")
+                    }
+                };
+                quote! {
+                    match #value {
+                        Some(v) => #some,
+                        None => #none.to_string(),
+                    }
+                }
+            }
+            TypeKind::Result { ok, err } => {
+                let (ok_format, err_format) = match style {
+                    Style::Display => ("ok({})", "err({})"),
+                    Style::Debug => ("Ok({})", "Err({})"),
+                };
+                let ok = self.generate_format(quote! { v }, *ok, style);
+                let err = self.generate_format(quote! { e }, *err, style);
+                quote! {
+                    match #value {
+                        Ok(v) => format!(#ok_format, #ok),
+                        Err(e) => format!(#err_format, #err),
+                    }
+                }
+            }
+            TypeKind::Tuple { elements } => {
+                let fields: Vec<TokenStream> = elements
+                    .iter()
+                    .enumerate()
+                    .map(|(i, element)| {
+                        let index = syn::Index::from(i);
+                        self.generate_format(quote! { &(#value).#index }, *element, style)
+                    })
+                    .collect();
+                // Rust writes a one-element tuple with a trailing comma
+                let format_str = if style == Style::Debug && fields.len() == 1 {
+                    "({},)"
+                } else {
+                    "({})"
+                };
+                quote! { format!(#format_str, [#(#fields),*].join(", ")) }
+            }
+            TypeKind::Array { element, .. } => self.generate_elements_format(value, *element),
+            TypeKind::List { element } => {
+                self.generate_elements_format(quote! { (#value).borrow() }, *element)
+            }
+            TypeKind::Map { value: entry, .. } => {
+                let key_format = match style {
+                    Style::Display => "{}: {}",
+                    Style::Debug => "{:?}: {}",
+                };
+                let entry = self.generate_format(quote! { v }, *entry, style);
+                quote! {
+                    {
+                        let map = #value;
+                        let map = map.borrow();
+                        let mut entries: Vec<_> = map.iter().collect();
+                        entries.sort_by(|a, b| a.0.cmp(b.0));
+                        let items: Vec<String> = entries
+                            .into_iter()
+                            .map(|(k, v)| format!(#key_format, k, #entry))
+                            .collect();
+                        format!("{{{}}}", items.join(", "))
+                    }
+                }
+            }
+            _ if type_id == TypeId::UNIT => quote! { format!("{:?}", #value) },
+            _ => quote! { format!("{}", #value) },
+        }
+    }
+
+    /// Generates `[a, b, ...]` for `elements`, a slice-like expression,
+    /// each element written in `Debug` style.
+    fn generate_elements_format(&self, elements: TokenStream, element: TypeId) -> TokenStream {
+        if !self.holds_shared_collection(element) {
+            return quote! { format!("{:?}", #elements) };
+        }
+
+        let element = self.generate_format(quote! { item }, element, Style::Debug);
+        quote! {
+            format!(
+                "[{}]",
+                (#elements).iter().map(|item| #element).collect::<Vec<String>>().join(", ")
+            )
+        }
+    }
+
+    /// Returns true if a value of the type contains a list or a map, whose
+    /// Rust `{:?}` output differs from how Rive writes it.
+    fn holds_shared_collection(&self, type_id: TypeId) -> bool {
+        let type_id = self.type_registry.resolve(type_id);
+        let Some(info) = self.type_registry.get(type_id) else {
+            return false;
+        };
+
+        match &info.kind {
+            TypeKind::List { .. } | TypeKind::Map { .. } => true,
+            TypeKind::Optional { inner } => self.holds_shared_collection(*inner),
+            TypeKind::Result { ok, err } => {
+                self.holds_shared_collection(*ok) || self.holds_shared_collection(*err)
+            }
+            TypeKind::Tuple { elements } => elements
+                .iter()
+                .any(|element| self.holds_shared_collection(*element)),
+            TypeKind::Array { element, .. } => self.holds_shared_collection(*element),
+            _ => false,
+        }
+    }
+}
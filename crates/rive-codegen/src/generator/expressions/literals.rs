@@ -22,8 +22,15 @@ impl CodeGenerator {
     }
 
     /// Generates code for an integer literal.
+    ///
+    /// Values outside the `i32` range are suffixed so Rust doesn't infer
+    /// its default integer type for them.
     pub(crate) fn generate_int_literal(&self, value: i64) -> Result<TokenStream> {
-        let lit = proc_macro2::Literal::i64_unsuffixed(value);
+        let lit = if i32::try_from(value).is_ok() {
+            proc_macro2::Literal::i64_unsuffixed(value)
+        } else {
+            proc_macro2::Literal::i64_suffixed(value)
+        };
         Ok(quote! { #lit })
     }
 
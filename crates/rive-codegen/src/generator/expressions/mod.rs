@@ -7,18 +7,24 @@
 //! - `operators`: Binary and unary operations
 //! - `collections`: Tuple, List, and Map literals
 //! - `methods`: Method call generation and dispatch
-//! - `calls`: Function calls (including print formatting)
+//! - `calls`: Function calls (including print)
+//! - `formatting`: How `print` and `assert_eq` write values
+//! - `builtins`: Built-in diagnostic functions (`panic`, `assert`, ...)
 //! - `nullable`: Null-related operations (Elvis, SafeCall, etc.)
+//! - `results`: Result constructors and error propagation
 //!
 //! Note: Control flow expressions (if, while, for, loop, match) are handled
 //! in the parent `control_flow` module.
 
+mod builtins;
 mod calls;
 mod collections;
+mod formatting;
 mod literals;
 mod methods;
 mod nullable;
 mod operators;
+mod results;
 
 use super::core::CodeGenerator;
 use proc_macro2::TokenStream;
@@ -39,6 +45,7 @@ impl CodeGenerator {
             RirExpression::StringLiteral { value, .. } => self.generate_string_literal(value),
             RirExpression::BoolLiteral { value, .. } => self.generate_bool_literal(*value),
             RirExpression::Variable { name, .. } => self.generate_variable(name),
+            RirExpression::Global { name, kind, .. } => Ok(self.generate_global_read(name, *kind)),
 
             // Operators
             RirExpression::Binary {
@@ -54,8 +61,9 @@ impl CodeGenerator {
             RirExpression::Call {
                 function,
                 arguments,
+                span,
                 ..
-            } => self.generate_call(function, arguments),
+            } => self.generate_call(function, arguments, *span),
 
             // Arrays
             RirExpression::ArrayLiteral { elements, .. } => self.generate_array_literal(elements),
@@ -81,9 +89,7 @@ impl CodeGenerator {
             } => self.generate_while_expr(condition, body, label, *result_type),
             RirExpression::For {
                 variable,
-                start,
-                end,
-                inclusive,
+                iterable,
                 body,
                 label,
                 result_type,
@@ -92,9 +98,7 @@ impl CodeGenerator {
                 use crate::generator::control_flow::ForLoopParams;
                 let params = ForLoopParams {
                     variable,
-                    start,
-                    end,
-                    inclusive: *inclusive,
+                    iterable,
                     body,
                     label,
                 };
@@ -109,6 +113,19 @@ impl CodeGenerator {
             } => self.generate_elvis(value, fallback),
             RirExpression::SafeCall { object, call, .. } => self.generate_safe_call(object, call),
             RirExpression::WrapOptional { value, .. } => self.generate_wrap_optional(value),
+            RirExpression::NonNullAssert { value, span, .. } => {
+                self.generate_non_null_assert(value, *span)
+            }
+            RirExpression::LetSome { name, value, .. } => self.generate_let_some(name, value),
+
+            // Result operations
+            RirExpression::ResultOk {
+                value, result_type, ..
+            } => self.generate_result_constructor(true, value, *result_type),
+            RirExpression::ResultErr {
+                value, result_type, ..
+            } => self.generate_result_constructor(false, value, *result_type),
+            RirExpression::Propagate { value, .. } => self.generate_propagate(value),
 
             // Collection literals
             RirExpression::TupleLiteral { elements, .. } => self.generate_tuple_literal(elements),
@@ -5,11 +5,14 @@
 //! - Elvis operator: `value ?: fallback` → `value.unwrap_or(fallback)`
 //! - Safe call: `object?.method()` → `object.and_then(|obj| ...)`
 //! - Wrap optional: `Some(value)`
+//! - Non-null assertion: `value!!` → `match value { Some(v) => v, None => panic!(..) }`
+//! - Null binding: `let name = value` → `let Some(name) = value`
 
 use super::super::core::CodeGenerator;
 use proc_macro2::TokenStream;
+use quote::format_ident;
 use quote::quote;
-use rive_core::Result;
+use rive_core::{Result, Span, type_system::TypeId};
 use rive_ir::RirExpression;
 
 impl CodeGenerator {
@@ -36,6 +39,7 @@ impl CodeGenerator {
     /// `value ?: fallback` compiles to:
     /// - `value.unwrap_or(fallback)` if fallback is a simple value
     /// - `value.unwrap_or_else(|| fallback)` if fallback is a complex expression
+    /// - `match value { Some(v) => v, None => fallback }` if fallback diverges
     pub(crate) fn generate_elvis(
         &mut self,
         value: &RirExpression,
@@ -44,6 +48,17 @@ impl CodeGenerator {
         let value_expr = self.generate_expression(value)?;
         let fallback_expr = self.generate_expression(fallback)?;
 
+        // A diverging fallback (e.g. `return`) must run in the enclosing
+        // function rather than inside a closure
+        if fallback.type_id() == TypeId::NEVER {
+            return Ok(quote! {
+                match #value_expr {
+                    Some(__value) => __value,
+                    None => #fallback_expr,
+                }
+            });
+        }
+
         // Check if fallback is a simple literal or variable
         // If so, use unwrap_or, otherwise use unwrap_or_else
         let is_simple = matches!(
@@ -87,4 +102,46 @@ impl CodeGenerator {
         // For now, we assume the call is self-contained
         Ok(quote! { #object_expr.and_then(|_obj| Some(#call_expr)) })
     }
+
+    /// Generates code for a non-null assertion.
+    ///
+    /// # Example
+    /// `value!!` compiles to:
+    /// `match value { Some(v) => v, None => panic!("null assertion failed at main.rive:3:14") }`
+    pub(crate) fn generate_non_null_assert(
+        &mut self,
+        value: &RirExpression,
+        span: Span,
+    ) -> Result<TokenStream> {
+        let value_expr = self.generate_expression(value)?;
+        let message = format!("null assertion failed at {}", self.source_location(span));
+
+        Ok(quote! {
+            match #value_expr {
+                Some(__value) => __value,
+                None => panic!(#message),
+            }
+        })
+    }
+
+    /// Generates code for a null binding condition.
+    ///
+    /// # Example
+    /// `if let v = maybe` compiles to `if let Some(v) = maybe.clone()`
+    ///
+    /// Variables of non-Copy types are cloned so they remain usable after the condition.
+    pub(crate) fn generate_let_some(
+        &mut self,
+        name: &str,
+        value: &RirExpression,
+    ) -> Result<TokenStream> {
+        let name = format_ident!("{}", name);
+        let value_expr = self.generate_expression(value)?;
+
+        if self.is_non_copy_variable(value) {
+            Ok(quote! { let Some(#name) = #value_expr.clone() })
+        } else {
+            Ok(quote! { let Some(#name) = #value_expr })
+        }
+    }
 }
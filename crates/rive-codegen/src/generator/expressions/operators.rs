@@ -21,7 +21,7 @@ impl CodeGenerator {
         result_type: TypeId,
     ) -> Result<TokenStream> {
         // Special handling for string concatenation
-        if *op == BinaryOp::Add && result_type == TypeId::TEXT {
+        if *op == BinaryOp::Add && self.type_registry.resolve(result_type) == TypeId::TEXT {
             let left_expr = self.generate_expression(left)?;
             let right_expr = self.generate_expression(right)?;
             return Ok(quote! { format!("{}{}", #left_expr, #right_expr) });
@@ -71,7 +71,9 @@ impl CodeGenerator {
         // For literals, directly generate negative/not literal without parentheses
         // This avoids unwanted spaces like "- 1" and makes output cleaner
         match (op, operand) {
-            (UnaryOp::Negate, RirExpression::IntLiteral { value, .. }) => {
+            (UnaryOp::Negate, RirExpression::IntLiteral { value, .. })
+                if value.checked_neg().is_some() =>
+            {
                 let lit = proc_macro2::Literal::i64_unsuffixed(-value);
                 Ok(quote! { #lit })
             }
@@ -81,10 +83,14 @@ impl CodeGenerator {
             }
             // For all other cases, generate operator without spaces using TokenStream
             _ => {
-                let operand_expr = self.generate_expression(operand)?;
+                let mut operand_expr = self.generate_expression(operand)?;
+                if needs_unary_parens(operand) {
+                    operand_expr = quote! { (#operand_expr) };
+                }
                 let op_char = match op {
                     UnaryOp::Negate => '-',
-                    UnaryOp::Not => '!',
+                    // Rust spells bitwise complement of integers as `!`
+                    UnaryOp::Not | UnaryOp::BitNot => '!',
                 };
 
                 let mut tokens = proc_macro2::TokenStream::new();
@@ -98,3 +104,18 @@ impl CodeGenerator {
         }
     }
 }
+
+/// Returns true if a unary operand must be parenthesized.
+///
+/// Rust prefix operators bind tighter than any binary operator, so
+/// `-(x + 3)` must keep its parentheses rather than become `-x + 3`.
+fn needs_unary_parens(operand: &RirExpression) -> bool {
+    matches!(
+        operand,
+        RirExpression::Binary { .. }
+            | RirExpression::If { .. }
+            | RirExpression::Match { .. }
+            | RirExpression::Block { .. }
+            | RirExpression::Elvis { .. }
+    )
+}
@@ -0,0 +1,69 @@
+//! Result type code generation.
+//!
+//! This module handles:
+//! - Result constructors: `ok(value)` → `Ok(value)`, `err(error)` → `Err(error)`
+//! - Error propagation: `value?` → `value?`
+
+use super::super::core::CodeGenerator;
+use super::super::types;
+use proc_macro2::TokenStream;
+use quote::quote;
+use rive_core::{
+    Error, Result,
+    type_system::{MemoryStrategy, TypeId, TypeKind},
+};
+use rive_ir::RirExpression;
+
+impl CodeGenerator {
+    /// Generates code for a result constructor.
+    ///
+    /// Both type arguments are spelled out so that Rust can infer the side
+    /// that is not constructed.
+    ///
+    /// # Example
+    /// - `ok(42)` → `Ok::<i64, String>(42)`
+    /// - `err("bad input")` → `Err::<i64, String>("bad input".to_string())`
+    pub(crate) fn generate_result_constructor(
+        &mut self,
+        is_ok: bool,
+        value: &RirExpression,
+        result_type: TypeId,
+    ) -> Result<TokenStream> {
+        let (ok_type, err_type) = self.result_type_arguments(result_type)?;
+        let mut value_expr = self.generate_expression(value)?;
+
+        // String literals are `&str` in Rust, but Result payloads of type Text are `String`
+        if matches!(value, RirExpression::StringLiteral { .. }) {
+            value_expr = quote! { #value_expr.to_string() };
+        }
+
+        if is_ok {
+            Ok(quote! { Ok::<#ok_type, #err_type>(#value_expr) })
+        } else {
+            Ok(quote! { Err::<#ok_type, #err_type>(#value_expr) })
+        }
+    }
+
+    /// Returns the Rust types of the ok and err sides of a Result type.
+    fn result_type_arguments(&self, result_type: TypeId) -> Result<(TokenStream, TokenStream)> {
+        match self.type_registry.get(result_type).map(|meta| &meta.kind) {
+            Some(TypeKind::Result { ok, err }) => Ok((
+                types::rust_type(*ok, MemoryStrategy::Copy, &self.type_registry)?,
+                types::rust_type(*err, MemoryStrategy::Copy, &self.type_registry)?,
+            )),
+            _ => Err(Error::Codegen(format!(
+                "Expected a Result type, found type {}",
+                result_type
+            ))),
+        }
+    }
+
+    /// Generates code for error propagation.
+    ///
+    /// # Example
+    /// `parse(input)?` → `parse(input)?`
+    pub(crate) fn generate_propagate(&mut self, value: &RirExpression) -> Result<TokenStream> {
+        let value_expr = self.generate_expression(value)?;
+        Ok(quote! { #value_expr? })
+    }
+}
@@ -0,0 +1,57 @@
+//! Module-level variable code generation.
+//!
+//! Globals are prefixed with `__RIVE_GLOBAL_`, so they never collide with
+//! each other or with local variables (a local binding named like a `const`
+//! item would be a pattern, not a new variable):
+//! - `const` globals with a value known at compile time become `const` items
+//! - `let` globals with a value known at compile time become `static` items
+//! - anything else is a `thread_local!` initialized on first use and cloned on read
+
+use super::{core::CodeGenerator, types};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use rive_core::Result;
+use rive_core::type_system::MemoryStrategy;
+use rive_ir::{GlobalKind, RirGlobal};
+
+impl CodeGenerator {
+    /// Generates the item declaring a global.
+    pub(crate) fn generate_global(&mut self, global: &RirGlobal) -> Result<TokenStream> {
+        let name = global_ident(&global.name);
+        let ty = types::rust_type(global.type_id, MemoryStrategy::Copy, &self.type_registry)?;
+        let value = self.generate_expression(&global.value)?;
+
+        Ok(match global.kind {
+            GlobalKind::Const => quote! {
+                #[allow(non_upper_case_globals)]
+                const #name: #ty = #value;
+            },
+            GlobalKind::Static => quote! {
+                #[allow(non_upper_case_globals)]
+                static #name: #ty = #value;
+            },
+            // String literals are generated as `&str`, so the value is
+            // converted into the declared type
+            GlobalKind::Lazy => quote! {
+                thread_local! {
+                    #[allow(non_upper_case_globals)]
+                    static #name: #ty = ::std::convert::Into::into(#value);
+                }
+            },
+        })
+    }
+
+    /// Generates a read of a global.
+    pub(crate) fn generate_global_read(&self, name: &str, kind: GlobalKind) -> TokenStream {
+        let name = global_ident(name);
+        match kind {
+            GlobalKind::Const | GlobalKind::Static => quote! { #name },
+            GlobalKind::Lazy => quote! { #name.with(|value| value.clone()) },
+        }
+    }
+}
+
+/// Returns the Rust name of a global.
+fn global_ident(name: &str) -> Ident {
+    format_ident!("__RIVE_GLOBAL_{}", name)
+}
@@ -0,0 +1,127 @@
+//! Test harness generation.
+//!
+//! The harness `main` runs every `@test` function whose name contains the
+//! first command-line argument, catching panics so one failing test doesn't
+//! stop the others. Its report follows `cargo test`:
+//!
+//! ```text
+//! running 2 tests
+//! test adds ... ok
+//! test divides ... FAILED
+//!
+//! failures:
+//!
+//! ---- divides at main.rive:8:1 ----
+//! assertion failed at main.rive:9:5
+//!
+//! test result: FAILED. 1 passed; 1 failed; 0 filtered out
+//! ```
+//!
+//! The process exits with status 101 if any test fails.
+
+use super::core::CodeGenerator;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use rive_ir::RirModule;
+
+impl CodeGenerator {
+    /// Generates the `main` function of a test harness for the module's tests.
+    pub(crate) fn generate_harness(&mut self, module: &RirModule) -> TokenStream {
+        let tests = module
+            .functions
+            .iter()
+            .filter_map(|function| {
+                let test = function.test.as_ref()?;
+                let name = function.name.as_str();
+                let ident = format_ident!("{}", function.name);
+                let location = self.source_location(function.span);
+                let should_panic = test.should_panic;
+                let expected = match &test.expected_message {
+                    Some(message) => quote! { Some(#message) },
+                    None => quote! { None },
+                };
+                Some(quote! { (#name, #location, #ident as fn(), #should_panic, #expected) })
+            })
+            .collect::<Vec<_>>();
+
+        quote! {
+            thread_local! {
+                static RIVE_TEST_PANIC: std::cell::RefCell<String> =
+                    std::cell::RefCell::new(String::new());
+            }
+
+            fn main() {
+                // Panic messages are reported with the failing test, not as they happen
+                std::panic::set_hook(Box::new(|info| {
+                    let payload = info.payload();
+                    let message = payload
+                        .downcast_ref::<String>()
+                        .map(String::as_str)
+                        .or_else(|| payload.downcast_ref::<&str>().copied())
+                        .unwrap_or("explicit panic")
+                        .to_string();
+                    RIVE_TEST_PANIC.with(|panic| *panic.borrow_mut() = message);
+                }));
+
+                let tests: Vec<(&str, &str, fn(), bool, Option<&str>)> = vec![#(#tests),*];
+                let filter = std::env::args().nth(1);
+                let selected: Vec<_> = tests
+                    .iter()
+                    .filter(|test| filter.as_deref().is_none_or(|filter| test.0.contains(filter)))
+                    .collect();
+                let filtered_out = tests.len() - selected.len();
+
+                let plural = if selected.len() == 1 { "" } else { "s" };
+                println!("\nrunning {} test{}", selected.len(), plural);
+
+                let mut failures = Vec::new();
+                for &&(name, location, test, should_panic, expected) in &selected {
+                    let panicked = std::panic::catch_unwind(test).is_err();
+                    let message = RIVE_TEST_PANIC.with(|panic| panic.take());
+                    let failure = match (panicked, should_panic, expected) {
+                        (false, false, _) | (true, true, None) => None,
+                        (false, true, _) => Some("test did not panic as expected".to_string()),
+                        (true, false, _) => Some(message),
+                        (true, true, Some(expected)) => {
+                            if message.contains(expected) {
+                                None
+                            } else {
+                                Some(format!(
+                                    "panic did not contain expected string\n      panic message: {:?}\n expected substring: {:?}",
+                                    message, expected
+                                ))
+                            }
+                        }
+                    };
+
+                    match failure {
+                        None => println!("test {} ... ok", name),
+                        Some(failure) => {
+                            println!("test {} ... FAILED", name);
+                            failures.push((name, location, failure));
+                        }
+                    }
+                }
+
+                if !failures.is_empty() {
+                    println!("\nfailures:\n");
+                    for (name, location, failure) in &failures {
+                        println!("---- {} at {} ----\n{}\n", name, location, failure);
+                    }
+                }
+
+                let result = if failures.is_empty() { "ok" } else { "FAILED" };
+                println!(
+                    "\ntest result: {}. {} passed; {} failed; {} filtered out\n",
+                    result,
+                    selected.len() - failures.len(),
+                    failures.len(),
+                    filtered_out
+                );
+                if !failures.is_empty() {
+                    std::process::exit(101);
+                }
+            }
+        }
+    }
+}
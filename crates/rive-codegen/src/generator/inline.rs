@@ -65,10 +65,8 @@ fn count_statements(block: &RirBlock) -> usize {
 fn has_complex_control_flow(block: &RirBlock) -> bool {
     for stmt in &block.statements {
         match stmt {
-            RirStatement::Block { block, .. } => {
-                if has_complex_control_flow(block) {
-                    return true;
-                }
+            RirStatement::Block { block, .. } if has_complex_control_flow(block) => {
+                return true;
             }
             RirStatement::If {
                 then_block,
@@ -110,15 +108,15 @@ fn has_recursive_calls(function: &RirFunction) -> bool {
 fn check_recursive_calls_in_block(block: &RirBlock, function_name: &str) -> bool {
     for stmt in &block.statements {
         match stmt {
-            RirStatement::Expression { expr, .. } => {
-                if check_recursive_calls_in_expr(expr, function_name) {
-                    return true;
-                }
+            RirStatement::Expression { expr, .. }
+                if check_recursive_calls_in_expr(expr, function_name) =>
+            {
+                return true;
             }
-            RirStatement::Let { value, .. } | RirStatement::Assign { value, .. } => {
-                if check_recursive_calls_in_expr(value, function_name) {
-                    return true;
-                }
+            RirStatement::Let { value, .. } | RirStatement::Assign { value, .. }
+                if check_recursive_calls_in_expr(value, function_name) =>
+            {
+                return true;
             }
             RirStatement::Return { value, .. } => {
                 if let Some(value) = value
@@ -127,10 +125,10 @@ fn check_recursive_calls_in_block(block: &RirBlock, function_name: &str) -> bool
                     return true;
                 }
             }
-            RirStatement::Block { block, .. } => {
-                if check_recursive_calls_in_block(block, function_name) {
-                    return true;
-                }
+            RirStatement::Block { block, .. }
+                if check_recursive_calls_in_block(block, function_name) =>
+            {
+                return true;
             }
             RirStatement::If {
                 then_block,
@@ -148,10 +146,10 @@ fn check_recursive_calls_in_block(block: &RirBlock, function_name: &str) -> bool
             }
             RirStatement::While { body, .. }
             | RirStatement::For { body, .. }
-            | RirStatement::Loop { body, .. } => {
-                if check_recursive_calls_in_block(body, function_name) {
-                    return true;
-                }
+            | RirStatement::Loop { body, .. }
+                if check_recursive_calls_in_block(body, function_name) =>
+            {
+                return true;
             }
             RirStatement::Match { arms, .. } => {
                 for (_, arm_body) in arms {
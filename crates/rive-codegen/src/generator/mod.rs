@@ -3,6 +3,8 @@
 mod control_flow;
 mod core;
 mod expressions;
+mod globals;
+mod harness;
 mod inline;
 mod labels;
 mod patterns;
@@ -3,8 +3,11 @@
 use super::core::CodeGenerator;
 use super::labels;
 use proc_macro2::TokenStream;
-use quote::quote;
-use rive_core::{Result, type_system::TypeId};
+use quote::{format_ident, quote};
+use rive_core::{
+    Result,
+    type_system::{TypeId, TypeKind},
+};
 use rive_ir::{RirBlock, RirExpression, RirPattern};
 
 impl CodeGenerator {
@@ -14,25 +17,21 @@ impl CodeGenerator {
         scrutinee: &RirExpression,
         arms: &[(RirPattern, Box<RirExpression>)],
     ) -> Result<TokenStream> {
-        let val = self.generate_expression(scrutinee)?;
-        let match_val = self.prepare_match_value(val, scrutinee.type_id());
-
         let match_arms: Result<Vec<_>> = arms
             .iter()
             .map(|(pattern, body)| {
                 let pat = self.generate_pattern(pattern)?;
+                let conversions = self.binding_conversions(pattern);
                 let body_expr = self.generate_expression(body)?;
-                Ok(quote! { #pat => #body_expr })
+                if conversions.is_empty() {
+                    Ok(quote! { #pat => #body_expr })
+                } else {
+                    Ok(quote! { #pat => { #(#conversions)* #body_expr } })
+                }
             })
             .collect();
 
-        let match_arms = match_arms?;
-
-        Ok(quote! {
-            match #match_val {
-                #(#match_arms),*
-            }
-        })
+        self.generate_match(scrutinee, match_arms?)
     }
 
     /// Generates code for a match statement.
@@ -41,24 +40,52 @@ impl CodeGenerator {
         scrutinee: &RirExpression,
         arms: &[(RirPattern, RirBlock)],
     ) -> Result<TokenStream> {
-        let val = self.generate_expression(scrutinee)?;
-        let match_val = self.prepare_match_value(val, scrutinee.type_id());
-
         let match_arms: Result<Vec<_>> = arms
             .iter()
             .map(|(pattern, body)| {
                 let pat = self.generate_pattern(pattern)?;
+                let conversions = self.binding_conversions(pattern);
                 let body_stmts = self.generate_block(body)?;
                 Ok(quote! {
                     #pat => {
+                        #(#conversions)*
                         #body_stmts
                     }
                 })
             })
             .collect();
 
-        let match_arms = match_arms?;
+        self.generate_match(scrutinee, match_arms?)
+    }
+
+    /// Generates a `match` over the prepared scrutinee with the given arms.
+    fn generate_match(
+        &mut self,
+        scrutinee: &RirExpression,
+        match_arms: Vec<TokenStream>,
+    ) -> Result<TokenStream> {
+        let type_id = scrutinee.type_id();
 
+        // Tuple views borrow several fields, so a temporary scrutinee is bound first
+        let is_tuple = self
+            .type_registry
+            .get(type_id)
+            .is_some_and(|meta| matches!(meta.kind, TypeKind::Tuple { .. }));
+        if is_tuple && !matches!(scrutinee, RirExpression::Variable { .. }) {
+            let value = self.generate_expression(scrutinee)?;
+            if let Some(view) = self.match_view(&quote! { __scrutinee }, type_id) {
+                return Ok(quote! {
+                    {
+                        let __scrutinee = #value;
+                        match #view {
+                            #(#match_arms),*
+                        }
+                    }
+                });
+            }
+        }
+
+        let match_val = self.prepare_match_value(scrutinee)?;
         Ok(quote! {
             match #match_val {
                 #(#match_arms),*
@@ -66,15 +93,105 @@ impl CodeGenerator {
         })
     }
 
-    /// Prepares a value for matching (converts Text to &str).
-    pub(crate) fn prepare_match_value(&self, val: TokenStream, type_id: TypeId) -> TokenStream {
-        if type_id == TypeId::TEXT {
-            quote! { (#val).as_str() }
+    /// Prepares a value for matching.
+    ///
+    /// Text is matched as `&str` (also inside nullables and tuples), and non-Copy
+    /// variables are cloned so that patterns binding their contents do not move them.
+    pub(crate) fn prepare_match_value(&mut self, scrutinee: &RirExpression) -> Result<TokenStream> {
+        let val = self.generate_expression(scrutinee)?;
+        if let Some(view) = self.match_view(&val, scrutinee.type_id()) {
+            Ok(view)
+        } else if self.is_non_copy_variable(scrutinee) {
+            Ok(quote! { #val.clone() })
         } else {
-            val
+            Ok(val)
+        }
+    }
+
+    /// Returns the form of `value` that string literal patterns can match, or
+    /// `None` if the type contains no Text.
+    fn match_view(&self, value: &TokenStream, type_id: TypeId) -> Option<TokenStream> {
+        if self.type_registry.resolve(type_id) == TypeId::TEXT {
+            return Some(quote! { &*(#value) });
+        }
+
+        match &self.type_registry.get(type_id)?.kind {
+            TypeKind::Optional { inner } if self.type_registry.resolve(*inner) == TypeId::TEXT => {
+                Some(quote! { (#value).as_deref() })
+            }
+            TypeKind::Optional { inner } => {
+                let inner_view = self.match_view(&quote! { __inner }, *inner)?;
+                Some(quote! { (#value).as_ref().map(|__inner| #inner_view) })
+            }
+            TypeKind::Tuple { elements } => {
+                if !elements
+                    .iter()
+                    .any(|&e| self.match_view(value, e).is_some())
+                {
+                    return None;
+                }
+                let fields = elements.iter().enumerate().map(|(index, &element)| {
+                    let index = syn::Index::from(index);
+                    let field = quote! { #value.#index };
+                    self.match_view(&field, element).unwrap_or_else(|| {
+                        if self.type_registry.get(element).is_some_and(|m| m.is_copy()) {
+                            field
+                        } else {
+                            quote! { #field.clone() }
+                        }
+                    })
+                });
+                Some(quote! { (#(#fields,)*) })
+            }
+            _ => None,
+        }
+    }
+
+    /// Converts a value bound from a match view back to its owned type, or
+    /// returns `None` if the binding already has its owned type.
+    fn owned_from_view(&self, value: &TokenStream, type_id: TypeId) -> Option<TokenStream> {
+        self.match_view(value, type_id)?;
+
+        if self.type_registry.resolve(type_id) == TypeId::TEXT {
+            return Some(quote! { #value.to_string() });
+        }
+
+        match &self.type_registry.get(type_id)?.kind {
+            TypeKind::Optional { inner } if self.type_registry.resolve(*inner) == TypeId::TEXT => {
+                Some(quote! { #value.map(str::to_string) })
+            }
+            TypeKind::Optional { inner } => {
+                let inner_owned = self.owned_from_view(&quote! { __inner }, *inner)?;
+                Some(quote! { #value.map(|__inner| #inner_owned) })
+            }
+            TypeKind::Tuple { elements } => {
+                let fields = elements.iter().enumerate().map(|(index, &element)| {
+                    let index = syn::Index::from(index);
+                    let field = quote! { #value.#index };
+                    self.owned_from_view(&field, element).unwrap_or(field)
+                });
+                Some(quote! { (#(#fields,)*) })
+            }
+            _ => None,
         }
     }
 
+    /// Generates `let` statements restoring the owned type of bindings that
+    /// were matched against a view (e.g. `&str` back to `String`).
+    fn binding_conversions(&self, pattern: &RirPattern) -> Vec<TokenStream> {
+        let mut bindings = Vec::new();
+        collect_bindings(pattern, &mut bindings);
+
+        bindings
+            .into_iter()
+            .filter_map(|(name, type_id)| {
+                let name = format_ident!("{}", name);
+                let owned = self.owned_from_view(&quote! { #name }, type_id)?;
+                Some(quote! { let #name = #owned; })
+            })
+            .collect()
+    }
+
     /// Generates code for a pattern.
     pub(crate) fn generate_pattern(&mut self, pattern: &RirPattern) -> Result<TokenStream> {
         Ok(match pattern {
@@ -106,6 +223,71 @@ impl CodeGenerator {
                 let end_expr = self.generate_expression(end)?;
                 labels::generate_range(&start_expr, &end_expr, *inclusive)
             }
+            RirPattern::ResultOk { binding, .. } => {
+                let binding = binding_pattern(binding.as_deref());
+                quote! { Ok(#binding) }
+            }
+            RirPattern::ResultErr { binding, .. } => {
+                let binding = binding_pattern(binding.as_deref());
+                quote! { Err(#binding) }
+            }
+            RirPattern::Null { .. } => quote! { None },
+            RirPattern::NonNull { inner, .. } => {
+                let inner = self.generate_pattern(inner)?;
+                quote! { Some(#inner) }
+            }
+            RirPattern::Binding { name, .. } => binding_pattern(Some(name)),
+            RirPattern::Tuple { elements, .. } => {
+                let elements = elements
+                    .iter()
+                    .map(|element| self.generate_pattern(element))
+                    .collect::<Result<Vec<_>>>()?;
+                quote! { (#(#elements,)*) }
+            }
+            RirPattern::Or { alternatives, .. } => {
+                let alternatives = alternatives
+                    .iter()
+                    .map(|alternative| self.generate_pattern(alternative))
+                    .collect::<Result<Vec<_>>>()?;
+                quote! { #(#alternatives)|* }
+            }
+            RirPattern::Guarded { pattern, guard, .. } => {
+                let pattern = self.generate_pattern(pattern)?;
+                let guard = self.generate_expression(guard)?;
+                quote! { #pattern if #guard }
+            }
         })
     }
 }
+
+/// Collects the names and types bound by a pattern.
+/// Or-pattern alternatives bind the same names, so only the first is visited.
+fn collect_bindings<'a>(pattern: &'a RirPattern, bindings: &mut Vec<(&'a str, TypeId)>) {
+    match pattern {
+        RirPattern::Binding { name, type_id, .. } => bindings.push((name, *type_id)),
+        RirPattern::NonNull { inner, .. } => collect_bindings(inner, bindings),
+        RirPattern::Guarded { pattern, .. } => collect_bindings(pattern, bindings),
+        RirPattern::Tuple { elements, .. } => {
+            for element in elements {
+                collect_bindings(element, bindings);
+            }
+        }
+        RirPattern::Or { alternatives, .. } => {
+            if let Some(first) = alternatives.first() {
+                collect_bindings(first, bindings);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Generates a binding name, or `_` when the value is ignored.
+fn binding_pattern(binding: Option<&str>) -> TokenStream {
+    match binding {
+        Some(name) => {
+            let name = format_ident!("{}", name);
+            quote! { #name }
+        }
+        None => quote! { _ },
+    }
+}
@@ -64,9 +64,7 @@ impl CodeGenerator {
 
             RirStatement::For {
                 variable,
-                start,
-                end,
-                inclusive,
+                iterable,
                 body,
                 label,
                 ..
@@ -74,9 +72,7 @@ impl CodeGenerator {
                 use crate::generator::control_flow::ForLoopParams;
                 let params = ForLoopParams {
                     variable,
-                    start,
-                    end,
-                    inclusive: *inclusive,
+                    iterable,
                     body,
                     label,
                 };
@@ -4,7 +4,7 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use rive_core::{
     Result,
-    type_system::{MemoryStrategy, TypeId},
+    type_system::{MemoryStrategy, TypeId, TypeKind, TypeRegistry},
 };
 
 /// Converts a TypeId and MemoryStrategy to a Rust type.
@@ -14,28 +14,85 @@ use rive_core::{
 /// - Copy: Direct value types (i64, f64, bool, String)
 /// - CoW: Copy-on-write types (Rc<String>, Rc<Vec<T>>)
 /// - Unique: Move-only types (not yet fully implemented)
-pub fn rust_type(type_id: TypeId, strategy: MemoryStrategy) -> Result<TokenStream> {
+///
+/// Composite types (optionals, results, tuples, arrays, lists and maps) are
+/// resolved through the registry and mirror the representation used by the
+/// expression generators. Aliases become the Rust type of their target.
+pub fn rust_type(
+    type_id: TypeId,
+    strategy: MemoryStrategy,
+    registry: &TypeRegistry,
+) -> Result<TokenStream> {
+    let type_id = registry.resolve(type_id);
     match type_id {
         TypeId::INT => Ok(quote! { i64 }),
         TypeId::FLOAT => Ok(quote! { f64 }),
         TypeId::BOOL => Ok(quote! { bool }),
         TypeId::UNIT => Ok(quote! { () }),
+        TypeId::NEVER => Ok(quote! { ! }),
         TypeId::TEXT => match strategy {
             MemoryStrategy::Copy => Ok(quote! { String }),
             MemoryStrategy::CoW => Ok(quote! { String }),
             MemoryStrategy::Unique => Ok(quote! { String }),
         },
+        _ => composite_rust_type(type_id, strategy, registry),
+    }
+}
+
+/// Converts a composite type to a Rust type.
+fn composite_rust_type(
+    type_id: TypeId,
+    strategy: MemoryStrategy,
+    registry: &TypeRegistry,
+) -> Result<TokenStream> {
+    let Some(meta) = registry.get(type_id) else {
+        return Ok(quote! { () });
+    };
+
+    match &meta.kind {
+        TypeKind::Optional { inner } => {
+            let inner = rust_type(*inner, strategy, registry)?;
+            Ok(quote! { Option<#inner> })
+        }
+        TypeKind::Result { ok, err } => {
+            let ok = rust_type(*ok, strategy, registry)?;
+            let err = rust_type(*err, strategy, registry)?;
+            Ok(quote! { Result<#ok, #err> })
+        }
+        TypeKind::Tuple { elements } => {
+            let elements = elements
+                .iter()
+                .map(|e| rust_type(*e, strategy, registry))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(quote! { (#(#elements,)*) })
+        }
+        TypeKind::Array { element, size } => {
+            let element = rust_type(*element, strategy, registry)?;
+            let size = proc_macro2::Literal::usize_unsuffixed(*size);
+            Ok(quote! { [#element; #size] })
+        }
+        TypeKind::List { element } => {
+            let element = rust_type(*element, strategy, registry)?;
+            Ok(quote! { std::rc::Rc<std::cell::RefCell<Vec<#element>>> })
+        }
+        TypeKind::Map { key, value } => {
+            let key = rust_type(*key, strategy, registry)?;
+            let value = rust_type(*value, strategy, registry)?;
+            Ok(quote! {
+                std::rc::Rc<std::cell::RefCell<std::collections::HashMap<#key, #value>>>
+            })
+        }
         _ => Ok(quote! { () }),
     }
 }
 
 /// Generates return type annotation.
-pub fn generate_return_type(type_id: TypeId) -> TokenStream {
-    if type_id == TypeId::UNIT {
+pub fn generate_return_type(type_id: TypeId, registry: &TypeRegistry) -> TokenStream {
+    if registry.resolve(type_id) == TypeId::UNIT {
         quote! {}
     } else {
         // Return types typically use Copy or CoW strategy, not RcRefCell
-        let rust_ty = rust_type(type_id, MemoryStrategy::Copy).unwrap();
+        let rust_ty = rust_type(type_id, MemoryStrategy::Copy, registry).unwrap();
         quote! {-> #rust_ty}
     }
 }
@@ -14,8 +14,12 @@ pub const fn operator_precedence(op: &BinaryOp) -> u8 {
         | BinaryOp::LessEqual
         | BinaryOp::GreaterThan
         | BinaryOp::GreaterEqual => 4,
-        BinaryOp::Add | BinaryOp::Subtract => 5,
-        BinaryOp::Multiply | BinaryOp::Divide | BinaryOp::Modulo => 6,
+        BinaryOp::BitOr => 5,
+        BinaryOp::BitXor => 6,
+        BinaryOp::BitAnd => 7,
+        BinaryOp::ShiftLeft | BinaryOp::ShiftRight => 8,
+        BinaryOp::Add | BinaryOp::Subtract => 9,
+        BinaryOp::Multiply | BinaryOp::Divide | BinaryOp::Modulo => 10,
     }
 }
 
@@ -41,5 +45,10 @@ pub fn binary_op_token(op: &BinaryOp) -> TokenStream {
         BinaryOp::GreaterEqual => quote! { >= },
         BinaryOp::And => quote! { && },
         BinaryOp::Or => quote! { || },
+        BinaryOp::BitAnd => quote! { & },
+        BinaryOp::BitOr => quote! { | },
+        BinaryOp::BitXor => quote! { ^ },
+        BinaryOp::ShiftLeft => quote! { << },
+        BinaryOp::ShiftRight => quote! { >> },
     }
 }
@@ -230,3 +230,567 @@ fn test_generate_nullable_type_conversion() {
     let rust_code = compile_to_rust(source);
     assert!(rust_code.contains("Some"), "T -> T? should wrap in Some");
 }
+
+#[test]
+fn test_generate_non_null_assert() {
+    let source = r#"fun main() {
+    let x: Int? = 42
+    let y = x!!
+}"#;
+    let rust_code = compile_to_rust(source);
+    assert!(rust_code.contains("None => panic!"));
+    assert!(
+        rust_code.contains("main.rive:3:13"),
+        "Panic message should report the Rive source location"
+    );
+}
+
+#[test]
+fn test_generate_if_let_and_while_let() {
+    let source = r#"
+        fun main() {
+            let mut x: Int? = 42
+            if let v = x {
+                print(v)
+            }
+            while let w = x {
+                x = null
+            }
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(rust_code.contains("if let Some(v) = x"));
+    assert!(rust_code.contains("while let Some(w) = x"));
+}
+
+#[test]
+fn test_generate_result() {
+    let source = r#"
+        fun check(x: Int): Result<Int, Text> {
+            if x < 0 {
+                return err("negative")
+            }
+            let v = check(x - 1)?
+            ok(v)
+        }
+
+        fun main() {
+            when check(3) {
+                ok(v) -> print(v)
+                err(e) -> print(e)
+            }
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(rust_code.contains("-> Result<i64, String>"));
+    assert!(rust_code.contains("Err::<i64, String>(\"negative\".to_string())"));
+    assert!(rust_code.contains("Ok::<i64, String>(v)"));
+    assert!(rust_code.contains("check(x - 1)?"));
+    assert!(rust_code.contains("Ok(v) =>"));
+    assert!(rust_code.contains("Err(e) =>"));
+}
+
+#[test]
+fn test_generate_result_constructors_in_branches() {
+    let source = r#"
+        fun parse(c: Bool): Result<Int, Text> {
+            if c { ok(1) } else { err("bad") }
+        }
+
+        fun main() {
+            let r: Result<Int, Text> = when parse(true) {
+                ok(v) -> ok(v * 2)
+                err(e) -> err(e)
+            }
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(
+        rust_code.contains("Ok::<i64, String>(1)"),
+        "got: {rust_code}"
+    );
+    assert!(rust_code.contains("Err::<i64, String>(\"bad\".to_string())"));
+    assert!(rust_code.contains("Ok(v) => Ok::<i64, String>(v * 2)"));
+}
+
+#[test]
+fn test_generate_trailing_call_as_return_value() {
+    let source = r#"
+        fun next(x: Int): Int { x + 1 }
+
+        fun twice(x: Int): Int {
+            let y = next(x)
+            next(y)
+        }
+
+        fun main() {
+            print(twice(1))
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(
+        rust_code.contains("next(y)\n}"),
+        "the trailing call should be the return value, got: {rust_code}"
+    );
+    assert!(!rust_code.contains("next(y);"));
+}
+
+#[test]
+fn test_generate_builtins() {
+    let source = r#"fun main() {
+    let x = 2
+    assert(x > 0)
+    assert_eq(x, 2)
+    let y: Int = when x {
+        2 -> 20
+        _ -> panic("bad")
+    }
+}"#;
+    let rust_code = compile_to_rust(source);
+    assert!(rust_code.contains("assertion failed at main.rive:3:5"));
+    assert!(rust_code.contains("failed at main.rive:4:5"));
+    assert!(rust_code.contains("\"{} at main.rive:7:14\""));
+    assert!(
+        rust_code.contains("std::panic::set_hook"),
+        "main should hide the generated Rust panic location"
+    );
+}
+
+#[test]
+fn test_generate_calls_without_builtins() {
+    let source = r#"fun double(x: Int): Int {
+    return x * 2
+}
+
+fun main() {
+    print(double(double(1)))
+}"#;
+    let rust_code = compile_to_rust(source);
+    assert!(rust_code.contains("double(double(1))"));
+    assert!(
+        !rust_code.contains("std::panic::set_hook"),
+        "only programs that report Rive locations need a panic hook"
+    );
+}
+
+#[test]
+fn test_generate_user_function_named_like_builtin() {
+    let source = r#"fun assert(x: Int): Int {
+    return x * 2
+}
+
+fun main() {
+    print(assert(5))
+}"#;
+    let rust_code = compile_to_rust(source);
+    assert!(rust_code.contains("fn assert(x: i64) -> i64"));
+    assert!(rust_code.contains("assert(5)"));
+    assert!(!rust_code.contains("assert!"));
+}
+
+#[test]
+fn test_generate_args_and_exit() {
+    let source = r#"fun main(): Int {
+    let arguments = args()
+    if arguments.is_empty() {
+        exit(2)
+    }
+    return 0
+}"#;
+    let rust_code = compile_to_rust(source);
+    assert!(rust_code.contains("std::env::args().skip(1)"));
+    assert!(rust_code.contains("std::process::exit((2) as i32)"));
+    assert!(
+        rust_code.contains("fn main() {"),
+        "Rust main cannot return the Int status"
+    );
+    assert!(rust_code.contains("std::process::exit(status as i32)"));
+}
+
+#[test]
+fn test_generate_test_harness() {
+    let source = r#"fun main() {
+    print("not a test")
+}
+
+@test
+fun adds() {
+    assert(1 + 1 == 2)
+}
+
+@test(panics = "boom")
+fun explodes() {
+    panic("boom")
+}"#;
+    let tokens = tokenize(source).unwrap();
+    let (ast, type_registry) = parse(&tokens).unwrap();
+    let rir_module = AstLowering::new(type_registry).lower_program(&ast).unwrap();
+    let rust_code = CodeGenerator::new().generate_tests(&rir_module).unwrap();
+
+    assert!(
+        !rust_code.contains("not a test"),
+        "the program's main is replaced"
+    );
+    assert!(rust_code.contains("(\"adds\", \"main.rive:6:1\", adds as fn (), false, None)"));
+    assert!(rust_code.contains("\"main.rive:11:1\", explodes as fn (), true, Some(\"boom\"))"));
+    assert!(rust_code.contains("std::panic::catch_unwind(test)"));
+    assert!(rust_code.contains("std::process::exit(101)"));
+}
+
+#[test]
+fn test_generate_diverging_expressions() {
+    let source = r#"
+        fun get(x: Int?): Int {
+            let y = x ?: return 0
+            let z = when y {
+                1 -> return 1
+                _ -> y * 2
+            }
+            return z
+        }
+
+        fun main() {
+            let mut i = 0
+            while i < 10 {
+                i = i + 1
+                if i > 5 { break }
+            }
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(
+        rust_code.contains("None => {\n            return 0;"),
+        "A diverging elvis fallback must not run inside a closure"
+    );
+    assert!(rust_code.contains("1 => {\n            return 1;"));
+    assert!(
+        !rust_code.contains("break 'loop_1 None"),
+        "break in a while statement carries no value"
+    );
+}
+
+#[test]
+fn test_generate_destructuring() {
+    let source = r#"
+        fun pair(n: Int): (Int, Int) {
+            return (n, n * 2)
+        }
+
+        fun main() {
+            let (a, (b, _)) = (1, pair(2))
+            let pairs = [(1, 10), (2, 20)]
+            for (i, v) in pairs {
+                print(i * v)
+            }
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(rust_code.contains("fn pair(n: i64) -> (i64, i64)"));
+    assert!(rust_code.contains("let __tuple_1 = (1, pair(2));"));
+    assert!(rust_code.contains("let a = __tuple_1.0;"));
+    assert!(rust_code.contains("let b = __tuple_1.1.0;"));
+    assert!(rust_code.contains("for __item_2 in (pairs).clone()"));
+    assert!(rust_code.contains("let i = __item_2.0;"));
+}
+
+#[test]
+fn test_generate_rich_when_patterns() {
+    let source = r#"
+        fun describe(p: (Int, Text), x: Int?): Int {
+            let y = when x {
+                null -> 0
+                1 | 2 -> 1
+                n -> 2
+            }
+            return when p {
+                (0, _) -> y
+                (n, "double") if n > 0 -> n * 2
+                (n, name) -> n + name.len()
+            }
+        }
+
+        fun main() {}
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(rust_code.contains("None => 0"));
+    assert!(rust_code.contains("Some(1) | Some(2) => 1"));
+    assert!(
+        rust_code.contains("Some(n) => 2"),
+        "a binding after the null arm binds the non-null value"
+    );
+    assert!(rust_code.contains("match (p.0, &*(p.1))"));
+    assert!(rust_code.contains("(n, \"double\") if n > 0 => n * 2"));
+    assert!(
+        rust_code.contains("let name = name.to_string();"),
+        "Text bindings are converted back to String"
+    );
+}
+
+#[test]
+fn test_generate_subjectless_when() {
+    let source = r#"
+        fun sign(x: Int): Int {
+            let s = when {
+                x < 0 -> -1
+                x == 0 -> 0
+                else -> 1
+            }
+            return s
+        }
+
+        fun main() {}
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(
+        rust_code.contains("if x < 0 { -1 } else { if x == 0 { 0 } else { 1 } }"),
+        "got: {rust_code}"
+    );
+}
+
+#[test]
+fn test_generate_bitwise_and_compound_assignment() {
+    let source = r#"
+        fun mix(a: Int, b: Int): Int {
+            let mut x = a & b
+            x += (a | b) ^ ~b
+            return (x << 2) >> 1
+        }
+
+        fun main() {}
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(rust_code.contains("let mut x = a & b;"), "got: {rust_code}");
+    assert!(
+        rust_code.contains("x = x + ((a | b) ^ !b);"),
+        "got: {rust_code}"
+    );
+    assert!(
+        rust_code.contains("return x << 2 >> 1;"),
+        "got: {rust_code}"
+    );
+}
+
+#[test]
+fn test_generate_negative_and_wide_int_literals() {
+    let source = r#"
+        fun main() {
+            let x = 10
+            let a = x-1
+            let b = -9223372036854775808
+            let c = 3000000000
+            let d = -7
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(rust_code.contains("let a = x - 1;"), "got: {rust_code}");
+    assert!(rust_code.contains("let b = -9223372036854775808i64;"));
+    assert!(rust_code.contains("let c = 3000000000i64;"));
+    assert!(rust_code.contains("let d = -7;"));
+}
+
+#[test]
+fn test_generate_parenthesized_unary_operands() {
+    let source = r#"
+        fun main() {
+            let x = 2
+            let a = ~(x | 1)
+            let b = -(2 ^ 3)
+            let c = -(x + 3)
+            let d = -x
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(rust_code.contains("let a = !(x | 1);"), "got: {rust_code}");
+    assert!(rust_code.contains("let b = -(2 ^ 3);"), "got: {rust_code}");
+    assert!(rust_code.contains("let c = -(x + 3);"), "got: {rust_code}");
+    assert!(rust_code.contains("let d = -x;"), "got: {rust_code}");
+}
+
+#[test]
+fn test_generate_default_and_named_arguments() {
+    let source = r#"
+        fun connect(host: Int, port: Int = 8080, retries: Int = 3): Int = host + port + retries
+
+        fun main() {
+            let a = connect(1, retries = 0)
+            let b = connect(port = 1, host = 2)
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(
+        rust_code.contains("connect(1, 8080, 0)"),
+        "got: {rust_code}"
+    );
+    assert!(rust_code.contains("connect(2, 1, 3)"));
+}
+
+#[test]
+fn test_generate_named_arguments_in_source_order() {
+    let source = r#"
+        fun pair(a: Int, b: Int): Int = a * 10 + b
+        fun note(n: Int): Int = n
+
+        fun main() {
+            let x = pair(b = note(2), a = note(1))
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(
+        rust_code.contains("let __arg_1 = note(2);")
+            && rust_code.contains("let __arg_2 = note(1);"),
+        "named arguments should be evaluated in source order, got: {rust_code}"
+    );
+    assert!(rust_code.contains("pair(__arg_2, __arg_1)"));
+}
+
+#[test]
+fn test_generate_defaults_in_declaration_scope() {
+    let source = r#"
+        let base: Int = 5
+
+        fun f(b: Int = base): Int = b
+
+        fun main() {
+            let base = 100
+            print(f())
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(
+        rust_code.contains("f(__RIVE_GLOBAL_base)"),
+        "the default should read the global, not the caller's local, got: {rust_code}"
+    );
+}
+
+#[test]
+fn test_generate_globals() {
+    let source = r#"
+        const LIMIT = 10
+        let scale = 3
+        let offset = compute(4)
+
+        fun compute(x: Int): Int = x * scale
+
+        fun main() {
+            print(LIMIT + offset)
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(
+        rust_code.contains("const __RIVE_GLOBAL_LIMIT: i64 = 10;"),
+        "got: {rust_code}"
+    );
+    assert!(rust_code.contains("static __RIVE_GLOBAL_scale: i64 = 3;"));
+    assert!(rust_code.contains("thread_local!"));
+    assert!(rust_code.contains("x * __RIVE_GLOBAL_scale"));
+    assert!(rust_code.contains("__RIVE_GLOBAL_offset.with("));
+}
+
+#[test]
+fn test_generate_globals_keep_their_case() {
+    let source = r#"
+        const G: Int = 5
+        let a = 1
+        let A = 2
+
+        fun main() {
+            let G = 100
+            print(G + a + A)
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(
+        rust_code.contains("static __RIVE_GLOBAL_a: i64 = 1;")
+            && rust_code.contains("static __RIVE_GLOBAL_A: i64 = 2;"),
+        "globals differing only in case should stay distinct, got: {rust_code}"
+    );
+    assert!(
+        rust_code.contains("let G = 100;"),
+        "a local may share a const's name, got: {rust_code}"
+    );
+    assert!(rust_code.contains("G + __RIVE_GLOBAL_a + __RIVE_GLOBAL_A"));
+}
+
+#[test]
+fn test_generate_local_functions() {
+    let source = r#"
+        fun main() {
+            let base = 10
+            fun add(x: Int): Int = x + base
+            fun fact(n: Int): Int {
+                if n <= 1 {
+                    return 1
+                }
+                return n * fact(n - 1)
+            }
+            print(add(1) + fact(3))
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(
+        rust_code.contains("fn main_add_1(x: i64, base: i64) -> i64"),
+        "got: {rust_code}"
+    );
+    assert!(rust_code.contains("main_add_1(1, base) + main_fact_2(3)"));
+    assert!(rust_code.contains("n * main_fact_2(n - 1)"));
+}
+
+#[test]
+fn test_generate_local_function_capturing_collection() {
+    let source = r#"
+        fun main() {
+            let xs = List(1, 2)
+            fun f(): Int {
+                return xs.len()
+            }
+            print(f())
+            print(xs)
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(
+        rust_code.contains("main_f_1(xs.clone())"),
+        "a captured collection should stay usable after the call, got: {rust_code}"
+    );
+}
+
+#[test]
+fn test_generate_type_aliases() {
+    let source = r#"
+        type UserId = Int
+        newtype Meters = Float
+
+        fun double(distance: Meters): Meters = distance + distance
+
+        fun main() {
+            let id: UserId = 7
+            print(double(Meters(1.5)))
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(
+        rust_code.contains("fn double(distance: f64) -> f64"),
+        "got: {rust_code}"
+    );
+    assert!(rust_code.contains("double(1.5f64)"));
+    assert!(!rust_code.contains("Meters"));
+}
+
+#[test]
+fn test_generate_print_nested_collections() {
+    let source = r#"
+        fun main() {
+            print(List(List(1)))
+            print({"a": 1})
+        }
+    "#;
+    let rust_code = compile_to_rust(source);
+    assert!(
+        !rust_code.contains("format!(\"{:?}\", (&(std::rc::Rc"),
+        "a nested list should not print through RefCell's Debug, got: {rust_code}"
+    );
+    assert!(
+        rust_code.contains("sort_by"),
+        "map entries should print in key order, got: {rust_code}"
+    );
+}
@@ -14,7 +14,8 @@ pub enum Error {
     Config(String),
 
     #[error("Lexer error: {0}")]
-    Lexer(String),
+    #[diagnostic(code(rive::lexer))]
+    Lexer(String, #[label("here")] Span),
 
     #[error("Parser error: {0}")]
     #[diagnostic(code(rive::parser))]
@@ -31,6 +32,41 @@ pub enum Error {
     Codegen(String),
 }
 
+impl Error {
+    /// Returns the source span the error points at, if it has one.
+    pub fn span_mut(&mut self) -> Option<&mut Span> {
+        match self {
+            Self::Lexer(_, span) | Self::Parser(_, span) | Self::SemanticWithSpan(_, span) => {
+                Some(span)
+            }
+            Self::Io(_) | Self::Config(_) | Self::Semantic(_) | Self::Codegen(_) => None,
+        }
+    }
+}
+
+/// A non-fatal diagnostic reported during compilation.
+#[derive(Error, Debug, Diagnostic, Clone, PartialEq)]
+#[error("{message}")]
+#[diagnostic(code(rive::warning), severity(Warning))]
+pub struct Warning {
+    /// Description of the problem
+    pub message: String,
+    /// Location the warning refers to
+    #[label("here")]
+    pub span: Span,
+}
+
+impl Warning {
+    /// Creates a new warning at the given location.
+    #[must_use]
+    pub fn new(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
 impl From<Span> for SourceSpan {
     fn from(span: Span) -> Self {
         SourceSpan::from(span.start.offset..span.end.offset)
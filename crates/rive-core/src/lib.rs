@@ -7,6 +7,6 @@ pub mod error;
 pub mod span;
 pub mod type_system;
 
-pub use error::{Error, Result};
-pub use span::Span;
+pub use error::{Error, Result, Warning};
+pub use span::{Location, Span};
 pub use type_system::{MemoryStrategy, TypeId, TypeKind, TypeMetadata, TypeRegistry};
@@ -48,7 +48,7 @@ impl MemoryStrategy {
     /// Returns the default strategy for a primitive type name
     pub fn for_primitive(name: &str) -> Self {
         match name {
-            "Int" | "Float" | "Bool" | "Unit" => Self::Copy,
+            "Int" | "Float" | "Bool" | "Unit" | "Never" => Self::Copy,
             "Text" => Self::CoW,
             _ => Self::CoW, // Default for complex types
         }
@@ -5,6 +5,7 @@
 /// - User-defined types (struct, enum)
 /// - Memory management strategies (Copy, CoW, Unique)
 /// - Type registration and lookup
+/// - Transparent and distinct type aliases
 mod memory_strategy;
 mod registry;
 mod type_id;
@@ -12,7 +13,7 @@ mod type_kind;
 mod type_metadata;
 
 pub use memory_strategy::MemoryStrategy;
-pub use registry::{MethodSignature, TypeRegistry};
+pub use registry::{MethodSignature, TypeAlias, TypeRegistry};
 pub use type_id::TypeId;
 pub use type_kind::TypeKind;
 pub use type_metadata::TypeMetadata;
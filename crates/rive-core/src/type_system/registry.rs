@@ -9,6 +9,15 @@ pub struct MethodSignature {
     pub return_type: TypeId,
 }
 
+/// A name given to another type with `type` or `newtype`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAlias {
+    pub name: String,
+    pub target: TypeId,
+    /// Distinct aliases (`newtype`) are not interchangeable with their target
+    pub distinct: bool,
+}
+
 /// Central registry for all types in the Rive type system
 ///
 /// The registry provides:
@@ -17,6 +26,7 @@ pub struct MethodSignature {
 /// - Type compatibility checking
 /// - Rust code generation helpers
 /// - Builtin method registration and lookup
+/// - Type aliases
 #[derive(Debug, Clone)]
 pub struct TypeRegistry {
     types: HashMap<TypeId, TypeMetadata>,
@@ -24,6 +34,8 @@ pub struct TypeRegistry {
     name_to_id: HashMap<String, TypeId>,
     /// Maps (type_id, method_name) to method signature
     methods: HashMap<(TypeId, String), MethodSignature>,
+    /// Maps the TypeId of an alias to its declaration
+    aliases: HashMap<TypeId, TypeAlias>,
 }
 
 impl TypeRegistry {
@@ -34,6 +46,7 @@ impl TypeRegistry {
             next_id: TypeId::USER_DEFINED_START,
             name_to_id: HashMap::new(),
             methods: HashMap::new(),
+            aliases: HashMap::new(),
         };
 
         // Register built-in primitive types
@@ -43,6 +56,7 @@ impl TypeRegistry {
         registry.register_builtin(TypeId::BOOL, TypeKind::Bool, "Bool");
         registry.register_builtin(TypeId::UNIT, TypeKind::Unit, "Unit");
         registry.register_builtin(TypeId::NULL, TypeKind::Null, "Null");
+        registry.register_builtin(TypeId::NEVER, TypeKind::Never, "Never");
 
         // Register builtin methods
         registry.register_builtin_methods();
@@ -65,16 +79,68 @@ impl TypeRegistry {
     }
 
     /// Registers a new type and returns its TypeId
+    ///
+    /// Only user-defined types are registered by name; composite types are
+    /// structural and can be created any number of times.
     pub fn register(&mut self, metadata: TypeMetadata) -> TypeId {
         let id = metadata.id;
-        let name = metadata.kind.name();
+        if metadata.kind.is_user_defined() {
+            self.name_to_id.insert(metadata.kind.name(), id);
+        }
         self.types.insert(id, metadata);
-        if !name.is_empty() {
-            self.name_to_id.insert(name, id);
+        id
+    }
+
+    /// Registers `name` as an alias of `target` and returns the alias TypeId
+    ///
+    /// The alias shares the structure and memory strategy of its target but
+    /// has its own TypeId, so diagnostics can refer to it by name. A distinct
+    /// alias is only compatible with itself.
+    pub fn register_alias(&mut self, name: &str, target: TypeId, distinct: bool) -> TypeId {
+        let id = self.generate_id();
+        let mut metadata = self.get_type_metadata(target).clone();
+        metadata.id = id;
+        self.types.insert(id, metadata);
+        self.name_to_id.insert(name.to_string(), id);
+        self.aliases.insert(
+            id,
+            TypeAlias {
+                name: name.to_string(),
+                target,
+                distinct,
+            },
+        );
+        id
+    }
+
+    /// Looks up the alias declaration of a TypeId
+    pub fn get_alias(&self, id: TypeId) -> Option<&TypeAlias> {
+        self.aliases.get(&id)
+    }
+
+    /// Returns the type an alias ultimately stands for
+    ///
+    /// Types that are not aliases are returned unchanged.
+    pub fn resolve(&self, mut id: TypeId) -> TypeId {
+        while let Some(alias) = self.aliases.get(&id) {
+            id = alias.target;
         }
         id
     }
 
+    /// Resolves transparent aliases, stopping at the first distinct one
+    fn resolve_transparent(&self, mut id: TypeId) -> TypeId {
+        while let Some(alias) = self.aliases.get(&id).filter(|alias| !alias.distinct) {
+            id = alias.target;
+        }
+        id
+    }
+
+    /// Returns true if the type is a distinct alias
+    fn is_distinct(&self, id: TypeId) -> bool {
+        self.aliases.get(&id).is_some_and(|alias| alias.distinct)
+    }
+
     /// Looks up type metadata by TypeId
     pub fn get(&self, id: TypeId) -> Option<&TypeMetadata> {
         self.types.get(&id)
@@ -87,10 +153,17 @@ impl TypeRegistry {
 
     /// Checks if two types are compatible for assignment
     pub fn are_compatible(&self, target: TypeId, source: TypeId) -> bool {
+        let target = self.resolve_transparent(target);
+        let source = self.resolve_transparent(source);
         if target == source {
             return true;
         }
 
+        // Never is the bottom type: a diverging expression fits anywhere
+        if source == TypeId::NEVER {
+            return true;
+        }
+
         let target_meta = self.get(target);
         let source_meta = self.get(source);
 
@@ -99,7 +172,7 @@ impl TypeRegistry {
                 // Special case 1: T → T? implicit conversion
                 // Check if target is Optional<source>
                 if let TypeKind::Optional { inner } = t.kind
-                    && inner == source
+                    && self.resolve_transparent(inner) == source
                 {
                     return true; // T can implicitly convert to T?
                 }
@@ -110,6 +183,11 @@ impl TypeRegistry {
                     return true; // Null can convert to any T?
                 }
 
+                // A distinct alias only matches itself
+                if self.is_distinct(target) || self.is_distinct(source) {
+                    return false;
+                }
+
                 // Fall back to kind compatibility check
                 self.kinds_compatible(&t.kind, &s.kind)
             }
@@ -140,11 +218,33 @@ impl TypeRegistry {
                 },
             ) => s1 == s2 && self.are_compatible(*e1, *e2),
 
+            // Lists and maps must have compatible element types
+            (TypeKind::List { element: e1 }, TypeKind::List { element: e2 }) => {
+                self.are_compatible(*e1, *e2)
+            }
+            (TypeKind::Map { key: k1, value: v1 }, TypeKind::Map { key: k2, value: v2 }) => {
+                self.are_compatible(*k1, *k2) && self.are_compatible(*v1, *v2)
+            }
+
             // Optional types
             (TypeKind::Optional { inner: i1 }, TypeKind::Optional { inner: i2 }) => {
                 self.are_compatible(*i1, *i2)
             }
 
+            // Result types must have compatible ok and err types
+            (TypeKind::Result { ok: o1, err: e1 }, TypeKind::Result { ok: o2, err: e2 }) => {
+                self.are_compatible(*o1, *o2) && self.are_compatible(*e1, *e2)
+            }
+
+            // Tuples must have the same arity and compatible elements
+            (TypeKind::Tuple { elements: t1 }, TypeKind::Tuple { elements: t2 }) => {
+                t1.len() == t2.len()
+                    && t1
+                        .iter()
+                        .zip(t2.iter())
+                        .all(|(a, b)| self.are_compatible(*a, *b))
+            }
+
             // Functions must have same signature
             (
                 TypeKind::Function {
@@ -207,6 +307,24 @@ impl TypeRegistry {
         id
     }
 
+    /// Creates a result type and returns its TypeId
+    pub fn create_result(&mut self, ok: TypeId, err: TypeId) -> TypeId {
+        let id = self.generate_id();
+        let kind = TypeKind::Result { ok, err };
+        // Result is Copy only if both sides are Copy, CoW otherwise
+        let all_copy = [ok, err]
+            .iter()
+            .all(|&t| self.get(t).is_some_and(|m| m.is_copy()));
+        let memory_strategy = if all_copy {
+            MemoryStrategy::Copy
+        } else {
+            MemoryStrategy::CoW
+        };
+        let metadata = TypeMetadata::composite(id, kind, memory_strategy);
+        self.register(metadata);
+        id
+    }
+
     /// Creates a function type and returns its TypeId
     pub fn create_function(&mut self, parameters: Vec<TypeId>, return_type: TypeId) -> TypeId {
         let id = self.generate_id();
@@ -228,10 +346,12 @@ impl TypeRegistry {
 
     /// Gets the name of a type for error messages
     ///
-    /// This uses `display_name()` which properly formats nullable types as `T?`
-    /// instead of `Optional`.
+    /// Aliases are shown by their own name. Other types use `display_name()`,
+    /// which properly formats nullable types as `T?` instead of `Optional`.
     pub fn get_type_name(&self, id: TypeId) -> String {
-        if let Some(meta) = self.get(id) {
+        if let Some(alias) = self.aliases.get(&id) {
+            alias.name.clone()
+        } else if let Some(meta) = self.get(id) {
             meta.display_name(self)
         } else {
             format!("Unknown({})", id.as_u64())
@@ -256,6 +376,8 @@ impl TypeRegistry {
 
     /// Looks up a method signature for a type
     pub fn get_method(&self, type_id: TypeId, name: &str) -> Option<MethodSignature> {
+        let type_id = self.resolve(type_id);
+
         // First try direct lookup for primitive types
         if let Some(sig) = self.methods.get(&(type_id, name.to_string())) {
             return Some(sig.clone());
@@ -385,15 +507,13 @@ impl TypeRegistry {
                         _ => {}
                     }
                 }
-                TypeKind::Tuple { .. } => {
-                    // Tuple methods
-                    if name == "len" {
-                        return Some(MethodSignature {
-                            name: "len".to_string(),
-                            parameters: vec![],
-                            return_type: TypeId::INT,
-                        });
-                    }
+                // Tuple methods
+                TypeKind::Tuple { .. } if name == "len" => {
+                    return Some(MethodSignature {
+                        name: "len".to_string(),
+                        parameters: vec![],
+                        return_type: TypeId::INT,
+                    });
                 }
                 _ => {}
             }
@@ -402,6 +522,43 @@ impl TypeRegistry {
         None
     }
 
+    /// Returns every builtin method of a type, sorted by name
+    pub fn methods(&self, type_id: TypeId) -> Vec<MethodSignature> {
+        // Methods of composite types aren't registered, so try each name they may have
+        const COMPOSITE_METHODS: &[&str] = &[
+            "append",
+            "clear",
+            "contains",
+            "contains_key",
+            "get",
+            "insert",
+            "is_empty",
+            "keys",
+            "len",
+            "remove",
+            "reverse",
+            "sort",
+            "values",
+        ];
+
+        let resolved = self.resolve(type_id);
+        let registered = self
+            .methods
+            .iter()
+            .filter(|((owner, _), _)| *owner == resolved)
+            .map(|((_, name), _)| name.as_str());
+        let mut names: Vec<&str> = registered
+            .chain(COMPOSITE_METHODS.iter().copied())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+
+        names
+            .into_iter()
+            .filter_map(|name| self.get_method(type_id, name))
+            .collect()
+    }
+
     /// Registers all builtin methods
     fn register_builtin_methods(&mut self) {
         // Int methods
@@ -497,6 +654,14 @@ mod tests {
         assert!(!registry.are_compatible(TypeId::INT, TypeId::FLOAT));
     }
 
+    #[test]
+    fn test_never_compatibility() {
+        let registry = TypeRegistry::new();
+        assert!(registry.are_compatible(TypeId::INT, TypeId::NEVER));
+        assert!(registry.are_compatible(TypeId::TEXT, TypeId::NEVER));
+        assert!(!registry.are_compatible(TypeId::NEVER, TypeId::INT));
+    }
+
     #[test]
     fn test_array_creation() {
         let mut registry = TypeRegistry::new();
@@ -513,6 +678,35 @@ mod tests {
         assert!(matches!(meta.kind, TypeKind::Optional { .. }));
     }
 
+    #[test]
+    fn test_result_creation() {
+        let mut registry = TypeRegistry::new();
+        let result_id = registry.create_result(TypeId::INT, TypeId::TEXT);
+        let meta = registry.get(result_id).unwrap();
+        assert!(matches!(meta.kind, TypeKind::Result { .. }));
+        assert!(!meta.is_copy());
+        assert_eq!(registry.get_type_name(result_id), "Result<Int, Text>");
+
+        let other_id = registry.create_result(TypeId::INT, TypeId::TEXT);
+        assert!(registry.are_compatible(result_id, other_id));
+        let mismatched = registry.create_result(TypeId::INT, TypeId::INT);
+        assert!(!registry.are_compatible(result_id, mismatched));
+    }
+
+    #[test]
+    fn test_tuple_compatibility() {
+        let mut registry = TypeRegistry::new();
+        let tuple_id = registry.create_tuple(vec![TypeId::INT, TypeId::TEXT]);
+        assert_eq!(registry.get_type_name(tuple_id), "(Int, Text)");
+
+        let other_id = registry.create_tuple(vec![TypeId::INT, TypeId::TEXT]);
+        assert!(registry.are_compatible(tuple_id, other_id));
+        let reordered = registry.create_tuple(vec![TypeId::TEXT, TypeId::INT]);
+        assert!(!registry.are_compatible(tuple_id, reordered));
+        let longer = registry.create_tuple(vec![TypeId::INT, TypeId::TEXT, TypeId::INT]);
+        assert!(!registry.are_compatible(tuple_id, longer));
+    }
+
     #[test]
     fn test_function_creation() {
         let mut registry = TypeRegistry::new();
@@ -521,6 +715,55 @@ mod tests {
         assert!(matches!(meta.kind, TypeKind::Function { .. }));
     }
 
+    #[test]
+    fn test_list_and_map_compatibility() {
+        let mut registry = TypeRegistry::new();
+        let list_id = registry.create_list(TypeId::INT);
+        let other_list = registry.create_list(TypeId::INT);
+        assert!(registry.are_compatible(list_id, other_list));
+        assert_eq!(registry.get_type_name(list_id), "List<Int>");
+        let text_list = registry.create_list(TypeId::TEXT);
+        assert!(!registry.are_compatible(list_id, text_list));
+
+        let map_id = registry.create_map(TypeId::TEXT, TypeId::INT);
+        let other_map = registry.create_map(TypeId::TEXT, TypeId::INT);
+        assert!(registry.are_compatible(map_id, other_map));
+        assert_eq!(registry.get_type_name(map_id), "Map<Text, Int>");
+    }
+
+    #[test]
+    fn test_type_alias() {
+        let mut registry = TypeRegistry::new();
+        let user_id = registry.register_alias("UserId", TypeId::INT, false);
+        assert_eq!(registry.get_by_name("UserId"), Some(user_id));
+        assert_eq!(registry.get_type_name(user_id), "UserId");
+        assert_eq!(registry.resolve(user_id), TypeId::INT);
+        assert_eq!(registry.rust_type(user_id), "i64");
+        assert!(registry.are_compatible(user_id, TypeId::INT));
+        assert!(registry.are_compatible(TypeId::INT, user_id));
+        assert!(!registry.are_compatible(user_id, TypeId::TEXT));
+
+        let optional = registry.create_optional(user_id);
+        assert_eq!(registry.get_type_name(optional), "UserId?");
+        assert!(registry.are_compatible(optional, TypeId::INT));
+    }
+
+    #[test]
+    fn test_distinct_alias() {
+        let mut registry = TypeRegistry::new();
+        let meters = registry.register_alias("Meters", TypeId::FLOAT, true);
+        let seconds = registry.register_alias("Seconds", TypeId::FLOAT, true);
+        assert!(registry.are_compatible(meters, meters));
+        assert!(!registry.are_compatible(meters, TypeId::FLOAT));
+        assert!(!registry.are_compatible(TypeId::FLOAT, meters));
+        assert!(!registry.are_compatible(meters, seconds));
+        assert_eq!(registry.resolve(meters), TypeId::FLOAT);
+
+        let distance = registry.register_alias("Distance", meters, false);
+        assert!(registry.are_compatible(distance, meters));
+        assert!(!registry.are_compatible(distance, TypeId::FLOAT));
+    }
+
     #[test]
     fn test_rust_type_generation() {
         let registry = TypeRegistry::new();
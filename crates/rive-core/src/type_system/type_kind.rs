@@ -24,6 +24,9 @@ pub enum TypeKind {
     /// Null type (bottom type for nullable values)
     Null,
 
+    /// Never type (bottom type for expressions that never produce a value)
+    Never,
+
     /// Array type with element type and size
     Array { element: TypeId, size: usize },
 
@@ -39,6 +42,9 @@ pub enum TypeKind {
     /// Optional/nullable type
     Optional { inner: TypeId },
 
+    /// Result type for recoverable errors: `Result<T, E>`
+    Result { ok: TypeId, err: TypeId },
+
     /// Function type
     Function {
         parameters: Vec<TypeId>,
@@ -66,7 +72,13 @@ impl TypeKind {
     pub const fn is_primitive(&self) -> bool {
         matches!(
             self,
-            Self::Int | Self::Float | Self::Text | Self::Bool | Self::Unit | Self::Null
+            Self::Int
+                | Self::Float
+                | Self::Text
+                | Self::Bool
+                | Self::Unit
+                | Self::Null
+                | Self::Never
         )
     }
 
@@ -79,6 +91,7 @@ impl TypeKind {
                 | Self::List { .. }
                 | Self::Map { .. }
                 | Self::Optional { .. }
+                | Self::Result { .. }
                 | Self::Function { .. }
         )
     }
@@ -97,11 +110,13 @@ impl TypeKind {
             Self::Bool => "Bool".to_string(),
             Self::Unit => "Unit".to_string(),
             Self::Null => "Null".to_string(),
+            Self::Never => "Never".to_string(),
             Self::Array { .. } => "Array".to_string(),
             Self::Tuple { .. } => "Tuple".to_string(),
             Self::List { .. } => "List".to_string(),
             Self::Map { .. } => "Map".to_string(),
             Self::Optional { .. } => "Optional".to_string(),
+            Self::Result { .. } => "Result".to_string(),
             Self::Function { .. } => "Function".to_string(),
             Self::Struct { name, .. } => name.clone(),
             Self::Enum { name, .. } => name.clone(),
@@ -144,6 +159,13 @@ mod tests {
             .is_composite()
         );
         assert!(TypeKind::Optional { inner: TypeId::INT }.is_composite());
+        assert!(
+            TypeKind::Result {
+                ok: TypeId::INT,
+                err: TypeId::TEXT
+            }
+            .is_composite()
+        );
         assert!(!TypeKind::Int.is_composite());
     }
 
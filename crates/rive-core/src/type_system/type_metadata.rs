@@ -81,11 +81,25 @@ impl TypeMetadata {
         match &self.kind {
             TypeKind::Optional { inner } => {
                 // Recursively get inner type's display name
-                if let Some(inner_meta) = registry.get(*inner) {
-                    format!("{}?", inner_meta.display_name(registry))
-                } else {
-                    "Unknown?".to_string()
-                }
+                format!("{}?", registry.get_type_name(*inner))
+            }
+            TypeKind::List { element } => format!("List<{}>", registry.get_type_name(*element)),
+            TypeKind::Map { key, value } => format!(
+                "Map<{}, {}>",
+                registry.get_type_name(*key),
+                registry.get_type_name(*value)
+            ),
+            TypeKind::Result { ok, err } => format!(
+                "Result<{}, {}>",
+                registry.get_type_name(*ok),
+                registry.get_type_name(*err)
+            ),
+            TypeKind::Tuple { elements } => {
+                let elements: Vec<_> = elements
+                    .iter()
+                    .map(|element| registry.get_type_name(*element))
+                    .collect();
+                format!("({})", elements.join(", "))
             }
             // For all other types, use the kind's name
             _ => self.kind.name(),
@@ -112,6 +126,7 @@ impl TypeMetadata {
                 // If it does, represent it as an empty Option
                 "Option<()>".to_string()
             }
+            TypeKind::Never => "!".to_string(),
             TypeKind::Array { element, size } => {
                 let elem_type = registry.rust_type(*element);
                 format!("[{elem_type}; {size}]")
@@ -147,6 +162,11 @@ impl TypeMetadata {
                 let inner_type = registry.rust_type(*inner);
                 format!("Option<{inner_type}>")
             }
+            TypeKind::Result { ok, err } => {
+                let ok_type = registry.rust_type(*ok);
+                let err_type = registry.rust_type(*err);
+                format!("Result<{ok_type}, {err_type}>")
+            }
             TypeKind::Function {
                 parameters,
                 return_type,
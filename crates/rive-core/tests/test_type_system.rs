@@ -222,3 +222,31 @@ fn test_chained_optional_conversions() {
         "Int should NOT directly convert to Int?? (must go through Int? first)"
     );
 }
+
+#[test]
+fn test_builtin_method_listing() {
+    let mut registry = TypeRegistry::new();
+    let names = |registry: &TypeRegistry, id| -> Vec<String> {
+        registry
+            .methods(id)
+            .into_iter()
+            .map(|method| method.name)
+            .collect()
+    };
+
+    assert_eq!(names(&registry, TypeId::INT), vec!["to_float"]);
+    assert!(names(&registry, TypeId::TEXT).contains(&"to_upper".to_string()));
+    assert!(names(&registry, TypeId::BOOL).is_empty());
+
+    let list = registry.create_list(TypeId::TEXT);
+    let list_methods = registry.methods(list);
+    let append = list_methods
+        .iter()
+        .find(|method| method.name == "append")
+        .unwrap();
+    assert_eq!(append.parameters, vec![TypeId::TEXT]);
+    assert!(!list_methods.iter().any(|method| method.name == "keys"));
+
+    let map = registry.create_map(TypeId::TEXT, TypeId::INT);
+    assert!(names(&registry, map).contains(&"contains_key".to_string()));
+}
@@ -0,0 +1,40 @@
+//! Source code formatter for the Rive language.
+//!
+//! Formatting works on a [`SyntaxTree`] built from lossless tokens, so no
+//! comment is lost. The formatter re-indents code, normalizes the spacing
+//! between tokens and lays out comma-separated lists: a list that fits in
+//! [`MAX_WIDTH`] columns goes on one line without a trailing comma, and one
+//! that doesn't gets one element per line, each followed by a comma. Line
+//! breaks between statements are kept, with at most one blank line in a row.
+
+mod printer;
+pub mod syntax;
+
+pub use printer::MAX_WIDTH;
+pub use syntax::{Delimiter, Group, SyntaxElement, SyntaxTree};
+
+use rive_core::Result;
+use rive_lexer::tokenize_lossless;
+
+/// Formats Rive source code.
+///
+/// Formatting is idempotent: formatted code formats to itself.
+///
+/// # Errors
+/// Returns an error if the source contains invalid tokens or unbalanced delimiters.
+///
+/// # Examples
+/// ```
+/// use rive_formatter::format_source;
+///
+/// let formatted = format_source("fun main(){print( 1+2 )}").unwrap();
+/// assert_eq!(formatted, "fun main() { print(1 + 2) }\n");
+/// ```
+pub fn format_source(source: &str) -> Result<String> {
+    let (tokens, mut errors) = tokenize_lossless(source);
+    if !errors.is_empty() {
+        return Err(errors.swap_remove(0));
+    }
+    let tree = SyntaxTree::build(tokens)?;
+    Ok(printer::print(&tree))
+}
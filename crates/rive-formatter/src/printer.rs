@@ -0,0 +1,620 @@
+//! Layout of a syntax tree as formatted source.
+
+use crate::syntax::{Delimiter, Group, SyntaxElement, SyntaxTree};
+use rive_lexer::{LosslessToken, TokenKind, Trivia, TriviaKind};
+
+/// Text of one level of indentation.
+const INDENT: &str = "    ";
+
+/// Maximum line width; lists that would make a line longer are broken up.
+pub const MAX_WIDTH: usize = 100;
+
+/// Type names that take type arguments in `<...>`.
+const GENERIC_TYPES: &[&str] = &["List", "Map", "Result"];
+
+/// Formats a syntax tree.
+pub(crate) fn print(tree: &SyntaxTree) -> String {
+    let mut printer = Printer::new(false);
+    printer.print_sequence(&tree.elements, Mode::Block(0), false);
+    printer.print_trivia(&tree.trailing_trivia, 0, true);
+    printer.finish()
+}
+
+/// How the elements of a sequence are placed on lines.
+#[derive(Debug, Clone, Copy)]
+enum Mode {
+    /// Statements at an indentation level; line breaks in the source are kept
+    Block(usize),
+    /// Parts of an expression; line breaks in the source are dropped, and
+    /// lines broken by comments continue one level deeper
+    Inline(usize),
+}
+
+/// How a group is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// A block of statements: `{ ... }`
+    Block,
+    /// A comma-separated list, broken into one element per line if it doesn't fit
+    List,
+    /// Delimiters hugging their contents: `(a + b)`, `f(x)`, `a[i]`
+    Hug,
+}
+
+/// How a token binds to the tokens around it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    /// Ends an operand: names, literals, closing delimiters and postfix operators
+    Operand,
+    /// An opening delimiter, followed by a space if `spaced`: `{ x }`
+    Open { spaced: bool },
+    /// Binds to what follows: prefix operators, `.`, `..`, `@`, and the `<`
+    /// of type arguments
+    Tight,
+    /// Keywords, binary operators and separators
+    Other,
+}
+
+/// The last token written.
+#[derive(Debug, Clone)]
+struct Previous {
+    kind: TokenKind,
+    role: Role,
+    /// Whether the token names a type that takes type arguments
+    generic_name: bool,
+}
+
+/// Writes formatted source line by line.
+struct Printer {
+    lines: Vec<String>,
+    /// The line being written, starting with its indentation
+    line: String,
+    /// Indentation level of the current line
+    line_indent: usize,
+    previous: Option<Previous>,
+    /// Number of type argument lists the last token is inside of
+    generic_depth: usize,
+    /// Whether a comment ends the current line
+    break_pending: bool,
+    /// Whether everything goes on one line, for measuring
+    flat: bool,
+}
+
+impl Printer {
+    const fn new(flat: bool) -> Self {
+        Self {
+            lines: Vec::new(),
+            line: String::new(),
+            line_indent: 0,
+            previous: None,
+            generic_depth: 0,
+            break_pending: false,
+            flat,
+        }
+    }
+
+    /// Returns the formatted source, ending with a single line break.
+    fn finish(mut self) -> String {
+        self.flush_line();
+        while self.lines.last().is_some_and(String::is_empty) {
+            self.lines.pop();
+        }
+        if self.lines.is_empty() {
+            return String::new();
+        }
+        let mut output = self.lines.join("\n");
+        output.push('\n');
+        output
+    }
+
+    fn flush_line(&mut self) {
+        let line = std::mem::take(&mut self.line);
+        let line = line.trim_end();
+        if !line.is_empty() {
+            self.lines.push(line.to_string());
+        }
+    }
+
+    /// Returns true if nothing but indentation is on the current line.
+    fn at_line_start(&self) -> bool {
+        self.line.trim().is_empty()
+    }
+
+    fn column(&self) -> usize {
+        self.line.chars().count()
+    }
+
+    /// Starts a new line at `indent`, after a blank line if `blank`.
+    fn newline(&mut self, indent: usize, blank: bool) {
+        if self.flat {
+            self.line.push(' ');
+            return;
+        }
+        self.flush_line();
+        if blank && self.lines.last().is_some_and(|line| !line.is_empty()) {
+            self.lines.push(String::new());
+        }
+        self.line = INDENT.repeat(indent);
+        self.line_indent = indent;
+        self.break_pending = false;
+    }
+
+    /// Writes the comments in `trivia` and returns the number of line breaks
+    /// after the last of them.
+    ///
+    /// A comment that starts a line in the source is put on a line of its
+    /// own at `indent`, after a blank line if there was one and `keep_blank`
+    /// is set. Other comments stay at the end of the current line.
+    fn print_trivia(&mut self, trivia: &[Trivia], indent: usize, keep_blank: bool) -> usize {
+        let mut newlines = 0;
+
+        for piece in trivia {
+            match piece.kind {
+                TriviaKind::Whitespace => newlines += piece.newlines(),
+                TriviaKind::Shebang => {
+                    self.line.push_str(piece.text.trim_end());
+                    self.break_pending = true;
+                }
+                TriviaKind::LineComment | TriviaKind::BlockComment => {
+                    if newlines > 0 || self.break_pending {
+                        self.newline(indent, keep_blank && newlines > 1);
+                    } else if !self.at_line_start() && !self.line.ends_with(['(', '[']) {
+                        self.line.push(' ');
+                    }
+                    self.line.push_str(piece.text.trim_end());
+
+                    if piece.kind == TriviaKind::LineComment || piece.text.contains('\n') {
+                        self.break_pending = true;
+                    } else if let Some(previous) = &mut self.previous {
+                        // Separate whatever follows from an inline block comment
+                        previous.role = Role::Other;
+                    }
+                    newlines = 0;
+                }
+            }
+        }
+
+        newlines
+    }
+
+    /// Prints a sequence of elements, starting on a new line if `break_first`.
+    fn print_sequence(&mut self, elements: &[SyntaxElement], mode: Mode, break_first: bool) {
+        for (i, element) in elements.iter().enumerate() {
+            let first = element.first_token();
+            let starts_block = i == 0 && break_first;
+            let (comment_indent, keep_blank) = match mode {
+                Mode::Block(indent) => (indent, !starts_block),
+                Mode::Inline(indent) if starts_block => (indent, false),
+                Mode::Inline(indent) => (indent + 1, false),
+            };
+            let newlines = self.print_trivia(&first.leading_trivia, comment_indent, keep_blank);
+
+            let line_break = match mode {
+                // `}` and `else` always share a line
+                Mode::Block(_) => {
+                    let joins_else = *first.kind() == TokenKind::Else
+                        && self
+                            .previous
+                            .as_ref()
+                            .is_some_and(|previous| previous.kind == TokenKind::RightBrace);
+                    newlines > 0 && !joins_else
+                }
+                Mode::Inline(_) => false,
+            };
+            if !self.flat && (self.break_pending || starts_block || line_break) {
+                let indent = match mode {
+                    Mode::Block(indent) => {
+                        let continues = !starts_block && self.continues_line(first.kind());
+                        indent + usize::from(continues)
+                    }
+                    Mode::Inline(_) => comment_indent,
+                };
+                self.newline(indent, keep_blank && newlines > 1);
+            }
+
+            let next = elements.get(i + 1).map(|next| next.first_token().kind());
+            match element {
+                SyntaxElement::Token(token) => self.write_token(token, next),
+                SyntaxElement::Group(group) => self.print_group(group, &elements[i + 1..], mode),
+            }
+        }
+    }
+
+    /// Returns true if a line starting with `next` continues the statement
+    /// on the line before it rather than starting a new one.
+    fn continues_line(&self, next: &TokenKind) -> bool {
+        let Some(previous) = &self.previous else {
+            return false;
+        };
+        (previous.role != Role::Operand && is_operator(&previous.kind))
+            || (is_operator(next)
+                && !matches!(
+                    next,
+                    TokenKind::Minus
+                        | TokenKind::Bang
+                        | TokenKind::Tilde
+                        | TokenKind::At
+                        | TokenKind::Colon
+                ))
+    }
+
+    /// Prints a group followed on its line by `tail`.
+    fn print_group(&mut self, group: &Group, tail: &[SyntaxElement], mode: Mode) {
+        let layout = layout(group);
+        let empty = group.children.is_empty() && !has_comments(&group.close.leading_trivia);
+        let broken = !self.flat
+            && !empty
+            && layout != Layout::Hug
+            && (forces_break(group) || !self.fits(group, tail, mode));
+        let outer = self.line_indent;
+
+        self.write_open(&group.open, layout == Layout::Block);
+        match (layout, broken) {
+            (Layout::Block, _) => {
+                self.print_sequence(&group.children, Mode::Block(outer + 1), broken)
+            }
+            (Layout::List, false) => self.print_flat_list(group),
+            (Layout::List, true) => self.print_broken_list(group, outer),
+            (Layout::Hug, _) => self.print_sequence(&group.children, Mode::Inline(outer), false),
+        }
+
+        let keep_blank = layout == Layout::Block;
+        self.print_trivia(&group.close.leading_trivia, outer + 1, keep_blank);
+        if broken || self.break_pending {
+            self.newline(outer, false);
+        }
+        self.write_close(&group.close, layout == Layout::Block);
+    }
+
+    /// Prints the elements of a list on the current line.
+    fn print_flat_list(&mut self, group: &Group) {
+        let items = split_list(&group.children);
+        // `(x,)` is a one-element tuple, so its comma stays
+        let keep_trailing_comma = items.len() == 1;
+
+        for (i, item) in items.iter().enumerate() {
+            self.print_sequence(item.elements, Mode::Inline(self.line_indent), false);
+            if let Some(comma) = item.comma
+                && (i + 1 < items.len() || keep_trailing_comma)
+            {
+                self.write_token(comma, None);
+            }
+        }
+    }
+
+    /// Prints the elements of a list one per line, each followed by a comma.
+    fn print_broken_list(&mut self, group: &Group, outer: usize) {
+        for item in split_list(&group.children) {
+            self.print_sequence(item.elements, Mode::Inline(outer + 1), true);
+            match item.comma {
+                Some(comma) => {
+                    self.print_trivia(&comma.leading_trivia, outer + 2, false);
+                    if self.break_pending {
+                        self.newline(outer + 2, false);
+                    }
+                    self.write_token(comma, None);
+                }
+                None => self.push(TokenKind::Comma, ",", Role::Other, false),
+            }
+        }
+    }
+
+    /// Returns true if `group` and what follows it fit on the current line.
+    fn fits(&self, group: &Group, tail: &[SyntaxElement], mode: Mode) -> bool {
+        let width = self.measure(self.previous.clone(), self.at_line_start(), |printer| {
+            printer.print_group(group, &[], mode);
+        });
+        self.column() + width + self.tail_width(tail, mode) <= MAX_WIDTH
+    }
+
+    /// Returns the width of what follows a group up to where its line may
+    /// break: a line break, a block, or the end of a list element.
+    fn tail_width(&self, tail: &[SyntaxElement], mode: Mode) -> usize {
+        let mut end = 0;
+        let mut extra = 0;
+        for element in tail {
+            let first = element.first_token();
+            let breaks_before = first.leading_trivia.iter().any(|trivia| {
+                trivia.is_comment() || (matches!(mode, Mode::Block(_)) && trivia.newlines() > 0)
+            });
+            if breaks_before {
+                break;
+            }
+            if let SyntaxElement::Group(group) = element
+                && layout(group) == Layout::Block
+            {
+                extra = " {".len();
+                break;
+            }
+            if matches!(mode, Mode::Inline(_)) && *first.kind() == TokenKind::Comma {
+                extra = ",".len();
+                break;
+            }
+            end += 1;
+        }
+
+        let after_group = Previous {
+            kind: TokenKind::RightParen,
+            role: Role::Operand,
+            generic_name: false,
+        };
+        extra
+            + self.measure(Some(after_group), false, |printer| {
+                printer.print_sequence(&tail[..end], Mode::Inline(0), false);
+            })
+    }
+
+    /// Returns the width of what `print` writes on one line after `previous`.
+    fn measure(
+        &self,
+        previous: Option<Previous>,
+        at_line_start: bool,
+        print: impl FnOnce(&mut Self),
+    ) -> usize {
+        let mut printer = Self::new(true);
+        printer.previous = previous;
+        printer.generic_depth = self.generic_depth;
+        if !at_line_start {
+            // Stands in for the rest of the line, so spacing is measured too
+            printer.line.push('x');
+        }
+        let start = printer.line.len();
+        print(&mut printer);
+        printer.line[start..].chars().count()
+    }
+
+    fn write_open(&mut self, token: &LosslessToken, spaced: bool) {
+        let space = match token.kind() {
+            TokenKind::LeftBrace => true,
+            _ => !self.follows_operand(),
+        };
+        let space = space && self.space_allowed();
+        self.push(
+            token.kind().clone(),
+            &token.token.text,
+            Role::Open { spaced },
+            space,
+        );
+    }
+
+    fn write_close(&mut self, token: &LosslessToken, spaced: bool) {
+        let empty = self
+            .previous
+            .as_ref()
+            .is_some_and(|previous| matches!(previous.role, Role::Open { .. }));
+        let space = spaced && !empty && self.space_allowed();
+        self.push(
+            token.kind().clone(),
+            &token.token.text,
+            Role::Operand,
+            space,
+        );
+    }
+
+    /// Writes a token that isn't a delimiter; `next` is the kind of the token after it.
+    fn write_token(&mut self, token: &LosslessToken, next: Option<&TokenKind>) {
+        use TokenKind::*;
+
+        let kind = token.kind();
+        let follows_operand = self.follows_operand();
+        let opens_generic = *kind == Less
+            && self
+                .previous
+                .as_ref()
+                .is_some_and(|previous| previous.generic_name);
+        let closes_generic = matches!(kind, Greater | GreaterGreater) && self.generic_depth > 0;
+        let elvis = *kind == Question && next == Some(&Colon);
+
+        let role = match kind {
+            Identifier | Integer | Float | String | True | False | Null | Print | Underscore
+            | BangBang | Error => Role::Operand,
+            Question if !elvis => Role::Operand,
+            Minus if !follows_operand => Role::Tight,
+            Bang | Tilde | Dot | DotDot | DotDotEq | At => Role::Tight,
+            _ if opens_generic => Role::Tight,
+            _ if closes_generic => Role::Operand,
+            _ => Role::Other,
+        };
+        let space = match kind {
+            Comma | Semicolon | Colon | Dot | DotDot | DotDotEq | BangBang => false,
+            Question => elvis,
+            _ => !opens_generic && !closes_generic,
+        };
+
+        if opens_generic {
+            self.generic_depth += 1;
+        } else if closes_generic {
+            let closed = if *kind == GreaterGreater { 2 } else { 1 };
+            self.generic_depth = self.generic_depth.saturating_sub(closed);
+        }
+
+        let text = token.token.text.trim_end();
+        let space = space && self.space_allowed();
+        self.push(kind.clone(), text, role, space);
+        if let Some(previous) = &mut self.previous {
+            previous.generic_name = *kind == Identifier && GENERIC_TYPES.contains(&text);
+        }
+        if *kind == DocComment {
+            self.break_pending = true;
+        }
+    }
+
+    /// Returns true if the last token on the line ends an operand, so a
+    /// `(` or `[` after it is a call or an index and `-` is a subtraction.
+    fn follows_operand(&self) -> bool {
+        !self.at_line_start()
+            && self.previous.as_ref().is_some_and(|previous| {
+                previous.role == Role::Operand && previous.kind != TokenKind::RightBrace
+            })
+    }
+
+    /// Returns true unless the previous token binds to the next one.
+    fn space_allowed(&self) -> bool {
+        if self.at_line_start() {
+            return false;
+        }
+        match self.previous.as_ref().map(|previous| previous.role) {
+            Some(Role::Open { spaced }) => spaced,
+            Some(Role::Tight) => false,
+            Some(Role::Operand | Role::Other) | None => true,
+        }
+    }
+
+    fn push(&mut self, kind: TokenKind, text: &str, role: Role, space: bool) {
+        if space {
+            self.line.push(' ');
+        }
+        self.line.push_str(text);
+        self.previous = Some(Previous {
+            kind,
+            role,
+            generic_name: false,
+        });
+    }
+}
+
+/// An element of a comma-separated list.
+struct ListItem<'a> {
+    elements: &'a [SyntaxElement],
+    comma: Option<&'a LosslessToken>,
+}
+
+/// Splits the children of a group at the commas between its elements.
+///
+/// Commas inside type arguments, like `Map<Text, Int>`, don't separate elements.
+fn split_list(children: &[SyntaxElement]) -> Vec<ListItem<'_>> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut generic_depth = 0usize;
+
+    for (i, child) in children.iter().enumerate() {
+        let Some(token) = child.as_token() else {
+            continue;
+        };
+        match token.kind() {
+            TokenKind::Less if i > 0 && is_generic_name(&children[i - 1]) => generic_depth += 1,
+            TokenKind::Greater => generic_depth = generic_depth.saturating_sub(1),
+            TokenKind::GreaterGreater => generic_depth = generic_depth.saturating_sub(2),
+            TokenKind::Comma if generic_depth == 0 => {
+                items.push(ListItem {
+                    elements: &children[start..i],
+                    comma: Some(token),
+                });
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if start < children.len() {
+        items.push(ListItem {
+            elements: &children[start..],
+            comma: None,
+        });
+    }
+    items
+}
+
+fn is_generic_name(element: &SyntaxElement) -> bool {
+    element.as_token().is_some_and(|token| {
+        *token.kind() == TokenKind::Identifier && GENERIC_TYPES.contains(&token.token.text.as_str())
+    })
+}
+
+fn layout(group: &Group) -> Layout {
+    let is_dict = matches!(
+        group.children.as_slice(),
+        [SyntaxElement::Token(key), SyntaxElement::Token(colon), ..]
+            if *key.kind() == TokenKind::String && *colon.kind() == TokenKind::Colon
+    );
+    match group.delimiter {
+        Delimiter::Brace if is_dict => Layout::List,
+        Delimiter::Brace => Layout::Block,
+        Delimiter::Paren | Delimiter::Bracket => {
+            if split_list(&group.children)
+                .iter()
+                .any(|item| item.comma.is_some())
+            {
+                Layout::List
+            } else {
+                Layout::Hug
+            }
+        }
+    }
+}
+
+/// Returns true if `group` can't be printed on one line: it contains a
+/// comment that ends its line, a doc comment, or a block written across lines.
+fn forces_break(group: &Group) -> bool {
+    let multiline_block = layout(group) == Layout::Block
+        && group
+            .children
+            .iter()
+            .map(|child| &child.first_token().leading_trivia)
+            .chain([&group.close.leading_trivia])
+            .any(|trivia| trivia.iter().any(|piece| piece.newlines() > 0));
+
+    multiline_block
+        || ends_line(&group.close.leading_trivia)
+        || group.children.iter().any(|child| {
+            ends_line(&child.first_token().leading_trivia)
+                || match child {
+                    SyntaxElement::Token(token) => *token.kind() == TokenKind::DocComment,
+                    SyntaxElement::Group(group) => forces_break(group),
+                }
+        })
+}
+
+/// Returns true if `trivia` has a line comment or a block comment spanning lines.
+fn ends_line(trivia: &[Trivia]) -> bool {
+    trivia.iter().any(|piece| match piece.kind {
+        TriviaKind::LineComment => true,
+        TriviaKind::BlockComment => piece.text.contains('\n'),
+        TriviaKind::Whitespace | TriviaKind::Shebang => false,
+    })
+}
+
+fn has_comments(trivia: &[Trivia]) -> bool {
+    trivia.iter().any(Trivia::is_comment)
+}
+
+/// Returns true for operators and punctuation that join two parts of an expression.
+fn is_operator(kind: &TokenKind) -> bool {
+    use TokenKind::*;
+
+    matches!(
+        kind,
+        Plus | Minus
+            | Star
+            | Slash
+            | Percent
+            | PlusEqual
+            | MinusEqual
+            | StarEqual
+            | SlashEqual
+            | PercentEqual
+            | Equal
+            | EqualEqual
+            | BangEqual
+            | Less
+            | LessEqual
+            | Greater
+            | GreaterEqual
+            | AmpersandAmpersand
+            | PipePipe
+            | Pipe
+            | Ampersand
+            | Caret
+            | Tilde
+            | LessLess
+            | GreaterGreater
+            | Bang
+            | DotDot
+            | DotDotEq
+            | Dot
+            | Arrow
+            | Colon
+            | Question
+            | At
+    )
+}
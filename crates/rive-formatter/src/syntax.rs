@@ -0,0 +1,177 @@
+//! Concrete syntax tree built from lossless tokens.
+//!
+//! The tree only knows about delimiters: each `(...)`, `[...]` and `{...}`
+//! becomes a [`Group`], and everything else stays a token. That is enough
+//! structure to lay out code, and because every token keeps its trivia, the
+//! tree still displays as the exact source it was built from.
+
+use rive_core::{Error, Result};
+use rive_lexer::{LosslessToken, LosslessTokens, TokenKind, Trivia};
+use std::fmt;
+
+/// The kinds of delimiters that open a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// `( ... )`
+    Paren,
+    /// `[ ... ]`
+    Bracket,
+    /// `{ ... }`
+    Brace,
+}
+
+impl Delimiter {
+    /// Returns the delimiter opened by `kind`, if any.
+    fn opened_by(kind: &TokenKind) -> Option<Self> {
+        match kind {
+            TokenKind::LeftParen => Some(Self::Paren),
+            TokenKind::LeftBracket => Some(Self::Bracket),
+            TokenKind::LeftBrace => Some(Self::Brace),
+            _ => None,
+        }
+    }
+
+    /// Returns true if `kind` closes this delimiter.
+    fn is_closed_by(self, kind: &TokenKind) -> bool {
+        matches!(
+            (self, kind),
+            (Self::Paren, TokenKind::RightParen)
+                | (Self::Bracket, TokenKind::RightBracket)
+                | (Self::Brace, TokenKind::RightBrace)
+        )
+    }
+}
+
+/// A token or a delimited group of elements.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntaxElement {
+    Token(LosslessToken),
+    Group(Group),
+}
+
+impl SyntaxElement {
+    /// Returns the first token of the element.
+    pub fn first_token(&self) -> &LosslessToken {
+        match self {
+            Self::Token(token) => token,
+            Self::Group(group) => &group.open,
+        }
+    }
+
+    /// Returns the token if this element is one.
+    pub fn as_token(&self) -> Option<&LosslessToken> {
+        match self {
+            Self::Token(token) => Some(token),
+            Self::Group(_) => None,
+        }
+    }
+}
+
+/// Elements between a pair of matching delimiters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    pub delimiter: Delimiter,
+    pub open: LosslessToken,
+    pub children: Vec<SyntaxElement>,
+    pub close: LosslessToken,
+}
+
+/// A whole source file as a concrete syntax tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxTree {
+    pub elements: Vec<SyntaxElement>,
+    /// Trivia after the last token
+    pub trailing_trivia: Vec<Trivia>,
+}
+
+impl SyntaxTree {
+    /// Builds the tree for a token stream.
+    ///
+    /// # Errors
+    /// Returns an error if the delimiters in the stream are unbalanced.
+    pub fn build(tokens: LosslessTokens) -> Result<Self> {
+        // Groups being built, innermost last
+        let mut open_groups: Vec<(Delimiter, LosslessToken, Vec<SyntaxElement>)> = Vec::new();
+        let mut elements = Vec::new();
+
+        for token in tokens.tokens {
+            if let Some(delimiter) = Delimiter::opened_by(token.kind()) {
+                open_groups.push((delimiter, token, std::mem::take(&mut elements)));
+                continue;
+            }
+
+            let closes_group = matches!(
+                token.kind(),
+                TokenKind::RightParen | TokenKind::RightBracket | TokenKind::RightBrace
+            );
+            if !closes_group {
+                elements.push(SyntaxElement::Token(token));
+                continue;
+            }
+
+            match open_groups.pop() {
+                Some((delimiter, open, outer)) if delimiter.is_closed_by(token.kind()) => {
+                    let children = std::mem::replace(&mut elements, outer);
+                    elements.push(SyntaxElement::Group(Group {
+                        delimiter,
+                        open,
+                        children,
+                        close: token,
+                    }));
+                }
+                _ => {
+                    return Err(Error::Parser(
+                        format!("Unexpected '{}'", token.token.text),
+                        token.span,
+                    ));
+                }
+            }
+        }
+
+        if let Some((_, open, _)) = open_groups.pop() {
+            return Err(Error::Parser(
+                format!("Unclosed '{}'", open.token.text),
+                open.span,
+            ));
+        }
+
+        Ok(Self {
+            elements,
+            trailing_trivia: tokens.trailing_trivia,
+        })
+    }
+}
+
+impl fmt::Display for SyntaxTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for element in &self.elements {
+            write!(f, "{element}")?;
+        }
+        for trivia in &self.trailing_trivia {
+            f.write_str(&trivia.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SyntaxElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Token(token) => write_token(f, token),
+            Self::Group(group) => {
+                write_token(f, &group.open)?;
+                for child in &group.children {
+                    write!(f, "{child}")?;
+                }
+                write_token(f, &group.close)
+            }
+        }
+    }
+}
+
+fn write_token(f: &mut fmt::Formatter<'_>, token: &LosslessToken) -> fmt::Result {
+    for trivia in &token.leading_trivia {
+        f.write_str(&trivia.text)?;
+    }
+    f.write_str(&token.token.text)
+}
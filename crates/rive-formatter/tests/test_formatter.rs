@@ -0,0 +1,213 @@
+//! Tests for the Rive formatter.
+
+use rive_formatter::{Delimiter, MAX_WIDTH, SyntaxElement, SyntaxTree, format_source};
+use rive_lexer::{TokenKind, tokenize, tokenize_lossless};
+
+/// Asserts that `source` formats to `expected`, and that `expected` is left alone.
+fn assert_formats(source: &str, expected: &str) {
+    let formatted = format_source(source).unwrap();
+    assert_eq!(formatted, expected);
+    assert_eq!(
+        format_source(&formatted).unwrap(),
+        formatted,
+        "not idempotent"
+    );
+}
+
+/// Returns the token texts of `source`, without commas before a closing delimiter.
+fn significant_tokens(source: &str) -> Vec<String> {
+    let tokens = tokenize(source).unwrap();
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(i, (token, _))| {
+            let closes_next = tokens.get(i + 1).is_some_and(|(next, _)| {
+                matches!(
+                    next.kind,
+                    TokenKind::RightParen | TokenKind::RightBracket | TokenKind::RightBrace
+                )
+            });
+            !(token.kind == TokenKind::Comma && closes_next)
+        })
+        .map(|(_, (token, _))| token.text.clone())
+        .collect()
+}
+
+const MESSY: &str = r#"#!/usr/bin/env rive
+// Shapes and sums
+type Pairs=Map<Text,List<Int>>
+
+
+/// Adds two numbers
+fun add(a:Int,b:Int=1):Int{
+a+b}
+@test(panics="boom")
+fun explodes(){panic( "boom" )}
+fun main(){
+let x=-1 // negative
+    let mut y:Int?=null
+  let z=y?:x*-2
+    let values=[1,2,3,]
+    let pair=(1,)
+    let d={"a":1,"b" :2}
+    for i in 0..=10{print(i)}
+    if x>0&&!(y==null)
+    {
+        print("positive")
+    }
+    else{print(values[0])}
+    let label=when x{
+        1->"one",
+        -1->"minus one"
+        _->{
+            /* other */
+            "other"
+        }
+    }
+    print(add(1,2),values.len(),d.get("a"),"a long string literal that is sure to overflow the line")
+    let total=x+
+        z
+}
+"#;
+
+#[test]
+fn test_format_spacing_and_indentation() {
+    assert_formats(
+        "fun add(a:Int,b:Int):Int{\na+b\n}\nfun main(){\nlet x:Int?=-add(1,2)*3\n  print(x?:0)\n}",
+        "fun add(a: Int, b: Int): Int {\n    a + b\n}\nfun main() {\n    let x: Int? = -add(1, 2) * 3\n    print(x ?: 0)\n}\n",
+    );
+    assert_formats(
+        "fun f(m:Map<Text,List<List<Int>>>):Result<Int,Text>{\n  return m.len()>>1 <3\n}\n",
+        "fun f(m: Map<Text, List<List<Int>>>): Result<Int, Text> {\n    return m.len() >> 1 < 3\n}\n",
+    );
+    assert_formats(
+        "@test(panics=\"boom\")\nfun t(){let r=0..-1\nlet n=y!!.len()\nlet s=y?.len()\n}\n",
+        "@test(panics = \"boom\")\nfun t() {\n    let r = 0..-1\n    let n = y!!.len()\n    let s = y?.len()\n}\n",
+    );
+}
+
+#[test]
+fn test_format_keeps_comments() {
+    let source = "// header\n\nfun main() { // opens\n    let x = 1 /* inline */\n\n\n    // before y\n    let y = call(x, // first\n        2)\n    // last\n}\n// end\n";
+    assert_formats(
+        source,
+        "// header\n\nfun main() { // opens\n    let x = 1 /* inline */\n\n    // before y\n    let y = call(\n        x, // first\n        2,\n    )\n    // last\n}\n// end\n",
+    );
+}
+
+#[test]
+fn test_format_lists_and_trailing_commas() {
+    // Lists that fit go on one line without a trailing comma
+    assert_formats(
+        "fun main() {\n    let v = [\n        1,\n        2,\n    ]\n    print(v, d,)\n}\n",
+        "fun main() {\n    let v = [1, 2]\n    print(v, d)\n}\n",
+    );
+    // The comma of a one-element tuple is kept
+    assert_formats("let t = (1 ,)\n", "let t = (1,)\n");
+
+    let long_call = format!(
+        "fun main() {{\n    report({}, second)\n}}\n",
+        "x".repeat(MAX_WIDTH)
+    );
+    let expected = format!(
+        "fun main() {{\n    report(\n        {},\n        second,\n    )\n}}\n",
+        "x".repeat(MAX_WIDTH)
+    );
+    assert_formats(&long_call, &expected);
+
+    // Commas in type arguments don't separate list elements
+    assert_formats(
+        "fun f(m: Map<Text, Int>) {}\n",
+        "fun f(m: Map<Text, Int>) {}\n",
+    );
+}
+
+#[test]
+fn test_format_line_width() {
+    let formatted = format_source(MESSY).unwrap();
+    assert!(
+        formatted
+            .lines()
+            .all(|line| line.chars().count() <= MAX_WIDTH)
+    );
+    assert!(formatted.contains(
+        "    print(\n        add(1, 2),\n        values.len(),\n        d.get(\"a\"),\n        \"a long string literal that is sure to overflow the line\",\n    )\n"
+    ));
+
+    let nested = "fun main() {\n    outer(inner_function_name(first_argument, second_argument, third_argument), another_argument_name)\n}\n";
+    assert_formats(
+        nested,
+        "fun main() {\n    outer(\n        inner_function_name(first_argument, second_argument, third_argument),\n        another_argument_name,\n    )\n}\n",
+    );
+}
+
+#[test]
+fn test_format_blocks() {
+    // Single-line blocks stay on one line, and `else` follows `}`
+    assert_formats(
+        "fun main() {\n  if a { b } else { c }\n  loop { }\n  if a {\n  b\n  }\n  else {\n  c\n  }\n}\n",
+        "fun main() {\n    if a { b } else { c }\n    loop {}\n    if a {\n        b\n    } else {\n        c\n    }\n}\n",
+    );
+    // Continuation lines are indented once
+    assert_formats(
+        "fun main() {\nlet total = first +\nsecond\nlet chained = list\n.first()\n}\n",
+        "fun main() {\n    let total = first +\n        second\n    let chained = list\n        .first()\n}\n",
+    );
+    // A block in a one-argument call hugs the parentheses
+    assert_formats(
+        "fun main() {\nprint(when x {\n1 -> 2\n_ -> 3\n})\n}\n",
+        "fun main() {\n    print(when x {\n        1 -> 2\n        _ -> 3\n    })\n}\n",
+    );
+}
+
+#[test]
+fn test_format_is_idempotent() {
+    let formatted = format_source(MESSY).unwrap();
+    assert_eq!(format_source(&formatted).unwrap(), formatted);
+    assert!(formatted.starts_with("#!/usr/bin/env rive\n// Shapes and sums\n"));
+    assert!(
+        formatted
+            .contains("/// Adds two numbers\nfun add(a: Int, b: Int = 1): Int {\n    a + b\n}\n")
+    );
+
+    for source in ["", "\n\n", "fun main() {}", "// only a comment"] {
+        let formatted = format_source(source).unwrap();
+        assert_eq!(format_source(&formatted).unwrap(), formatted);
+    }
+}
+
+#[test]
+fn test_format_keeps_tokens() {
+    let formatted = format_source(MESSY).unwrap();
+    assert_eq!(significant_tokens(&formatted), significant_tokens(MESSY));
+}
+
+#[test]
+fn test_syntax_tree_is_lossless() {
+    let (tokens, errors) = tokenize_lossless(MESSY);
+    assert!(errors.is_empty());
+    let tree = SyntaxTree::build(tokens).unwrap();
+    assert_eq!(tree.to_string(), MESSY);
+
+    let functions = tree
+        .elements
+        .iter()
+        .filter_map(|element| match element {
+            SyntaxElement::Group(group) if group.delimiter == Delimiter::Brace => Some(group),
+            _ => None,
+        })
+        .count();
+    assert_eq!(functions, 3);
+}
+
+#[test]
+fn test_format_errors() {
+    let error = format_source("fun main() {\n    print(1]\n}").unwrap_err();
+    assert!(error.to_string().contains("Unexpected ']'"));
+
+    let error = format_source("fun main() {").unwrap_err();
+    assert!(error.to_string().contains("Unclosed '{'"));
+
+    let error = format_source("fun main() { let s = \"open }").unwrap_err();
+    assert!(error.to_string().contains("Unterminated string literal"));
+}
@@ -0,0 +1,115 @@
+//! Built-in functions: `print`, the diagnostic functions `panic`, `assert`,
+//! `assert_eq`, `todo` and `unreachable`, and the process functions `args`
+//! and `exit`.
+//!
+//! Failure messages are those of the compiled program, ending with the Rive
+//! source location of the call (e.g. `main.rive:3:5`).
+
+use super::{Eval, Interpreter, Unwind, invalid, operators};
+use crate::value::{RustDebug, Value};
+use rive_core::Span;
+use rive_ir::RirExpression;
+use std::rc::Rc;
+
+impl Interpreter<'_> {
+    /// Calls a built-in function.
+    ///
+    /// Returns `None` if `function` is not a builtin.
+    pub(super) fn call_builtin(
+        &mut self,
+        function: &str,
+        arguments: &[RirExpression],
+        span: Span,
+    ) -> Eval<Option<Value>> {
+        let value = match (function, arguments) {
+            ("print", _) => {
+                let line: String = self
+                    .eval_all(arguments)?
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect();
+                writeln!(self.out, "{line}").map_err(|error| {
+                    Unwind::Panic(format!("failed printing to stdout: {error}"))
+                })?;
+                Value::Unit
+            }
+            ("panic", [message]) => {
+                let message = self.eval(message)?;
+                return Err(Unwind::Panic(format!(
+                    "{message} at {}",
+                    self.location(span)
+                )));
+            }
+            ("assert", [condition]) => {
+                if !self.eval_bool(condition)? {
+                    return Err(Unwind::Panic(format!(
+                        "assertion failed at {}",
+                        self.location(span)
+                    )));
+                }
+                Value::Unit
+            }
+            ("assert", [condition, message]) => {
+                if !self.eval_bool(condition)? {
+                    let message = self.eval(message)?;
+                    return Err(Unwind::Panic(format!(
+                        "assertion failed: {message} at {}",
+                        self.location(span)
+                    )));
+                }
+                Value::Unit
+            }
+            ("assert_eq", [left, right]) => {
+                let left = self.eval(left)?;
+                let right = self.eval(right)?;
+                if !operators::equal(&left, &right) {
+                    return Err(Unwind::Panic(format!(
+                        "assertion `left == right` failed at {}\n  left: {}\n right: {}",
+                        self.location(span),
+                        RustDebug(&left),
+                        RustDebug(&right)
+                    )));
+                }
+                Value::Unit
+            }
+            ("todo", []) => {
+                return Err(Unwind::Panic(format!(
+                    "not yet implemented at {}",
+                    self.location(span)
+                )));
+            }
+            ("todo", [message]) => {
+                let message = self.eval(message)?;
+                return Err(Unwind::Panic(format!(
+                    "not yet implemented: {message} at {}",
+                    self.location(span)
+                )));
+            }
+            ("unreachable", []) => {
+                return Err(Unwind::Panic(format!(
+                    "entered unreachable code at {}",
+                    self.location(span)
+                )));
+            }
+            ("unreachable", [message]) => {
+                let message = self.eval(message)?;
+                return Err(Unwind::Panic(format!(
+                    "entered unreachable code: {message} at {}",
+                    self.location(span)
+                )));
+            }
+            ("args", []) => Value::List(Rc::new(
+                self.args.iter().map(|arg| Value::text(arg)).collect(),
+            )),
+            ("exit", [status]) => {
+                let Value::Int(status) = self.eval(status)? else {
+                    return Err(invalid("exit status is not an Int"));
+                };
+                return Err(Unwind::Exit(status as i32));
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(value))
+    }
+}
@@ -0,0 +1,276 @@
+//! Conditionals, loops and pattern matching.
+
+use super::{Eval, Interpreter, Scope, Unwind, invalid};
+use crate::value::Value;
+use rive_ir::{RirBlock, RirExpression, RirIterable, RirPattern};
+use std::rc::Rc;
+
+impl Interpreter<'_> {
+    /// Evaluates an `if`, with `Unit` as the value of a missing `else`.
+    pub(super) fn eval_if(
+        &mut self,
+        condition: &RirExpression,
+        then_block: &RirBlock,
+        else_block: Option<&RirBlock>,
+    ) -> Eval<Value> {
+        match self.condition(condition)? {
+            Some(bindings) => {
+                self.scoped(bindings, |interpreter| interpreter.eval_block(then_block))
+            }
+            None => match else_block {
+                Some(else_block) => self.eval_block(else_block),
+                None => Ok(Value::Unit),
+            },
+        }
+    }
+
+    /// Evaluates a `while` loop, whose value is the value it breaks with, or
+    /// null.
+    pub(super) fn eval_while(
+        &mut self,
+        condition: &RirExpression,
+        body: &RirBlock,
+        label: &Option<String>,
+    ) -> Eval<Value> {
+        loop {
+            let Some(bindings) = self.condition(condition)? else {
+                return Ok(Value::Nullable(None));
+            };
+            if let Some(result) = self.iterate(body, bindings, label)? {
+                return Ok(result);
+            }
+        }
+    }
+
+    /// Evaluates a `loop`, whose value is the value it breaks with, or null.
+    pub(super) fn eval_loop(&mut self, body: &RirBlock, label: &Option<String>) -> Eval<Value> {
+        loop {
+            if let Some(result) = self.iterate(body, Scope::new(), label)? {
+                return Ok(result);
+            }
+        }
+    }
+
+    /// Evaluates a `for` loop, whose value is the value it breaks with, or
+    /// null.
+    ///
+    /// Collections are iterated as they were when the loop started, so the
+    /// body may change the collection it iterates over.
+    pub(super) fn eval_for(
+        &mut self,
+        variable: &str,
+        iterable: &RirIterable,
+        body: &RirBlock,
+        label: &Option<String>,
+    ) -> Eval<Value> {
+        match iterable {
+            RirIterable::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let (Value::Int(start), Value::Int(end)) = (self.eval(start)?, self.eval(end)?)
+                else {
+                    return Err(invalid("range bounds are not Ints"));
+                };
+                if *inclusive {
+                    self.each(variable, (start..=end).map(Value::Int), body, label)
+                } else {
+                    self.each(variable, (start..end).map(Value::Int), body, label)
+                }
+            }
+            RirIterable::Collection { collection } => match self.eval(collection)? {
+                Value::Array(elements) | Value::List(elements) => {
+                    self.each(variable, elements.iter().cloned(), body, label)
+                }
+                Value::Map(entries) => {
+                    let entries = entries.iter().map(|(key, value)| {
+                        Value::Tuple(Rc::new(vec![Value::text(key), value.clone()]))
+                    });
+                    self.each(variable, entries, body, label)
+                }
+                value => Err(invalid(format!("cannot iterate over {value}"))),
+            },
+        }
+    }
+
+    /// Runs a loop body once for each item, bound to `variable`.
+    fn each(
+        &mut self,
+        variable: &str,
+        items: impl Iterator<Item = Value>,
+        body: &RirBlock,
+        label: &Option<String>,
+    ) -> Eval<Value> {
+        for item in items {
+            let bindings = Scope::from([(variable.to_string(), item)]);
+            if let Some(result) = self.iterate(body, bindings, label)? {
+                return Ok(result);
+            }
+        }
+        Ok(Value::Nullable(None))
+    }
+
+    /// Runs one iteration of a loop body with `bindings` in scope. Returns
+    /// the loop's value if the body breaks out of the loop.
+    fn iterate(
+        &mut self,
+        body: &RirBlock,
+        bindings: Scope,
+        label: &Option<String>,
+    ) -> Eval<Option<Value>> {
+        // A `break` or `continue` without a label targets the innermost loop
+        let targets_loop = |target: &Option<String>| target.is_none() || target == label;
+
+        match self.scoped(bindings, |interpreter| interpreter.eval_block(body)) {
+            Ok(_) => Ok(None),
+            Err(Unwind::Break { label, value }) if targets_loop(&label) => Ok(Some(
+                value.map_or(Value::Nullable(None), Value::into_nullable),
+            )),
+            Err(Unwind::Continue { label }) if targets_loop(&label) => Ok(None),
+            Err(unwind) => Err(unwind),
+        }
+    }
+
+    /// Evaluates the condition of an `if` or `while`. Returns `None` if it
+    /// doesn't hold, or else the variables it binds: a null binding
+    /// (`let name = value`) holds for non-null values and binds `name`.
+    fn condition(&mut self, condition: &RirExpression) -> Eval<Option<Scope>> {
+        if let RirExpression::LetSome { name, value, .. } = condition {
+            return Ok(match self.eval(value)? {
+                Value::Nullable(None) => None,
+                Value::Nullable(Some(value)) => Some(Scope::from([(name.clone(), *value)])),
+                value => Some(Scope::from([(name.clone(), value)])),
+            });
+        }
+        Ok(self.eval_bool(condition)?.then(Scope::new))
+    }
+
+    /// Evaluates a `match` expression.
+    pub(super) fn eval_match(
+        &mut self,
+        scrutinee: &RirExpression,
+        arms: &[(RirPattern, Box<RirExpression>)],
+    ) -> Eval<Value> {
+        let value = self.eval(scrutinee)?;
+        for (pattern, body) in arms {
+            if let Some(bindings) = self.match_arm(pattern, &value)? {
+                return self.scoped(bindings, |interpreter| interpreter.eval(body));
+            }
+        }
+        Err(invalid(format!("no match arm matches {value}")))
+    }
+
+    /// Matches a value against an arm's pattern and guard. Returns the
+    /// variables the pattern binds if it matches.
+    pub(super) fn match_arm(&mut self, pattern: &RirPattern, value: &Value) -> Eval<Option<Scope>> {
+        let (pattern, guard) = match pattern {
+            RirPattern::Guarded { pattern, guard, .. } => (&**pattern, Some(guard)),
+            pattern => (pattern, None),
+        };
+
+        let mut bindings = Scope::new();
+        if !self.matches(pattern, value, &mut bindings)? {
+            return Ok(None);
+        }
+        let Some(guard) = guard else {
+            return Ok(Some(bindings));
+        };
+
+        // The guard sees the pattern's bindings
+        self.scopes.push(bindings);
+        let holds = self.eval_bool(guard);
+        let bindings = self.scopes.pop().expect("the guard scope was pushed");
+        Ok(holds?.then_some(bindings))
+    }
+
+    /// Returns true if a value matches a pattern, adding the variables the
+    /// pattern binds to `bindings`.
+    fn matches(&mut self, pattern: &RirPattern, value: &Value, bindings: &mut Scope) -> Eval<bool> {
+        Ok(match (pattern, value) {
+            (RirPattern::Wildcard { .. }, _) => true,
+            (
+                RirPattern::IntLiteral {
+                    value: expected, ..
+                },
+                Value::Int(value),
+            ) => expected == value,
+            (
+                RirPattern::FloatLiteral {
+                    value: expected, ..
+                },
+                Value::Float(value),
+            ) => expected == value,
+            (
+                RirPattern::StringLiteral {
+                    value: expected, ..
+                },
+                Value::Text(value),
+            ) => **value == **expected,
+            (
+                RirPattern::BoolLiteral {
+                    value: expected, ..
+                },
+                Value::Bool(value),
+            ) => expected == value,
+            (
+                RirPattern::RangePattern {
+                    start,
+                    end,
+                    inclusive,
+                    ..
+                },
+                value,
+            ) => {
+                let start = self.eval(start)?;
+                let end = self.eval(end)?;
+                *value >= start
+                    && if *inclusive {
+                        *value <= end
+                    } else {
+                        *value < end
+                    }
+            }
+            (RirPattern::ResultOk { binding, .. }, Value::Result(Ok(value)))
+            | (RirPattern::ResultErr { binding, .. }, Value::Result(Err(value))) => {
+                if let Some(name) = binding {
+                    bindings.insert(name.clone(), (**value).clone());
+                }
+                true
+            }
+            (RirPattern::Null { .. }, Value::Nullable(None)) => true,
+            (RirPattern::NonNull { inner, .. }, Value::Nullable(Some(value))) => {
+                self.matches(inner, value, bindings)?
+            }
+            (RirPattern::Binding { name, .. }, value) => {
+                bindings.insert(name.clone(), value.clone());
+                true
+            }
+            (RirPattern::Tuple { elements, .. }, Value::Tuple(values)) => {
+                if elements.len() != values.len() {
+                    return Ok(false);
+                }
+                for (element, value) in elements.iter().zip(values.iter()) {
+                    if !self.matches(element, value, bindings)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            (RirPattern::Or { alternatives, .. }, value) => {
+                for alternative in alternatives {
+                    let mut alternative_bindings = Scope::new();
+                    if self.matches(alternative, value, &mut alternative_bindings)? {
+                        bindings.extend(alternative_bindings);
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            (RirPattern::Guarded { .. }, _) => {
+                return Err(invalid("guards are only allowed on match arms"));
+            }
+            _ => false,
+        })
+    }
+}
@@ -0,0 +1,199 @@
+//! Evaluation of expressions.
+
+use super::{Eval, Interpreter, Unwind, index_out_of_bounds, invalid, methods, operators};
+use crate::value::Value;
+use rive_ir::{BinaryOp, RirExpression};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+impl Interpreter<'_> {
+    /// Evaluates an expression.
+    pub(crate) fn eval(&mut self, expr: &RirExpression) -> Eval<Value> {
+        match expr {
+            RirExpression::IntLiteral { value, .. } => Ok(Value::Int(*value)),
+            RirExpression::FloatLiteral { value, .. } => Ok(Value::Float(*value)),
+            RirExpression::StringLiteral { value, .. } => Ok(Value::text(value)),
+            RirExpression::BoolLiteral { value, .. } => Ok(Value::Bool(*value)),
+            RirExpression::Unit { .. } => Ok(Value::Unit),
+            RirExpression::NullLiteral { .. } => Ok(Value::Nullable(None)),
+            RirExpression::Variable { name, .. } => self.variable(name).cloned(),
+            RirExpression::Global { name, .. } => self.global(name),
+
+            RirExpression::Binary {
+                op, left, right, ..
+            } => {
+                let left = self.eval(left)?;
+                // `&&` and `||` only evaluate their right side when needed
+                match (op, &left) {
+                    (BinaryOp::And, Value::Bool(false)) | (BinaryOp::Or, Value::Bool(true)) => {
+                        return Ok(left);
+                    }
+                    (BinaryOp::And | BinaryOp::Or, _) => return self.eval(right),
+                    _ => {}
+                }
+                let right = self.eval(right)?;
+                operators::binary(*op, left, right, self.overflow_checks)
+            }
+            RirExpression::Unary { op, operand, .. } => {
+                let operand = self.eval(operand)?;
+                operators::unary(*op, operand, self.overflow_checks)
+            }
+
+            RirExpression::Call {
+                function,
+                arguments,
+                span,
+                ..
+            } => {
+                // User functions shadow every builtin except `print`
+                let shadowed =
+                    function != "print" && self.functions.contains_key(function.as_str());
+                if !shadowed && let Some(value) = self.call_builtin(function, arguments, *span)? {
+                    return Ok(value);
+                }
+                let function = self
+                    .functions
+                    .get(function.as_str())
+                    .copied()
+                    .ok_or_else(|| invalid(format!("undefined function '{function}'")))?;
+                let arguments = self.eval_all(arguments)?;
+                self.call_function(function, arguments)
+            }
+            RirExpression::MethodCall {
+                object,
+                method,
+                arguments,
+                ..
+            } => {
+                // Mutating methods change the variable the object is stored in
+                if methods::is_mutating(method)
+                    && let Some(place) = self.place(object)?
+                {
+                    let arguments = self.eval_all(arguments)?;
+                    return methods::call(self.place_mut(&place)?, method, arguments);
+                }
+                let mut object = self.eval(object)?;
+                let arguments = self.eval_all(arguments)?;
+                methods::call(&mut object, method, arguments)
+            }
+
+            RirExpression::ArrayLiteral { elements, .. } => {
+                Ok(Value::Array(Rc::new(self.eval_all(elements)?)))
+            }
+            RirExpression::TupleLiteral { elements, .. } => {
+                Ok(Value::Tuple(Rc::new(self.eval_all(elements)?)))
+            }
+            RirExpression::ListLiteral { elements, .. } => {
+                Ok(Value::List(Rc::new(self.eval_all(elements)?)))
+            }
+            RirExpression::DictLiteral { entries, .. } => {
+                let mut map = BTreeMap::new();
+                for (key, value) in entries {
+                    map.insert(key.clone(), self.eval(value)?);
+                }
+                Ok(Value::Map(Rc::new(map)))
+            }
+            RirExpression::Index { array, index, .. } => {
+                let array = self.eval(array)?;
+                let (Value::Array(elements) | Value::List(elements), Value::Int(index)) =
+                    (&array, self.eval(index)?)
+                else {
+                    return Err(invalid(format!("cannot index {array}")));
+                };
+                usize::try_from(index)
+                    .ok()
+                    .and_then(|i| elements.get(i))
+                    .cloned()
+                    .ok_or_else(|| index_out_of_bounds(elements.len(), index))
+            }
+            RirExpression::FieldAccess { object, field, .. } => {
+                let object = self.eval(object)?;
+                let element = match (&object, field.parse::<usize>()) {
+                    (Value::Tuple(elements), Ok(index)) => elements.get(index).cloned(),
+                    _ => None,
+                };
+                element.ok_or_else(|| invalid(format!("{object} has no field '{field}'")))
+            }
+
+            RirExpression::If {
+                condition,
+                then_block,
+                else_block,
+                ..
+            } => self.eval_if(condition, then_block, Some(else_block)),
+            RirExpression::Match {
+                scrutinee, arms, ..
+            } => self.eval_match(scrutinee, arms),
+            RirExpression::Block { block, result, .. } => self
+                .scoped(Default::default(), |interpreter| {
+                    interpreter.eval_statements(&block.statements, result.as_deref())
+                }),
+            RirExpression::While {
+                condition,
+                body,
+                label,
+                ..
+            } => self.eval_while(condition, body, label),
+            RirExpression::For {
+                variable,
+                iterable,
+                body,
+                label,
+                ..
+            } => self.eval_for(variable, iterable, body, label),
+            RirExpression::Loop { body, label, .. } => self.eval_loop(body, label),
+
+            RirExpression::Elvis {
+                value,
+                fallback,
+                result_type,
+                ..
+            } => match self.eval(value)? {
+                Value::Nullable(None) => self.eval(fallback),
+                Value::Nullable(Some(value)) if !self.is_nullable(*result_type) => Ok(*value),
+                value => Ok(value),
+            },
+            RirExpression::SafeCall { object, call, .. } => match self.eval(object)? {
+                Value::Nullable(None) => Ok(Value::Nullable(None)),
+                _ => Ok(self.eval(call)?.into_nullable()),
+            },
+            RirExpression::WrapOptional { value, .. } => Ok(self.eval(value)?.into_nullable()),
+            RirExpression::NonNullAssert { value, span, .. } => match self.eval(value)? {
+                Value::Nullable(Some(value)) => Ok(*value),
+                Value::Nullable(None) => Err(Unwind::Panic(format!(
+                    "null assertion failed at {}",
+                    self.location(*span)
+                ))),
+                value => Ok(value),
+            },
+            RirExpression::LetSome { .. } => Err(invalid(
+                "null bindings are only allowed as if and while conditions",
+            )),
+
+            RirExpression::ResultOk { value, .. } => {
+                Ok(Value::Result(Ok(Box::new(self.eval(value)?))))
+            }
+            RirExpression::ResultErr { value, .. } => {
+                Ok(Value::Result(Err(Box::new(self.eval(value)?))))
+            }
+            RirExpression::Propagate { value, .. } => match self.eval(value)? {
+                Value::Result(Ok(value)) => Ok(*value),
+                Value::Result(Err(error)) => Err(Unwind::Return(Value::Result(Err(error)))),
+                value => Err(invalid(format!("cannot propagate {value}"))),
+            },
+        }
+    }
+
+    /// Evaluates expressions from left to right.
+    pub(super) fn eval_all(&mut self, exprs: &[RirExpression]) -> Eval<Vec<Value>> {
+        exprs.iter().map(|expr| self.eval(expr)).collect()
+    }
+
+    /// Evaluates an expression of type `Bool`.
+    pub(super) fn eval_bool(&mut self, expr: &RirExpression) -> Eval<bool> {
+        match self.eval(expr)? {
+            Value::Bool(value) => Ok(value),
+            value => Err(invalid(format!("{value} is not a Bool"))),
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Builtin methods of primitives, lists and maps.
+
+use super::{Eval, invalid};
+use crate::value::Value;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// Returns true if a method changes the collection it is called on.
+pub(super) fn is_mutating(method: &str) -> bool {
+    matches!(
+        method,
+        "append" | "insert" | "remove" | "clear" | "reverse" | "sort"
+    )
+}
+
+/// Calls a method on `object`, which mutating methods change in place.
+pub(super) fn call(object: &mut Value, method: &str, arguments: Vec<Value>) -> Eval<Value> {
+    let mut arguments = arguments.into_iter();
+    let mut argument = || {
+        arguments
+            .next()
+            .ok_or_else(|| invalid(format!("missing argument of '{method}'")))
+    };
+
+    match object {
+        Value::List(elements) => list_method(elements, method, &mut argument),
+        Value::Map(entries) => map_method(entries, method, &mut argument),
+        Value::Text(text) => text_method(text, method, &mut argument),
+        Value::Tuple(elements) if method == "len" => Ok(Value::Int(elements.len() as i64)),
+        Value::Int(value) if method == "to_float" => Ok(Value::Float(*value as f64)),
+        Value::Float(value) => float_method(*value, method),
+        object => Err(invalid(format!("unknown method '{method}' of {object}"))),
+    }
+}
+
+/// Returns a list or text index, or `None` if it is negative.
+fn index(value: Value) -> Option<usize> {
+    match value {
+        Value::Int(index) => usize::try_from(index).ok(),
+        _ => None,
+    }
+}
+
+/// Returns a map key.
+fn key(value: Value) -> Eval<String> {
+    match value {
+        Value::Text(key) => Ok(key.to_string()),
+        value => Err(invalid(format!("map key {value} is not Text"))),
+    }
+}
+
+fn list_method(
+    elements: &mut Rc<Vec<Value>>,
+    method: &str,
+    argument: &mut impl FnMut() -> Eval<Value>,
+) -> Eval<Value> {
+    let value = match method {
+        "len" => Value::Int(elements.len() as i64),
+        "is_empty" => Value::Bool(elements.is_empty()),
+        "get" => Value::Nullable(
+            index(argument()?)
+                .and_then(|index| elements.get(index))
+                .cloned()
+                .map(Box::new),
+        ),
+        "contains" => Value::Bool(elements.contains(&argument()?)),
+        "append" => {
+            let value = argument()?;
+            Rc::make_mut(elements).push(value);
+            Value::Unit
+        }
+        "insert" => {
+            let index = index(argument()?);
+            let value = argument()?;
+            if let Some(index) = index.filter(|index| *index <= elements.len()) {
+                Rc::make_mut(elements).insert(index, value);
+            }
+            Value::Unit
+        }
+        "remove" => {
+            if let Some(index) = index(argument()?).filter(|index| *index < elements.len()) {
+                Rc::make_mut(elements).remove(index);
+            }
+            Value::Unit
+        }
+        "clear" => {
+            Rc::make_mut(elements).clear();
+            Value::Unit
+        }
+        "reverse" => {
+            Rc::make_mut(elements).reverse();
+            Value::Unit
+        }
+        "sort" => {
+            Rc::make_mut(elements)
+                .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            Value::Unit
+        }
+        _ => return Err(invalid(format!("unknown list method '{method}'"))),
+    };
+    Ok(value)
+}
+
+fn map_method(
+    entries: &mut Rc<BTreeMap<String, Value>>,
+    method: &str,
+    argument: &mut impl FnMut() -> Eval<Value>,
+) -> Eval<Value> {
+    let value = match method {
+        "len" => Value::Int(entries.len() as i64),
+        "is_empty" => Value::Bool(entries.is_empty()),
+        "get" => Value::Nullable(entries.get(&key(argument()?)?).cloned().map(Box::new)),
+        "contains_key" => Value::Bool(entries.contains_key(&key(argument()?)?)),
+        "keys" => Value::List(Rc::new(
+            entries.keys().map(|key| Value::text(key)).collect(),
+        )),
+        "values" => Value::List(Rc::new(entries.values().cloned().collect())),
+        "insert" => {
+            let key = key(argument()?)?;
+            let value = argument()?;
+            Rc::make_mut(entries).insert(key, value);
+            Value::Unit
+        }
+        "remove" => {
+            let key = key(argument()?)?;
+            Rc::make_mut(entries).remove(&key);
+            Value::Unit
+        }
+        _ => return Err(invalid(format!("unknown map method '{method}'"))),
+    };
+    Ok(value)
+}
+
+fn text_method(
+    text: &str,
+    method: &str,
+    argument: &mut impl FnMut() -> Eval<Value>,
+) -> Eval<Value> {
+    let mut text_argument = || match argument()? {
+        Value::Text(text) => Ok(text),
+        value => Err(invalid(format!("{value} is not Text"))),
+    };
+
+    let value = match method {
+        // Length in bytes, as in the compiled program
+        "len" => Value::Int(text.len() as i64),
+        "is_empty" => Value::Bool(text.is_empty()),
+        "contains" => Value::Bool(text.contains(&*text_argument()?)),
+        "to_upper" => Value::text(&text.to_uppercase()),
+        "to_lower" => Value::text(&text.to_lowercase()),
+        "trim" => Value::text(text.trim()),
+        "replace" => {
+            let from = text_argument()?;
+            let to = text_argument()?;
+            Value::text(&text.replace(&*from, &to))
+        }
+        "split" => {
+            let delimiter = text_argument()?;
+            Value::List(Rc::new(text.split(&*delimiter).map(Value::text).collect()))
+        }
+        _ => return Err(invalid(format!("unknown Text method '{method}'"))),
+    };
+    Ok(value)
+}
+
+fn float_method(value: f64, method: &str) -> Eval<Value> {
+    Ok(match method {
+        // Truncates toward zero, and is null for NaN and infinities
+        "to_int" => Value::Nullable(
+            value
+                .is_finite()
+                .then(|| Box::new(Value::Int(value.trunc() as i64))),
+        ),
+        "is_nan" => Value::Bool(value.is_nan()),
+        "is_infinite" => Value::Bool(value.is_infinite()),
+        "is_finite" => Value::Bool(value.is_finite()),
+        "round" => Value::Float(value.round()),
+        _ => return Err(invalid(format!("unknown Float method '{method}'"))),
+    })
+}
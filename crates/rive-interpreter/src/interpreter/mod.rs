@@ -0,0 +1,343 @@
+//! Tree-walking evaluation of RIR.
+
+mod builtins;
+mod control_flow;
+mod expressions;
+mod methods;
+mod operators;
+mod statements;
+
+use crate::value::{RustDebug, Value};
+use rive_core::Span;
+use rive_core::type_system::{TypeId, TypeKind, TypeRegistry};
+use rive_ir::{RirFunction, RirGlobal, RirModule, RirStatement};
+use std::collections::HashMap;
+use std::io::Write;
+use thiserror::Error;
+
+/// How running a program failed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RuntimeError {
+    /// The program panicked, e.g. through `panic`, a failed assertion or
+    /// integer overflow
+    #[error("{0}")]
+    Panic(String),
+    /// `main` returned an error result, shown as Rust shows it
+    #[error("Error: {0}")]
+    Failed(String),
+    /// The module has no `main` function to run
+    #[error("Missing main function")]
+    MissingMain,
+    /// Statements run by [`Interpreter::run_statements`] called `exit`
+    #[error("Exited with status {0}")]
+    Exit(i32),
+}
+
+/// Why evaluation left an expression or statement before finishing it.
+pub(crate) enum Unwind {
+    /// `break`, with the label of the loop it leaves
+    Break {
+        label: Option<String>,
+        value: Option<Value>,
+    },
+    /// `continue`, with the label of the loop it continues
+    Continue { label: Option<String> },
+    /// `return` from the running function
+    Return(Value),
+    /// `exit(status)`
+    Exit(i32),
+    /// A panic, with its message
+    Panic(String),
+}
+
+/// Result of evaluating an expression or statement.
+pub(crate) type Eval<T> = Result<T, Unwind>;
+
+/// Variables of one scope by name.
+type Scope = HashMap<String, Value>;
+
+/// Interpreter for RIR modules.
+///
+/// Programs run from their RIR directly, without generating Rust, and
+/// behave like the program compiled in the `dev` profile: integer overflow
+/// panics unless overflow checks are turned off, and failures report the
+/// same messages and Rive source locations.
+pub struct Interpreter<'a> {
+    /// Functions of the module by name
+    functions: HashMap<&'a str, &'a RirFunction>,
+    /// Globals of the module by name
+    globals: HashMap<&'a str, &'a RirGlobal>,
+    /// Values of the globals used so far; each is computed on first use
+    global_values: HashMap<&'a str, Value>,
+    type_registry: &'a TypeRegistry,
+    /// Scopes of every active call, innermost last
+    scopes: Vec<Scope>,
+    /// Index in `scopes` of the running function's outermost scope
+    frame_start: usize,
+    /// Arguments returned by `args()`
+    args: Vec<String>,
+    /// Where `print` writes
+    out: &'a mut dyn Write,
+    /// Name of the source file that failure locations refer to
+    source_name: String,
+    /// Line of the source text that the source file starts on
+    first_line: usize,
+    /// Whether integer overflow panics instead of wrapping around
+    overflow_checks: bool,
+}
+
+impl<'a> Interpreter<'a> {
+    /// Creates an interpreter for a module that prints to `out`.
+    pub fn new(module: &'a RirModule, out: &'a mut dyn Write) -> Self {
+        Self {
+            functions: module
+                .functions
+                .iter()
+                .map(|function| (function.name.as_str(), function))
+                .collect(),
+            globals: module
+                .globals
+                .iter()
+                .map(|global| (global.name.as_str(), global))
+                .collect(),
+            global_values: HashMap::new(),
+            type_registry: &module.type_registry,
+            scopes: Vec::new(),
+            frame_start: 0,
+            args: Vec::new(),
+            out,
+            source_name: "main.rive".to_string(),
+            first_line: 1,
+            overflow_checks: true,
+        }
+    }
+
+    /// Sets the program arguments, which `args()` returns.
+    #[must_use]
+    pub fn with_args(mut self, args: Vec<String>) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// Sets the name of the source file that failure locations refer to,
+    /// `main.rive` by default.
+    #[must_use]
+    pub fn with_source_name(mut self, name: impl Into<String>) -> Self {
+        self.source_name = name.into();
+        self
+    }
+
+    /// Sets the line of the source text that the source file starts on, 1
+    /// by default, for a file embedded in a larger text. Lines of failure
+    /// locations are counted from it.
+    #[must_use]
+    pub fn with_first_line(mut self, line: usize) -> Self {
+        self.first_line = line;
+        self
+    }
+
+    /// Sets whether integer overflow panics, as it does by default, or wraps
+    /// around like the compiled program built without overflow checks.
+    #[must_use]
+    pub fn with_overflow_checks(mut self, overflow_checks: bool) -> Self {
+        self.overflow_checks = overflow_checks;
+        self
+    }
+
+    /// Formats a span as a Rive source location (e.g., `main.rive:3:14`).
+    pub(crate) fn location(&self, span: Span) -> String {
+        format!(
+            "{}:{}:{}",
+            self.source_name,
+            (span.start.line + 1).saturating_sub(self.first_line),
+            span.start.column
+        )
+    }
+
+    /// Runs `main` and returns the program's exit status: the value `main`
+    /// returns if it returns an `Int`, the status passed to `exit`, or 0.
+    ///
+    /// # Errors
+    /// Returns an error if the program panics, `main` returns an error
+    /// result, or the module has no `main`.
+    pub fn run(&mut self) -> Result<i32, RuntimeError> {
+        let main = self
+            .functions
+            .get("main")
+            .copied()
+            .ok_or(RuntimeError::MissingMain)?;
+        let result = self.call_function(main, Vec::new());
+        // Output already printed stays printed, like in the compiled program
+        let _ = self.out.flush();
+
+        match result {
+            // The compiled program exits with `main`'s value truncated the same way
+            Ok(Value::Int(status)) => Ok(status as i32),
+            Ok(Value::Result(Err(error))) => {
+                Err(RuntimeError::Failed(RustDebug(&error).to_string()))
+            }
+            Ok(_) => Ok(0),
+            Err(Unwind::Exit(status)) => Ok(status),
+            Err(Unwind::Panic(message)) => Err(RuntimeError::Panic(message)),
+            Err(_) => Err(RuntimeError::Panic(
+                "internal error: unexpected control flow out of main".to_string(),
+            )),
+        }
+    }
+
+    /// Runs statements in a scope holding `variables`, the way `rive repl`
+    /// runs each input, and returns the variables defined once they finish.
+    ///
+    /// # Errors
+    /// Returns an error if the statements panic, or [`RuntimeError::Exit`]
+    /// if they call `exit`.
+    pub fn run_statements(
+        &mut self,
+        statements: &[RirStatement],
+        variables: HashMap<String, Value>,
+    ) -> Result<HashMap<String, Value>, RuntimeError> {
+        let frame_start = std::mem::replace(&mut self.frame_start, self.scopes.len());
+        self.scopes.push(variables);
+        let result = self.eval_statements(statements, None);
+        self.scopes.truncate(self.frame_start + 1);
+        let variables = self.scopes.pop().unwrap_or_default();
+        self.frame_start = frame_start;
+        let _ = self.out.flush();
+
+        match result {
+            Ok(_) | Err(Unwind::Return(_)) => Ok(variables),
+            Err(Unwind::Exit(status)) => Err(RuntimeError::Exit(status)),
+            Err(Unwind::Panic(message)) => Err(RuntimeError::Panic(message)),
+            Err(Unwind::Break { .. } | Unwind::Continue { .. }) => Err(RuntimeError::Panic(
+                "internal error: break or continue outside of a loop".to_string(),
+            )),
+        }
+    }
+
+    /// Calls a function of the module with evaluated arguments.
+    pub(crate) fn call_function(
+        &mut self,
+        function: &'a RirFunction,
+        arguments: Vec<Value>,
+    ) -> Eval<Value> {
+        let scope = function
+            .parameters
+            .iter()
+            .zip(arguments)
+            .map(|(parameter, argument)| {
+                (
+                    parameter.name.clone(),
+                    self.coerce(argument, parameter.type_id),
+                )
+            })
+            .collect();
+
+        match self.in_frame(scope, |interpreter| interpreter.eval_block(&function.body)) {
+            Ok(value) | Err(Unwind::Return(value)) => Ok(self.coerce(value, function.return_type)),
+            Err(Unwind::Break { .. } | Unwind::Continue { .. }) => {
+                Err(invalid("break or continue outside of a loop"))
+            }
+            Err(unwind) => Err(unwind),
+        }
+    }
+
+    /// Returns the value of a global, computing it on first use.
+    pub(crate) fn global(&mut self, name: &str) -> Eval<Value> {
+        if let Some(value) = self.global_values.get(name) {
+            return Ok(value.clone());
+        }
+
+        let global = self
+            .globals
+            .get(name)
+            .copied()
+            .ok_or_else(|| invalid(format!("undefined global '{name}'")))?;
+        let value = self.in_frame(Scope::new(), |interpreter| interpreter.eval(&global.value))?;
+        let value = self.coerce(value, global.type_id);
+        self.global_values
+            .insert(global.name.as_str(), value.clone());
+        Ok(value)
+    }
+
+    /// Runs `f` in a new call frame whose outermost scope is `scope`.
+    fn in_frame<T>(&mut self, scope: Scope, f: impl FnOnce(&mut Self) -> Eval<T>) -> Eval<T> {
+        let frame_start = std::mem::replace(&mut self.frame_start, self.scopes.len());
+        self.scopes.push(scope);
+        let result = f(self);
+        self.scopes.truncate(self.frame_start);
+        self.frame_start = frame_start;
+        result
+    }
+
+    /// Runs `f` in a new scope holding `scope`'s variables.
+    pub(crate) fn scoped<T>(
+        &mut self,
+        scope: Scope,
+        f: impl FnOnce(&mut Self) -> Eval<T>,
+    ) -> Eval<T> {
+        let depth = self.scopes.len();
+        self.scopes.push(scope);
+        let result = f(self);
+        self.scopes.truncate(depth);
+        result
+    }
+
+    /// Defines a variable in the innermost scope.
+    pub(crate) fn define(&mut self, name: &str, value: Value) {
+        self.scopes
+            .last_mut()
+            .expect("code runs inside a call frame")
+            .insert(name.to_string(), value);
+    }
+
+    /// Returns the value of a variable of the running function.
+    pub(crate) fn variable(&self, name: &str) -> Eval<&Value> {
+        self.scopes[self.frame_start..]
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .ok_or_else(|| invalid(format!("undefined variable '{name}'")))
+    }
+
+    /// Returns a variable of the running function for assignment.
+    pub(crate) fn variable_mut(&mut self, name: &str) -> Eval<&mut Value> {
+        self.scopes[self.frame_start..]
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+            .ok_or_else(|| invalid(format!("undefined variable '{name}'")))
+    }
+
+    /// Returns true if `type_id` is a nullable type.
+    pub(crate) fn is_nullable(&self, type_id: TypeId) -> bool {
+        let type_id = self.type_registry.resolve(type_id);
+        matches!(
+            self.type_registry
+                .get(type_id)
+                .map(|metadata| &metadata.kind),
+            Some(TypeKind::Optional { .. })
+        )
+    }
+
+    /// Converts a value stored as `type_id` to a nullable one if the type is
+    /// nullable, as the compiled program wraps it in `Some`.
+    pub(crate) fn coerce(&self, value: Value, type_id: TypeId) -> Value {
+        if self.is_nullable(type_id) {
+            value.into_nullable()
+        } else {
+            value
+        }
+    }
+}
+
+/// Reports a program that semantic analysis should have rejected.
+pub(crate) fn invalid(message: impl Into<String>) -> Unwind {
+    Unwind::Panic(format!("internal error: {}", message.into()))
+}
+
+/// The panic of indexing out of bounds, worded as Rust words it.
+pub(crate) fn index_out_of_bounds(len: usize, index: i64) -> Unwind {
+    Unwind::Panic(format!(
+        "index out of bounds: the len is {len} but the index is {index}"
+    ))
+}
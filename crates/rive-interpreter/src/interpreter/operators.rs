@@ -0,0 +1,153 @@
+//! Binary and unary operators.
+//!
+//! Integer arithmetic panics with Rust's messages on overflow, as the
+//! compiled program does in the `dev` profile, or wraps around when overflow
+//! checks are off, as in the `release` profile.
+
+use super::{Eval, Unwind, invalid};
+use crate::value::Value;
+use rive_ir::{BinaryOp, UnaryOp};
+
+/// Applies a binary operator other than `&&` and `||`, which short-circuit.
+///
+/// Integer overflow panics if `overflow_checks` is set and wraps otherwise.
+/// Division overflow and division by zero always panic, as in Rust.
+pub(super) fn binary(
+    op: BinaryOp,
+    left: Value,
+    right: Value,
+    overflow_checks: bool,
+) -> Eval<Value> {
+    use Value::{Bool, Float, Int, Text};
+
+    Ok(match (op, left, right) {
+        (BinaryOp::Equal, left, right) => Bool(equal(&left, &right)),
+        (BinaryOp::NotEqual, left, right) => Bool(!equal(&left, &right)),
+        (BinaryOp::LessThan, left, right) => Bool(left < right),
+        (BinaryOp::LessEqual, left, right) => Bool(left <= right),
+        (BinaryOp::GreaterThan, left, right) => Bool(left > right),
+        (BinaryOp::GreaterEqual, left, right) => Bool(left >= right),
+
+        (BinaryOp::Add, Int(left), Int(right)) => Int(integer(
+            left.checked_add(right),
+            left.wrapping_add(right),
+            overflow_checks,
+            "add",
+        )?),
+        (BinaryOp::Subtract, Int(left), Int(right)) => Int(integer(
+            left.checked_sub(right),
+            left.wrapping_sub(right),
+            overflow_checks,
+            "subtract",
+        )?),
+        (BinaryOp::Multiply, Int(left), Int(right)) => Int(integer(
+            left.checked_mul(right),
+            left.wrapping_mul(right),
+            overflow_checks,
+            "multiply",
+        )?),
+        (BinaryOp::Divide, Int(_), Int(0)) => {
+            return Err(Unwind::Panic("attempt to divide by zero".to_string()));
+        }
+        (BinaryOp::Divide, Int(left), Int(right)) => {
+            Int(left.checked_div(right).ok_or_else(|| overflow("divide"))?)
+        }
+        (BinaryOp::Modulo, Int(_), Int(0)) => {
+            return Err(Unwind::Panic(
+                "attempt to calculate the remainder with a divisor of zero".to_string(),
+            ));
+        }
+        (BinaryOp::Modulo, Int(left), Int(right)) => Int(left
+            .checked_rem(right)
+            .ok_or_else(|| overflow("calculate the remainder"))?),
+
+        (BinaryOp::Add, Float(left), Float(right)) => Float(left + right),
+        (BinaryOp::Subtract, Float(left), Float(right)) => Float(left - right),
+        (BinaryOp::Multiply, Float(left), Float(right)) => Float(left * right),
+        (BinaryOp::Divide, Float(left), Float(right)) => Float(left / right),
+        (BinaryOp::Modulo, Float(left), Float(right)) => Float(left % right),
+
+        (BinaryOp::Add, Text(left), Text(right)) => Text(format!("{left}{right}").into()),
+
+        (BinaryOp::BitAnd, Int(left), Int(right)) => Int(left & right),
+        (BinaryOp::BitOr, Int(left), Int(right)) => Int(left | right),
+        (BinaryOp::BitXor, Int(left), Int(right)) => Int(left ^ right),
+        (BinaryOp::BitAnd, Bool(left), Bool(right)) => Bool(left & right),
+        (BinaryOp::BitOr, Bool(left), Bool(right)) => Bool(left | right),
+        (BinaryOp::BitXor, Bool(left), Bool(right)) => Bool(left ^ right),
+        // Without overflow checks Rust masks the shift amount to the bit width
+        (BinaryOp::ShiftLeft, Int(left), Int(right)) => Int(integer(
+            u32::try_from(right)
+                .ok()
+                .and_then(|right| left.checked_shl(right)),
+            left.wrapping_shl(right as u32),
+            overflow_checks,
+            "shift left",
+        )?),
+        (BinaryOp::ShiftRight, Int(left), Int(right)) => Int(integer(
+            u32::try_from(right)
+                .ok()
+                .and_then(|right| left.checked_shr(right)),
+            left.wrapping_shr(right as u32),
+            overflow_checks,
+            "shift right",
+        )?),
+
+        (op, left, right) => {
+            return Err(invalid(format!(
+                "cannot apply {op:?} to {left} and {right}"
+            )));
+        }
+    })
+}
+
+/// Applies a unary operator, negating integers as `binary` does arithmetic.
+pub(super) fn unary(op: UnaryOp, operand: Value, overflow_checks: bool) -> Eval<Value> {
+    Ok(match (op, operand) {
+        (UnaryOp::Negate, Value::Int(value)) => Value::Int(integer(
+            value.checked_neg(),
+            value.wrapping_neg(),
+            overflow_checks,
+            "negate",
+        )?),
+        (UnaryOp::Negate, Value::Float(value)) => Value::Float(-value),
+        (UnaryOp::Not, Value::Bool(value)) => Value::Bool(!value),
+        (UnaryOp::Not | UnaryOp::BitNot, Value::Int(value)) => Value::Int(!value),
+        (op, operand) => return Err(invalid(format!("cannot apply {op:?} to {operand}"))),
+    })
+}
+
+/// Compares values with `==`. A nullable value equals a non-null value
+/// if it holds an equal value.
+pub(super) fn equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Nullable(Some(left)), right) if !matches!(right, Value::Nullable(_)) => {
+            **left == *right
+        }
+        (left, Value::Nullable(Some(right))) if !matches!(left, Value::Nullable(_)) => {
+            *left == **right
+        }
+        _ => left == right,
+    }
+}
+
+/// Returns the result of an integer operation: the `checked` result, which
+/// is `None` on overflow, if `overflow_checks` is set, and the `wrapped`
+/// result otherwise.
+fn integer(
+    checked: Option<i64>,
+    wrapped: i64,
+    overflow_checks: bool,
+    operation: &str,
+) -> Eval<i64> {
+    if overflow_checks {
+        checked.ok_or_else(|| overflow(operation))
+    } else {
+        Ok(wrapped)
+    }
+}
+
+/// The panic of overflowing integer arithmetic, worded as Rust words it.
+fn overflow(operation: &str) -> Unwind {
+    Unwind::Panic(format!("attempt to {operation} with overflow"))
+}
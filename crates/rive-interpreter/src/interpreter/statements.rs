@@ -0,0 +1,198 @@
+//! Execution of statements and blocks, and assignment.
+
+use super::{Eval, Interpreter, Scope, Unwind, index_out_of_bounds, invalid};
+use crate::value::Value;
+use rive_ir::{RirBlock, RirExpression, RirStatement};
+use std::rc::Rc;
+
+/// A variable, or an element nested inside one, that can be changed in place.
+pub(super) struct Place {
+    variable: String,
+    /// Indices of the element within the variable, outermost first
+    path: Vec<i64>,
+}
+
+impl Interpreter<'_> {
+    /// Runs a block in a scope of its own and returns its value.
+    pub(crate) fn eval_block(&mut self, block: &RirBlock) -> Eval<Value> {
+        self.scoped(Scope::new(), |interpreter| {
+            interpreter.eval_statements(&block.statements, block.final_expr.as_deref())
+        })
+    }
+
+    /// Runs statements in the current scope and returns the value of
+    /// `result`. Without one, the value is that of a trailing `if`, `match`
+    /// or block statement, as the generated Rust ends in that statement.
+    pub(super) fn eval_statements(
+        &mut self,
+        statements: &[RirStatement],
+        result: Option<&RirExpression>,
+    ) -> Eval<Value> {
+        let mut last = Value::Unit;
+        for statement in statements {
+            last = self.exec(statement)?;
+        }
+        match result {
+            Some(result) => self.eval(result),
+            None => Ok(last),
+        }
+    }
+
+    /// Runs a statement. `if`, `match` and block statements return the
+    /// value of the block they ran; all others return `Unit`.
+    fn exec(&mut self, statement: &RirStatement) -> Eval<Value> {
+        match statement {
+            RirStatement::Let {
+                name,
+                type_id,
+                value,
+                ..
+            } => {
+                let value = self.eval(value)?;
+                let value = self.coerce(value, *type_id);
+                self.define(name, value);
+            }
+            RirStatement::Assign { name, value, .. } => {
+                let value = self.eval(value)?;
+                let variable = self.variable_mut(name)?;
+                *variable = if matches!(variable, Value::Nullable(_)) {
+                    value.into_nullable()
+                } else {
+                    value
+                };
+            }
+            RirStatement::AssignIndex {
+                array,
+                index,
+                value,
+                ..
+            } => {
+                let Value::Int(index) = self.eval(index)? else {
+                    return Err(invalid("index is not an Int"));
+                };
+                let value = self.eval(value)?;
+                let place = Place {
+                    variable: array.clone(),
+                    path: vec![index],
+                };
+                *self.place_mut(&place)? = value;
+            }
+            RirStatement::Return { value, .. } => {
+                let value = match value {
+                    Some(value) => self.eval(value)?,
+                    None => Value::Unit,
+                };
+                return Err(Unwind::Return(value));
+            }
+            RirStatement::If {
+                condition,
+                then_block,
+                else_block,
+                ..
+            } => return self.eval_if(condition, then_block, else_block.as_ref()),
+            RirStatement::While {
+                condition,
+                body,
+                label,
+                ..
+            } => {
+                self.eval_while(condition, body, label)?;
+            }
+            RirStatement::For {
+                variable,
+                iterable,
+                body,
+                label,
+                ..
+            } => {
+                self.eval_for(variable, iterable, body, label)?;
+            }
+            RirStatement::Loop { body, label, .. } => {
+                self.eval_loop(body, label)?;
+            }
+            RirStatement::Break { label, value, .. } => {
+                let value = match value {
+                    Some(value) => Some(self.eval(value)?),
+                    None => None,
+                };
+                return Err(Unwind::Break {
+                    label: label.clone(),
+                    value,
+                });
+            }
+            RirStatement::Continue { label, .. } => {
+                return Err(Unwind::Continue {
+                    label: label.clone(),
+                });
+            }
+            RirStatement::Match {
+                scrutinee, arms, ..
+            } => {
+                let value = self.eval(scrutinee)?;
+                for (pattern, body) in arms {
+                    if let Some(bindings) = self.match_arm(pattern, &value)? {
+                        return self.scoped(bindings, |interpreter| interpreter.eval_block(body));
+                    }
+                }
+                return Err(invalid(format!("no match arm matches {value}")));
+            }
+            RirStatement::Expression { expr, .. } => {
+                self.eval(expr)?;
+            }
+            RirStatement::Block { block, .. } => return self.eval_block(block),
+        }
+        Ok(Value::Unit)
+    }
+
+    /// Returns the place an expression refers to, or `None` if it evaluates
+    /// to a temporary value.
+    pub(super) fn place(&mut self, expr: &RirExpression) -> Eval<Option<Place>> {
+        match expr {
+            RirExpression::Variable { name, .. } => Ok(Some(Place {
+                variable: name.clone(),
+                path: Vec::new(),
+            })),
+            RirExpression::FieldAccess { object, field, .. } => {
+                let Some(mut place) = self.place(object)? else {
+                    return Ok(None);
+                };
+                let index = field
+                    .parse()
+                    .map_err(|_| invalid(format!("unknown field '{field}'")))?;
+                place.path.push(index);
+                Ok(Some(place))
+            }
+            RirExpression::Index { array, index, .. } => {
+                let Some(mut place) = self.place(array)? else {
+                    return Ok(None);
+                };
+                let Value::Int(index) = self.eval(index)? else {
+                    return Err(invalid("index is not an Int"));
+                };
+                place.path.push(index);
+                Ok(Some(place))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns a place for changing it, copying the collections on the way
+    /// that other values still share.
+    pub(super) fn place_mut(&mut self, place: &Place) -> Eval<&mut Value> {
+        let mut value = self.variable_mut(&place.variable)?;
+        for &index in &place.path {
+            let elements = match value {
+                Value::Array(elements) | Value::List(elements) | Value::Tuple(elements) => {
+                    Rc::make_mut(elements)
+                }
+                _ => return Err(invalid(format!("cannot index {value}"))),
+            };
+            let len = elements.len();
+            value = usize::try_from(index)
+                .ok()
+                .and_then(|index| elements.get_mut(index))
+                .ok_or_else(|| index_out_of_bounds(len, index))?;
+        }
+        Ok(value)
+    }
+}
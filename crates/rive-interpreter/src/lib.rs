@@ -0,0 +1,53 @@
+//! Interpreter for Rive Intermediate Representation (RIR).
+//!
+//! Runs a lowered [`RirModule`] directly instead of generating Rust and
+//! compiling it, so programs start instantly and don't need a Rust
+//! toolchain. The interpreter follows the compiled program's behavior —
+//! output, panic messages and exit status — so the two can be compared on
+//! the same program:
+//!
+//! - values for every type, with lists and maps copied on write
+//! - labeled `break` and `continue`, and loops breaking with a value
+//! - nullable operators (`?.`, `?:`, `!!`) and null bindings
+//! - results with `?` propagation, and pattern matching
+//! - the builtin functions and methods, with checked integer arithmetic
+//!
+//! # Examples
+//! ```
+//! use rive_core::type_system::{TypeId, TypeRegistry};
+//! use rive_core::Span;
+//! use rive_ir::{RirBlock, RirExpression, RirFunction, RirModule};
+//!
+//! let span = Span::from_range(0, 0);
+//! let mut body = RirBlock::new(span);
+//! body.set_final_expr(RirExpression::IntLiteral { value: 3, span });
+//! let mut module = RirModule::new(TypeRegistry::new());
+//! module.add_function(RirFunction::new("main".to_string(), Vec::new(), TypeId::INT, body, span));
+//!
+//! let mut out = Vec::new();
+//! assert_eq!(rive_interpreter::run(&module, Vec::new(), &mut out), Ok(3));
+//! ```
+
+mod interpreter;
+mod value;
+
+pub use interpreter::{Interpreter, RuntimeError};
+pub use value::Value;
+
+use rive_ir::RirModule;
+use std::io::Write;
+
+/// Runs a module's `main` function with program arguments, printing to `out`.
+///
+/// # Returns
+/// The program's exit status
+///
+/// # Errors
+/// Returns an error if the program panics or has no `main`.
+pub fn run(
+    module: &RirModule,
+    args: Vec<String>,
+    out: &mut dyn Write,
+) -> Result<i32, RuntimeError> {
+    Interpreter::new(module, out).with_args(args).run()
+}
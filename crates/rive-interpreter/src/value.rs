@@ -0,0 +1,141 @@
+//! Runtime values of interpreted programs.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::Rc;
+
+/// A value of a running Rive program.
+///
+/// Collections hold their elements behind an `Rc` and are copied on write:
+/// `let b = a` shares the elements, and changing `b` copies them first if
+/// `a` still holds them, so every variable behaves as if it owned its own
+/// collection.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    Text(Rc<str>),
+    Bool(bool),
+    Unit,
+    /// A value of a nullable type, `None` being `null`
+    Nullable(Option<Box<Value>>),
+    /// A fixed-size array
+    Array(Rc<Vec<Value>>),
+    Tuple(Rc<Vec<Value>>),
+    List(Rc<Vec<Value>>),
+    /// A map from text keys, iterated in key order
+    Map(Rc<BTreeMap<String, Value>>),
+    Result(Result<Box<Value>, Box<Value>>),
+}
+
+impl Value {
+    /// Creates a `Text` value.
+    pub fn text(text: &str) -> Self {
+        Self::Text(Rc::from(text))
+    }
+
+    /// Returns the value as a non-null nullable, unless it is nullable already.
+    #[must_use]
+    pub fn into_nullable(self) -> Self {
+        match self {
+            Self::Nullable(_) => self,
+            value => Self::Nullable(Some(Box::new(value))),
+        }
+    }
+}
+
+/// Formats a value the way `print` writes it.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value}"),
+            Self::Text(text) => f.write_str(text),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Unit => f.write_str("()"),
+            Self::Nullable(Some(value)) => write!(f, "{value}"),
+            Self::Nullable(None) => f.write_str("null"),
+            Self::Result(Ok(value)) => write!(f, "ok({value})"),
+            Self::Result(Err(error)) => write!(f, "err({error})"),
+            Self::Tuple(elements) => {
+                f.write_str("(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                f.write_str(")")
+            }
+            Self::Array(elements) | Self::List(elements) => write_elements(f, elements),
+            Self::Map(entries) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{key}: {value}")?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// Formats a value the way the compiled program writes elements of printed
+/// lists and `assert_eq` operands: as Rust's `{:?}` would, with nested
+/// lists as `[...]` and maps as `{"key": value}`.
+pub(crate) struct RustDebug<'a>(pub &'a Value);
+
+impl fmt::Display for RustDebug<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Value::Int(value) => write!(f, "{value:?}"),
+            Value::Float(value) => write!(f, "{value:?}"),
+            Value::Text(text) => write!(f, "{text:?}"),
+            Value::Bool(value) => write!(f, "{value:?}"),
+            Value::Unit => f.write_str("()"),
+            Value::Nullable(Some(value)) => write!(f, "Some({})", RustDebug(value)),
+            Value::Nullable(None) => f.write_str("None"),
+            Value::Result(Ok(value)) => write!(f, "Ok({})", RustDebug(value)),
+            Value::Result(Err(error)) => write!(f, "Err({})", RustDebug(error)),
+            Value::Tuple(elements) => {
+                f.write_str("(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", RustDebug(element))?;
+                }
+                if elements.len() == 1 {
+                    f.write_str(",")?;
+                }
+                f.write_str(")")
+            }
+            Value::Array(elements) | Value::List(elements) => write_elements(f, elements),
+            // Entries are written in key order, like the compiled program does
+            Value::Map(entries) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{key:?}: {}", RustDebug(value))?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// Writes `[a, b, ...]` with each element formatted as Rust's `{:?}` would.
+fn write_elements(f: &mut fmt::Formatter<'_>, elements: &[Value]) -> fmt::Result {
+    f.write_str("[")?;
+    for (i, element) in elements.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}", RustDebug(element))?;
+    }
+    f.write_str("]")
+}
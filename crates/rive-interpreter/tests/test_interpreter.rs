@@ -0,0 +1,387 @@
+//! Tests for running programs with the RIR interpreter.
+
+use rive_interpreter::{Interpreter, RuntimeError};
+use rive_ir::{AstLowering, RirModule};
+use rive_lexer::tokenize;
+use rive_parser::parse;
+use rive_semantic::analyze_program;
+
+/// Compiles a program to RIR.
+fn lower(source: &str) -> RirModule {
+    let tokens = tokenize(source).unwrap();
+    let (ast, type_registry) = parse(&tokens).unwrap();
+    let analysis = analyze_program(&ast, type_registry).unwrap();
+    let mut lowering = AstLowering::new(analysis.type_registry);
+    lowering.lower_program(&ast).unwrap()
+}
+
+/// Runs a program and returns its exit status and output.
+fn run_with_args(source: &str, args: &[&str]) -> (Result<i32, RuntimeError>, String) {
+    let module = lower(source);
+    let mut out = Vec::new();
+    let args = args.iter().map(ToString::to_string).collect();
+    let result = rive_interpreter::run(&module, args, &mut out);
+    (result, String::from_utf8(out).unwrap())
+}
+
+fn run(source: &str) -> (Result<i32, RuntimeError>, String) {
+    run_with_args(source, &[])
+}
+
+/// Runs a program that should finish successfully and returns its output.
+fn output(source: &str) -> String {
+    let (result, output) = run(source);
+    assert_eq!(result, Ok(0), "output so far: {output}");
+    output
+}
+
+/// Runs a program that should panic and returns the panic message.
+fn panic_message(source: &str) -> String {
+    match run(source).0 {
+        Err(RuntimeError::Panic(message)) => message,
+        result => panic!("expected a panic, got {result:?}"),
+    }
+}
+
+#[test]
+fn test_print_values() {
+    let source = r#"
+        fun main() {
+            print(1 + 2 * 3, " ", 7 / 2, " ", -7 % 3)
+            print(1.5 * 2.0, " ", 0.1 + 0.2)
+            print("text", true)
+            print((1, "a", 2.5))
+            print(List("a", "b"), List(1, 2))
+            print(List(List(1)))
+            print({"b": 2, "a": 1})
+            let x: Int? = 5
+            let y: Text? = null
+            print(x, " ", y)
+            let good: Result<Int, Text> = ok(1)
+            let bad: Result<Int, Text> = err("bad")
+            print(good, " ", bad)
+        }
+    "#;
+    assert_eq!(
+        output(source),
+        "7 3 -1\n3 0.30000000000000004\ntexttrue\n(1, a, 2.5)\n[\"a\", \"b\"][1, 2]\n\
+         [[1]]\n{a: 1, b: 2}\n5 null\nok(1) err(bad)\n"
+    );
+}
+
+#[test]
+fn test_functions_and_recursion() {
+    let source = r#"
+        fun fib(n: Int): Int {
+            if n < 2 { return n }
+            return fib(n - 1) + fib(n - 2)
+        }
+
+        fun sign(n: Int): Text = when {
+            n > 0 -> "positive"
+            n < 0 -> "negative"
+            else -> "zero"
+        }
+
+        fun main() {
+            let offset = 10
+            fun shifted(n: Int): Int = n + offset
+            print(fib(20))
+            print(sign(-3), " ", sign(0))
+            print(shifted(5))
+        }
+    "#;
+    assert_eq!(output(source), "6765\nnegative zero\n15\n");
+}
+
+#[test]
+fn test_loops_with_labels_and_break_values() {
+    let source = r#"
+        fun main() {
+            let found = outer: for i in 1..10 {
+                for j in 1..10 {
+                    if j > i { continue outer }
+                    if i * j == 12 { break outer with (i, j) }
+                }
+            }
+            print(found ?: (0, 0))
+
+            let mut n = 0
+            let first = loop {
+                n += 1
+                if n % 7 == 0 { break with n }
+            }
+            print(first ?: 0)
+
+            let none = while n < 10 { n += 1 }
+            print(none)
+
+            let mut total = 0
+            for i in 1..=4 { total += i }
+            print(total)
+        }
+    "#;
+    assert_eq!(output(source), "(4, 3)\n7\nnull\n10\n");
+}
+
+#[test]
+fn test_collections_are_copied_on_write() {
+    let source = r#"
+        fun add_one(items: List<Int>): List<Int> {
+            items.append(1)
+            return items
+        }
+
+        fun main() {
+            let a = List(3, 1, 2)
+            let b = a
+            b.append(4)
+            print(a, " ", b)
+
+            let c = add_one(a)
+            print(a, " ", c)
+
+            for x in a {
+                a.append(x)
+            }
+            print(a.len())
+
+            a.sort()
+            a.remove(0)
+            a.insert(10, 0)
+            print(a, " ", a.get(0), " ", a.get(-1))
+
+            let scores = {"bob": 2}
+            let copy = scores
+            copy.insert("amy", 1)
+            print(scores, " ", copy.keys(), " ", copy.get("amy"))
+        }
+    "#;
+    assert_eq!(
+        output(source),
+        "[3, 1, 2] [3, 1, 2, 4]\n[3, 1, 2] [3, 1, 2, 1]\n6\n\
+         [1, 2, 2, 3, 3] 1 null\n{bob: 2} [\"amy\", \"bob\"] 1\n"
+    );
+}
+
+#[test]
+fn test_nullable_operators() {
+    let source = r#"
+        fun find(items: List<Text>, wanted: Text): Int? {
+            let count = items.len()
+            for i in 0..count {
+                let item = items.get(i) ?: ""
+                if item == wanted { return i }
+            }
+            return null
+        }
+
+        fun len_of(items: List<Text>): Int = items.len()
+
+        fun main() {
+            let items = List("a", "b")
+            print(find(items, "b") ?: -1, " ", find(items, "z") ?: -1)
+            print(find(items, "a")!!)
+
+            let maybe: Int? = 4
+            let missing: Int? = null
+            print(maybe?.len_of(items), " ", missing?.len_of(items))
+
+            if let index = find(items, "b") {
+                print("found ", index)
+            }
+            let mut cursor: Int? = 3
+            while let n = cursor {
+                print(n)
+                cursor = null
+                if n > 1 { cursor = n - 1 }
+            }
+        }
+    "#;
+    assert_eq!(output(source), "1 -1\n0\n2 null\nfound 1\n3\n2\n1\n");
+}
+
+#[test]
+fn test_match_patterns() {
+    let source = r#"
+        fun describe(n: Int): Text = when n {
+            0 -> "zero"
+            1 | 2 -> "small"
+            in 3..=9 -> "digit"
+            x if x < 0 -> "negative"
+            _ -> "large"
+        }
+
+        fun main() {
+            print(describe(0), describe(2), describe(5), describe(-4), describe(50))
+            let pair = (2, "b")
+            when pair {
+                (1, name) -> print("one ", name)
+                (n, name) -> print(n, " ", name)
+            }
+            let maybe: Int? = null
+            print(when maybe {
+                null -> "none"
+                _ -> "some"
+            })
+            let present: Int? = 5
+            print(when present {
+                null -> 0
+                m if m > 2 -> m * 10
+                m -> m
+            })
+        }
+    "#;
+    assert_eq!(
+        output(source),
+        "zerosmalldigitnegativelarge\n2 b\nnone\n50\n"
+    );
+}
+
+#[test]
+fn test_results_and_propagation() {
+    let source = r#"
+        fun parse_digit(text: Text): Result<Int, Text> {
+            if text == "0" { return ok(0) }
+            if text == "1" { return ok(1) }
+            return err("not a digit: " + text)
+        }
+
+        fun sum(a: Text, b: Text): Result<Int, Text> {
+            let x = parse_digit(a)?
+            let y = parse_digit(b)?
+            return ok(x + y)
+        }
+
+        fun main() {
+            print(sum("1", "1"), " ", sum("1", "x"))
+            when sum("0", "1") {
+                ok(v) -> print("value ", v)
+                err(e) -> print("error ", e)
+            }
+        }
+    "#;
+    assert_eq!(output(source), "ok(2) err(not a digit: x)\nvalue 1\n");
+}
+
+#[test]
+fn test_globals() {
+    let source = r#"
+        const LIMIT: Int = 3
+        let names = List("a", "b")
+        let count = names.len() * LIMIT
+
+        fun main() {
+            print(LIMIT, " ", names, " ", count)
+        }
+    "#;
+    assert_eq!(output(source), "3 [\"a\", \"b\"] 6\n");
+}
+
+#[test]
+fn test_default_and_named_arguments() {
+    let source = r#"
+let base: Int = 5
+
+fun f(b: Int = base): Int = b
+
+fun note(n: Int): Int {
+    print(n)
+    return n
+}
+
+fun pair(a: Int, b: Int = 0): Int = a * 10 + b
+
+fun main() {
+    let base = 100
+    print(f())
+    print(pair(b = note(2), a = note(1)))
+}
+"#;
+    assert_eq!(output(source), "5\n2\n1\n12\n");
+}
+
+#[test]
+fn test_panics() {
+    assert_eq!(
+        panic_message("fun main() {\n    panic(\"boom\")\n}"),
+        "boom at main.rive:2:5"
+    );
+    assert_eq!(
+        panic_message("fun main() {\n  assert_eq(List(1), List(2))\n}"),
+        "assertion `left == right` failed at main.rive:2:3\n  left: [1]\n right: [2]"
+    );
+    assert_eq!(
+        panic_message("fun main() {\n  let x: Int? = null\n  print(x!!)\n}"),
+        "null assertion failed at main.rive:3:9"
+    );
+    assert_eq!(
+        panic_message("fun main() { let n = 9223372036854775807\n print(n + 1) }"),
+        "attempt to add with overflow"
+    );
+    assert_eq!(
+        panic_message("fun main() { let d = 0\n print(1 / d) }"),
+        "attempt to divide by zero"
+    );
+
+    // Output printed before the panic is kept
+    let (result, output) = run("fun main() {\n print(1)\n todo()\n}");
+    assert_eq!(
+        result,
+        Err(RuntimeError::Panic(
+            "not yet implemented at main.rive:3:2".to_string()
+        ))
+    );
+    assert_eq!(output, "1\n");
+}
+
+#[test]
+fn test_overflow_wraps_without_overflow_checks() {
+    let source = r#"
+        fun main() {
+            let n = 9223372036854775807
+            let shift = 65
+            print(n + 1, " ", -(n + 1), " ", n * 2, " ", 1 << shift)
+            print((n + 1) / -1)
+        }
+    "#;
+    let module = lower(source);
+    let mut out = Vec::new();
+    let result = Interpreter::new(&module, &mut out)
+        .with_overflow_checks(false)
+        .run();
+
+    // Division overflow panics even without overflow checks, as in Rust
+    assert_eq!(
+        result,
+        Err(RuntimeError::Panic(
+            "attempt to divide with overflow".to_string()
+        ))
+    );
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "-9223372036854775808 -9223372036854775808 -2 2\n"
+    );
+}
+
+#[test]
+fn test_user_functions_shadow_builtins() {
+    let source = "fun assert(x: Int): Int = x * 2\nfun main() { print(assert(5)) }";
+    assert_eq!(run(source), (Ok(0), "10\n".to_string()));
+}
+
+#[test]
+fn test_exit_status_and_args() {
+    assert_eq!(run("fun main(): Int = 3").0, Ok(3));
+    assert_eq!(
+        run("fun main() {\n print(1)\n exit(4)\n print(2)\n}"),
+        (Ok(4), "1\n".to_string())
+    );
+
+    let (result, output) = run_with_args(
+        "fun main() { print(args().len(), \" \", args()) }",
+        &["a", "b"],
+    );
+    assert_eq!(result, Ok(0));
+    assert_eq!(output, "2 [\"a\", \"b\"]\n");
+}
@@ -98,12 +98,12 @@ impl ExprBuilder {
         }
     }
 
-    /// Creates an array literal
+    /// Creates an array literal of the given array type
     #[must_use]
-    pub fn array(elements: Vec<RirExpression>, element_type: TypeId, span: Span) -> RirExpression {
+    pub fn array(elements: Vec<RirExpression>, result_type: TypeId, span: Span) -> RirExpression {
         RirExpression::ArrayLiteral {
             elements,
-            element_type,
+            result_type,
             span,
         }
     }
@@ -12,7 +12,7 @@ impl fmt::Display for RirExpression {
             Self::StringLiteral { value, .. } => write!(f, "\"{value}\""),
             Self::BoolLiteral { value, .. } => write!(f, "{value}"),
             Self::Unit { .. } => write!(f, "()"),
-            Self::Variable { name, .. } => write!(f, "{name}"),
+            Self::Variable { name, .. } | Self::Global { name, .. } => write!(f, "{name}"),
             Self::Binary {
                 op, left, right, ..
             } => {
@@ -60,6 +60,11 @@ impl fmt::Display for RirExpression {
             } => write!(f, "({value} ?: {fallback})"),
             Self::SafeCall { object, call, .. } => write!(f, "({object}?.{call})"),
             Self::WrapOptional { value, .. } => write!(f, "Some({value})"),
+            Self::NonNullAssert { value, .. } => write!(f, "{value}!!"),
+            Self::ResultOk { value, .. } => write!(f, "ok({value})"),
+            Self::ResultErr { value, .. } => write!(f, "err({value})"),
+            Self::Propagate { value, .. } => write!(f, "{value}?"),
+            Self::LetSome { name, value, .. } => write!(f, "let {name} = {value}"),
             Self::TupleLiteral { elements, .. } => {
                 write!(f, "(")?;
                 for (i, elem) in elements.iter().enumerate() {
@@ -2,12 +2,24 @@
 
 use std::fmt;
 
-use crate::{RirFunction, RirModule};
+use crate::{GlobalKind, RirFunction, RirModule};
 
 impl fmt::Display for RirModule {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "RIR Module")?;
         writeln!(f, "==========")?;
+        for global in &self.globals {
+            let keyword = match global.kind {
+                GlobalKind::Const => "const",
+                GlobalKind::Static => "static",
+                GlobalKind::Lazy => "lazy",
+            };
+            writeln!(
+                f,
+                "{keyword} {}: {:?} = {}",
+                global.name, global.type_id, global.value
+            )?;
+        }
         for func in &self.functions {
             write!(f, "{func}")?;
         }
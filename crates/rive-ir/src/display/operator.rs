@@ -20,6 +20,11 @@ impl fmt::Display for BinaryOp {
             Self::GreaterEqual => ">=",
             Self::And => "&&",
             Self::Or => "||",
+            Self::BitAnd => "&",
+            Self::BitOr => "|",
+            Self::BitXor => "^",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
         };
         write!(f, "{symbol}")
     }
@@ -30,6 +35,7 @@ impl fmt::Display for UnaryOp {
         let symbol = match self {
             Self::Negate => "-",
             Self::Not => "!",
+            Self::BitNot => "~",
         };
         write!(f, "{symbol}")
     }
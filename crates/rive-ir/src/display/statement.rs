@@ -2,7 +2,7 @@
 
 use std::fmt;
 
-use crate::RirStatement;
+use crate::{RirIterable, RirStatement};
 
 impl fmt::Display for RirStatement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -67,12 +67,19 @@ impl fmt::Display for RirStatement {
                 write!(f, "{{\n{block}}}")
             }
             Self::For {
-                variable, label, ..
+                variable,
+                iterable,
+                label,
+                ..
             } => {
+                let source = match iterable {
+                    RirIterable::Range { .. } => "<range>",
+                    RirIterable::Collection { .. } => "<collection>",
+                };
                 if let Some(lbl) = label {
-                    write!(f, "{lbl}: for {variable} in <range> {{ ... }}")
+                    write!(f, "{lbl}: for {variable} in {source} {{ ... }}")
                 } else {
-                    write!(f, "for {variable} in <range> {{ ... }}")
+                    write!(f, "for {variable} in {source} {{ ... }}")
                 }
             }
             Self::Loop { label, .. } => {
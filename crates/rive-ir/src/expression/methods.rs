@@ -15,6 +15,7 @@ impl RirExpression {
             | Self::BoolLiteral { span, .. }
             | Self::Unit { span }
             | Self::Variable { span, .. }
+            | Self::Global { span, .. }
             | Self::Binary { span, .. }
             | Self::Unary { span, .. }
             | Self::Call { span, .. }
@@ -30,6 +31,11 @@ impl RirExpression {
             | Self::Elvis { span, .. }
             | Self::SafeCall { span, .. }
             | Self::WrapOptional { span, .. }
+            | Self::NonNullAssert { span, .. }
+            | Self::ResultOk { span, .. }
+            | Self::ResultErr { span, .. }
+            | Self::Propagate { span, .. }
+            | Self::LetSome { span, .. }
             | Self::TupleLiteral { span, .. }
             | Self::ListLiteral { span, .. }
             | Self::DictLiteral { span, .. }
@@ -48,6 +54,7 @@ impl RirExpression {
             Self::BoolLiteral { .. } => TypeId::BOOL,
             Self::Unit { .. } => TypeId::UNIT,
             Self::Variable { type_id, .. }
+            | Self::Global { type_id, .. }
             | Self::Binary {
                 result_type: type_id,
                 ..
@@ -101,6 +108,22 @@ impl RirExpression {
                 result_type: type_id,
                 ..
             }
+            | Self::NonNullAssert {
+                result_type: type_id,
+                ..
+            }
+            | Self::ResultOk {
+                result_type: type_id,
+                ..
+            }
+            | Self::ResultErr {
+                result_type: type_id,
+                ..
+            }
+            | Self::Propagate {
+                result_type: type_id,
+                ..
+            }
             | Self::TupleLiteral {
                 result_type: type_id,
                 ..
@@ -117,8 +140,9 @@ impl RirExpression {
                 result_type: type_id,
                 ..
             } => *type_id,
-            Self::ArrayLiteral { element_type, .. } => *element_type,
+            Self::ArrayLiteral { result_type, .. } => *result_type,
             Self::MethodCall { return_type, .. } => *return_type,
+            Self::LetSome { .. } => TypeId::BOOL,
         }
     }
 
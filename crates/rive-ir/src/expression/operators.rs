@@ -21,6 +21,13 @@ pub enum BinaryOp {
     // Logical
     And,
     Or,
+
+    // Bitwise
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
 }
 
 /// Unary operators
@@ -28,6 +35,7 @@ pub enum BinaryOp {
 pub enum UnaryOp {
     Negate,
     Not,
+    BitNot,
 }
 
 impl BinaryOp {
@@ -59,6 +67,15 @@ impl BinaryOp {
     pub const fn is_logical(self) -> bool {
         matches!(self, Self::And | Self::Or)
     }
+
+    /// Returns true if this is a bitwise or shift operator
+    #[must_use]
+    pub const fn is_bitwise(self) -> bool {
+        matches!(
+            self,
+            Self::BitAnd | Self::BitOr | Self::BitXor | Self::ShiftLeft | Self::ShiftRight
+        )
+    }
 }
 
 #[cfg(test)]
@@ -78,5 +95,9 @@ mod tests {
         assert!(!BinaryOp::And.is_arithmetic());
         assert!(!BinaryOp::And.is_comparison());
         assert!(BinaryOp::And.is_logical());
+
+        assert!(BinaryOp::ShiftLeft.is_bitwise());
+        assert!(!BinaryOp::ShiftLeft.is_arithmetic());
+        assert!(!BinaryOp::Or.is_bitwise());
     }
 }
@@ -2,7 +2,7 @@
 
 use rive_core::{span::Span, type_system::TypeId};
 
-use crate::{RirBlock, RirPattern};
+use crate::{GlobalKind, RirBlock, RirIterable, RirPattern};
 
 use super::operators::{BinaryOp, UnaryOp};
 
@@ -31,6 +31,14 @@ pub enum RirExpression {
         span: Span,
     },
 
+    /// Module-level variable reference
+    Global {
+        name: String,
+        kind: GlobalKind,
+        type_id: TypeId,
+        span: Span,
+    },
+
     /// Binary operation
     Binary {
         op: BinaryOp,
@@ -59,7 +67,7 @@ pub enum RirExpression {
     /// Array literal
     ArrayLiteral {
         elements: Vec<RirExpression>,
-        element_type: TypeId,
+        result_type: TypeId,
         span: Span,
     },
 
@@ -108,9 +116,7 @@ pub enum RirExpression {
     /// For loop expression (can break with value)
     For {
         variable: String,
-        start: Box<RirExpression>,
-        end: Box<RirExpression>,
-        inclusive: bool,
+        iterable: RirIterable,
         body: RirBlock,
         label: Option<String>,
         result_type: TypeId,
@@ -157,6 +163,51 @@ pub enum RirExpression {
         span: Span,
     },
 
+    /// Non-null assertion: `value!!`
+    ///
+    /// Compiles to a `match` that panics with the Rive source location on `None`.
+    NonNullAssert {
+        value: Box<RirExpression>,
+        result_type: TypeId,
+        span: Span,
+    },
+
+    /// Result success constructor: `ok(value)`
+    ///
+    /// Compiles to: `Ok(value)`
+    ResultOk {
+        value: Box<RirExpression>,
+        result_type: TypeId,
+        span: Span,
+    },
+
+    /// Result failure constructor: `err(error)`
+    ///
+    /// Compiles to: `Err(error)`
+    ResultErr {
+        value: Box<RirExpression>,
+        result_type: TypeId,
+        span: Span,
+    },
+
+    /// Error propagation: `value?`
+    ///
+    /// Compiles to: `value?`
+    Propagate {
+        value: Box<RirExpression>,
+        result_type: TypeId,
+        span: Span,
+    },
+
+    /// Null binding condition: `let name = value`
+    ///
+    /// Compiles to `let Some(name) = value` inside an `if`/`while` condition.
+    LetSome {
+        name: String,
+        value: Box<RirExpression>,
+        span: Span,
+    },
+
     /// Tuple literal
     TupleLiteral {
         elements: Vec<RirExpression>,
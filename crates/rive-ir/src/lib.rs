@@ -13,8 +13,8 @@ mod statement;
 pub use builder::{BlockBuilder, ExprBuilder, RirBuilder};
 pub use expression::{BinaryOp, RirExpression, UnaryOp};
 pub use lowering::AstLowering;
-pub use module::{RirBlock, RirFunction, RirModule, RirParameter};
-pub use statement::{RirPattern, RirStatement};
+pub use module::{GlobalKind, RirBlock, RirFunction, RirGlobal, RirModule, RirParameter, RirTest};
+pub use statement::{RirIterable, RirPattern, RirStatement};
 
 use rive_core::Result;
 use rive_core::type_system::TypeRegistry;
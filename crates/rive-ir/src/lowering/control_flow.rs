@@ -1,20 +1,29 @@
 //! Control flow lowering from AST to RIR.
 
 use crate::lowering::core::AstLowering;
-use crate::{RirBlock, RirExpression, RirStatement};
-use rive_core::{Error, Result, TypeId};
+use crate::{RirBlock, RirExpression, RirIterable, RirStatement};
+use rive_core::{Error, Result, TypeId, TypeKind};
 use rive_parser::Expression;
-use rive_parser::control_flow::{Break, Continue, For, If, Loop, While};
+use rive_parser::ast::BindingPattern;
+use rive_parser::control_flow::{Break, Continue, For, If, Loop, When, While};
 
 impl AstLowering {
     /// Lowers an if expression to RIR.
-    pub(crate) fn lower_if_expr(&mut self, if_expr: &If) -> Result<RirExpression> {
-        let condition = Box::new(self.lower_expression(&if_expr.condition)?);
-        let then_block = self.lower_block(&if_expr.then_block)?;
+    ///
+    /// The value of each branch is lowered against `expected`, if given.
+    pub(crate) fn lower_if_expr(
+        &mut self,
+        if_expr: &If,
+        expected: Option<TypeId>,
+    ) -> Result<RirExpression> {
+        let lower_branch =
+            |this: &mut Self, block: &rive_parser::Block| this.lower_value_block(block, expected);
+        let (condition, then_block) =
+            self.lower_guarded_block(&if_expr.condition, &if_expr.then_block, lower_branch)?;
 
         // If must have else to be an expression
-        let else_block = if let Some(else_blk) = &if_expr.else_block {
-            self.lower_block(else_blk)?
+        let mut else_block = if let Some(else_blk) = &if_expr.else_block {
+            self.lower_value_block(else_blk, expected)?
         } else {
             // This should have been caught by semantic analysis
             return Err(Error::Semantic(
@@ -22,26 +31,27 @@ impl AstLowering {
             ));
         };
 
-        // Get result type from the then block's last expression
-        let result_type = then_block
-            .final_expr
-            .as_ref()
-            .map(|e| e.type_id())
-            .unwrap_or(TypeId::UNIT);
+        // Build nested if-else expressions from the else-if chain
+        for else_if in if_expr.else_if_branches.iter().rev() {
+            let (else_if_cond, else_if_then) =
+                self.lower_guarded_block(&else_if.condition, &else_if.block, lower_branch)?;
+            let nested_if = if_expression(else_if_cond, else_if_then, else_block, else_if.span);
+            else_block = expression_block(nested_if, else_if.span);
+        }
 
-        Ok(RirExpression::If {
+        Ok(if_expression(
             condition,
             then_block,
             else_block,
-            result_type,
-            span: if_expr.span,
-        })
+            if_expr.span,
+        ))
     }
 
     /// Lowers an if as a statement.
     pub(crate) fn lower_if_stmt(&mut self, if_expr: &If) -> Result<RirStatement> {
-        let condition = Box::new(self.lower_expression(&if_expr.condition)?);
-        let then_block = self.lower_block(&if_expr.then_block)?;
+        let (condition, then_block) =
+            self.lower_guarded_block(&if_expr.condition, &if_expr.then_block, Self::lower_block)?;
+        let condition = Box::new(condition);
 
         // Handle else-if chain by converting to nested if-else
         let else_block = if !if_expr.else_if_branches.is_empty() || if_expr.else_block.is_some() {
@@ -54,11 +64,14 @@ impl AstLowering {
 
             // Process else-if branches in reverse order
             for else_if in if_expr.else_if_branches.iter().rev() {
-                let else_if_cond = Box::new(self.lower_expression(&else_if.condition)?);
-                let else_if_then = self.lower_block(&else_if.block)?;
+                let (else_if_cond, else_if_then) = self.lower_guarded_block(
+                    &else_if.condition,
+                    &else_if.block,
+                    Self::lower_block,
+                )?;
 
                 let nested_if = RirStatement::If {
-                    condition: else_if_cond,
+                    condition: Box::new(else_if_cond),
                     then_block: else_if_then,
                     else_block: current_else,
                     span: else_if.span,
@@ -84,14 +97,90 @@ impl AstLowering {
         })
     }
 
+    /// Lowers a subject-less when expression to a nested if-else chain.
+    ///
+    /// Arm values are lowered against `expected`, if given.
+    pub(crate) fn lower_when_expr(
+        &mut self,
+        when: &When,
+        expected: Option<TypeId>,
+    ) -> Result<RirExpression> {
+        let Some(else_arm) = &when.else_arm else {
+            // This should have been caught by semantic analysis
+            return Err(Error::Semantic(
+                "When expression must have an else arm".to_string(),
+            ));
+        };
+
+        let else_value = self.lower_branch_value(else_arm, expected)?;
+        let mut else_block = expression_block(else_value, else_arm.span());
+
+        for (i, arm) in when.arms.iter().enumerate().rev() {
+            let condition = self.lower_expression(&arm.condition)?;
+            let body = self.lower_branch_value(&arm.body, expected)?;
+            let span = if i == 0 { when.span } else { arm.span };
+            let nested_if = if_expression(
+                condition,
+                expression_block(body, arm.span),
+                else_block,
+                span,
+            );
+            else_block = expression_block(nested_if, span);
+        }
+
+        // The outermost if is the final expression of the last block built
+        let chain = else_block
+            .final_expr
+            .expect("when has at least one condition arm");
+        Ok(*chain)
+    }
+
+    /// Lowers a subject-less when as a statement.
+    pub(crate) fn lower_when_stmt(&mut self, when: &When) -> Result<RirStatement> {
+        let mut else_block = when
+            .else_arm
+            .as_ref()
+            .map(|else_arm| {
+                let span = else_arm.span();
+                self.lower_expression(else_arm)
+                    .map(|value| statement_block(value, span))
+            })
+            .transpose()?;
+
+        for (i, arm) in when.arms.iter().enumerate().rev() {
+            let condition = self.lower_expression(&arm.condition)?;
+            let body = self.lower_expression(&arm.body)?;
+            let nested_if = RirStatement::If {
+                condition: Box::new(condition),
+                then_block: statement_block(body, arm.span),
+                else_block,
+                span: if i == 0 { when.span } else { arm.span },
+            };
+
+            if i == 0 {
+                return Ok(nested_if);
+            }
+            else_block = Some(RirBlock {
+                statements: vec![nested_if],
+                final_expr: None,
+                span: arm.span,
+            });
+        }
+
+        // This should have been caught by the parser
+        Err(Error::Semantic(
+            "When expression must have at least one condition arm".to_string(),
+        ))
+    }
+
     /// Lowers a while loop expression to RIR.
     pub(crate) fn lower_while_expr(&mut self, while_loop: &While) -> Result<RirExpression> {
-        let condition = Box::new(self.lower_expression(&while_loop.condition)?);
-
         // Enter loop context with optional user label
         let label = self.enter_loop(while_loop.label.clone());
 
-        let body = self.lower_block(&while_loop.body)?;
+        let (condition, body) =
+            self.lower_guarded_block(&while_loop.condition, &while_loop.body, Self::lower_block)?;
+        let condition = Box::new(condition);
 
         // Exit loop context
         self.exit_loop();
@@ -108,32 +197,96 @@ impl AstLowering {
         })
     }
 
+    /// Lowers an `if`/`while` condition together with the block it guards.
+    ///
+    /// A null binding (`let name = value`) becomes a `LetSome` condition and
+    /// brings `name` into scope, with its non-null type, for the guarded block only.
+    /// The block itself is lowered with `lower_body`.
+    fn lower_guarded_block(
+        &mut self,
+        condition: &Expression,
+        block: &rive_parser::Block,
+        lower_body: impl FnOnce(&mut Self, &rive_parser::Block) -> Result<RirBlock>,
+    ) -> Result<(RirExpression, RirBlock)> {
+        let Expression::LetBinding { name, value, span } = condition else {
+            let condition = self.lower_expression(condition)?;
+            return Ok((condition, lower_body(self, block)?));
+        };
+
+        let value = self.lower_expression(value)?;
+        let inner_type = self
+            .get_nullable_inner(value.type_id())
+            .unwrap_or(value.type_id());
+
+        self.enter_scope();
+        self.define_variable(name.clone(), inner_type, false);
+        let block = lower_body(self, block);
+        self.exit_scope();
+
+        let condition = RirExpression::LetSome {
+            name: name.clone(),
+            value: Box::new(value),
+            span: *span,
+        };
+        Ok((condition, block?))
+    }
+
     /// Lowers a for loop expression to RIR.
     pub(crate) fn lower_for_expr(&mut self, for_loop: &For) -> Result<RirExpression> {
-        // Extract range from iterable
-        let (start, end, inclusive) = match &*for_loop.iterable {
+        let (iterable, element_type) = match &*for_loop.iterable {
             Expression::Range(range) => {
                 let start = self.lower_expression(&range.start)?;
                 let end = self.lower_expression(&range.end)?;
-                (Box::new(start), Box::new(end), range.inclusive)
+                let iterable = RirIterable::Range {
+                    start: Box::new(start),
+                    end: Box::new(end),
+                    inclusive: range.inclusive,
+                };
+                (iterable, TypeId::INT)
             }
-            _ => {
-                return Err(Error::Semantic(
-                    "For loop iterable must be a range".to_string(),
-                ));
+            collection => {
+                let collection = self.lower_expression(collection)?;
+                let element_type = self.iteration_element_type(collection.type_id())?;
+                let iterable = RirIterable::Collection {
+                    collection: Box::new(collection),
+                };
+                (iterable, element_type)
             }
         };
 
-        // Enter new scope for loop variable
+        // Enter new scope for loop variables
         self.enter_scope();
 
-        // Define loop variable (Int type for ranges, immutable)
-        self.define_variable(for_loop.variable.clone(), TypeId::INT, false);
+        // Simple names bind the loop variable directly; other patterns bind a hidden
+        // loop variable that is destructured at the start of each iteration
+        let (variable, bindings) = match &for_loop.pattern {
+            BindingPattern::Name {
+                name,
+                mutable: false,
+                ..
+            } => {
+                self.define_variable(name.clone(), element_type, false);
+                (name.clone(), Vec::new())
+            }
+            BindingPattern::Wildcard { .. } => ("_".to_string(), Vec::new()),
+            pattern => {
+                let variable = self.fresh_temp("__item");
+                self.define_variable(variable.clone(), element_type, false);
+                let item = RirExpression::Variable {
+                    name: variable.clone(),
+                    type_id: element_type,
+                    span: pattern.span(),
+                };
+                let bindings = self.lower_binding_pattern(pattern, item, pattern.span())?;
+                (variable, bindings)
+            }
+        };
 
         // Enter loop context with optional user label
         let label = self.enter_loop(for_loop.label.clone());
 
-        let body = self.lower_block(&for_loop.body)?;
+        let mut body = self.lower_block(&for_loop.body)?;
+        body.statements.splice(0..0, bindings);
 
         // Exit loop context
         self.exit_loop();
@@ -145,10 +298,8 @@ impl AstLowering {
         let result_type = self.infer_loop_result_type(&body);
 
         Ok(RirExpression::For {
-            variable: for_loop.variable.clone(),
-            start,
-            end,
-            inclusive,
+            variable,
+            iterable,
             body,
             label,
             result_type,
@@ -156,6 +307,24 @@ impl AstLowering {
         })
     }
 
+    /// Returns the type of the elements produced by iterating over a collection.
+    fn iteration_element_type(&mut self, collection_type: TypeId) -> Result<TypeId> {
+        let kind = self
+            .type_registry
+            .get(collection_type)
+            .map(|meta| meta.kind.clone());
+
+        match kind {
+            Some(TypeKind::Array { element, .. } | TypeKind::List { element }) => Ok(element),
+            Some(TypeKind::Map { key, value }) => {
+                Ok(self.type_registry.create_tuple(vec![key, value]))
+            }
+            _ => Err(Error::Semantic(
+                "For loop iterable must be a range or collection".to_string(),
+            )),
+        }
+    }
+
     /// Lowers an infinite loop expression to RIR.
     pub(crate) fn lower_loop_expr(&mut self, loop_expr: &Loop) -> Result<RirExpression> {
         // Enter loop context with optional user label
@@ -207,3 +376,42 @@ impl AstLowering {
         })
     }
 }
+
+/// Builds an if-else expression, taking its type from the first branch
+/// that doesn't diverge.
+fn if_expression(
+    condition: RirExpression,
+    then_block: RirBlock,
+    else_block: RirBlock,
+    span: rive_core::Span,
+) -> RirExpression {
+    let result_type = match then_block.result_type() {
+        TypeId::NEVER => else_block.result_type(),
+        then_type => then_type,
+    };
+
+    RirExpression::If {
+        condition: Box::new(condition),
+        then_block,
+        else_block,
+        result_type,
+        span,
+    }
+}
+
+/// Wraps an expression as the value of a block.
+fn expression_block(value: RirExpression, span: rive_core::Span) -> RirBlock {
+    let mut block = RirBlock::new(span);
+    block.set_final_expr(value);
+    block
+}
+
+/// Wraps an expression as the only statement of a block.
+fn statement_block(value: RirExpression, span: rive_core::Span) -> RirBlock {
+    let mut block = RirBlock::new(span);
+    block.add_statement(RirStatement::Expression {
+        expr: Box::new(value),
+        span,
+    });
+    block
+}
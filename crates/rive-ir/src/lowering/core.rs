@@ -1,6 +1,9 @@
 //! Core AST lowering structure and symbol management.
 
+use crate::{GlobalKind, RirFunction};
+use rive_core::Result;
 use rive_core::type_system::{TypeId, TypeRegistry};
+use rive_parser::Parameter;
 use std::collections::HashMap;
 
 /// Symbol information during lowering.
@@ -11,6 +14,24 @@ pub struct SymbolInfo {
     pub mutable: bool,
 }
 
+/// A local function, lifted to a module-level function.
+#[derive(Debug, Clone)]
+pub(crate) struct LocalFunction {
+    /// Name of the lifted function
+    pub name: String,
+    /// Variables of enclosing functions it reads, passed as extra arguments
+    pub captures: Vec<(String, TypeId)>,
+}
+
+/// Variables captured by a local function whose body is being lowered.
+#[derive(Debug)]
+pub(crate) struct CaptureFrame {
+    /// Index of the function's own outermost scope; anything found in a
+    /// non-global scope below it is captured
+    pub floor: usize,
+    pub captures: Vec<(String, TypeId)>,
+}
+
 /// Converts AST to RIR with type information.
 pub struct AstLowering {
     pub(crate) type_registry: TypeRegistry,
@@ -18,10 +39,27 @@ pub struct AstLowering {
     pub(crate) symbols: Vec<HashMap<String, SymbolInfo>>,
     /// Function signatures for function calls
     pub(crate) functions: HashMap<String, (Vec<TypeId>, TypeId)>,
+    /// Declared parameters of each function, for filling in call arguments,
+    /// and the number of scopes visible where it was declared
+    pub(crate) function_params: HashMap<String, (Vec<Parameter>, usize)>,
+    /// Storage of each global declared so far
+    pub(crate) globals: HashMap<String, GlobalKind>,
+    /// Local functions declared in each scope, parallel to `symbols`
+    pub(crate) local_functions: Vec<HashMap<String, LocalFunction>>,
+    /// Local functions being lowered, innermost last
+    pub(crate) capture_frames: Vec<CaptureFrame>,
+    /// Lowered local functions waiting to be added to the module
+    pub(crate) lifted_functions: Vec<RirFunction>,
+    /// Name of the function being lowered, used to name its local functions
+    pub(crate) current_function: String,
     /// Current loop nesting depth
     pub(crate) loop_depth: usize,
     /// Stack of loop labels for break/continue
     pub(crate) loop_labels: Vec<Option<String>>,
+    /// Declared return type of the function being lowered
+    pub(crate) current_return_type: TypeId,
+    /// Counter for compiler-generated temporary variable names
+    pub(crate) temp_counter: usize,
 }
 
 impl AstLowering {
@@ -32,19 +70,49 @@ impl AstLowering {
             type_registry,
             symbols: vec![HashMap::new()], // Global scope
             functions: HashMap::new(),
+            function_params: HashMap::new(),
+            globals: HashMap::new(),
+            local_functions: vec![HashMap::new()],
+            capture_frames: Vec::new(),
+            lifted_functions: Vec::new(),
+            current_function: String::new(),
             loop_depth: 0,
             loop_labels: Vec::new(),
+            current_return_type: TypeId::UNIT,
+            temp_counter: 0,
         }
     }
 
+    /// Returns a fresh name for a compiler-generated temporary variable.
+    pub(crate) fn fresh_temp(&mut self, prefix: &str) -> String {
+        self.temp_counter += 1;
+        format!("{prefix}_{}", self.temp_counter)
+    }
+
     /// Enters a new scope.
     pub(crate) fn enter_scope(&mut self) {
         self.symbols.push(HashMap::new());
+        self.local_functions.push(HashMap::new());
     }
 
     /// Exits the current scope.
     pub(crate) fn exit_scope(&mut self) {
         self.symbols.pop();
+        self.local_functions.pop();
+    }
+
+    /// Lowers with only the outermost `depth` scopes visible.
+    pub(crate) fn lower_in_scope<T>(
+        &mut self,
+        depth: usize,
+        lower: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let hidden_symbols = self.symbols.split_off(depth);
+        let hidden_functions = self.local_functions.split_off(depth);
+        let result = lower(self);
+        self.symbols.extend(hidden_symbols);
+        self.local_functions.extend(hidden_functions);
+        result
     }
 
     /// Defines a variable in the current scope.
@@ -64,6 +132,23 @@ impl AstLowering {
         None
     }
 
+    /// Looks up a variable, also returning the index of the scope it was found in.
+    pub(crate) fn lookup_variable_with_depth(&self, name: &str) -> Option<(usize, &SymbolInfo)> {
+        self.symbols
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(depth, scope)| scope.get(name).map(|info| (depth, info)))
+    }
+
+    /// Looks up the local function a call to `name` refers to, if any.
+    pub(crate) fn lookup_local_function(&self, name: &str) -> Option<&LocalFunction> {
+        self.local_functions
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+    }
+
     /// Defines a function signature.
     pub(crate) fn define_function(
         &mut self,
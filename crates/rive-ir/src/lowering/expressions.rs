@@ -3,7 +3,8 @@
 use crate::RirExpression;
 use crate::lowering::core::AstLowering;
 use rive_core::{Error, Result};
-use rive_parser::Expression as AstExpression;
+use rive_parser::ast::is_result_constructor;
+use rive_parser::{Expression as AstExpression, NamedArgument};
 
 impl AstLowering {
     /// Lowers an expression.
@@ -34,19 +35,7 @@ impl AstLowering {
                 span: *span,
             }),
 
-            AstExpression::Variable { name, span } => {
-                // Look up variable type from symbol table
-                let type_id = self
-                    .lookup_variable(name)
-                    .map(|info| info.type_id)
-                    .ok_or_else(|| Error::Semantic(format!("Undefined variable '{name}'")))?;
-
-                Ok(RirExpression::Variable {
-                    name: name.clone(),
-                    type_id,
-                    span: *span,
-                })
-            }
+            AstExpression::Variable { name, span } => self.lower_variable(name, *span),
 
             AstExpression::Binary {
                 left,
@@ -88,28 +77,77 @@ impl AstLowering {
             AstExpression::Call {
                 callee,
                 arguments,
+                named_arguments,
                 span,
             } => {
-                let args = arguments
-                    .iter()
-                    .map(|arg| self.lower_expression(arg))
-                    .collect::<Result<Vec<_>>>()?;
+                if is_result_constructor(callee) {
+                    return Err(Error::Semantic(format!(
+                        "Cannot infer the type of '{callee}(..)'"
+                    )));
+                }
+
+                // Distinct aliases have no runtime representation of their own
+                if self.lookup_function(callee).is_none()
+                    && self.lookup_local_function(callee).is_none()
+                    && let Some(alias) = self
+                        .type_registry
+                        .get_by_name(callee)
+                        .and_then(|id| self.type_registry.get_alias(id))
+                    && alias.distinct
+                    && let [argument] = arguments.as_slice()
+                {
+                    let target = alias.target;
+                    return self.lower_expression_expecting(argument, target);
+                }
+
+                // Calls to a local function go to its lifted function,
+                // passing along the variables it captures
+                let (function, captures) = match self.lookup_local_function(callee) {
+                    Some(local) => (local.name.clone(), local.captures.clone()),
+                    None => (callee.clone(), Vec::new()),
+                };
+
+                let (temporaries, mut args) =
+                    self.lower_call_arguments(&function, arguments, named_arguments, *span)?;
+                for (name, _) in &captures {
+                    args.push(self.lower_variable(name, *span)?);
+                }
 
                 // Look up function return type from function signatures
-                // Special case for built-in print function
+                // Special case for built-in functions, which user functions
+                // shadow (except `print`)
                 let return_type = if callee == "print" {
                     rive_core::type_system::TypeId::UNIT
+                } else if let Some((_, return_type)) = self.lookup_function(&function) {
+                    *return_type
+                } else if callee == "args" {
+                    let text = rive_core::type_system::TypeId::TEXT;
+                    self.type_registry.create_list(text)
                 } else {
-                    self.lookup_function(callee)
-                        .map(|(_, return_type)| *return_type)
+                    builtin_return_type(callee)
                         .ok_or_else(|| Error::Semantic(format!("Undefined function '{callee}'")))?
                 };
 
-                Ok(RirExpression::Call {
-                    function: callee.clone(),
+                let call = RirExpression::Call {
+                    function,
                     arguments: args,
                     return_type,
                     span: *span,
+                };
+                if temporaries.is_empty() {
+                    return Ok(call);
+                }
+
+                let mut block = crate::RirBlock::new(*span);
+                for statement in temporaries {
+                    block.add_statement(statement);
+                }
+                block.set_final_expr(call.clone());
+                Ok(RirExpression::Block {
+                    block,
+                    result: Some(Box::new(call)),
+                    result_type: return_type,
+                    span: *span,
                 })
             }
 
@@ -124,23 +162,41 @@ impl AstLowering {
                 } else {
                     rive_core::type_system::TypeId::INT // Default for empty arrays
                 };
+                let result_type = self
+                    .type_registry
+                    .create_array(element_type, rir_elements.len());
 
                 Ok(RirExpression::ArrayLiteral {
                     elements: rir_elements,
-                    element_type,
+                    result_type,
                     span: *span,
                 })
             }
 
-            AstExpression::If(if_expr) => self.lower_if_expr(if_expr),
+            AstExpression::If(if_expr) => self.lower_if_expr(if_expr, None),
             AstExpression::While(while_loop) => self.lower_while_expr(while_loop),
             AstExpression::For(for_loop) => self.lower_for_expr(for_loop),
             AstExpression::Loop(loop_expr) => self.lower_loop_expr(loop_expr),
-            AstExpression::Match(match_expr) => self.lower_match_expr(match_expr),
+            AstExpression::Match(match_expr) => self.lower_match_expr(match_expr, None),
+            AstExpression::When(when) => self.lower_when_expr(when, None),
             AstExpression::Range(_) => Err(Error::Semantic(
                 "Range expressions can only be used in for loops".to_string(),
             )),
-            AstExpression::Block(block) => self.lower_block_expr(block),
+            AstExpression::Block(block) => self.lower_block_expr(block, None),
+
+            // Diverging control flow becomes a block that never completes
+            AstExpression::Return { value, span } => {
+                let statement = self.lower_return(value.as_deref(), *span)?;
+                Ok(diverging_block(statement, *span))
+            }
+            AstExpression::Break(break_expr) => {
+                let statement = self.lower_break(break_expr)?;
+                Ok(diverging_block(statement, break_expr.span))
+            }
+            AstExpression::Continue(continue_expr) => {
+                let statement = self.lower_continue(continue_expr)?;
+                Ok(diverging_block(statement, continue_expr.span))
+            }
 
             // Null safety operators
             AstExpression::Elvis {
@@ -201,6 +257,27 @@ impl AstLowering {
                 })
             }
 
+            AstExpression::NonNullAssert { value, span } => {
+                let value_expr = self.lower_expression(value)?;
+
+                // Asserting a non-nullable value is a no-op
+                let Some(result_type) = self.get_nullable_inner(value_expr.type_id()) else {
+                    return Ok(value_expr);
+                };
+
+                Ok(RirExpression::NonNullAssert {
+                    value: Box::new(value_expr),
+                    result_type,
+                    span: *span,
+                })
+            }
+
+            AstExpression::Propagate { value, span } => self.lower_propagate(value, *span),
+
+            AstExpression::LetBinding { .. } => Err(Error::Semantic(
+                "'let' bindings are only allowed in if and while conditions".to_string(),
+            )),
+
             // New collection literals
             AstExpression::Tuple { elements, span } => {
                 let rir_elements = elements
@@ -339,17 +416,105 @@ impl AstLowering {
         }
     }
 
-    /// Lowers a block expression to RIR.
-    pub(crate) fn lower_block_expr(&mut self, block: &rive_parser::Block) -> Result<RirExpression> {
-        let rir_block = self.lower_block(block)?;
-
-        // Check if the block has a final expression
-        let (result, result_type) = if let Some(ref final_expr) = rir_block.final_expr {
-            (rir_block.final_expr.clone(), final_expr.type_id())
-        } else {
-            (None, rive_core::type_system::TypeId::UNIT)
+    /// Lowers a call's arguments in parameter order, filling in default values
+    /// for the parameters the call leaves out.
+    ///
+    /// Defaults are lowered in the scope the function was declared in, so
+    /// they never see the caller's variables. When the call has named
+    /// arguments, the given arguments other than literals are bound to
+    /// temporaries first so they are still evaluated in source order.
+    fn lower_call_arguments(
+        &mut self,
+        function: &str,
+        arguments: &[AstExpression],
+        named_arguments: &[NamedArgument],
+        span: rive_core::Span,
+    ) -> Result<(Vec<crate::RirStatement>, Vec<RirExpression>)> {
+        // Parameter types fix the type of result constructor arguments
+        let param_types = self
+            .lookup_function(function)
+            .map(|(params, _)| params.clone())
+            .unwrap_or_default();
+        let Some((params, scope_depth)) = self.function_params.get(function).cloned() else {
+            let args = arguments
+                .iter()
+                .enumerate()
+                .map(|(i, arg)| self.lower_argument(arg, param_types.get(i).copied()))
+                .collect::<Result<Vec<_>>>()?;
+            return Ok((Vec::new(), args));
         };
 
+        let given = arguments
+            .iter()
+            .enumerate()
+            .chain(named_arguments.iter().filter_map(|named| {
+                let index = params.iter().position(|param| param.name == named.name)?;
+                Some((index, &named.value))
+            }));
+        let mut temporaries = Vec::new();
+        let mut args: Vec<Option<RirExpression>> = vec![None; params.len()];
+        for (index, argument) in given {
+            let mut value = self.lower_argument(argument, param_types.get(index).copied())?;
+            if !named_arguments.is_empty() && !value.is_literal() {
+                let type_id = value.type_id();
+                let temp = self.fresh_temp("__arg");
+                temporaries.push(crate::RirStatement::Let {
+                    name: temp.clone(),
+                    type_id,
+                    is_mutable: false,
+                    value: Box::new(value),
+                    memory_strategy: self.determine_memory_strategy(type_id),
+                    span,
+                });
+                value = RirExpression::Variable {
+                    name: temp,
+                    type_id,
+                    span,
+                };
+            }
+            if let Some(slot) = args.get_mut(index) {
+                *slot = Some(value);
+            }
+        }
+
+        let mut lowered = Vec::with_capacity(params.len());
+        for ((param, arg), param_type) in params.iter().zip(args).zip(param_types) {
+            match (arg, &param.default) {
+                (Some(arg), _) => lowered.push(arg),
+                (None, Some(default)) => {
+                    lowered.push(self.lower_in_scope(scope_depth, |this| {
+                        this.lower_argument(default, Some(param_type))
+                    })?);
+                }
+                (None, None) => {}
+            }
+        }
+        Ok((temporaries, lowered))
+    }
+
+    /// Lowers an argument, expecting its parameter's type if known.
+    fn lower_argument(
+        &mut self,
+        argument: &AstExpression,
+        param_type: Option<rive_core::type_system::TypeId>,
+    ) -> Result<RirExpression> {
+        match param_type {
+            Some(param_type) => self.lower_expression_expecting(argument, param_type),
+            None => self.lower_expression(argument),
+        }
+    }
+
+    /// Lowers a block expression to RIR.
+    pub(crate) fn lower_block_expr(
+        &mut self,
+        block: &rive_parser::Block,
+        expected: Option<rive_core::type_system::TypeId>,
+    ) -> Result<RirExpression> {
+        let rir_block = self.lower_value_block(block, expected)?;
+
+        let result = rir_block.final_expr.clone();
+        let result_type = rir_block.result_type();
+
         Ok(RirExpression::Block {
             block: rir_block,
             result,
@@ -358,3 +523,27 @@ impl AstLowering {
         })
     }
 }
+
+/// Returns the return type of a built-in diagnostic function.
+fn builtin_return_type(name: &str) -> Option<rive_core::type_system::TypeId> {
+    use rive_core::type_system::TypeId;
+
+    match name {
+        "panic" | "todo" | "unreachable" | "exit" => Some(TypeId::NEVER),
+        "assert" | "assert_eq" => Some(TypeId::UNIT),
+        _ => None,
+    }
+}
+
+/// Wraps a return, break or continue statement in a block expression of type `Never`.
+fn diverging_block(statement: crate::RirStatement, span: rive_core::Span) -> RirExpression {
+    let mut block = crate::RirBlock::new(span);
+    block.add_statement(statement);
+
+    RirExpression::Block {
+        block,
+        result: None,
+        result_type: rive_core::type_system::TypeId::NEVER,
+        span,
+    }
+}
@@ -0,0 +1,123 @@
+//! Local function and variable reference lowering.
+//!
+//! Local functions are lifted to module-level functions. The variables they
+//! read from enclosing functions become extra trailing parameters, and every
+//! call passes the current values along.
+
+use crate::RirExpression;
+use crate::lowering::core::{AstLowering, CaptureFrame, LocalFunction};
+use rive_core::type_system::TypeId;
+use rive_core::{Error, Result, Span};
+use rive_parser::ast::Function as AstFunction;
+
+impl AstLowering {
+    /// Lowers a local function declaration into a lifted function.
+    pub(crate) fn lower_local_function(&mut self, func: &AstFunction) -> Result<()> {
+        let name = self.fresh_temp(&format!("{}_{}", self.current_function, func.name));
+        let param_types = func.params.iter().map(|p| p.param_type).collect();
+        self.define_function(name.clone(), param_types, func.return_type);
+        self.function_params
+            .insert(name.clone(), (func.params.clone(), self.symbols.len()));
+
+        // The captured variables are only known once the body has been lowered,
+        // so it is lowered once to find them and again with them in place
+        let temp_counter = self.temp_counter;
+        let lifted_count = self.lifted_functions.len();
+        self.declare_local_function(&func.name, name.clone(), Vec::new());
+        let captures = self.lower_captures_of(func, &name)?;
+        self.temp_counter = temp_counter;
+        self.lifted_functions.truncate(lifted_count);
+
+        self.declare_local_function(&func.name, name.clone(), captures.clone());
+        let previous_function = std::mem::replace(&mut self.current_function, name.clone());
+        let enclosing_return_type = self.current_return_type;
+        let enclosing_loops = (self.loop_depth, std::mem::take(&mut self.loop_labels));
+        self.loop_depth = 0;
+
+        let lifted = self.lower_function_as(func, name, &captures);
+
+        self.loop_depth = enclosing_loops.0;
+        self.loop_labels = enclosing_loops.1;
+        self.current_return_type = enclosing_return_type;
+        self.current_function = previous_function;
+
+        self.lifted_functions.push(lifted?);
+        Ok(())
+    }
+
+    /// Lowers a local function's body, returning the enclosing variables it reads.
+    fn lower_captures_of(
+        &mut self,
+        func: &AstFunction,
+        name: &str,
+    ) -> Result<Vec<(String, TypeId)>> {
+        let previous_function = std::mem::replace(&mut self.current_function, name.to_string());
+        let enclosing_return_type = self.current_return_type;
+        let enclosing_loops = (self.loop_depth, std::mem::take(&mut self.loop_labels));
+        self.loop_depth = 0;
+        self.capture_frames.push(CaptureFrame {
+            floor: self.symbols.len(),
+            captures: Vec::new(),
+        });
+
+        let result = self.lower_function_as(func, name.to_string(), &[]);
+
+        let frame = self.capture_frames.pop();
+        self.loop_depth = enclosing_loops.0;
+        self.loop_labels = enclosing_loops.1;
+        self.current_return_type = enclosing_return_type;
+        self.current_function = previous_function;
+
+        result?;
+        Ok(frame.map(|frame| frame.captures).unwrap_or_default())
+    }
+
+    /// Makes a local function callable in the current scope.
+    fn declare_local_function(
+        &mut self,
+        source_name: &str,
+        name: String,
+        captures: Vec<(String, TypeId)>,
+    ) {
+        if let Some(scope) = self.local_functions.last_mut() {
+            scope.insert(source_name.to_string(), LocalFunction { name, captures });
+        }
+    }
+
+    /// Lowers a variable reference.
+    ///
+    /// Globals are read through their storage, and reads of an enclosing
+    /// function's variables are recorded as captures.
+    pub(crate) fn lower_variable(&mut self, name: &str, span: Span) -> Result<RirExpression> {
+        let (depth, type_id) = self
+            .lookup_variable_with_depth(name)
+            .map(|(depth, info)| (depth, info.type_id))
+            .ok_or_else(|| Error::Semantic(format!("Undefined variable '{name}'")))?;
+
+        if depth == 0
+            && let Some(kind) = self.globals.get(name)
+        {
+            return Ok(RirExpression::Global {
+                name: name.to_string(),
+                kind: *kind,
+                type_id,
+                span,
+            });
+        }
+
+        for frame in &mut self.capture_frames {
+            if depth > 0
+                && depth < frame.floor
+                && !frame.captures.iter().any(|(captured, _)| captured == name)
+            {
+                frame.captures.push((name.to_string(), type_id));
+            }
+        }
+
+        Ok(RirExpression::Variable {
+            name: name.to_string(),
+            type_id,
+            span,
+        })
+    }
+}
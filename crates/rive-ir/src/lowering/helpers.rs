@@ -22,6 +22,11 @@ impl AstLowering {
             BinaryOperator::GreaterEqual => BinaryOp::GreaterEqual,
             BinaryOperator::And => BinaryOp::And,
             BinaryOperator::Or => BinaryOp::Or,
+            BinaryOperator::BitAnd => BinaryOp::BitAnd,
+            BinaryOperator::BitOr => BinaryOp::BitOr,
+            BinaryOperator::BitXor => BinaryOp::BitXor,
+            BinaryOperator::ShiftLeft => BinaryOp::ShiftLeft,
+            BinaryOperator::ShiftRight => BinaryOp::ShiftRight,
         }
     }
 
@@ -30,6 +35,7 @@ impl AstLowering {
         match op {
             UnaryOperator::Negate => UnaryOp::Negate,
             UnaryOperator::Not => UnaryOp::Not,
+            UnaryOperator::BitNot => UnaryOp::BitNot,
         }
     }
 
@@ -47,6 +53,22 @@ impl AstLowering {
         }
     }
 
+    /// Wraps `value` in a `WrapOptional` node if `target` is `T?` and the value is `T`.
+    pub(crate) fn wrap_if_nullable(&self, value: RirExpression, target: TypeId) -> RirExpression {
+        if let Some(inner_type) = self.get_nullable_inner(target)
+            && value.type_id() == inner_type
+        {
+            let span = value.span();
+            RirExpression::WrapOptional {
+                value: Box::new(value),
+                result_type: target,
+                span,
+            }
+        } else {
+            value
+        }
+    }
+
     /// Determines the memory strategy for a given type.
     pub(crate) const fn determine_memory_strategy(&self, type_id: TypeId) -> MemoryStrategy {
         match type_id {
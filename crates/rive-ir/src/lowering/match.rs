@@ -2,30 +2,45 @@
 
 use crate::lowering::core::AstLowering;
 use crate::{RirBlock, RirExpression, RirPattern, RirStatement};
-use rive_core::{Error, Result, TypeId};
-use rive_parser::control_flow::{Match, Pattern};
+use rive_core::{Error, Result, TypeId, TypeKind};
+use rive_parser::control_flow::{Match, MatchArm, Pattern};
 
 impl AstLowering {
     /// Lowers a match expression to RIR.
-    pub(crate) fn lower_match_expr(&mut self, match_expr: &Match) -> Result<RirExpression> {
+    ///
+    /// Arm values are lowered against `expected`, if given.
+    pub(crate) fn lower_match_expr(
+        &mut self,
+        match_expr: &Match,
+        expected: Option<TypeId>,
+    ) -> Result<RirExpression> {
         let scrutinee = Box::new(self.lower_expression(&match_expr.scrutinee)?);
+        let scrutinee_type = scrutinee.type_id();
 
+        let mut null_covered = false;
         let arms: Result<Vec<_>> = match_expr
             .arms
             .iter()
             .map(|arm| {
-                let pattern = self.lower_pattern(&arm.pattern)?;
-                let body = Box::new(self.lower_expression(&arm.body)?);
+                // Pattern bindings are scoped to the arm
+                self.enter_scope();
+                let pattern = self.lower_arm_pattern(arm, scrutinee_type, null_covered)?;
+                null_covered |= arm.covers_null();
+                let body = Box::new(self.lower_branch_value(&arm.body, expected)?);
+                self.exit_scope();
                 Ok((pattern, body))
             })
             .collect();
 
         let arms = arms?;
 
-        // Get result type from first arm (all arms have same type after semantic analysis)
+        // Get result type from the first non-diverging arm
+        // (all other arms have the same type or Never after semantic analysis)
         let result_type = arms
-            .first()
+            .iter()
             .map(|(_, expr)| expr.type_id())
+            .find(|&arm_type| arm_type != TypeId::NEVER)
+            .or_else(|| arms.first().map(|(_, expr)| expr.type_id()))
             .unwrap_or(TypeId::UNIT);
 
         Ok(RirExpression::Match {
@@ -39,15 +54,21 @@ impl AstLowering {
     /// Lowers a match as a statement.
     pub(crate) fn lower_match_stmt(&mut self, match_expr: &Match) -> Result<RirStatement> {
         let scrutinee = Box::new(self.lower_expression(&match_expr.scrutinee)?);
+        let scrutinee_type = scrutinee.type_id();
 
+        let mut null_covered = false;
         let arms: Result<Vec<_>> = match_expr
             .arms
             .iter()
             .map(|arm| {
-                let pattern = self.lower_pattern(&arm.pattern)?;
+                // Pattern bindings are scoped to the arm
+                self.enter_scope();
+                let pattern = self.lower_arm_pattern(arm, scrutinee_type, null_covered)?;
+                null_covered |= arm.covers_null();
 
                 // Convert expression to block
                 let body_expr = self.lower_expression(&arm.body)?;
+                self.exit_scope();
                 let body = RirBlock {
                     statements: vec![RirStatement::Expression {
                         expr: Box::new(body_expr),
@@ -68,8 +89,76 @@ impl AstLowering {
         })
     }
 
-    /// Lowers a pattern to RIR.
-    pub(crate) fn lower_pattern(&mut self, pattern: &Pattern) -> Result<RirPattern> {
+    /// Lowers the pattern of a match arm together with its guard.
+    ///
+    /// Once an earlier arm has caught null (`null_covered`), a binding only
+    /// matches the non-null value and binds it unwrapped.
+    fn lower_arm_pattern(
+        &mut self,
+        arm: &MatchArm,
+        scrutinee_type: TypeId,
+        null_covered: bool,
+    ) -> Result<RirPattern> {
+        let pattern = match (&arm.pattern, self.get_nullable_inner(scrutinee_type)) {
+            (Pattern::Binding { span, .. }, Some(inner_type)) if null_covered => {
+                RirPattern::NonNull {
+                    inner: Box::new(self.lower_pattern(&arm.pattern, inner_type)?),
+                    span: *span,
+                }
+            }
+            _ => self.lower_pattern(&arm.pattern, scrutinee_type)?,
+        };
+
+        let Some(guard) = &arm.guard else {
+            return Ok(pattern);
+        };
+        let guard = self.lower_expression(guard)?;
+        Ok(RirPattern::Guarded {
+            pattern: Box::new(pattern),
+            guard: Box::new(guard),
+            span: arm.span,
+        })
+    }
+
+    /// Lowers a pattern to RIR, defining any bindings it introduces.
+    pub(crate) fn lower_pattern(
+        &mut self,
+        pattern: &Pattern,
+        scrutinee_type: TypeId,
+    ) -> Result<RirPattern> {
+        match pattern {
+            Pattern::Wildcard { span } => return Ok(RirPattern::Wildcard { span: *span }),
+            Pattern::Null { span } => return Ok(RirPattern::Null { span: *span }),
+            Pattern::Binding { name, span } => {
+                self.define_variable(name.clone(), scrutinee_type, false);
+                return Ok(RirPattern::Binding {
+                    name: name.clone(),
+                    type_id: scrutinee_type,
+                    span: *span,
+                });
+            }
+            Pattern::Or { alternatives, span } => {
+                let alternatives = alternatives
+                    .iter()
+                    .map(|alternative| self.lower_pattern(alternative, scrutinee_type))
+                    .collect::<Result<Vec<_>>>()?;
+                return Ok(RirPattern::Or {
+                    alternatives,
+                    span: *span,
+                });
+            }
+            _ => {}
+        }
+
+        // Other patterns match the contents of a non-null value
+        if let Some(inner_type) = self.get_nullable_inner(scrutinee_type) {
+            let inner = self.lower_pattern(pattern, inner_type)?;
+            return Ok(RirPattern::NonNull {
+                inner: Box::new(inner),
+                span: pattern.span(),
+            });
+        }
+
         Ok(match pattern {
             Pattern::Integer { value, span } => RirPattern::IntLiteral {
                 value: *value,
@@ -87,12 +176,6 @@ impl AstLowering {
                 value: *value,
                 span: *span,
             },
-            Pattern::Null { span: _ } => {
-                return Err(Error::Semantic(
-                    "Null patterns not yet supported".to_string(),
-                ));
-            }
-            Pattern::Wildcard { span } => RirPattern::Wildcard { span: *span },
             Pattern::Range {
                 start,
                 end,
@@ -108,6 +191,49 @@ impl AstLowering {
                     span: *span,
                 }
             }
+            Pattern::Ok { binding, span } | Pattern::Err { binding, span } => {
+                let is_ok = matches!(pattern, Pattern::Ok { .. });
+                let (ok_type, err_type) = self
+                    .get_result_parts(scrutinee_type)
+                    .ok_or_else(|| Error::Semantic("Result pattern on non-Result value".into()))?;
+
+                if let Some(name) = binding {
+                    let binding_type = if is_ok { ok_type } else { err_type };
+                    self.define_variable(name.clone(), binding_type, false);
+                }
+
+                let binding = binding.clone();
+                if is_ok {
+                    RirPattern::ResultOk {
+                        binding,
+                        span: *span,
+                    }
+                } else {
+                    RirPattern::ResultErr {
+                        binding,
+                        span: *span,
+                    }
+                }
+            }
+            Pattern::Tuple { elements, span } => {
+                let element_types = match self.type_registry.get(scrutinee_type).map(|m| &m.kind) {
+                    Some(TypeKind::Tuple { elements }) => elements.clone(),
+                    _ => return Err(Error::Semantic("Tuple pattern on non-tuple value".into())),
+                };
+                let elements = elements
+                    .iter()
+                    .zip(element_types)
+                    .map(|(element, element_type)| self.lower_pattern(element, element_type))
+                    .collect::<Result<Vec<_>>>()?;
+                RirPattern::Tuple {
+                    elements,
+                    span: *span,
+                }
+            }
+            Pattern::Wildcard { .. }
+            | Pattern::Null { .. }
+            | Pattern::Binding { .. }
+            | Pattern::Or { .. } => unreachable!("handled above"),
         })
     }
 }
@@ -3,9 +3,11 @@
 mod control_flow;
 mod core;
 mod expressions;
+mod functions;
 mod helpers;
 mod r#match;
 mod program;
+mod results;
 mod statements;
 
 pub use core::AstLowering;
@@ -1,37 +1,49 @@
 //! Program and function lowering.
 
 use crate::lowering::core::AstLowering;
-use crate::{RirBlock, RirFunction, RirModule, RirParameter};
-use rive_core::Result;
-use rive_parser::ast::{Function as AstFunction, FunctionBody, Item, Program};
+use crate::{
+    GlobalKind, RirBlock, RirFunction, RirGlobal, RirModule, RirParameter, RirStatement, RirTest,
+};
+use rive_core::{Result, TypeId};
+use rive_parser::ast::{Function as AstFunction, FunctionBody, Global, Item, Program, Statement};
 
 impl AstLowering {
     /// Lowers a complete program to RIR.
     pub fn lower_program(&mut self, program: &Program) -> Result<RirModule> {
         // First pass: register all function signatures
         for item in &program.items {
-            match item {
-                Item::Function(func) => {
-                    let param_types: Vec<_> = func.params.iter().map(|p| p.param_type).collect();
-                    let return_type = func.return_type;
-                    self.define_function(func.name.clone(), param_types, return_type);
-                }
+            if let Item::Function(func) = item {
+                let param_types: Vec<_> = func.params.iter().map(|p| p.param_type).collect();
+                let return_type = func.return_type;
+                self.define_function(func.name.clone(), param_types, return_type);
+                self.function_params
+                    .insert(func.name.clone(), (func.params.clone(), 1));
             }
         }
 
-        // Second pass: lower function bodies
+        // Globals are lowered in declaration order into the global scope
+        let mut globals = Vec::new();
+        for item in &program.items {
+            if let Item::Global(global) = item {
+                globals.push(self.lower_global(global)?);
+            }
+        }
+
+        // Second pass: lower function bodies, each followed by its local functions
         let mut functions = Vec::new();
         for item in &program.items {
-            match item {
-                Item::Function(func) => {
-                    let rir_func = self.lower_function(func)?;
-                    functions.push(rir_func);
-                }
+            if let Item::Function(func) = item {
+                let rir_func = self.lower_function(func)?;
+                functions.push(rir_func);
+                functions.append(&mut self.lifted_functions);
             }
         }
 
         // Create module with the updated type registry (after all types have been created)
         let mut module = RirModule::new(self.type_registry.clone());
+        for global in globals {
+            module.add_global(global);
+        }
         for func in functions {
             module.add_function(func);
         }
@@ -39,38 +51,97 @@ impl AstLowering {
         Ok(module)
     }
 
+    /// Lowers a global declaration.
+    ///
+    /// Values of primitive types that only depend on literals and other such
+    /// globals are stored as a `const` or `static`; anything else is computed lazily.
+    fn lower_global(&mut self, global: &Global) -> Result<RirGlobal> {
+        let is_fixed = global.initializer.is_constant(&|name| {
+            matches!(
+                self.globals.get(name),
+                Some(GlobalKind::Const | GlobalKind::Static)
+            )
+        });
+
+        let RirStatement::Let { type_id, value, .. } = self.lower_variable_declaration(
+            &global.name,
+            false,
+            &global.var_type,
+            false,
+            &global.initializer,
+            global.span,
+        )?
+        else {
+            unreachable!("variable declarations lower to a let statement");
+        };
+
+        let is_primitive = matches!(
+            self.type_registry.resolve(type_id),
+            TypeId::INT | TypeId::FLOAT | TypeId::BOOL
+        );
+        let kind = match (is_fixed && is_primitive, global.constant) {
+            (true, true) => GlobalKind::Const,
+            (true, false) => GlobalKind::Static,
+            (false, _) => GlobalKind::Lazy,
+        };
+        self.globals.insert(global.name.clone(), kind);
+
+        Ok(RirGlobal {
+            name: global.name.clone(),
+            type_id,
+            kind,
+            value: *value,
+            span: global.span,
+        })
+    }
+
     /// Lowers a function declaration.
     pub(crate) fn lower_function(&mut self, func: &AstFunction) -> Result<RirFunction> {
+        self.current_function = func.name.clone();
+        self.lower_function_as(func, func.name.clone(), &[])
+    }
+
+    /// Lowers a function declaration under the given name, with the captured
+    /// variables as extra trailing parameters.
+    pub(crate) fn lower_function_as(
+        &mut self,
+        func: &AstFunction,
+        name: String,
+        captures: &[(String, TypeId)],
+    ) -> Result<RirFunction> {
         // Enter function scope
         self.enter_scope();
 
         // Register parameters in symbol table
-        let parameters: Vec<RirParameter> = func
-            .params
+        let declared = func.params.iter().map(|p| (&p.name, p.param_type, p.span));
+        let captured = captures
             .iter()
-            .map(|p| {
-                let type_id = p.param_type;
-                self.define_variable(p.name.clone(), type_id, false);
+            .map(|(name, type_id)| (name, *type_id, func.span));
+        let parameters: Vec<RirParameter> = declared
+            .chain(captured)
+            .map(|(name, type_id, span)| {
+                self.define_variable(name.clone(), type_id, false);
                 let memory_strategy = self.determine_memory_strategy(type_id);
                 Ok(RirParameter::new(
-                    p.name.clone(),
+                    name.clone(),
                     type_id,
                     false, // Parameters are not mutable by default in Rive
                     memory_strategy,
-                    p.span,
+                    span,
                 ))
             })
             .collect::<Result<Vec<_>>>()?;
 
         let return_type = func.return_type;
+        self.current_return_type = return_type;
 
         // Lower function body based on its type
         let body = match &func.body {
-            FunctionBody::Block(block) => self.lower_block(block)?,
+            FunctionBody::Block(block) => self.lower_function_block(block, return_type)?,
             FunctionBody::Expression(expr) => {
                 // For expression bodies, create a block with just the expression as final_expr
                 let mut rir_block = RirBlock::new(expr.span());
-                let final_expr = self.lower_expression(expr)?;
+                let final_expr = self.lower_expression_expecting(expr, return_type)?;
                 rir_block.final_expr = Some(Box::new(final_expr));
                 rir_block
             }
@@ -79,13 +150,58 @@ impl AstLowering {
         // Exit function scope
         self.exit_scope();
 
-        Ok(RirFunction::new(
-            func.name.clone(),
-            parameters,
-            return_type,
-            body,
-            func.span,
-        ))
+        let mut function = RirFunction::new(name, parameters, return_type, body, func.span);
+        function.captures = captures.len();
+        function.test = func.test.as_ref().map(|test| RirTest {
+            should_panic: test.should_panic,
+            expected_message: test.expected_message.clone(),
+        });
+        Ok(function)
+    }
+
+    /// Lowers a function body block.
+    ///
+    /// In a function that returns a value, a trailing expression with a value
+    /// becomes the final expression, typed by the declared return type.
+    fn lower_function_block(
+        &mut self,
+        block: &rive_parser::Block,
+        return_type: TypeId,
+    ) -> Result<RirBlock> {
+        match block.statements.last() {
+            Some(Statement::Expression { expression, .. }) if expression.produces_value() => {
+                self.lower_value_block(block, Some(return_type))
+            }
+            _ => self.lower_block(block),
+        }
+    }
+
+    /// Lowers a block whose trailing expression is its value of type `expected`.
+    ///
+    /// Unlike [`Self::lower_block`], a trailing call, `if` or `when` becomes the
+    /// final expression, lowered against `expected`. Without an expected type
+    /// the block is lowered like any other.
+    pub(crate) fn lower_value_block(
+        &mut self,
+        block: &rive_parser::Block,
+        expected: Option<TypeId>,
+    ) -> Result<RirBlock> {
+        let Some(expected) = expected.filter(|&expected| expected != TypeId::UNIT) else {
+            return self.lower_block(block);
+        };
+        let Some((Statement::Expression { expression, .. }, rest)) = block.statements.split_last()
+        else {
+            return self.lower_block(block);
+        };
+
+        let mut rir_block = RirBlock::new(block.span);
+        for stmt in rest {
+            self.lower_statement_into(stmt, &mut rir_block)?;
+        }
+        let final_expr = self.lower_expression_expecting(expression, expected)?;
+        rir_block.final_expr = Some(Box::new(final_expr));
+
+        Ok(rir_block)
     }
 
     /// Lowers a block of statements.
@@ -107,6 +223,7 @@ impl AstLowering {
                     rive_parser::Expression::Call { .. }
                         | rive_parser::Expression::If(_)
                         | rive_parser::Expression::Match(_)
+                        | rive_parser::Expression::When(_)
                 );
 
                 if should_be_final {
@@ -119,8 +236,7 @@ impl AstLowering {
                 }
             }
 
-            let rir_stmt = self.lower_statement(stmt)?;
-            rir_block.add_statement(rir_stmt);
+            self.lower_statement_into(stmt, &mut rir_block)?;
         }
 
         Ok(rir_block)
@@ -0,0 +1,139 @@
+//! Result constructor and error propagation lowering.
+
+use crate::RirExpression;
+use crate::lowering::core::AstLowering;
+use rive_core::type_system::{TypeId, TypeKind};
+use rive_core::{Error, Result, Span};
+use rive_parser::Expression as AstExpression;
+use rive_parser::ast::is_result_constructor;
+
+impl AstLowering {
+    /// Lowers an expression whose type is fixed by its context.
+    ///
+    /// Result constructors take their full `Result<T, E>` type from `expected`,
+    /// tuple literals lower each element against the expected element type
+    /// (wrapping `T` elements of a `T?` position), and the branches of an `if`,
+    /// `when` or block lower their values against `expected`; everything else
+    /// is lowered normally.
+    pub(crate) fn lower_expression_expecting(
+        &mut self,
+        expr: &AstExpression,
+        expected: TypeId,
+    ) -> Result<RirExpression> {
+        if let AstExpression::Call {
+            callee,
+            arguments,
+            span,
+            ..
+        } = expr
+            && is_result_constructor(callee)
+            && let [argument] = arguments.as_slice()
+        {
+            return self.lower_result_constructor(callee, argument, expected, *span);
+        }
+
+        match expr {
+            AstExpression::If(if_expr) => return self.lower_if_expr(if_expr, Some(expected)),
+            AstExpression::Match(match_expr) => {
+                return self.lower_match_expr(match_expr, Some(expected));
+            }
+            AstExpression::When(when) => return self.lower_when_expr(when, Some(expected)),
+            AstExpression::Block(block) => return self.lower_block_expr(block, Some(expected)),
+            _ => {}
+        }
+
+        if let AstExpression::Tuple { elements, span } = expr
+            && let Some(TypeKind::Tuple {
+                elements: element_types,
+            }) = self
+                .type_registry
+                .get(expected)
+                .map(|meta| meta.kind.clone())
+            && element_types.len() == elements.len()
+        {
+            let elements = elements
+                .iter()
+                .zip(element_types)
+                .map(|(element, element_type)| {
+                    let value = self.lower_expression_expecting(element, element_type)?;
+                    Ok(self.wrap_if_nullable(value, element_type))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(RirExpression::TupleLiteral {
+                elements,
+                result_type: expected,
+                span: *span,
+            });
+        }
+
+        self.lower_expression(expr)
+    }
+
+    /// Lowers the value of a branch, against the type expected by the
+    /// enclosing expression if there is one.
+    pub(crate) fn lower_branch_value(
+        &mut self,
+        expr: &AstExpression,
+        expected: Option<TypeId>,
+    ) -> Result<RirExpression> {
+        match expected {
+            Some(expected) => self.lower_expression_expecting(expr, expected),
+            None => self.lower_expression(expr),
+        }
+    }
+
+    /// Lowers `ok(value)` or `err(error)` with the expected result type.
+    fn lower_result_constructor(
+        &mut self,
+        callee: &str,
+        argument: &AstExpression,
+        expected: TypeId,
+        span: Span,
+    ) -> Result<RirExpression> {
+        let (ok_type, err_type) = self
+            .get_result_parts(expected)
+            .ok_or_else(|| Error::Semantic(format!("Cannot infer the type of '{callee}(..)'")))?;
+
+        if callee == "ok" {
+            let value = self.lower_expression_expecting(argument, ok_type)?;
+            Ok(RirExpression::ResultOk {
+                value: Box::new(value),
+                result_type: expected,
+                span,
+            })
+        } else {
+            let value = self.lower_expression_expecting(argument, err_type)?;
+            Ok(RirExpression::ResultErr {
+                value: Box::new(value),
+                result_type: expected,
+                span,
+            })
+        }
+    }
+
+    /// Lowers an error propagation: `value?`
+    pub(crate) fn lower_propagate(
+        &mut self,
+        value: &AstExpression,
+        span: Span,
+    ) -> Result<RirExpression> {
+        let value = self.lower_expression(value)?;
+        let (result_type, _) = self
+            .get_result_parts(value.type_id())
+            .ok_or_else(|| Error::Semantic("'?' can only be applied to a Result".to_string()))?;
+
+        Ok(RirExpression::Propagate {
+            value: Box::new(value),
+            result_type,
+            span,
+        })
+    }
+
+    /// Returns the `(ok, err)` types if `type_id` is a `Result<T, E>`.
+    pub(crate) fn get_result_parts(&self, type_id: TypeId) -> Option<(TypeId, TypeId)> {
+        match self.type_registry.get(type_id)?.kind {
+            TypeKind::Result { ok, err } => Some((ok, err)),
+            _ => None,
+        }
+    }
+}
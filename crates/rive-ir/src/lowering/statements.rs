@@ -1,11 +1,40 @@
 //! Statement lowering.
 
 use crate::lowering::core::AstLowering;
-use crate::{RirExpression, RirStatement};
-use rive_core::Result;
-use rive_parser::ast::Statement as AstStatement;
+use crate::{RirBlock, RirExpression, RirStatement};
+use rive_core::type_system::TypeKind;
+use rive_core::{Error, Result, Span};
+use rive_parser::ast::{BindingPattern, Statement as AstStatement};
 
 impl AstLowering {
+    /// Lowers a statement and appends the result to `block`.
+    /// Destructuring declarations expand to one `let` per binding.
+    pub(crate) fn lower_statement_into(
+        &mut self,
+        stmt: &AstStatement,
+        block: &mut RirBlock,
+    ) -> Result<()> {
+        if let AstStatement::Destructure {
+            pattern,
+            initializer,
+            span,
+        } = stmt
+        {
+            let value = self.lower_expression(initializer)?;
+            let bindings = self.lower_binding_pattern(pattern, value, *span)?;
+            block.statements.extend(bindings);
+            return Ok(());
+        }
+
+        if let AstStatement::Function(func) = stmt {
+            return self.lower_local_function(func);
+        }
+
+        let rir_stmt = self.lower_statement(stmt)?;
+        block.add_statement(rir_stmt);
+        Ok(())
+    }
+
     /// Lowers a statement.
     pub(crate) fn lower_statement(&mut self, stmt: &AstStatement) -> Result<RirStatement> {
         match stmt {
@@ -40,8 +69,21 @@ impl AstLowering {
                 *span,
             ),
 
+            AstStatement::Destructure { span, .. } => Err(Error::SemanticWithSpan(
+                "Destructuring declaration cannot be lowered to a single statement".to_string(),
+                *span,
+            )),
+
+            AstStatement::Function(func) => Err(Error::SemanticWithSpan(
+                "Local function declaration cannot be lowered to a single statement".to_string(),
+                func.span,
+            )),
+
             AstStatement::Assignment { name, value, span } => {
-                let rir_value = self.lower_expression(value)?;
+                let rir_value = match self.lookup_variable(name).map(|info| info.type_id) {
+                    Some(var_type) => self.lower_expression_expecting(value, var_type)?,
+                    None => self.lower_expression(value)?,
+                };
                 Ok(RirStatement::Assign {
                     name: name.clone(),
                     value: Box::new(rir_value),
@@ -49,11 +91,40 @@ impl AstLowering {
                 })
             }
 
+            AstStatement::CompoundAssignment {
+                name,
+                operator,
+                value,
+                span,
+            } => {
+                // `x op= v` is lowered to `x = x op v`
+                let current = self.lower_expression(&rive_parser::Expression::Variable {
+                    name: name.clone(),
+                    span: *span,
+                })?;
+                let value = self.lower_expression(value)?;
+                let op = self.lower_binary_op(operator);
+                let result_type = self.infer_binary_result_type(&current, &value, op);
+
+                Ok(RirStatement::Assign {
+                    name: name.clone(),
+                    value: Box::new(RirExpression::Binary {
+                        op,
+                        left: Box::new(current),
+                        right: Box::new(value),
+                        result_type,
+                        span: *span,
+                    }),
+                    span: *span,
+                })
+            }
+
             AstStatement::Expression { expression, span } => {
                 // Special handling for control flow that can be statements
                 match expression {
                     rive_parser::Expression::If(if_expr) => self.lower_if_stmt(if_expr),
                     rive_parser::Expression::Match(match_expr) => self.lower_match_stmt(match_expr),
+                    rive_parser::Expression::When(when) => self.lower_when_stmt(when),
                     _ => {
                         let rir_expr = self.lower_expression(expression)?;
                         Ok(RirStatement::Expression {
@@ -64,24 +135,30 @@ impl AstLowering {
                 }
             }
 
-            AstStatement::Return { value, span } => {
-                let rir_value = value
-                    .as_ref()
-                    .map(|v| self.lower_expression(v))
-                    .transpose()?;
-                Ok(RirStatement::Return {
-                    value: rir_value.map(Box::new),
-                    span: *span,
-                })
-            }
+            AstStatement::Return { value, span } => self.lower_return(value.as_ref(), *span),
 
             AstStatement::Break(break_stmt) => self.lower_break(break_stmt),
             AstStatement::Continue(continue_stmt) => self.lower_continue(continue_stmt),
         }
     }
 
+    /// Lowers a return statement.
+    pub(crate) fn lower_return(
+        &mut self,
+        value: Option<&rive_parser::Expression>,
+        span: rive_core::Span,
+    ) -> Result<RirStatement> {
+        let rir_value = value
+            .map(|v| self.lower_expression_expecting(v, self.current_return_type))
+            .transpose()?;
+        Ok(RirStatement::Return {
+            value: rir_value.map(Box::new),
+            span,
+        })
+    }
+
     /// Lowers a variable declaration (let or const).
-    fn lower_variable_declaration(
+    pub(crate) fn lower_variable_declaration(
         &mut self,
         name: &str,
         mutable: bool,
@@ -90,7 +167,10 @@ impl AstLowering {
         initializer: &rive_parser::Expression,
         span: rive_core::Span,
     ) -> Result<RirStatement> {
-        let value = self.lower_expression(initializer)?;
+        let value = match var_type {
+            Some(explicit_type) => self.lower_expression_expecting(initializer, *explicit_type)?,
+            None => self.lower_expression(initializer)?,
+        };
 
         // Determine the final type
         let type_id = if let Some(explicit_type) = var_type {
@@ -104,24 +184,7 @@ impl AstLowering {
             value.type_id()
         };
 
-        // Check if we need T -> T? conversion
-        let final_value = if value.type_id() != type_id {
-            // Check if this is T -> T? conversion
-            if let Some(inner_type) = self.get_nullable_inner(type_id)
-                && value.type_id() == inner_type
-            {
-                // Create WrapOptional node for T -> T? conversion
-                RirExpression::WrapOptional {
-                    value: Box::new(value),
-                    result_type: type_id,
-                    span,
-                }
-            } else {
-                value
-            }
-        } else {
-            value
-        };
+        let final_value = self.wrap_if_nullable(value, type_id);
 
         // Register variable in symbol table
         self.define_variable(name.to_string(), type_id, mutable);
@@ -138,4 +201,95 @@ impl AstLowering {
             span,
         })
     }
+
+    /// Lowers a binding pattern matched against `value` to a sequence of `let` statements.
+    ///
+    /// Tuple values that are not already places are first bound to a temporary,
+    /// and each element is then bound through a field access (`tmp.0`, `tmp.1`, ...).
+    pub(crate) fn lower_binding_pattern(
+        &mut self,
+        pattern: &BindingPattern,
+        value: RirExpression,
+        span: Span,
+    ) -> Result<Vec<RirStatement>> {
+        match pattern {
+            BindingPattern::Name { name, mutable, .. } => Ok(vec![self.bind_variable(
+                name.clone(),
+                *mutable,
+                value,
+                span,
+            )]),
+
+            BindingPattern::Wildcard { .. } => Ok(vec![RirStatement::Expression {
+                expr: Box::new(value),
+                span,
+            }]),
+
+            BindingPattern::Tuple { elements, .. } => {
+                let tuple_type = value.type_id();
+                let element_types = match self.type_registry.get(tuple_type).map(|m| &m.kind) {
+                    Some(TypeKind::Tuple { elements }) => elements.clone(),
+                    _ => {
+                        return Err(Error::SemanticWithSpan(
+                            "Cannot destructure a non-tuple value".to_string(),
+                            span,
+                        ));
+                    }
+                };
+
+                let mut statements = Vec::new();
+                let source = if matches!(
+                    value,
+                    RirExpression::Variable { .. } | RirExpression::FieldAccess { .. }
+                ) {
+                    value
+                } else {
+                    let temp = self.fresh_temp("__tuple");
+                    statements.push(self.bind_variable(temp.clone(), false, value, span));
+                    RirExpression::Variable {
+                        name: temp,
+                        type_id: tuple_type,
+                        span,
+                    }
+                };
+
+                for (index, (element, element_type)) in
+                    elements.iter().zip(element_types).enumerate()
+                {
+                    if matches!(element, BindingPattern::Wildcard { .. }) {
+                        continue;
+                    }
+                    let field = RirExpression::FieldAccess {
+                        object: Box::new(source.clone()),
+                        field: index.to_string(),
+                        result_type: element_type,
+                        span,
+                    };
+                    statements.extend(self.lower_binding_pattern(element, field, span)?);
+                }
+                Ok(statements)
+            }
+        }
+    }
+
+    /// Defines a variable and creates the `let` statement binding it to `value`.
+    fn bind_variable(
+        &mut self,
+        name: String,
+        mutable: bool,
+        value: RirExpression,
+        span: Span,
+    ) -> RirStatement {
+        let type_id = value.type_id();
+        self.define_variable(name.clone(), type_id, mutable);
+
+        RirStatement::Let {
+            name,
+            type_id,
+            is_mutable: mutable,
+            value: Box::new(value),
+            memory_strategy: self.determine_memory_strategy(type_id),
+            span,
+        }
+    }
 }
@@ -10,6 +10,8 @@ use crate::{RirExpression, RirStatement};
 /// Top-level RIR module representing a complete Rive program
 #[derive(Debug, Clone)]
 pub struct RirModule {
+    /// Module-level variables, in declaration order
+    pub globals: Vec<RirGlobal>,
     /// All functions in the module
     pub functions: Vec<RirFunction>,
     /// Type registry shared across the module
@@ -21,11 +23,17 @@ impl RirModule {
     #[must_use]
     pub fn new(type_registry: TypeRegistry) -> Self {
         Self {
+            globals: Vec::new(),
             functions: Vec::new(),
             type_registry,
         }
     }
 
+    /// Adds a global to the module
+    pub fn add_global(&mut self, global: RirGlobal) {
+        self.globals.push(global);
+    }
+
     /// Adds a function to the module
     pub fn add_function(&mut self, function: RirFunction) {
         self.functions.push(function);
@@ -44,6 +52,32 @@ impl RirModule {
     }
 }
 
+/// A module-level variable in RIR
+#[derive(Debug, Clone)]
+pub struct RirGlobal {
+    /// Global name
+    pub name: String,
+    /// Type of the value
+    pub type_id: TypeId,
+    /// How the value is stored
+    pub kind: GlobalKind,
+    /// Initial value
+    pub value: RirExpression,
+    /// Source location
+    pub span: Span,
+}
+
+/// How a global's value is stored in the generated program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalKind {
+    /// A compile-time constant, declared with `const`
+    Const,
+    /// A `let` whose value is known at compile time
+    Static,
+    /// A value computed on first use
+    Lazy,
+}
+
 /// A function in RIR
 #[derive(Debug, Clone)]
 pub struct RirFunction {
@@ -51,14 +85,28 @@ pub struct RirFunction {
     pub name: String,
     /// Function parameters
     pub parameters: Vec<RirParameter>,
+    /// Number of trailing parameters that pass variables captured from
+    /// enclosing functions
+    pub captures: usize,
     /// Return type
     pub return_type: TypeId,
     /// Function body as a block
     pub body: RirBlock,
+    /// Set for functions annotated with `@test`
+    pub test: Option<RirTest>,
     /// Source location
     pub span: Span,
 }
 
+/// How a test function is expected to finish.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RirTest {
+    /// Whether the test passes only if it panics
+    pub should_panic: bool,
+    /// Text the panic message must contain
+    pub expected_message: Option<String>,
+}
+
 impl RirFunction {
     /// Creates a new RIR function
     #[must_use]
@@ -72,8 +120,10 @@ impl RirFunction {
         Self {
             name,
             parameters,
+            captures: 0,
             return_type,
             body,
+            test: None,
             span,
         }
     }
@@ -158,6 +208,29 @@ impl RirBlock {
         self.final_expr = Some(Box::new(expr));
     }
 
+    /// Returns the type of the value this block produces.
+    ///
+    /// This is the type of the final expression, `Never` if the block ends by
+    /// leaving it (return, break, continue or a diverging expression), or Unit.
+    #[must_use]
+    pub fn result_type(&self) -> TypeId {
+        if let Some(final_expr) = &self.final_expr {
+            return final_expr.type_id();
+        }
+
+        match self.statements.last() {
+            Some(
+                RirStatement::Return { .. }
+                | RirStatement::Break { .. }
+                | RirStatement::Continue { .. },
+            ) => TypeId::NEVER,
+            Some(RirStatement::Expression { expr, .. }) if expr.type_id() == TypeId::NEVER => {
+                TypeId::NEVER
+            }
+            _ => TypeId::UNIT,
+        }
+    }
+
     /// Returns true if this block is empty
     #[must_use]
     pub fn is_empty(&self) -> bool {
@@ -14,7 +14,15 @@ impl RirPattern {
             | Self::StringLiteral { span, .. }
             | Self::BoolLiteral { span, .. }
             | Self::Wildcard { span }
-            | Self::RangePattern { span, .. } => *span,
+            | Self::RangePattern { span, .. }
+            | Self::ResultOk { span, .. }
+            | Self::ResultErr { span, .. }
+            | Self::Null { span }
+            | Self::NonNull { span, .. }
+            | Self::Binding { span, .. }
+            | Self::Tuple { span, .. }
+            | Self::Or { span, .. }
+            | Self::Guarded { span, .. } => *span,
         }
     }
 }
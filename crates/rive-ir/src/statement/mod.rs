@@ -3,4 +3,4 @@
 mod methods;
 mod types;
 
-pub use types::{RirPattern, RirStatement};
+pub use types::{RirIterable, RirPattern, RirStatement};
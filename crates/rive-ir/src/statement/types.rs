@@ -28,6 +28,51 @@ pub enum RirPattern {
         inclusive: bool,
         span: Span,
     },
+
+    /// Result success pattern (`ok(name)`), with an optional binding
+    ResultOk { binding: Option<String>, span: Span },
+    /// Result failure pattern (`err(name)`), with an optional binding
+    ResultErr { binding: Option<String>, span: Span },
+
+    /// Null pattern (matches an absent nullable value)
+    Null { span: Span },
+    /// Matches a present nullable value whose contents match `inner`
+    NonNull { inner: Box<RirPattern>, span: Span },
+    /// Binding pattern (matches anything and binds it)
+    Binding {
+        name: String,
+        type_id: TypeId,
+        span: Span,
+    },
+    /// Tuple pattern
+    Tuple {
+        elements: Vec<RirPattern>,
+        span: Span,
+    },
+    /// Or-pattern (matches if any alternative matches)
+    Or {
+        alternatives: Vec<RirPattern>,
+        span: Span,
+    },
+    /// Pattern with a guard condition (only at the top level of an arm)
+    Guarded {
+        pattern: Box<RirPattern>,
+        guard: Box<RirExpression>,
+        span: Span,
+    },
+}
+
+/// The sequence a for loop iterates over
+#[derive(Debug, Clone)]
+pub enum RirIterable {
+    /// Integer range (start..end or start..=end)
+    Range {
+        start: Box<RirExpression>,
+        end: Box<RirExpression>,
+        inclusive: bool,
+    },
+    /// Elements of an Array or List, or (key, value) tuples of a Map
+    Collection { collection: Box<RirExpression> },
 }
 
 /// A statement in RIR
@@ -103,16 +148,12 @@ pub enum RirStatement {
         span: Span,
     },
 
-    /// For loop (range iteration)
+    /// For loop (range or collection iteration)
     For {
         /// Iterator variable name
         variable: String,
-        /// Start of range
-        start: Box<RirExpression>,
-        /// End of range
-        end: Box<RirExpression>,
-        /// Whether range is inclusive (..=) or exclusive (..)
-        inclusive: bool,
+        /// Sequence being iterated
+        iterable: RirIterable,
         /// Loop body
         body: RirBlock,
         /// Optional label for multi-level break/continue
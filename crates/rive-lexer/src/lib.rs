@@ -3,11 +3,13 @@
 //! This crate provides tokenization of Rive source code into a stream of tokens.
 
 mod token;
+mod trivia;
 
-pub use token::{Token, TokenKind};
+pub use token::{LexError, Token, TokenKind};
+pub use trivia::{LosslessToken, LosslessTokens, Trivia, TriviaKind, tokenize_lossless};
 
 use logos::Logos;
-use rive_core::{Error, Result, Span};
+use rive_core::{Error, Location, Result, Span};
 
 /// Tokenizes Rive source code into a vector of tokens.
 ///
@@ -18,7 +20,7 @@ use rive_core::{Error, Result, Span};
 /// A vector of tokens with their spans
 ///
 /// # Errors
-/// Returns an error if the source contains invalid tokens
+/// Returns the first lexer error if the source contains invalid tokens
 ///
 /// # Examples
 /// ```
@@ -28,21 +30,94 @@ use rive_core::{Error, Result, Span};
 /// let tokens = tokenize(source).unwrap();
 /// ```
 pub fn tokenize(source: &str) -> Result<Vec<(Token, Span)>> {
+    let (tokens, mut errors) = tokenize_with_errors(source);
+    if errors.is_empty() {
+        Ok(tokens)
+    } else {
+        Err(errors.swap_remove(0))
+    }
+}
+
+/// Tokenizes Rive source code, continuing past invalid input.
+///
+/// Each piece of rejected text becomes a [`TokenKind::Error`] token in the
+/// stream and a spanned [`Error::Lexer`] in the returned error list.
+pub fn tokenize_with_errors(source: &str) -> (Vec<(Token, Span)>, Vec<Error>) {
     let mut tokens = Vec::new();
+    let mut errors = Vec::new();
     let mut lexer = TokenKind::lexer(source);
+    let mut cursor = Cursor::new(source);
 
-    while let Some(result) = lexer.next() {
-        let kind = result.map_err(|_| {
-            Error::Lexer(format!("Invalid token at position {}", lexer.span().start))
-        })?;
+    // A `#!` line at the very start makes a script executable; skip it
+    if source.starts_with("#!") {
+        lexer.bump(source.find('\n').unwrap_or(source.len()));
+    }
 
+    while let Some(result) = lexer.next() {
         let span = lexer.span();
         let text = lexer.slice().to_string();
+        let start = cursor.advance_to(span.start);
+
+        let kind = match result {
+            Ok(kind) => kind,
+            Err(error) => {
+                // Unterminated literals run to the end of the file, so only
+                // their opening delimiter is highlighted
+                let label_end = match error {
+                    LexError::UnterminatedString => span.start + 1,
+                    LexError::UnterminatedBlockComment => span.start + 2,
+                    LexError::InvalidToken | LexError::MalformedNumber => span.end,
+                };
+                let label = Span::new(start, cursor.advance_to(label_end));
+                errors.push(Error::Lexer(error_message(&error, &text), label));
+                TokenKind::Error
+            }
+        };
 
-        tokens.push((Token { kind, text }, Span::from_range(span.start, span.end)));
+        let end = cursor.advance_to(span.end);
+        tokens.push((Token { kind, text }, Span::new(start, end)));
     }
 
-    Ok(tokens)
+    (tokens, errors)
+}
+
+/// Describes a lexer error for the rejected `text`.
+fn error_message(error: &LexError, text: &str) -> String {
+    match error {
+        LexError::InvalidToken => format!("Invalid character '{text}'"),
+        LexError::UnterminatedString => "Unterminated string literal".to_string(),
+        LexError::MalformedNumber => format!("Malformed number literal '{text}'"),
+        LexError::UnterminatedBlockComment => "Unterminated block comment".to_string(),
+    }
+}
+
+/// Tracks 1-based line and column numbers while walking forward through the source.
+struct Cursor<'a> {
+    source: &'a str,
+    location: Location,
+}
+
+impl<'a> Cursor<'a> {
+    const fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            location: Location::with_offset(1, 1, 0),
+        }
+    }
+
+    /// Moves the cursor to `offset` (which must not be behind it) and returns its location.
+    fn advance_to(&mut self, offset: usize) -> Location {
+        for ch in self.source[self.location.offset..offset].chars() {
+            if ch == '\n' {
+                self.location.line += 1;
+                self.location.column = 1;
+            } else {
+                self.location.column += 1;
+            }
+        }
+        self.location.offset = offset;
+        self.location
+    }
 }
 
 #[cfg(test)]
@@ -60,4 +135,17 @@ mod tests {
         assert!(matches!(tokens[2].0.kind, TokenKind::Equal));
         assert!(matches!(tokens[3].0.kind, TokenKind::Integer));
     }
+
+    #[test]
+    fn test_token_locations() {
+        let source = "let x = 1\n  x!!";
+        let tokens = tokenize(source).unwrap();
+
+        let (_, span) = &tokens[4];
+        assert_eq!((span.start.line, span.start.column), (2, 3));
+        let (_, span) = &tokens[5];
+        assert!(matches!(tokens[5].0.kind, TokenKind::BangBang));
+        assert_eq!((span.start.line, span.start.column), (2, 4));
+        assert_eq!(span.end.offset, source.len());
+    }
 }
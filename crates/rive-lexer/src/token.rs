@@ -1,6 +1,6 @@
 //! Token definitions for the Rive lexer.
 
-use logos::Logos;
+use logos::{FilterResult, Lexer, Logos};
 use std::fmt;
 
 /// Represents a token in the Rive language.
@@ -10,8 +10,23 @@ pub struct Token {
     pub text: String,
 }
 
+/// Errors the lexer can report for a piece of source text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum LexError {
+    /// Text that doesn't start any token
+    #[default]
+    InvalidToken,
+    /// A `"` without a closing quote
+    UnterminatedString,
+    /// Digits running straight into letters, like `12abc`
+    MalformedNumber,
+    /// A `/*` without a matching `*/`
+    UnterminatedBlockComment,
+}
+
 /// Represents the different kinds of tokens in Rive.
 #[derive(Logos, Debug, Clone, PartialEq)]
+#[logos(error = LexError)]
 #[logos(skip r"[ \t\r\n\f]+")]
 #[logos(skip r"//[^\n]*")]
 pub enum TokenKind {
@@ -28,6 +43,12 @@ pub enum TokenKind {
     #[token("fun")]
     Fun,
 
+    #[token("type")]
+    Type,
+
+    #[token("newtype")]
+    Newtype,
+
     #[token("if")]
     If,
 
@@ -73,14 +94,28 @@ pub enum TokenKind {
     #[token("print")]
     Print,
 
+    // Comments
+    /// Doc comment: `/// text`, attached to the item that follows it
+    #[regex(r"///[^\n]*")]
+    DocComment,
+
+    /// Block comment: `/* ... */`, which may be nested; always skipped
+    #[token("/*", skip_block_comment)]
+    BlockComment,
+
+    /// Stands in for text the lexer rejected so lexing can continue after it
+    #[regex(r#""([^"\\]|\\.)*"#, unterminated_string)]
+    #[regex(r"[0-9]+(\.[0-9]+)?[A-Za-z_][A-Za-z0-9_]*", malformed_number)]
+    Error,
+
     // Identifiers and literals
     #[regex(r"[a-zA-Z_][a-zA-Z0-9_]*")]
     Identifier,
 
-    #[regex(r"-?[0-9]+")]
+    #[regex(r"[0-9]+")]
     Integer,
 
-    #[regex(r"-?[0-9]+\.[0-9]+")]
+    #[regex(r"[0-9]+\.[0-9]+")]
     Float,
 
     #[regex(r#""([^"\\]|\\.)*""#)]
@@ -102,6 +137,21 @@ pub enum TokenKind {
     #[token("%")]
     Percent,
 
+    #[token("+=")]
+    PlusEqual,
+
+    #[token("-=")]
+    MinusEqual,
+
+    #[token("*=")]
+    StarEqual,
+
+    #[token("/=")]
+    SlashEqual,
+
+    #[token("%=")]
+    PercentEqual,
+
     #[token("=")]
     Equal,
 
@@ -129,9 +179,30 @@ pub enum TokenKind {
     #[token("||")]
     PipePipe,
 
+    #[token("|")]
+    Pipe,
+
+    #[token("&")]
+    Ampersand,
+
+    #[token("^")]
+    Caret,
+
+    #[token("~")]
+    Tilde,
+
+    #[token("<<")]
+    LessLess,
+
+    #[token(">>")]
+    GreaterGreater,
+
     #[token("!")]
     Bang,
 
+    #[token("!!")]
+    BangBang,
+
     // Range operators (order matters: ..= before ..)
     #[token("..=")]
     DotDotEq,
@@ -178,6 +249,10 @@ pub enum TokenKind {
 
     #[token("->")]
     Arrow,
+
+    /// Starts an attribute: `@test`
+    #[token("@")]
+    At,
 }
 
 impl fmt::Display for TokenKind {
@@ -187,6 +262,8 @@ impl fmt::Display for TokenKind {
             Self::Const => write!(f, "const"),
             Self::Mut => write!(f, "mut"),
             Self::Fun => write!(f, "fun"),
+            Self::Type => write!(f, "type"),
+            Self::Newtype => write!(f, "newtype"),
             Self::If => write!(f, "if"),
             Self::Else => write!(f, "else"),
             Self::While => write!(f, "while"),
@@ -203,6 +280,10 @@ impl fmt::Display for TokenKind {
             Self::Null => write!(f, "null"),
             Self::Print => write!(f, "print"),
 
+            Self::DocComment => write!(f, "doc comment"),
+            Self::BlockComment => write!(f, "block comment"),
+            Self::Error => write!(f, "invalid token"),
+
             Self::Identifier => write!(f, "identifier"),
             Self::Integer => write!(f, "integer"),
             Self::Float => write!(f, "float"),
@@ -213,6 +294,11 @@ impl fmt::Display for TokenKind {
             Self::Star => write!(f, "*"),
             Self::Slash => write!(f, "/"),
             Self::Percent => write!(f, "%"),
+            Self::PlusEqual => write!(f, "+="),
+            Self::MinusEqual => write!(f, "-="),
+            Self::StarEqual => write!(f, "*="),
+            Self::SlashEqual => write!(f, "/="),
+            Self::PercentEqual => write!(f, "%="),
             Self::Equal => write!(f, "="),
             Self::EqualEqual => write!(f, "=="),
             Self::BangEqual => write!(f, "!="),
@@ -222,7 +308,14 @@ impl fmt::Display for TokenKind {
             Self::GreaterEqual => write!(f, ">="),
             Self::AmpersandAmpersand => write!(f, "&&"),
             Self::PipePipe => write!(f, "||"),
+            Self::Pipe => write!(f, "|"),
+            Self::Ampersand => write!(f, "&"),
+            Self::Caret => write!(f, "^"),
+            Self::Tilde => write!(f, "~"),
+            Self::LessLess => write!(f, "<<"),
+            Self::GreaterGreater => write!(f, ">>"),
             Self::Bang => write!(f, "!"),
+            Self::BangBang => write!(f, "!!"),
 
             Self::DotDotEq => write!(f, "..="),
             Self::DotDot => write!(f, ".."),
@@ -240,6 +333,43 @@ impl fmt::Display for TokenKind {
             Self::Question => write!(f, "?"),
             Self::Dot => write!(f, "."),
             Self::Arrow => write!(f, "->"),
+            Self::At => write!(f, "@"),
         }
     }
 }
+
+fn unterminated_string(_: &mut Lexer<TokenKind>) -> Result<(), LexError> {
+    Err(LexError::UnterminatedString)
+}
+
+fn malformed_number(_: &mut Lexer<TokenKind>) -> Result<(), LexError> {
+    Err(LexError::MalformedNumber)
+}
+
+/// Skips the rest of a block comment whose `/*` was just matched, honoring nesting.
+fn skip_block_comment(lexer: &mut Lexer<TokenKind>) -> FilterResult<(), LexError> {
+    let remainder = lexer.remainder().as_bytes();
+    let mut depth = 1;
+    let mut i = 0;
+
+    while i < remainder.len() {
+        match (remainder[i], remainder.get(i + 1)) {
+            (b'/', Some(b'*')) => {
+                depth += 1;
+                i += 2;
+            }
+            (b'*', Some(b'/')) => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    lexer.bump(i);
+                    return FilterResult::Skip;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    lexer.bump(remainder.len());
+    FilterResult::Error(LexError::UnterminatedBlockComment)
+}
@@ -0,0 +1,171 @@
+//! Lossless tokenization that keeps whitespace and comments.
+//!
+//! [`tokenize`](crate::tokenize) drops everything the parser doesn't need.
+//! Tools that rewrite source, like the formatter, need it back: here every
+//! token carries the trivia in front of it, so the original text can be
+//! rebuilt exactly.
+
+use crate::{Cursor, Token, TokenKind, tokenize_with_errors};
+use rive_core::{Error, Span};
+use std::fmt;
+
+/// The kinds of source text the parser ignores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    /// Spaces, tabs and line breaks
+    Whitespace,
+    /// `// ...` up to the end of the line
+    LineComment,
+    /// `/* ... */`, which may be nested and span lines
+    BlockComment,
+    /// A `#!` line at the very start of a script
+    Shebang,
+}
+
+/// A piece of whitespace or a comment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+    pub span: Span,
+}
+
+impl Trivia {
+    /// Returns the number of line breaks in this trivia.
+    pub fn newlines(&self) -> usize {
+        self.text.matches('\n').count()
+    }
+
+    /// Returns true for line and block comments.
+    pub fn is_comment(&self) -> bool {
+        matches!(
+            self.kind,
+            TriviaKind::LineComment | TriviaKind::BlockComment
+        )
+    }
+}
+
+/// A token with the trivia that comes before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LosslessToken {
+    pub token: Token,
+    pub span: Span,
+    pub leading_trivia: Vec<Trivia>,
+}
+
+impl LosslessToken {
+    /// Returns the kind of the token.
+    pub const fn kind(&self) -> &TokenKind {
+        &self.token.kind
+    }
+}
+
+/// Every token of a source file with all the trivia between them.
+///
+/// Displaying it reproduces the source text byte for byte.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LosslessTokens {
+    pub tokens: Vec<LosslessToken>,
+    /// Trivia after the last token
+    pub trailing_trivia: Vec<Trivia>,
+}
+
+impl fmt::Display for LosslessTokens {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for token in &self.tokens {
+            for trivia in &token.leading_trivia {
+                f.write_str(&trivia.text)?;
+            }
+            f.write_str(&token.token.text)?;
+        }
+        for trivia in &self.trailing_trivia {
+            f.write_str(&trivia.text)?;
+        }
+        Ok(())
+    }
+}
+
+/// Tokenizes Rive source code, keeping whitespace and comments.
+///
+/// Lexer errors are reported the same way as by
+/// [`tokenize_with_errors`](crate::tokenize_with_errors), and the rejected
+/// text is kept as [`TokenKind::Error`] tokens.
+pub fn tokenize_lossless(source: &str) -> (LosslessTokens, Vec<Error>) {
+    let (tokens, errors) = tokenize_with_errors(source);
+    let mut cursor = Cursor::new(source);
+    let mut lossless = LosslessTokens::default();
+
+    for (token, span) in tokens {
+        let leading_trivia = scan_trivia(source, &mut cursor, span.start.offset);
+        cursor.advance_to(span.end.offset);
+        lossless.tokens.push(LosslessToken {
+            token,
+            span,
+            leading_trivia,
+        });
+    }
+    lossless.trailing_trivia = scan_trivia(source, &mut cursor, source.len());
+
+    (lossless, errors)
+}
+
+/// Splits the text between the cursor and `end` into trivia.
+fn scan_trivia(source: &str, cursor: &mut Cursor, end: usize) -> Vec<Trivia> {
+    let mut trivia = Vec::new();
+
+    while cursor.location.offset < end {
+        let start = cursor.location;
+        let rest = &source[start.offset..end];
+        let (kind, len) = if start.offset == 0 && rest.starts_with("#!") {
+            (TriviaKind::Shebang, rest.find('\n').unwrap_or(rest.len()))
+        } else if rest.starts_with("//") {
+            (
+                TriviaKind::LineComment,
+                rest.find('\n').unwrap_or(rest.len()),
+            )
+        } else if rest.starts_with("/*") {
+            (TriviaKind::BlockComment, block_comment_len(rest))
+        } else {
+            // Anything else was lexed as a token, so this is whitespace
+            let len = rest
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len());
+            (TriviaKind::Whitespace, len)
+        };
+
+        let end_location = cursor.advance_to(start.offset + len);
+        trivia.push(Trivia {
+            kind,
+            text: rest[..len].to_string(),
+            span: Span::new(start, end_location),
+        });
+    }
+
+    trivia
+}
+
+/// Returns the length of the nested block comment at the start of `text`.
+fn block_comment_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'/', Some(b'*')) => {
+                depth += 1;
+                i += 2;
+            }
+            (b'*', Some(b'/')) => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    bytes.len()
+}
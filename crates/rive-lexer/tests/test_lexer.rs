@@ -1,6 +1,9 @@
 //! Tests for the Rive lexer.
 
-use rive_lexer::{TokenKind, tokenize};
+use rive_core::Error;
+use rive_lexer::{
+    TokenKind, Trivia, TriviaKind, tokenize, tokenize_lossless, tokenize_with_errors,
+};
 
 #[test]
 fn test_keywords() {
@@ -20,6 +23,15 @@ fn test_keywords() {
     assert!(matches!(tokens[9].0.kind, TokenKind::Continue));
 }
 
+#[test]
+fn test_type_keywords() {
+    let tokens = tokenize("type newtype types").unwrap();
+
+    assert!(matches!(tokens[0].0.kind, TokenKind::Type));
+    assert!(matches!(tokens[1].0.kind, TokenKind::Newtype));
+    assert!(matches!(tokens[2].0.kind, TokenKind::Identifier));
+}
+
 #[test]
 fn test_literals() {
     let source = r#"42 3.14 "hello" true false null"#;
@@ -42,10 +54,10 @@ fn test_literals() {
 
 #[test]
 fn test_operators() {
-    let source = "+ - * / % = == != < <= > >= && || !";
+    let source = "+ - * / % = == != < <= > >= && || ! |";
     let tokens = tokenize(source).unwrap();
 
-    assert_eq!(tokens.len(), 15);
+    assert_eq!(tokens.len(), 16);
     assert!(matches!(tokens[0].0.kind, TokenKind::Plus));
     assert!(matches!(tokens[1].0.kind, TokenKind::Minus));
     assert!(matches!(tokens[2].0.kind, TokenKind::Star));
@@ -61,6 +73,32 @@ fn test_operators() {
     assert!(matches!(tokens[12].0.kind, TokenKind::AmpersandAmpersand));
     assert!(matches!(tokens[13].0.kind, TokenKind::PipePipe));
     assert!(matches!(tokens[14].0.kind, TokenKind::Bang));
+    assert!(matches!(tokens[15].0.kind, TokenKind::Pipe));
+}
+
+#[test]
+fn test_compound_and_bitwise_operators() {
+    let source = "+= -= *= /= %= & ^ ~ << >> -> &&";
+    let tokens = tokenize(source).unwrap();
+
+    let kinds: Vec<_> = tokens.iter().map(|t| t.0.kind.clone()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::PlusEqual,
+            TokenKind::MinusEqual,
+            TokenKind::StarEqual,
+            TokenKind::SlashEqual,
+            TokenKind::PercentEqual,
+            TokenKind::Ampersand,
+            TokenKind::Caret,
+            TokenKind::Tilde,
+            TokenKind::LessLess,
+            TokenKind::GreaterGreater,
+            TokenKind::Arrow,
+            TokenKind::AmpersandAmpersand,
+        ]
+    );
 }
 
 #[test]
@@ -145,6 +183,99 @@ fn test_comments_ignored() {
     assert!(tokens.iter().any(|t| t.0.text == "y"));
 }
 
+#[test]
+fn test_block_comments_nest() {
+    let source = "let /* outer /* inner */ still comment */ x = 1 /**/";
+    let tokens = tokenize(source).unwrap();
+
+    let kinds: Vec<_> = tokens.iter().map(|t| t.0.kind.clone()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::Let,
+            TokenKind::Identifier,
+            TokenKind::Equal,
+            TokenKind::Integer
+        ]
+    );
+}
+
+#[test]
+fn test_unterminated_block_comment() {
+    let source = "let x = 1\n  /* open /* nested */ never closed";
+    let error = tokenize(source).unwrap_err();
+
+    let Error::Lexer(message, span) = error else {
+        panic!("Expected a lexer error, got {error:?}");
+    };
+    assert_eq!(message, "Unterminated block comment");
+    assert_eq!(span.start.line, 2);
+    assert_eq!(span.start.column, 3);
+    assert_eq!(span.end.offset - span.start.offset, 2);
+}
+
+#[test]
+fn test_lexer_errors_recover() {
+    let source = "let a = 1 ` 2\nlet b = 12abc $";
+    let (tokens, errors) = tokenize_with_errors(source);
+
+    let messages: Vec<_> = errors
+        .iter()
+        .map(|error| match error {
+            Error::Lexer(message, span) => (message.as_str(), span.start.line, span.start.column),
+            other => panic!("Expected a lexer error, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            ("Invalid character '`'", 1, 11),
+            ("Malformed number literal '12abc'", 2, 9),
+            ("Invalid character '$'", 2, 15),
+        ]
+    );
+
+    // Lexing continues past each error, leaving an error token in its place
+    let error_tokens = tokens
+        .iter()
+        .filter(|t| t.0.kind == TokenKind::Error)
+        .count();
+    assert_eq!(error_tokens, 3);
+    assert_eq!(tokens.last().unwrap().0.text, "$");
+    assert!(tokens.iter().any(|t| t.0.text == "b"));
+}
+
+#[test]
+fn test_unterminated_string() {
+    let source = "let s = \"hello\nlet t = 1";
+    let (tokens, errors) = tokenize_with_errors(source);
+
+    assert_eq!(errors.len(), 1);
+    let Error::Lexer(message, span) = &errors[0] else {
+        panic!("Expected a lexer error");
+    };
+    assert_eq!(message, "Unterminated string literal");
+    assert_eq!((span.start.column, span.end.column), (9, 10));
+    assert_eq!(tokens.last().unwrap().0.kind, TokenKind::Error);
+}
+
+#[test]
+fn test_numbers_before_ranges_and_members() {
+    let tokens = tokenize("0..10 1.5 x.0").unwrap();
+    assert!(!tokens.iter().any(|t| t.0.kind == TokenKind::Error));
+}
+
+#[test]
+fn test_doc_comments() {
+    let source = "/// Adds one.\n//// not a doc\nfun f() {}";
+    let tokens = tokenize(source).unwrap();
+
+    assert!(matches!(tokens[0].0.kind, TokenKind::DocComment));
+    assert_eq!(tokens[0].0.text, "/// Adds one.");
+    assert!(matches!(tokens[1].0.kind, TokenKind::DocComment));
+    assert!(matches!(tokens[2].0.kind, TokenKind::Fun));
+}
+
 #[test]
 fn test_array_syntax() {
     let source = "[Int; 10]";
@@ -162,14 +293,36 @@ fn test_array_syntax() {
 
 #[test]
 fn test_negative_numbers() {
+    // Numeric literals are unsigned; the sign is a separate token
     let source = "-42 -3.14";
     let tokens = tokenize(source).unwrap();
 
-    assert_eq!(tokens.len(), 2);
-    assert!(matches!(tokens[0].0.kind, TokenKind::Integer));
-    assert_eq!(tokens[0].0.text, "-42");
-    assert!(matches!(tokens[1].0.kind, TokenKind::Float));
-    assert_eq!(tokens[1].0.text, "-3.14");
+    assert_eq!(tokens.len(), 4);
+    assert!(matches!(tokens[0].0.kind, TokenKind::Minus));
+    assert!(matches!(tokens[1].0.kind, TokenKind::Integer));
+    assert_eq!(tokens[1].0.text, "42");
+    assert!(matches!(tokens[2].0.kind, TokenKind::Minus));
+    assert!(matches!(tokens[3].0.kind, TokenKind::Float));
+    assert_eq!(tokens[3].0.text, "3.14");
+}
+
+#[test]
+fn test_subtraction_without_spaces() {
+    let source = "x-1 2-3.5";
+    let tokens = tokenize(source).unwrap();
+
+    let kinds: Vec<_> = tokens.iter().map(|t| t.0.kind.clone()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::Identifier,
+            TokenKind::Minus,
+            TokenKind::Integer,
+            TokenKind::Integer,
+            TokenKind::Minus,
+            TokenKind::Float,
+        ]
+    );
 }
 
 #[test]
@@ -277,3 +430,60 @@ fn test_match_expression() {
             .any(|t| matches!(t.0.kind, TokenKind::Underscore))
     );
 }
+
+#[test]
+fn test_shebang_line() {
+    let source = "#!/usr/bin/env rive\nfun main() {}";
+    let tokens = tokenize(source).unwrap();
+
+    assert!(matches!(tokens[0].0.kind, TokenKind::Fun));
+    assert_eq!(tokens[0].1.start.line, 2);
+    assert_eq!(tokens[0].1.start.column, 1);
+
+    // Only a shebang at the very start of the file is skipped
+    assert!(tokenize("fun main() {}\n#!/usr/bin/env rive").is_err());
+}
+
+#[test]
+fn test_lossless_tokens() {
+    let source = "#!/usr/bin/env rive\n/// Docs\nfun main() { // start\n  let x = 1 /* a /* b */ */\n}\n// end\n";
+    let (tokens, errors) = tokenize_lossless(source);
+
+    assert!(errors.is_empty());
+    assert_eq!(tokens.to_string(), source);
+    assert_eq!(tokens.tokens.len(), tokenize(source).unwrap().len());
+
+    let kinds = |trivia: &[Trivia]| trivia.iter().map(|t| t.kind).collect::<Vec<_>>();
+    assert_eq!(
+        kinds(&tokens.tokens[0].leading_trivia),
+        vec![TriviaKind::Shebang, TriviaKind::Whitespace]
+    );
+    let let_token = &tokens.tokens[6];
+    assert!(matches!(let_token.kind(), TokenKind::Let));
+    assert_eq!(
+        kinds(&let_token.leading_trivia),
+        vec![
+            TriviaKind::Whitespace,
+            TriviaKind::LineComment,
+            TriviaKind::Whitespace
+        ]
+    );
+    assert_eq!(let_token.leading_trivia[1].text, "// start");
+    assert_eq!(let_token.leading_trivia[2].span.end.line, 4);
+
+    let close = tokens.tokens.last().unwrap();
+    assert_eq!(close.leading_trivia[1].text, "/* a /* b */ */");
+    assert_eq!(
+        kinds(&tokens.trailing_trivia),
+        vec![
+            TriviaKind::Whitespace,
+            TriviaKind::LineComment,
+            TriviaKind::Whitespace
+        ]
+    );
+
+    // Rejected text is kept as error tokens
+    let (tokens, errors) = tokenize_lossless("let $ = 1");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(tokens.to_string(), "let $ = 1");
+}
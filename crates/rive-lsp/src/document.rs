@@ -0,0 +1,523 @@
+//! Analysis of an open document.
+//!
+//! A document is lexed, parsed and checked whenever its text changes. The
+//! type checker stops at the first error, but what it learned up to that
+//! point (expression types, declarations and their uses) is kept, so hover
+//! and navigation keep working in code before the error.
+
+use rive_core::type_system::{TypeId, TypeKind, TypeRegistry};
+use rive_core::{Error, Span};
+use rive_lexer::{Token, TokenKind, tokenize_with_errors};
+use rive_parser::ast::{Item, Program};
+use rive_parser::parse;
+use rive_semantic::{Symbol, SymbolReference, SymbolTable, TypeChecker};
+use std::collections::HashMap;
+
+/// How serious a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// An error or warning found in a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub message: String,
+    pub severity: Severity,
+    /// `None` for errors that don't point at the source
+    pub span: Option<Span>,
+}
+
+impl Diagnostic {
+    fn error(mut error: Error) -> Self {
+        let span = error.span_mut().copied();
+        Self {
+            message: error.to_string(),
+            severity: Severity::Error,
+            span,
+        }
+    }
+}
+
+/// What a completion item completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    Variable,
+    Function,
+    Method,
+}
+
+/// A name that can be written at the cursor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion {
+    pub label: String,
+    pub kind: CompletionKind,
+    /// Type or signature of the completed name
+    pub detail: String,
+}
+
+/// What a document symbol declares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Function,
+    Variable,
+    Constant,
+    TypeAlias,
+}
+
+/// A top-level declaration of a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub detail: String,
+    /// The whole declaration
+    pub span: Span,
+    /// The declared name
+    pub name_span: Span,
+}
+
+/// Everything the compiler front end learned about one version of a text.
+struct Analysis {
+    tokens: Vec<(Token, Span)>,
+    /// `None` if the text doesn't parse
+    program: Option<Program>,
+    registry: TypeRegistry,
+    expression_types: HashMap<Span, TypeId>,
+    declarations: Vec<Symbol>,
+    references: Vec<SymbolReference>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Analysis {
+    fn run(source: &str) -> Self {
+        let (tokens, errors) = tokenize_with_errors(source);
+        let mut analysis = Self {
+            tokens,
+            program: None,
+            registry: TypeRegistry::new(),
+            expression_types: HashMap::new(),
+            declarations: Vec::new(),
+            references: Vec::new(),
+            diagnostics: errors.into_iter().map(Diagnostic::error).collect(),
+        };
+        if !analysis.diagnostics.is_empty() {
+            return analysis;
+        }
+
+        let (program, registry) = match parse(&analysis.tokens) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                analysis.diagnostics.push(Diagnostic::error(error));
+                return analysis;
+            }
+        };
+
+        let mut checker = TypeChecker::with_symbols(SymbolTable::with_registry(registry));
+        if let Err(error) = checker.check_program(&program) {
+            analysis.diagnostics.push(Diagnostic::error(error));
+        }
+        analysis
+            .diagnostics
+            .extend(
+                checker
+                    .take_warnings()
+                    .into_iter()
+                    .map(|warning| Diagnostic {
+                        message: warning.message,
+                        severity: Severity::Warning,
+                        span: Some(warning.span),
+                    }),
+            );
+        analysis.expression_types = checker.take_expression_types();
+        analysis.declarations = checker.symbols().declarations().to_vec();
+        analysis.references = checker.symbols().references().to_vec();
+        analysis.registry = checker.into_type_registry();
+        analysis.program = Some(program);
+        analysis
+    }
+
+    /// Narrows the span of a declaration or use down to the name it contains.
+    fn name_span(&self, span: Span, name: &str) -> Span {
+        self.tokens
+            .iter()
+            .find(|(token, token_span)| {
+                token.kind == TokenKind::Identifier
+                    && token.text == name
+                    && token_span.start.offset >= span.start.offset
+                    && token_span.end.offset <= span.end.offset
+            })
+            .map_or(span, |(_, token_span)| *token_span)
+    }
+
+    /// Returns the name and declaration span of the symbol named at `offset`.
+    fn symbol_at(&self, offset: usize) -> Option<(&str, Span)> {
+        let touches = |span: Span| span.start.offset <= offset && offset <= span.end.offset;
+        self.references
+            .iter()
+            .find(|reference| touches(self.name_span(reference.span, &reference.name)))
+            .map(|reference| (reference.name.as_str(), reference.declaration))
+            .or_else(|| {
+                self.declarations
+                    .iter()
+                    .find(|symbol| {
+                        symbol
+                            .span
+                            .is_some_and(|span| touches(self.name_span(span, &symbol.name)))
+                    })
+                    .and_then(|symbol| Some((symbol.name.as_str(), symbol.span?)))
+            })
+    }
+
+    fn declaration(&self, name: &str, span: Span) -> Option<&Symbol> {
+        self.declarations
+            .iter()
+            .find(|symbol| symbol.name == name && symbol.span == Some(span))
+    }
+
+    /// Returns the smallest checked expression around `offset`.
+    fn expression_at(&self, offset: usize) -> Option<(Span, TypeId)> {
+        self.expression_types
+            .iter()
+            .filter(|(span, _)| span.start.offset <= offset && offset < span.end.offset)
+            .min_by_key(|(span, _)| (span.end.offset - span.start.offset, span.start.offset))
+            .map(|(span, type_id)| (*span, *type_id))
+    }
+
+    /// Returns the signature shown for a declared symbol.
+    fn signature(&self, symbol: &Symbol) -> String {
+        let Some(params) = &symbol.params else {
+            let keyword = if symbol.mutable { "let mut" } else { "let" };
+            let type_name = self.registry.get_type_name(symbol.symbol_type);
+            return format!("{keyword} {}: {type_name}", symbol.name);
+        };
+
+        let params = params
+            .iter()
+            .map(|param| {
+                let type_name = self.registry.get_type_name(param.param_type);
+                format!("{}: {type_name}", param.name)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        match &self.registry.get_type_metadata(symbol.symbol_type).kind {
+            TypeKind::Function { return_type, .. } if *return_type != TypeId::UNIT => {
+                let return_type = self.registry.get_type_name(*return_type);
+                format!("fun {}({params}): {return_type}", symbol.name)
+            }
+            _ => format!("fun {}({params})", symbol.name),
+        }
+    }
+
+    /// Returns the `///` documentation of a top-level declaration.
+    fn docs(&self, span: Span) -> Option<&str> {
+        self.program.as_ref()?.items.iter().find_map(|item| {
+            let (item_span, docs) = match item {
+                Item::Function(function) => (function.span, &function.docs),
+                Item::Global(global) => (global.span, &global.docs),
+                Item::TypeAlias(alias) => (alias.span, &alias.docs),
+            };
+            (item_span == span).then_some(docs.as_deref()).flatten()
+        })
+    }
+
+    /// Returns the declarations visible at `offset`, innermost last.
+    ///
+    /// Top-level functions and globals are visible everywhere; other
+    /// declarations are visible after them in the same top-level function.
+    fn visible_declarations(&self, offset: usize) -> Vec<&Symbol> {
+        let Some(program) = &self.program else {
+            return Vec::new();
+        };
+        let top_level: Vec<Span> = program
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Function(function) => Some(function.span),
+                Item::Global(global) => Some(global.span),
+                Item::TypeAlias(_) => None,
+            })
+            .collect();
+        let enclosing = program.items.iter().find_map(|item| match item {
+            Item::Function(function)
+                if function.span.start.offset <= offset && offset <= function.span.end.offset =>
+            {
+                Some(function.span)
+            }
+            _ => None,
+        });
+
+        self.declarations
+            .iter()
+            .filter(|symbol| {
+                let Some(span) = symbol.span else {
+                    return false;
+                };
+                top_level.contains(&span)
+                    || enclosing.is_some_and(|function| {
+                        function.start.offset <= span.start.offset
+                            && span.end.offset <= function.end.offset
+                            && span.start.offset < offset
+                    })
+            })
+            .collect()
+    }
+}
+
+/// The text of an open document and what is known about it.
+///
+/// Positions count lines from 0 and characters in UTF-16 code units, as in
+/// the language server protocol.
+pub struct Document {
+    text: String,
+    /// Byte offset where each line starts
+    line_starts: Vec<usize>,
+    analysis: Analysis,
+}
+
+impl Document {
+    /// Analyzes a new version of a document.
+    pub fn new(text: String) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            line_starts,
+            analysis: Analysis::run(&text),
+            text,
+        }
+    }
+
+    /// Returns the text of the document.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the line and character of a byte offset.
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.text.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let start = self.line_starts[line];
+        (line, self.text[start..offset].encode_utf16().count())
+    }
+
+    /// Returns the byte offset of a line and character, clamped to the text.
+    pub fn offset(&self, line: usize, character: usize) -> usize {
+        let Some(&start) = self.line_starts.get(line) else {
+            return self.text.len();
+        };
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.text.len(), |next| next - 1);
+
+        let mut units = 0;
+        for (i, c) in self.text[start..end].char_indices() {
+            if units >= character {
+                return start + i;
+            }
+            units += c.len_utf16();
+        }
+        end
+    }
+
+    /// Returns the errors and warnings of the document.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.analysis.diagnostics
+    }
+
+    /// Describes what is at `offset`: the signature of a named symbol, or the
+    /// type of the innermost expression. Returns markdown and the span it covers.
+    pub fn hover(&self, offset: usize) -> Option<(String, Span)> {
+        let analysis = &self.analysis;
+        if let Some((name, declaration)) = analysis.symbol_at(offset)
+            && let Some(symbol) = analysis.declaration(name, declaration)
+        {
+            let mut contents = format!("```rive\n{}\n```", analysis.signature(symbol));
+            if let Some(docs) = analysis.docs(declaration) {
+                contents.push_str("\n\n");
+                contents.push_str(docs);
+            }
+            let span = analysis
+                .references
+                .iter()
+                .map(|reference| analysis.name_span(reference.span, &reference.name))
+                .chain(std::iter::once(analysis.name_span(declaration, name)))
+                .find(|span| span.start.offset <= offset && offset <= span.end.offset)
+                .unwrap_or(declaration);
+            return Some((contents, span));
+        }
+
+        let (span, type_id) = analysis.expression_at(offset)?;
+        let type_name = analysis.registry.get_type_name(type_id);
+        Some((format!("```rive\n{type_name}\n```"), span))
+    }
+
+    /// Returns the name span of the declaration of the symbol at `offset`.
+    pub fn definition(&self, offset: usize) -> Option<Span> {
+        let (name, declaration) = self.analysis.symbol_at(offset)?;
+        Some(self.analysis.name_span(declaration, name))
+    }
+
+    /// Returns the name spans of every use of the symbol at `offset`, in
+    /// source order, along with its declaration if asked for.
+    pub fn references(&self, offset: usize, include_declaration: bool) -> Vec<Span> {
+        let analysis = &self.analysis;
+        let Some((name, declaration)) = analysis.symbol_at(offset) else {
+            return Vec::new();
+        };
+
+        let mut spans: Vec<Span> = analysis
+            .references
+            .iter()
+            .filter(|reference| reference.declaration == declaration && reference.name == name)
+            .map(|reference| analysis.name_span(reference.span, name))
+            .collect();
+        if include_declaration {
+            spans.push(analysis.name_span(declaration, name));
+        }
+        spans.sort_by_key(|span| span.start.offset);
+        spans.dedup();
+        spans
+    }
+
+    /// Returns the names that can be written at `offset`.
+    ///
+    /// After a `.`, these are the builtin methods of the value before it.
+    /// Otherwise they are the variables and functions in scope.
+    pub fn completions(&self, offset: usize) -> Vec<Completion> {
+        let offset = offset.min(self.text.len());
+        let before = &self.text[..offset];
+        let word_start = before
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .map_or(0, |i| i + 1);
+
+        // The name being typed usually doesn't check yet, so analyze the
+        // text without it
+        if let Some(receiver_end) = before[..word_start].strip_suffix('.') {
+            let dot = receiver_end.len();
+            let receiver_end = receiver_end.strip_suffix('?').unwrap_or(receiver_end).len();
+            let without_member = format!("{}{}", &self.text[..dot], &self.text[offset..]);
+            return method_completions(&Analysis::run(&without_member), receiver_end);
+        }
+
+        let fallback;
+        let analysis = if self.analysis.program.is_some() {
+            &self.analysis
+        } else {
+            fallback = Analysis::run(&format!(
+                "{}{}",
+                &self.text[..word_start],
+                &self.text[offset..]
+            ));
+            &fallback
+        };
+
+        let mut completions: Vec<Completion> = Vec::new();
+        for symbol in analysis.visible_declarations(word_start) {
+            // Inner declarations shadow outer ones with the same name
+            completions.retain(|completion| completion.label != symbol.name);
+            let (kind, detail) = match &symbol.params {
+                Some(_) => (CompletionKind::Function, analysis.signature(symbol)),
+                None => (
+                    CompletionKind::Variable,
+                    analysis.registry.get_type_name(symbol.symbol_type),
+                ),
+            };
+            completions.push(Completion {
+                label: symbol.name.clone(),
+                kind,
+                detail,
+            });
+        }
+        completions.sort_by(|a, b| a.label.cmp(&b.label));
+        completions
+    }
+
+    /// Returns the top-level declarations of the document.
+    pub fn symbols(&self) -> Vec<DocumentSymbol> {
+        let analysis = &self.analysis;
+        let Some(program) = &analysis.program else {
+            return Vec::new();
+        };
+
+        program
+            .items
+            .iter()
+            .map(|item| {
+                let (name, kind, span, detail) = match item {
+                    Item::Function(function) => {
+                        let detail = analysis
+                            .declaration(&function.name, function.span)
+                            .map(|symbol| analysis.signature(symbol))
+                            .unwrap_or_default();
+                        (&function.name, SymbolKind::Function, function.span, detail)
+                    }
+                    Item::Global(global) => {
+                        let kind = if global.constant {
+                            SymbolKind::Constant
+                        } else {
+                            SymbolKind::Variable
+                        };
+                        let detail = analysis
+                            .declaration(&global.name, global.span)
+                            .map(|symbol| analysis.registry.get_type_name(symbol.symbol_type))
+                            .unwrap_or_default();
+                        (&global.name, kind, global.span, detail)
+                    }
+                    Item::TypeAlias(alias) => {
+                        let detail = analysis.registry.get_type_name(alias.target);
+                        (&alias.name, SymbolKind::TypeAlias, alias.span, detail)
+                    }
+                };
+                DocumentSymbol {
+                    name: name.clone(),
+                    kind,
+                    detail,
+                    span,
+                    name_span: analysis.name_span(span, name),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns the builtin methods of the expression ending at `receiver_end`.
+fn method_completions(analysis: &Analysis, receiver_end: usize) -> Vec<Completion> {
+    let Some(receiver_type) = analysis
+        .expression_types
+        .iter()
+        .filter(|(span, _)| span.end.offset == receiver_end)
+        .max_by_key(|(span, _)| span.end.offset - span.start.offset)
+        .map(|(_, type_id)| *type_id)
+    else {
+        return Vec::new();
+    };
+
+    // `value?.` calls methods of the value inside the nullable type
+    let registry = &analysis.registry;
+    let receiver_type = match &registry.get_type_metadata(receiver_type).kind {
+        TypeKind::Optional { inner } => *inner,
+        _ => receiver_type,
+    };
+
+    registry
+        .methods(receiver_type)
+        .into_iter()
+        .map(|method| {
+            let params = method
+                .parameters
+                .iter()
+                .map(|param| registry.get_type_name(*param))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let return_type = registry.get_type_name(method.return_type);
+            Completion {
+                detail: format!("{}({params}): {return_type}", method.name),
+                label: method.name,
+                kind: CompletionKind::Method,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,33 @@
+//! Language server for Rive.
+//!
+//! The server speaks the language server protocol over any pair of byte
+//! streams, normally the editor's stdin and stdout. Documents are analyzed
+//! with the compiler's parser and [`TypeChecker`](rive_semantic::TypeChecker)
+//! on every change, which provides:
+//!
+//! - diagnostics for lexer, parser and semantic errors and warnings
+//! - hover with the signature of a symbol or the type of an expression
+//! - go-to-definition and find-references for variables and functions
+//! - completion of variables, functions and builtin methods after `.`
+//! - the top-level declarations as document symbols
+//!
+//! # Examples
+//! ```
+//! use rive_lsp::{Json, Server};
+//!
+//! let mut server = Server::new();
+//! let replies = server.handle(&Json::parse(r#"{"jsonrpc": "2.0", "id": 1, "method": "shutdown"}"#).unwrap());
+//! assert_eq!(replies[0].to_string(), r#"{"jsonrpc":"2.0","id":1,"result":null}"#);
+//! ```
+
+mod document;
+mod json;
+mod server;
+mod transport;
+
+pub use document::{
+    Completion, CompletionKind, Diagnostic, Document, DocumentSymbol, Severity, SymbolKind,
+};
+pub use json::{Json, ParseError};
+pub use server::{Server, serve};
+pub use transport::{read_message, write_message};
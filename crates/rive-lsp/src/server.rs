@@ -0,0 +1,335 @@
+//! Handling of language server requests and notifications.
+
+use crate::document::{CompletionKind, Document, Severity, SymbolKind};
+use crate::json::Json;
+use crate::transport::{read_message, write_message};
+use rive_core::Span;
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+/// JSON-RPC error codes used by the server.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Error returned for a request: a JSON-RPC error code and message.
+type RequestError = (i64, String);
+
+/// A language server for Rive documents.
+///
+/// The server is independent of how messages travel: [`Server::handle`]
+/// takes one message and returns the messages to send back, and [`serve`]
+/// connects it to a reader and a writer.
+#[derive(Default)]
+pub struct Server {
+    /// Open documents by URI
+    documents: HashMap<String, Document>,
+    shutdown_requested: bool,
+    exited: bool,
+}
+
+impl Server {
+    /// Creates a server with no open documents.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true once the client has sent the `exit` notification.
+    pub fn exited(&self) -> bool {
+        self.exited
+    }
+
+    /// Handles a message from the client and returns the messages to send back.
+    pub fn handle(&mut self, message: &Json) -> Vec<Json> {
+        let Some(method) = message.get("method").and_then(Json::as_str) else {
+            // Responses to server requests are not expected
+            return Vec::new();
+        };
+        let params = message.get("params").unwrap_or(&Json::Null);
+
+        let Some(id) = message.get("id") else {
+            return self.notification(method, params);
+        };
+        let result = if self.shutdown_requested {
+            Err((INVALID_REQUEST, "The server is shutting down".to_string()))
+        } else {
+            self.request(method, params)
+        };
+        vec![response(id.clone(), result)]
+    }
+
+    fn request(&mut self, method: &str, params: &Json) -> Result<Json, RequestError> {
+        match method {
+            "initialize" => Ok(initialize_result()),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Ok(Json::Null)
+            }
+            "textDocument/hover" => {
+                let (_, document, offset) = self.position(params)?;
+                Ok(document
+                    .hover(offset)
+                    .map_or(Json::Null, |(contents, span)| {
+                        Json::object([
+                            (
+                                "contents",
+                                Json::object([
+                                    ("kind", "markdown".into()),
+                                    ("value", contents.into()),
+                                ]),
+                            ),
+                            ("range", range(document, span)),
+                        ])
+                    }))
+            }
+            "textDocument/definition" => {
+                let (uri, document, offset) = self.position(params)?;
+                Ok(document
+                    .definition(offset)
+                    .map_or(Json::Null, |span| location(uri, document, span)))
+            }
+            "textDocument/references" => {
+                let (uri, document, offset) = self.position(params)?;
+                let include_declaration = params
+                    .path("context/includeDeclaration")
+                    .and_then(Json::as_bool)
+                    .unwrap_or(false);
+                let locations = document
+                    .references(offset, include_declaration)
+                    .into_iter()
+                    .map(|span| location(uri, document, span))
+                    .collect::<Vec<_>>();
+                Ok(locations.into())
+            }
+            "textDocument/completion" => {
+                let (_, document, offset) = self.position(params)?;
+                let items = document
+                    .completions(offset)
+                    .into_iter()
+                    .map(|completion| {
+                        let kind: usize = match completion.kind {
+                            CompletionKind::Method => 2,
+                            CompletionKind::Function => 3,
+                            CompletionKind::Variable => 6,
+                        };
+                        Json::object([
+                            ("label", completion.label.into()),
+                            ("kind", kind.into()),
+                            ("detail", completion.detail.into()),
+                        ])
+                    })
+                    .collect::<Vec<_>>();
+                Ok(items.into())
+            }
+            "textDocument/documentSymbol" => {
+                let (_, document) = self.document(params)?;
+                let symbols = document
+                    .symbols()
+                    .into_iter()
+                    .map(|symbol| {
+                        let kind: usize = match symbol.kind {
+                            SymbolKind::Function => 12,
+                            SymbolKind::Variable => 13,
+                            SymbolKind::Constant => 14,
+                            SymbolKind::TypeAlias => 26,
+                        };
+                        Json::object([
+                            ("name", symbol.name.into()),
+                            ("detail", symbol.detail.into()),
+                            ("kind", kind.into()),
+                            ("range", range(document, symbol.span)),
+                            ("selectionRange", range(document, symbol.name_span)),
+                        ])
+                    })
+                    .collect::<Vec<_>>();
+                Ok(symbols.into())
+            }
+            _ => Err((METHOD_NOT_FOUND, format!("Unknown method '{method}'"))),
+        }
+    }
+
+    fn notification(&mut self, method: &str, params: &Json) -> Vec<Json> {
+        let uri = params.path("textDocument/uri").and_then(Json::as_str);
+        match (method, uri) {
+            ("exit", _) => {
+                self.exited = true;
+                Vec::new()
+            }
+            ("textDocument/didOpen", Some(uri)) => {
+                let text = params.path("textDocument/text").and_then(Json::as_str);
+                self.update(uri, text.unwrap_or_default())
+            }
+            ("textDocument/didChange", Some(uri)) => {
+                // Changes carry the whole text, as asked for in `initialize`
+                let text = params
+                    .get("contentChanges")
+                    .and_then(Json::as_array)
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change.get("text"))
+                    .and_then(Json::as_str);
+                match text {
+                    Some(text) => self.update(uri, text),
+                    None => Vec::new(),
+                }
+            }
+            ("textDocument/didClose", Some(uri)) => {
+                self.documents.remove(uri);
+                vec![publish_diagnostics(uri, Vec::new())]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Analyzes the new text of a document and publishes its diagnostics.
+    fn update(&mut self, uri: &str, text: &str) -> Vec<Json> {
+        let document = Document::new(text.to_string());
+        let diagnostics = document
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| {
+                let severity: usize = match diagnostic.severity {
+                    Severity::Error => 1,
+                    Severity::Warning => 2,
+                };
+                let range = match diagnostic.span {
+                    Some(span) => range(&document, span),
+                    None => range(&document, Span::from_range(0, 0)),
+                };
+                Json::object([
+                    ("range", range),
+                    ("severity", severity.into()),
+                    ("source", "rive".into()),
+                    ("message", diagnostic.message.clone().into()),
+                ])
+            })
+            .collect();
+        self.documents.insert(uri.to_string(), document);
+        vec![publish_diagnostics(uri, diagnostics)]
+    }
+
+    /// Returns the open document named by `params`.
+    fn document<'a>(&self, params: &'a Json) -> Result<(&'a str, &Document), RequestError> {
+        let uri = params
+            .path("textDocument/uri")
+            .and_then(Json::as_str)
+            .ok_or_else(|| (INVALID_PARAMS, "Missing textDocument.uri".to_string()))?;
+        let document = self
+            .documents
+            .get(uri)
+            .ok_or_else(|| (INVALID_PARAMS, format!("Document '{uri}' is not open")))?;
+        Ok((uri, document))
+    }
+
+    /// Returns the open document named by `params` and the offset of its `position`.
+    fn position<'a>(&self, params: &'a Json) -> Result<(&'a str, &Document, usize), RequestError> {
+        let (uri, document) = self.document(params)?;
+        let line = params.path("position/line").and_then(Json::as_u64);
+        let character = params.path("position/character").and_then(Json::as_u64);
+        let (Some(line), Some(character)) = (line, character) else {
+            return Err((INVALID_PARAMS, "Missing position".to_string()));
+        };
+        Ok((
+            uri,
+            document,
+            document.offset(line as usize, character as usize),
+        ))
+    }
+}
+
+/// Runs a server over a pair of streams until the client sends `exit` or
+/// closes its stream.
+///
+/// Returns true if the client asked the server to shut down before leaving,
+/// which is how a well-behaved client ends a session.
+///
+/// # Errors
+/// Returns an error if reading or writing fails.
+pub fn serve(mut reader: impl BufRead, mut writer: impl Write) -> io::Result<bool> {
+    let mut server = Server::new();
+
+    loop {
+        let message = match read_message(&mut reader) {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(error) if error.kind() == io::ErrorKind::InvalidData => {
+                let reply = response(Json::Null, Err((PARSE_ERROR, error.to_string())));
+                write_message(&mut writer, &reply)?;
+                continue;
+            }
+            Err(error) => return Err(error),
+        };
+
+        for reply in server.handle(&message) {
+            write_message(&mut writer, &reply)?;
+        }
+        if server.exited() {
+            break;
+        }
+    }
+
+    Ok(server.shutdown_requested)
+}
+
+fn initialize_result() -> Json {
+    let capabilities = Json::object([
+        // Full text on every change
+        ("textDocumentSync", Json::from(1usize)),
+        ("hoverProvider", true.into()),
+        ("definitionProvider", true.into()),
+        ("referencesProvider", true.into()),
+        (
+            "completionProvider",
+            Json::object([("triggerCharacters", vec![Json::from(".")].into())]),
+        ),
+        ("documentSymbolProvider", true.into()),
+    ]);
+    Json::object([
+        ("capabilities", capabilities),
+        (
+            "serverInfo",
+            Json::object([
+                ("name", "rive-lsp".into()),
+                ("version", env!("CARGO_PKG_VERSION").into()),
+            ]),
+        ),
+    ])
+}
+
+fn response(id: Json, result: Result<Json, RequestError>) -> Json {
+    let outcome = match result {
+        Ok(result) => ("result", result),
+        Err((code, message)) => (
+            "error",
+            Json::object([("code", code.into()), ("message", message.into())]),
+        ),
+    };
+    Json::object([("jsonrpc", "2.0".into()), ("id", id), outcome])
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Json>) -> Json {
+    Json::object([
+        ("jsonrpc", "2.0".into()),
+        ("method", "textDocument/publishDiagnostics".into()),
+        (
+            "params",
+            Json::object([("uri", uri.into()), ("diagnostics", diagnostics.into())]),
+        ),
+    ])
+}
+
+fn position(document: &Document, offset: usize) -> Json {
+    let (line, character) = document.position(offset);
+    Json::object([("line", line.into()), ("character", character.into())])
+}
+
+fn range(document: &Document, span: Span) -> Json {
+    Json::object([
+        ("start", position(document, span.start.offset)),
+        ("end", position(document, span.end.offset)),
+    ])
+}
+
+fn location(uri: &str, document: &Document, span: Span) -> Json {
+    Json::object([("uri", uri.into()), ("range", range(document, span))])
+}
@@ -0,0 +1,72 @@
+//! Framing of JSON-RPC messages over a byte stream.
+//!
+//! Each message is a header block followed by a JSON body:
+//!
+//! ```text
+//! Content-Length: 44\r\n
+//! \r\n
+//! {"jsonrpc":"2.0","id":1,"method":"shutdown"}
+//! ```
+
+use crate::json::Json;
+use std::io::{self, BufRead, Write};
+
+/// Reads the next message, or `None` once the stream has ended.
+///
+/// # Errors
+/// Returns an error if reading fails, a header is malformed, or the body is
+/// not valid JSON.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Json>> {
+    let mut content_length = None;
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return match content_length {
+                None => Ok(None),
+                Some(_) => Err(invalid_data("Stream ended inside a message header")),
+            };
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            // Tolerate blank lines between messages
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(invalid_data(&format!("Malformed header '{line}'")));
+        };
+        if name.eq_ignore_ascii_case("Content-Length") {
+            let length = value
+                .trim()
+                .parse()
+                .map_err(|_| invalid_data(&format!("Invalid Content-Length '{}'", value.trim())))?;
+            content_length = Some(length);
+        }
+    }
+
+    let mut body = vec![0; content_length.unwrap_or_default()];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8(body).map_err(|_| invalid_data("Message is not UTF-8"))?;
+    Json::parse(&body)
+        .map(Some)
+        .map_err(|error| invalid_data(&error.to_string()))
+}
+
+/// Writes a message with its header and flushes the writer.
+///
+/// # Errors
+/// Returns an error if writing fails.
+pub fn write_message(writer: &mut impl Write, message: &Json) -> io::Result<()> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    writer.flush()
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,465 @@
+//! Tests for the Rive language server, driven by a scripted JSON-RPC client.
+
+use rive_lsp::{Json, read_message, serve, write_message};
+use std::io::Cursor;
+
+const URI: &str = "file:///project/src/main.rive";
+
+const SOURCE: &str = r#"/// Adds two numbers
+fun add(a: Int, b: Int): Int {
+    a + b
+}
+
+fun main() {
+    let total = add(1, 2)
+    let mut name = "rive"
+    print(total, name.len())
+}
+"#;
+
+/// Sends `messages` to a server and returns its replies, and whether the
+/// session ended cleanly.
+fn run_session(messages: &[Json]) -> (Vec<Json>, bool) {
+    let mut input = Vec::new();
+    for message in messages {
+        write_message(&mut input, message).unwrap();
+    }
+
+    let mut output = Vec::new();
+    let clean_exit = serve(Cursor::new(input), &mut output).unwrap();
+
+    let mut reader = Cursor::new(output);
+    let mut replies = Vec::new();
+    while let Some(reply) = read_message(&mut reader).unwrap() {
+        replies.push(reply);
+    }
+    (replies, clean_exit)
+}
+
+fn request(id: usize, method: &str, params: Json) -> Json {
+    Json::object([
+        ("jsonrpc", "2.0".into()),
+        ("id", id.into()),
+        ("method", method.into()),
+        ("params", params),
+    ])
+}
+
+fn notification(method: &str, params: Json) -> Json {
+    Json::object([
+        ("jsonrpc", "2.0".into()),
+        ("method", method.into()),
+        ("params", params),
+    ])
+}
+
+fn open(text: &str) -> Json {
+    notification(
+        "textDocument/didOpen",
+        Json::object([(
+            "textDocument",
+            Json::object([
+                ("uri", URI.into()),
+                ("languageId", "rive".into()),
+                ("version", 1usize.into()),
+                ("text", text.into()),
+            ]),
+        )]),
+    )
+}
+
+fn change(text: &str) -> Json {
+    notification(
+        "textDocument/didChange",
+        Json::object([
+            (
+                "textDocument",
+                Json::object([("uri", URI.into()), ("version", 2usize.into())]),
+            ),
+            (
+                "contentChanges",
+                vec![Json::object([("text", text.into())])].into(),
+            ),
+        ]),
+    )
+}
+
+fn at(line: usize, character: usize) -> Json {
+    Json::object([
+        ("textDocument", Json::object([("uri", URI.into())])),
+        (
+            "position",
+            Json::object([("line", line.into()), ("character", character.into())]),
+        ),
+    ])
+}
+
+/// Runs `requests` against `SOURCE` and returns the result of each, by id.
+fn results(text: &str, requests: Vec<Json>) -> Vec<Json> {
+    let mut messages = vec![open(text)];
+    messages.extend(requests);
+    let (replies, _) = run_session(&messages);
+    let mut responses: Vec<_> = replies
+        .into_iter()
+        .filter(|reply| reply.get("id").is_some())
+        .collect();
+    responses.sort_by_key(|reply| reply.get("id").and_then(Json::as_u64));
+    responses
+        .into_iter()
+        .map(|reply| reply.get("result").cloned().unwrap_or(Json::Null))
+        .collect()
+}
+
+/// Returns `(line, character)` of a range's start and end.
+fn range(value: &Json) -> ((u64, u64), (u64, u64)) {
+    let point = |name: &str| {
+        let line = value.path(&format!("{name}/line")).and_then(Json::as_u64);
+        let character = value
+            .path(&format!("{name}/character"))
+            .and_then(Json::as_u64);
+        (line.unwrap(), character.unwrap())
+    };
+    (point("start"), point("end"))
+}
+
+fn labels(items: &Json) -> Vec<&str> {
+    items
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|item| item.get("label").and_then(Json::as_str))
+        .collect()
+}
+
+#[test]
+fn test_json_values() {
+    let text = r#"{"a": [1, -2.5, true, null], "b": "line\n\"quoted\" é 😀", "c": {}}"#;
+    let value = Json::parse(text).unwrap();
+    assert_eq!(value.path("a").and_then(Json::as_array).unwrap().len(), 4);
+    assert_eq!(
+        value.get("b").and_then(Json::as_str),
+        Some("line\n\"quoted\" é 😀")
+    );
+    assert_eq!(Json::parse(&value.to_string()).unwrap(), value);
+    assert_eq!(
+        value.to_string(),
+        r#"{"a":[1,-2.5,true,null],"b":"line\n\"quoted\" é 😀","c":{}}"#
+    );
+
+    for invalid in ["", "{", "[1,]", r#"{"a" 1}"#, "tru", r#""open"#, "1 2"] {
+        assert!(
+            Json::parse(invalid).is_err(),
+            "{invalid:?} should not parse"
+        );
+    }
+}
+
+#[test]
+fn test_initialize_and_shutdown() {
+    let (replies, clean_exit) = run_session(&[
+        request(1, "initialize", Json::object([])),
+        notification("initialized", Json::object([])),
+        request(2, "shutdown", Json::Null),
+        request(3, "textDocument/hover", at(0, 0)),
+        notification("exit", Json::Null),
+        // Nothing after `exit` is read
+        request(4, "initialize", Json::object([])),
+    ]);
+    assert!(clean_exit);
+    assert_eq!(replies.len(), 3);
+
+    let capabilities = replies[0].path("result/capabilities").unwrap();
+    assert_eq!(
+        capabilities.get("textDocumentSync").and_then(Json::as_u64),
+        Some(1)
+    );
+    for provider in [
+        "hoverProvider",
+        "definitionProvider",
+        "referencesProvider",
+        "documentSymbolProvider",
+    ] {
+        assert_eq!(
+            capabilities.get(provider).and_then(Json::as_bool),
+            Some(true)
+        );
+    }
+    assert!(capabilities.get("completionProvider").is_some());
+
+    assert_eq!(
+        replies[1].to_string(),
+        r#"{"jsonrpc":"2.0","id":2,"result":null}"#
+    );
+    // Requests after `shutdown` are invalid
+    assert_eq!(
+        replies[2].path("error/code").map(Json::to_string),
+        Some("-32600".to_string())
+    );
+
+    // Leaving without `shutdown` is not a clean exit
+    let (_, clean_exit) = run_session(&[notification("exit", Json::Null)]);
+    assert!(!clean_exit);
+}
+
+#[test]
+fn test_protocol_errors() {
+    let mut input = Vec::new();
+    input.extend_from_slice(b"Content-Length: 5\r\n\r\n{oops");
+    write_message(&mut input, &request(1, "unknown/method", Json::Null)).unwrap();
+    write_message(&mut input, &request(2, "textDocument/hover", at(0, 0))).unwrap();
+
+    let mut output = Vec::new();
+    serve(Cursor::new(input), &mut output).unwrap();
+    let mut reader = Cursor::new(output);
+    let mut codes = Vec::new();
+    while let Some(reply) = read_message(&mut reader).unwrap() {
+        let error = reply.get("error").unwrap();
+        codes.push(error.get("code").unwrap().to_string());
+    }
+    assert_eq!(codes, ["-32700", "-32601", "-32602"]);
+}
+
+#[test]
+fn test_diagnostics_on_open_and_change() {
+    let (replies, _) = run_session(&[
+        open(SOURCE),
+        change("fun main() {\n    let x: Int = \"text\"\n}\n"),
+        change("fun main() {\n    let y = 1 `\n}\n"),
+        change("fun main() {\n    print(\n}\n"),
+        notification(
+            "textDocument/didClose",
+            Json::object([("textDocument", Json::object([("uri", URI.into())]))]),
+        ),
+    ]);
+    assert_eq!(replies.len(), 5);
+    for reply in &replies {
+        assert_eq!(
+            reply.get("method").and_then(Json::as_str),
+            Some("textDocument/publishDiagnostics")
+        );
+        assert_eq!(reply.path("params/uri").and_then(Json::as_str), Some(URI));
+    }
+    let diagnostics = |i: usize| -> &[Json] {
+        replies[i]
+            .path("params/diagnostics")
+            .and_then(Json::as_array)
+            .unwrap()
+    };
+
+    assert!(diagnostics(0).is_empty());
+
+    let semantic = &diagnostics(1)[0];
+    assert_eq!(semantic.get("severity").and_then(Json::as_u64), Some(1));
+    assert_eq!(semantic.get("source").and_then(Json::as_str), Some("rive"));
+    assert!(
+        semantic
+            .get("message")
+            .and_then(Json::as_str)
+            .unwrap()
+            .contains("type mismatch")
+    );
+    assert_eq!(range(semantic.get("range").unwrap()).0, (1, 4));
+
+    let lexer = &diagnostics(2)[0];
+    assert_eq!(range(lexer.get("range").unwrap()), ((1, 14), (1, 15)));
+
+    let parser = diagnostics(3)[0].get("message").and_then(Json::as_str);
+    assert!(parser.unwrap().starts_with("Parser error"));
+
+    assert!(diagnostics(4).is_empty());
+}
+
+#[test]
+fn test_warnings_are_reported() {
+    let source = "fun main() {\n    let x = 1\n    let r = when x {\n        _ -> 1\n        1 -> 2\n    }\n    print(r)\n}\n";
+    let (replies, _) = run_session(&[open(source)]);
+    let diagnostics = replies[0]
+        .path("params/diagnostics")
+        .and_then(Json::as_array)
+        .unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].get("severity").and_then(Json::as_u64),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_hover() {
+    let results = results(
+        SOURCE,
+        vec![
+            request(1, "textDocument/hover", at(8, 11)),
+            request(2, "textDocument/hover", at(6, 17)),
+            request(3, "textDocument/hover", at(7, 20)),
+            request(4, "textDocument/hover", at(7, 12)),
+            request(5, "textDocument/hover", at(4, 0)),
+        ],
+    );
+    let contents = |i: usize| results[i].path("contents/value").and_then(Json::as_str);
+
+    assert_eq!(contents(0), Some("```rive\nlet total: Int\n```"));
+    assert_eq!(range(results[0].get("range").unwrap()), ((8, 10), (8, 15)));
+
+    let add = contents(1).unwrap();
+    assert!(add.starts_with("```rive\nfun add(a: Int, b: Int): Int\n```"));
+    assert!(add.contains("Adds two numbers"));
+
+    assert_eq!(contents(2), Some("```rive\nText\n```"));
+    assert_eq!(contents(3), Some("```rive\nlet mut name: Text\n```"));
+    assert!(results[4].is_null());
+}
+
+#[test]
+fn test_definition_and_references() {
+    let results = results(
+        SOURCE,
+        vec![
+            request(1, "textDocument/definition", at(6, 18)),
+            request(2, "textDocument/definition", at(2, 8)),
+            request(3, "textDocument/definition", at(1, 5)),
+            request(
+                4,
+                "textDocument/references",
+                Json::object([
+                    ("textDocument", Json::object([("uri", URI.into())])),
+                    (
+                        "position",
+                        Json::object([("line", 8usize.into()), ("character", 12usize.into())]),
+                    ),
+                    (
+                        "context",
+                        Json::object([("includeDeclaration", true.into())]),
+                    ),
+                ]),
+            ),
+            request(5, "textDocument/references", at(1, 5)),
+            request(6, "textDocument/definition", at(8, 4)),
+        ],
+    );
+
+    assert_eq!(results[0].get("uri").and_then(Json::as_str), Some(URI));
+    assert_eq!(range(results[0].get("range").unwrap()), ((1, 4), (1, 7)));
+    assert_eq!(range(results[1].get("range").unwrap()), ((1, 16), (1, 17)));
+    // A declaration is its own definition
+    assert_eq!(range(results[2].get("range").unwrap()), ((1, 4), (1, 7)));
+
+    let ranges = |value: &Json| -> Vec<_> {
+        value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|location| range(location.get("range").unwrap()))
+            .collect()
+    };
+    assert_eq!(ranges(&results[3]), [((6, 8), (6, 13)), ((8, 10), (8, 15))]);
+    assert_eq!(ranges(&results[4]), [((6, 16), (6, 19))]);
+
+    // Builtins have no declaration in the source
+    assert!(results[5].is_null());
+}
+
+#[test]
+fn test_completion() {
+    let results = results(
+        SOURCE,
+        vec![
+            request(1, "textDocument/completion", at(8, 4)),
+            request(2, "textDocument/completion", at(2, 4)),
+        ],
+    );
+
+    assert_eq!(labels(&results[0]), ["add", "main", "name", "total"]);
+    let add = &results[0].as_array().unwrap()[0];
+    assert_eq!(add.get("kind").and_then(Json::as_u64), Some(3));
+    assert_eq!(
+        add.get("detail").and_then(Json::as_str),
+        Some("fun add(a: Int, b: Int): Int")
+    );
+    let total = &results[0].as_array().unwrap()[3];
+    assert_eq!(total.get("kind").and_then(Json::as_u64), Some(6));
+    assert_eq!(total.get("detail").and_then(Json::as_str), Some("Int"));
+
+    // Locals of other functions are not in scope
+    assert_eq!(labels(&results[1]), ["a", "add", "b", "main"]);
+}
+
+#[test]
+fn test_method_completion() {
+    // The member being typed doesn't parse, so it is left out to find the
+    // type of the value before the dot
+    let completions = |statement: &str| {
+        let source = format!(
+            "fun main() {{\n    let name = \"rive\"\n    let items = List(1, 2)\n    let maybe: Text? = null\n    {statement}\n}}\n"
+        );
+        let character = statement.find(')').unwrap_or(statement.len());
+        results(
+            &source,
+            vec![request(1, "textDocument/completion", at(4, 4 + character))],
+        )
+        .remove(0)
+    };
+
+    let text = completions("print(name.to)");
+    let text_methods = labels(&text);
+    assert!(text_methods.contains(&"to_upper"));
+    assert!(text_methods.contains(&"len"));
+    assert!(!text_methods.contains(&"append"));
+    let replace = text
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item.get("label").and_then(Json::as_str) == Some("replace"))
+        .unwrap();
+    assert_eq!(replace.get("kind").and_then(Json::as_u64), Some(2));
+    assert_eq!(
+        replace.get("detail").and_then(Json::as_str),
+        Some("replace(Text, Text): Text")
+    );
+
+    let list = completions("items.");
+    assert!(labels(&list).contains(&"append"));
+    assert!(!labels(&list).contains(&"to_upper"));
+
+    // Safe calls complete the methods of the value inside the nullable type
+    assert_eq!(labels(&completions("print(maybe?.)")), text_methods);
+}
+
+#[test]
+fn test_document_symbols() {
+    let source = "type Names = List<Text>\nconst LIMIT = 10\nlet count = 0\n\nfun main() {\n    print(LIMIT, count)\n}\n";
+    let results = results(
+        source,
+        vec![request(
+            1,
+            "textDocument/documentSymbol",
+            Json::object([("textDocument", Json::object([("uri", URI.into())]))]),
+        )],
+    );
+
+    let symbols = results[0].as_array().unwrap();
+    let summary: Vec<_> = symbols
+        .iter()
+        .map(|symbol| {
+            (
+                symbol.get("name").and_then(Json::as_str).unwrap(),
+                symbol.get("kind").and_then(Json::as_u64).unwrap(),
+                symbol.get("detail").and_then(Json::as_str).unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("Names", 26, "List<Text>"),
+            ("LIMIT", 14, "Int"),
+            ("count", 13, "Int"),
+            ("main", 12, "fun main()"),
+        ]
+    );
+    assert_eq!(
+        range(symbols[3].get("selectionRange").unwrap()),
+        ((4, 4), (4, 8))
+    );
+    assert_eq!(range(symbols[3].get("range").unwrap()).0, (4, 0));
+}
@@ -13,6 +13,37 @@ pub struct Program {
 #[derive(Debug, Clone, PartialEq)]
 pub enum Item {
     Function(Function),
+    Global(Global),
+    TypeAlias(TypeAlias),
+}
+
+/// Type alias: `type Name = T` or the distinct `newtype Name = T`.
+///
+/// The alias is registered in the type registry while parsing, so later
+/// annotations already refer to `type_id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAlias {
+    pub name: String,
+    pub type_id: TypeId,
+    pub target: TypeId,
+    /// Declared with `newtype`, so it is not interchangeable with its target
+    pub distinct: bool,
+    /// Text of the `///` comments written before the declaration
+    pub docs: Option<String>,
+    pub span: Span,
+}
+
+/// Module-level variable: `let name[: type] = expr` or `const name[: type] = expr`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Global {
+    pub name: String,
+    /// Declared with `const`, so the initializer must be a constant expression
+    pub constant: bool,
+    pub var_type: Option<TypeId>,
+    pub initializer: Expression,
+    /// Text of the `///` comments written before the declaration
+    pub docs: Option<String>,
+    pub span: Span,
 }
 
 /// Function declaration.
@@ -22,6 +53,20 @@ pub struct Function {
     pub params: Vec<Parameter>,
     pub return_type: TypeId,
     pub body: FunctionBody,
+    /// Text of the `///` comments written before the function
+    pub docs: Option<String>,
+    /// Set when the function is annotated with `@test`
+    pub test: Option<TestAttribute>,
+    pub span: Span,
+}
+
+/// `@test` attribute, which marks a function as a test run by `rive test`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestAttribute {
+    /// Written `@test(panics)`: the test passes only if it panics
+    pub should_panic: bool,
+    /// Text the panic message must contain: `@test(panics = "overflow")`
+    pub expected_message: Option<String>,
     pub span: Span,
 }
 
@@ -39,6 +84,16 @@ pub enum FunctionBody {
 pub struct Parameter {
     pub name: String,
     pub param_type: TypeId,
+    /// Value used when a call leaves the parameter out: `port: Int = 8080`
+    pub default: Option<Expression>,
+    pub span: Span,
+}
+
+/// Argument passed by parameter name: `port = 9000`
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedArgument {
+    pub name: String,
+    pub value: Expression,
     pub span: Span,
 }
 
@@ -63,6 +118,13 @@ pub enum Statement {
         span: Span,
     },
 
+    /// Destructuring declaration: `let (a, (mut b, _)) = expr`
+    Destructure {
+        pattern: BindingPattern,
+        initializer: Expression,
+        span: Span,
+    },
+
     /// Constant declaration: `const name[?] [: type[?]] = expr`
     Const {
         name: String,
@@ -80,6 +142,14 @@ pub enum Statement {
         span: Span,
     },
 
+    /// Compound assignment statement: `name += expr`, `name -= expr`, ...
+    CompoundAssignment {
+        name: String,
+        operator: BinaryOperator,
+        value: Expression,
+        span: Span,
+    },
+
     /// Expression statement
     Expression { expression: Expression, span: Span },
 
@@ -94,6 +164,55 @@ pub enum Statement {
 
     /// Continue statement: `continue [depth]`
     Continue(crate::control_flow::Continue),
+
+    /// Local function declaration, visible in the rest of its block
+    Function(Box<Function>),
+}
+
+impl Statement {
+    /// Returns the span of this statement.
+    #[must_use]
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Let { span, .. }
+            | Self::Destructure { span, .. }
+            | Self::Const { span, .. }
+            | Self::Assignment { span, .. }
+            | Self::CompoundAssignment { span, .. }
+            | Self::Expression { span, .. }
+            | Self::Return { span, .. } => *span,
+            Self::Break(break_stmt) => break_stmt.span,
+            Self::Continue(continue_stmt) => continue_stmt.span,
+            Self::Function(func) => func.span,
+        }
+    }
+}
+
+/// Binding pattern on the left of a destructuring `let` or in a `for` loop.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BindingPattern {
+    /// Binds a value to a name: `name` or `mut name`
+    Name {
+        name: String,
+        mutable: bool,
+        span: Span,
+    },
+    /// Ignores a value: `_`
+    Wildcard { span: Span },
+    /// Destructures a tuple: `(a, (b, _))`
+    Tuple {
+        elements: Vec<BindingPattern>,
+        span: Span,
+    },
+}
+
+impl BindingPattern {
+    /// Returns the span of this pattern.
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Name { span, .. } | Self::Wildcard { span } | Self::Tuple { span, .. } => *span,
+        }
+    }
 }
 
 /// Expressions in Rive.
@@ -132,10 +251,11 @@ pub enum Expression {
         span: Span,
     },
 
-    /// Function call: `name(args...)`
+    /// Function call: `name(args..., param = value...)`
     Call {
         callee: String,
         arguments: Vec<Expression>,
+        named_arguments: Vec<NamedArgument>,
         span: Span,
     },
 
@@ -157,6 +277,23 @@ pub enum Expression {
     /// Infinite loop: `loop { ... }`
     Loop(Box<crate::control_flow::Loop>),
 
+    /// Subject-less when: `when { cond -> expr, else -> expr }`
+    When(Box<crate::control_flow::When>),
+
+    /// Return in expression position: `x ?: return`
+    ///
+    /// Diverges, so its type is `Never`.
+    Return {
+        value: Option<Box<Expression>>,
+        span: Span,
+    },
+
+    /// Break in expression position: `when x { 0 -> break }`
+    Break(Box<crate::control_flow::Break>),
+
+    /// Continue in expression position: `x ?: continue`
+    Continue(crate::control_flow::Continue),
+
     /// Match expression: `match expr { pattern -> expr, ... }`
     Match(Box<crate::control_flow::Match>),
 
@@ -184,6 +321,26 @@ pub enum Expression {
         span: Span,
     },
 
+    /// Non-null assertion: `value!!`
+    ///
+    /// Evaluates to the unwrapped value, panicking at runtime if `value` is null.
+    NonNullAssert { value: Box<Expression>, span: Span },
+
+    /// Error propagation: `value?`
+    ///
+    /// Unwraps an `ok` result, or returns the `err` from the enclosing function.
+    Propagate { value: Box<Expression>, span: Span },
+
+    /// Null binding condition: `let name = value`
+    ///
+    /// Only valid as an `if`/`while` condition; binds the non-null value of
+    /// `value` to `name` inside the guarded block.
+    LetBinding {
+        name: String,
+        value: Box<Expression>,
+        span: Span,
+    },
+
     /// Tuple literal: `(a, b, c)` or `(a,)` for single element
     Tuple {
         elements: Vec<Expression>,
@@ -219,6 +376,18 @@ pub enum Expression {
 }
 
 impl Expression {
+    /// Returns true if this expression has a value when it ends a block:
+    /// an `if` or `when` without an `else` and the `while`/`for` loops don't.
+    #[must_use]
+    pub fn produces_value(&self) -> bool {
+        match self {
+            Self::If(if_expr) => if_expr.else_block.is_some(),
+            Self::When(when) => when.else_arm.is_some(),
+            Self::While(_) | Self::For(_) => false,
+            _ => true,
+        }
+    }
+
     /// Returns the span of this expression.
     #[must_use]
     pub fn span(&self) -> Span {
@@ -237,11 +406,18 @@ impl Expression {
             Self::While(expr) => expr.span,
             Self::For(expr) => expr.span,
             Self::Loop(expr) => expr.span,
+            Self::When(expr) => expr.span,
+            Self::Return { span, .. } => *span,
+            Self::Break(expr) => expr.span,
+            Self::Continue(expr) => expr.span,
             Self::Match(expr) => expr.span,
             Self::Range(expr) => expr.span,
             Self::Block(block) => block.span,
             Self::Elvis { span, .. } => *span,
             Self::SafeCall { span, .. } => *span,
+            Self::NonNullAssert { span, .. } => *span,
+            Self::Propagate { span, .. } => *span,
+            Self::LetBinding { span, .. } => *span,
             Self::Tuple { span, .. } => *span,
             Self::List { span, .. } => *span,
             Self::Dict { span, .. } => *span,
@@ -249,6 +425,31 @@ impl Expression {
             Self::FieldAccess { span, .. } => *span,
         }
     }
+
+    /// Returns true if this expression only combines literals and names
+    /// for which `is_constant_name` holds using operators.
+    #[must_use]
+    pub fn is_constant(&self, is_constant_name: &dyn Fn(&str) -> bool) -> bool {
+        match self {
+            Self::Integer { .. }
+            | Self::Float { .. }
+            | Self::String { .. }
+            | Self::Boolean { .. }
+            | Self::Null { .. } => true,
+            Self::Variable { name, .. } => is_constant_name(name),
+            Self::Unary { operand, .. } => operand.is_constant(is_constant_name),
+            Self::Binary { left, right, .. } => {
+                left.is_constant(is_constant_name) && right.is_constant(is_constant_name)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Returns true if `callee` names a builtin result constructor (`ok` or `err`).
+#[must_use]
+pub fn is_result_constructor(callee: &str) -> bool {
+    matches!(callee, "ok" | "err")
 }
 
 /// Binary operators.
@@ -272,6 +473,13 @@ pub enum BinaryOperator {
     // Logical
     And,
     Or,
+
+    // Bitwise (Int only)
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
 }
 
 /// Unary operators.
@@ -279,4 +487,6 @@ pub enum BinaryOperator {
 pub enum UnaryOperator {
     Negate,
     Not,
+    /// Bitwise complement: `~x` (Int only)
+    BitNot,
 }
@@ -64,8 +64,8 @@ pub struct While {
 pub struct For {
     /// Optional label for this loop
     pub label: Option<String>,
-    /// Iterator variable name
-    pub variable: String,
+    /// Iterator variable (a name or a destructuring pattern)
+    pub pattern: crate::ast::BindingPattern,
     /// Range/iterable expression
     pub iterable: Box<Expression>,
     /// Loop body
@@ -134,16 +134,55 @@ pub struct Match {
 pub struct MatchArm {
     /// Pattern to match against
     pub pattern: Pattern,
+    /// Optional guard condition (`pattern if condition -> ...`)
+    pub guard: Option<Box<Expression>>,
     /// Expression to evaluate if pattern matches
     pub body: Box<Expression>,
     /// Source span
     pub span: Span,
 }
 
+impl MatchArm {
+    /// Returns true if this arm catches every `null` that reaches it, so a
+    /// binding in a later arm only sees non-null values.
+    #[must_use]
+    pub fn covers_null(&self) -> bool {
+        self.guard.is_none() && self.pattern.matches_null()
+    }
+}
+
+/// Subject-less when expression - a chain of conditions.
+///
+/// `when { x < 0 -> "neg", x == 0 -> "zero", else -> "pos" }`
+///
+/// Arms are tried in order, like `if`/`else if`/`else`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct When {
+    /// Condition arms, tried in order
+    pub arms: Vec<WhenArm>,
+    /// Optional `else` arm, taken when no condition holds
+    pub else_arm: Option<Box<Expression>>,
+    /// Source span
+    pub span: Span,
+}
+
+/// A single condition arm in a subject-less when expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhenArm {
+    /// Boolean condition
+    pub condition: Box<Expression>,
+    /// Expression to evaluate if the condition holds
+    pub body: Box<Expression>,
+    /// Source span
+    pub span: Span,
+}
+
 /// Patterns for match expressions.
 ///
-/// Phase 1: Simple literal patterns and wildcard
-/// Future: Destructuring, guards, ranges, etc.
+/// Literal, range and tuple patterns may also be matched against a nullable
+/// value, in which case they only match non-null values. A binding matches
+/// null too, unless an earlier arm already caught it (see
+/// [`MatchArm::covers_null`]); it then binds the non-null value.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Pattern {
     /// Integer literal pattern: `42`
@@ -171,10 +210,24 @@ pub enum Pattern {
         inclusive: bool,
         span: Span,
     },
-    // Future patterns (not implemented yet):
-    // Variable { name: String, span: Span },
-    // Tuple { elements: Vec<Pattern>, span: Span },
-    // Range { start: i64, end: i64, inclusive: bool, span: Span },
+
+    /// Result success pattern: `ok(name)` or `ok(_)`
+    Ok { binding: Option<String>, span: Span },
+
+    /// Result failure pattern: `err(name)` or `err(_)`
+    Err { binding: Option<String>, span: Span },
+
+    /// Binding pattern: `name` (matches anything and binds it)
+    Binding { name: String, span: Span },
+
+    /// Tuple pattern: `(a, 0, _)`
+    Tuple { elements: Vec<Pattern>, span: Span },
+
+    /// Or-pattern: `1 | 2 | 3` (matches if any alternative matches)
+    Or {
+        alternatives: Vec<Pattern>,
+        span: Span,
+    },
 }
 
 impl Pattern {
@@ -188,7 +241,22 @@ impl Pattern {
             | Self::Boolean { span, .. }
             | Self::Null { span }
             | Self::Wildcard { span }
-            | Self::Range { span, .. } => *span,
+            | Self::Range { span, .. }
+            | Self::Ok { span, .. }
+            | Self::Err { span, .. }
+            | Self::Binding { span, .. }
+            | Self::Tuple { span, .. }
+            | Self::Or { span, .. } => *span,
+        }
+    }
+
+    /// Returns true if this pattern matches `null`.
+    #[must_use]
+    pub fn matches_null(&self) -> bool {
+        match self {
+            Self::Null { .. } | Self::Wildcard { .. } | Self::Binding { .. } => true,
+            Self::Or { alternatives, .. } => alternatives.iter().any(Self::matches_null),
+            _ => false,
         }
     }
 }
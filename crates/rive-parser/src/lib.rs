@@ -4,16 +4,19 @@
 
 pub mod ast;
 pub mod control_flow;
+pub mod tree;
 
 mod parsing;
 
 pub use ast::{
-    BinaryOperator, Block, Expression, Function, Item, Parameter, Program, Statement, UnaryOperator,
+    BinaryOperator, BindingPattern, Block, Expression, Function, Global, Item, NamedArgument,
+    Parameter, Program, Statement, TestAttribute, TypeAlias, UnaryOperator,
 };
 pub use control_flow::{
-    Break, Continue, ElseIf, For, If, Loop, Match, MatchArm, Pattern, Range, While,
+    Break, Continue, ElseIf, For, If, Loop, Match, MatchArm, Pattern, Range, When, WhenArm, While,
 };
 pub use parsing::Parser;
+pub use tree::{SyntaxNode, syntax_tree};
 
 use rive_core::type_system::TypeRegistry;
 use rive_core::{Result, Span};
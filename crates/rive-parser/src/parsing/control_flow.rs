@@ -4,7 +4,8 @@ use super::parser::Parser;
 use crate::{
     ast::Expression,
     control_flow::{
-        Break, Continue, ElseIf, For, If, Loop, Match, MatchArm, Pattern, Range, While,
+        Break, Continue, ElseIf, For, If, Loop, Match, MatchArm, Pattern, Range, When, WhenArm,
+        While,
     },
 };
 use rive_core::{Error, Result};
@@ -73,7 +74,7 @@ impl<'a> Parser<'a> {
     pub(crate) fn parse_for(&mut self, label: Option<String>) -> Result<For> {
         let start = self.expect(&TokenKind::For)?;
 
-        let variable = self.expect_identifier()?;
+        let pattern = self.parse_binding_pattern()?;
         self.expect(&TokenKind::In)?;
         let iterable = Box::new(self.parse_expression()?);
 
@@ -82,7 +83,7 @@ impl<'a> Parser<'a> {
 
         Ok(For {
             label,
-            variable,
+            pattern,
             iterable,
             body,
             span: start.merge(end),
@@ -112,9 +113,7 @@ impl<'a> Parser<'a> {
         while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
             let arm = self.parse_match_arm()?;
             arms.push(arm);
-
-            // Optional comma
-            self.match_token(&TokenKind::Comma);
+            self.skip_arm_separator();
         }
 
         let end = self.expect(&TokenKind::RightBrace)?;
@@ -133,28 +132,112 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parses a subject-less when expression: `when { cond -> expr, else -> expr }`.
+    pub(crate) fn parse_when(&mut self) -> Result<When> {
+        let start = self.expect(&TokenKind::When)?;
+        self.expect(&TokenKind::LeftBrace)?;
+
+        let mut arms = Vec::new();
+        let mut else_arm = None;
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if else_arm.is_some() {
+                return Err(Error::Parser(
+                    "The 'else' arm must be the last arm of a when".to_string(),
+                    self.current_span(),
+                ));
+            }
+
+            if self.match_token(&TokenKind::Else) {
+                self.expect(&TokenKind::Arrow)?;
+                else_arm = Some(Box::new(self.parse_arm_body()?));
+            } else {
+                let condition = self.parse_expression()?;
+                self.expect(&TokenKind::Arrow)?;
+                let body = self.parse_arm_body()?;
+                let span = condition.span().merge(body.span());
+                arms.push(WhenArm {
+                    condition: Box::new(condition),
+                    body: Box::new(body),
+                    span,
+                });
+            }
+            self.skip_arm_separator();
+        }
+
+        let end = self.expect(&TokenKind::RightBrace)?;
+
+        if arms.is_empty() {
+            return Err(Error::Parser(
+                "When expression must have at least one condition arm".to_string(),
+                start.merge(end),
+            ));
+        }
+
+        Ok(When {
+            arms,
+            else_arm,
+            span: start.merge(end),
+        })
+    }
+
+    /// Skips an optional `,` or `;` between when arms.
+    fn skip_arm_separator(&mut self) {
+        if !self.match_token(&TokenKind::Comma) {
+            self.match_token(&TokenKind::Semicolon);
+        }
+    }
+
+    /// Parses the body of a when arm: a block or a single expression.
+    fn parse_arm_body(&mut self) -> Result<Expression> {
+        if self.check(&TokenKind::LeftBrace) {
+            Ok(Expression::Block(Box::new(self.parse_block()?)))
+        } else {
+            self.parse_arm_expression()
+        }
+    }
+
     /// Parses a single match arm.
     fn parse_match_arm(&mut self) -> Result<MatchArm> {
         let arm_start = self.peek().1;
         let pattern = self.parse_pattern()?;
-        self.expect(&TokenKind::Arrow)?;
-
-        let body = if self.check(&TokenKind::LeftBrace) {
-            Expression::Block(Box::new(self.parse_block()?))
+        let guard = if self.match_token(&TokenKind::If) {
+            Some(Box::new(self.parse_expression()?))
         } else {
-            self.parse_expression()?
+            None
         };
+        self.expect(&TokenKind::Arrow)?;
+
+        let body = self.parse_arm_body()?;
         let arm_end = body.span();
 
         Ok(MatchArm {
             pattern,
+            guard,
             body: Box::new(body),
             span: arm_start.merge(arm_end),
         })
     }
 
-    /// Parses a pattern for match expressions.
+    /// Parses a pattern for match expressions, including `|` alternatives.
     pub(crate) fn parse_pattern(&mut self) -> Result<Pattern> {
+        let first = self.parse_single_pattern()?;
+        if !self.check(&TokenKind::Pipe) {
+            return Ok(first);
+        }
+
+        let mut alternatives = vec![first];
+        while self.match_token(&TokenKind::Pipe) {
+            alternatives.push(self.parse_single_pattern()?);
+        }
+
+        let span = alternatives[0]
+            .span()
+            .merge(alternatives[alternatives.len() - 1].span());
+        Ok(Pattern::Or { alternatives, span })
+    }
+
+    /// Parses a pattern without `|` alternatives.
+    fn parse_single_pattern(&mut self) -> Result<Pattern> {
         let token = self.peek();
         let span = token.1;
 
@@ -163,6 +246,19 @@ impl<'a> Parser<'a> {
                 self.advance();
                 Ok(Pattern::Wildcard { span })
             }
+            TokenKind::Null => {
+                self.advance();
+                Ok(Pattern::Null { span })
+            }
+            TokenKind::LeftParen => self.parse_tuple_pattern(span),
+            TokenKind::Minus => match self.parse_negative_literal()? {
+                Some(Expression::Integer { value, span }) => Ok(Pattern::Integer { value, span }),
+                Some(Expression::Float { value, span }) => Ok(Pattern::Float { value, span }),
+                _ => Err(Error::Parser(
+                    "Expected a number after '-' in pattern".to_string(),
+                    span,
+                )),
+            },
             TokenKind::Integer => {
                 let value = self.parse_i64_literal()?;
                 Ok(Pattern::Integer { value, span })
@@ -181,20 +277,63 @@ impl<'a> Parser<'a> {
                 Ok(Pattern::Boolean { value, span })
             }
             TokenKind::In => self.parse_range_pattern(span),
+            TokenKind::Identifier
+                if (token.0.text == "ok" || token.0.text == "err")
+                    && self.check_ahead(1, &TokenKind::LeftParen) =>
+            {
+                self.parse_result_pattern(span)
+            }
+            TokenKind::Identifier => {
+                let name = token.0.text.clone();
+                self.advance();
+                Ok(Pattern::Binding { name, span })
+            }
             _ => Err(Error::Parser(
-                "Expected pattern (literal, '_', or 'in range')".to_string(),
+                "Expected pattern (literal, name, '_', 'null', tuple, 'in range', 'ok(..)' or 'err(..)')"
+                    .to_string(),
                 span,
             )),
         }
     }
 
+    /// Parses a tuple pattern: `(a, b)`, `(a,)`, or a parenthesized pattern `(a)`.
+    fn parse_tuple_pattern(&mut self, span: rive_core::Span) -> Result<Pattern> {
+        self.advance(); // consume '('
+
+        let mut elements = Vec::new();
+        let mut trailing_comma = false;
+        while !self.check(&TokenKind::RightParen) && !self.is_at_end() {
+            elements.push(self.parse_pattern()?);
+            trailing_comma = self.match_token(&TokenKind::Comma);
+            if !trailing_comma {
+                break;
+            }
+        }
+        let end = self.expect(&TokenKind::RightParen)?;
+
+        if elements.is_empty() {
+            return Err(Error::Parser(
+                "Empty tuple pattern".to_string(),
+                span.merge(end),
+            ));
+        }
+        if elements.len() == 1 && !trailing_comma {
+            return Ok(elements.remove(0));
+        }
+
+        Ok(Pattern::Tuple {
+            elements,
+            span: span.merge(end),
+        })
+    }
+
     /// Parses a range pattern: `in start..end` or `in start..=end`.
     fn parse_range_pattern(&mut self, span: rive_core::Span) -> Result<Pattern> {
         self.advance(); // consume 'in'
 
-        let start = Box::new(self.parse_primary()?);
+        let start = Box::new(self.parse_range_bound()?);
         let inclusive = self.parse_range_operator()?;
-        let end = Box::new(self.parse_primary()?);
+        let end = Box::new(self.parse_range_bound()?);
         let end_span = end.span();
 
         Ok(Pattern::Range {
@@ -205,6 +344,36 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parses a bound of a range pattern: a primary expression, optionally negated.
+    fn parse_range_bound(&mut self) -> Result<Expression> {
+        if self.check(&TokenKind::Minus) {
+            self.parse_unary()
+        } else {
+            self.parse_primary()
+        }
+    }
+
+    /// Parses a result pattern: `ok(name)`, `err(name)`, `ok(_)` or `err(_)`.
+    fn parse_result_pattern(&mut self, span: rive_core::Span) -> Result<Pattern> {
+        let is_ok = self.peek().0.text == "ok";
+        self.advance(); // consume 'ok' / 'err'
+
+        self.expect(&TokenKind::LeftParen)?;
+        let binding = if self.match_token(&TokenKind::Underscore) {
+            None
+        } else {
+            Some(self.expect_identifier()?)
+        };
+        let end = self.expect(&TokenKind::RightParen)?;
+
+        let span = span.merge(end);
+        Ok(if is_ok {
+            Pattern::Ok { binding, span }
+        } else {
+            Pattern::Err { binding, span }
+        })
+    }
+
     /// Parses a range expression: `start..end` or `start..=end`.
     pub(crate) fn parse_range(&mut self, start: Expression) -> Result<Range> {
         let start_span = start.span();
@@ -1,7 +1,7 @@
 //! Expression parsing with operator precedence.
 
 use super::parser::Parser;
-use crate::ast::{BinaryOperator, Expression, UnaryOperator};
+use crate::ast::{BinaryOperator, Expression, NamedArgument, UnaryOperator};
 use rive_core::{Error, Result};
 use rive_lexer::TokenKind;
 
@@ -104,7 +104,7 @@ impl<'a> Parser<'a> {
 
     /// Parses comparison expression (<, <=, >, >=).
     fn parse_comparison(&mut self) -> Result<Expression> {
-        let mut expr = self.parse_term()?;
+        let mut expr = self.parse_bit_or()?;
 
         while let Some(op_kind) = self.match_tokens(&[
             TokenKind::Less,
@@ -119,7 +119,65 @@ impl<'a> Parser<'a> {
                 TokenKind::GreaterEqual => BinaryOperator::GreaterEqual,
                 _ => unreachable!(),
             };
-            let right = self.parse_term()?;
+            let right = self.parse_bit_or()?;
+            let span = expr.span().merge(right.span());
+            expr = Expression::Binary {
+                left: Box::new(expr),
+                operator,
+                right: Box::new(right),
+                span,
+            };
+        }
+
+        Ok(expr)
+    }
+
+    /// Parses bitwise OR expression (|).
+    fn parse_bit_or(&mut self) -> Result<Expression> {
+        self.parse_binary_level(
+            &[(TokenKind::Pipe, BinaryOperator::BitOr)],
+            Self::parse_bit_xor,
+        )
+    }
+
+    /// Parses bitwise XOR expression (^).
+    fn parse_bit_xor(&mut self) -> Result<Expression> {
+        self.parse_binary_level(
+            &[(TokenKind::Caret, BinaryOperator::BitXor)],
+            Self::parse_bit_and,
+        )
+    }
+
+    /// Parses bitwise AND expression (&).
+    fn parse_bit_and(&mut self) -> Result<Expression> {
+        self.parse_binary_level(
+            &[(TokenKind::Ampersand, BinaryOperator::BitAnd)],
+            Self::parse_shift,
+        )
+    }
+
+    /// Parses shift expression (<<, >>).
+    fn parse_shift(&mut self) -> Result<Expression> {
+        self.parse_binary_level(
+            &[
+                (TokenKind::LessLess, BinaryOperator::ShiftLeft),
+                (TokenKind::GreaterGreater, BinaryOperator::ShiftRight),
+            ],
+            Self::parse_term,
+        )
+    }
+
+    /// Parses a left-associative binary level whose operands are parsed by `operand`.
+    fn parse_binary_level(
+        &mut self,
+        operators: &[(TokenKind, BinaryOperator)],
+        operand: fn(&mut Self) -> Result<Expression>,
+    ) -> Result<Expression> {
+        let mut expr = operand(self)?;
+
+        while let Some(&(_, operator)) = operators.iter().find(|(kind, _)| self.check(kind)) {
+            self.advance();
+            let right = operand(self)?;
             let span = expr.span().merge(right.span());
             expr = Expression::Binary {
                 left: Box::new(expr),
@@ -133,10 +191,20 @@ impl<'a> Parser<'a> {
     }
 
     /// Parses addition/subtraction expression.
+    ///
+    /// Like a `(`, a `-` at the start of a line begins a new statement or
+    /// `when` arm, not a subtraction, unless it is inside parentheses,
+    /// brackets or a map literal.
     fn parse_term(&mut self) -> Result<Expression> {
         let mut expr = self.parse_range_expr()?;
 
-        while let Some(op_kind) = self.match_tokens(&[TokenKind::Plus, TokenKind::Minus]) {
+        loop {
+            if self.minus_starts_next_statement() {
+                break;
+            }
+            let Some(op_kind) = self.match_tokens(&[TokenKind::Plus, TokenKind::Minus]) else {
+                break;
+            };
             let operator = match op_kind {
                 TokenKind::Plus => BinaryOperator::Add,
                 TokenKind::Minus => BinaryOperator::Subtract,
@@ -194,13 +262,38 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
-    /// Parses unary expression (-, !).
-    fn parse_unary(&mut self) -> Result<Expression> {
-        if let Some(op_kind) = self.match_tokens(&[TokenKind::Minus, TokenKind::Bang]) {
+    /// Parses unary expression (-, !, ~).
+    pub(crate) fn parse_unary(&mut self) -> Result<Expression> {
+        // `!!x` in prefix position is a double negation, not a null assertion
+        if self.match_token(&TokenKind::BangBang) {
+            let start_span = self.previous_span();
+            let operand = self.parse_unary()?;
+            let span = start_span.merge(operand.span());
+            let inner = Expression::Unary {
+                operator: UnaryOperator::Not,
+                operand: Box::new(operand),
+                span,
+            };
+            return Ok(Expression::Unary {
+                operator: UnaryOperator::Not,
+                operand: Box::new(inner),
+                span,
+            });
+        }
+
+        // `-` directly before a numeric literal is part of the literal
+        if let Some(literal) = self.parse_negative_literal()? {
+            return Ok(literal);
+        }
+
+        if let Some(op_kind) =
+            self.match_tokens(&[TokenKind::Minus, TokenKind::Bang, TokenKind::Tilde])
+        {
             let start_span = self.previous_span();
             let operator = match op_kind {
                 TokenKind::Minus => UnaryOperator::Negate,
                 TokenKind::Bang => UnaryOperator::Not,
+                TokenKind::Tilde => UnaryOperator::BitNot,
                 _ => unreachable!(),
             };
             let operand = self.parse_unary()?;
@@ -222,15 +315,21 @@ impl<'a> Parser<'a> {
     /// - Method calls: `obj.method()`
     /// - Field access: `obj.field` or `tuple.0`
     /// - Safe calls: `obj?.method()`
+    /// - Non-null assertions: `obj!!`
+    /// - Error propagation: `obj?`
     /// - List constructor: `List(args...)`
     fn parse_call(&mut self) -> Result<Expression> {
         let mut expr = self.parse_primary()?;
 
         loop {
-            if self.check(&TokenKind::LeftParen) {
+            // A `(` on a new line starts a new statement or pattern, not a call
+            if self.check(&TokenKind::LeftParen)
+                && self.current_span().start.line == self.previous_span().end.line
+            {
                 // Regular function call or List constructor
                 self.advance();
-                let arguments = self.parse_argument_list()?;
+                let (arguments, named_arguments) =
+                    self.with_delimiters(true, Self::parse_call_arguments)?;
                 let end_span = self.expect(&TokenKind::RightParen)?;
 
                 if let Expression::Variable { name, .. } = &expr {
@@ -238,6 +337,12 @@ impl<'a> Parser<'a> {
 
                     // Special handling for List constructor
                     if name == "List" {
+                        if let Some(named) = named_arguments.first() {
+                            return Err(Error::Parser(
+                                "List elements cannot be named".to_string(),
+                                named.span,
+                            ));
+                        }
                         expr = Expression::List {
                             elements: arguments,
                             span,
@@ -246,6 +351,7 @@ impl<'a> Parser<'a> {
                         expr = Expression::Call {
                             callee: name.clone(),
                             arguments,
+                            named_arguments,
                             span,
                         };
                     }
@@ -281,7 +387,7 @@ impl<'a> Parser<'a> {
                 // Check if it's a method call (followed by `(`)
                 if self.check(&TokenKind::LeftParen) {
                     self.advance();
-                    let arguments = self.parse_argument_list()?;
+                    let arguments = self.with_delimiters(true, Self::parse_argument_list)?;
                     let end_span = self.expect(&TokenKind::RightParen)?;
                     let span = expr.span().merge(end_span);
 
@@ -316,6 +422,21 @@ impl<'a> Parser<'a> {
                     call: Box::new(call_expr),
                     span,
                 };
+            } else if self.check(&TokenKind::Question) && !self.check_ahead(1, &TokenKind::Colon) {
+                // Error propagation `?` (but not Elvis `?:`)
+                self.advance();
+                let span = expr.span().merge(self.previous_span());
+                expr = Expression::Propagate {
+                    value: Box::new(expr),
+                    span,
+                };
+            } else if self.match_token(&TokenKind::BangBang) {
+                // Non-null assertion `!!`
+                let span = expr.span().merge(self.previous_span());
+                expr = Expression::NonNullAssert {
+                    value: Box::new(expr),
+                    span,
+                };
             } else {
                 // No more calls/safe calls/field access
                 break;
@@ -325,7 +446,46 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
-    /// Parses an argument list for function calls.
+    /// Parses the arguments of a function call, where positional arguments
+    /// come first and are followed by any `name = value` arguments.
+    fn parse_call_arguments(&mut self) -> Result<(Vec<Expression>, Vec<NamedArgument>)> {
+        let mut arguments = Vec::new();
+        let mut named_arguments = Vec::new();
+
+        if !self.check(&TokenKind::RightParen) {
+            loop {
+                if self.check(&TokenKind::Identifier) && self.check_ahead(1, &TokenKind::Equal) {
+                    let name_span = self.current_span();
+                    let name = self.expect_identifier()?;
+                    self.advance(); // consume `=`
+                    let value = self.parse_expression()?;
+                    let span = name_span.merge(value.span());
+                    named_arguments.push(NamedArgument { name, value, span });
+                } else {
+                    let argument = self.parse_expression()?;
+                    if !named_arguments.is_empty() {
+                        return Err(Error::Parser(
+                            "Positional arguments must come before named arguments".to_string(),
+                            argument.span(),
+                        ));
+                    }
+                    arguments.push(argument);
+                }
+
+                if !self.match_token(&TokenKind::Comma) {
+                    break;
+                }
+                // Allow trailing comma
+                if self.check(&TokenKind::RightParen) {
+                    break;
+                }
+            }
+        }
+
+        Ok((arguments, named_arguments))
+    }
+
+    /// Parses an argument list for method calls.
     fn parse_argument_list(&mut self) -> Result<Vec<Expression>> {
         let mut arguments = Vec::new();
 
@@ -335,6 +495,10 @@ impl<'a> Parser<'a> {
                 if !self.match_token(&TokenKind::Comma) {
                     break;
                 }
+                // Allow trailing comma
+                if self.check(&TokenKind::RightParen) {
+                    break;
+                }
             }
         }
 
@@ -1,9 +1,9 @@
 //! Function and parameter parsing.
 
 use super::parser::Parser;
-use crate::ast::{Function, FunctionBody, Parameter};
-use rive_core::Result;
+use crate::ast::{Function, FunctionBody, Parameter, TestAttribute};
 use rive_core::type_system::TypeId;
+use rive_core::{Error, Result};
 use rive_lexer::TokenKind;
 
 impl<'a> Parser<'a> {
@@ -11,6 +11,7 @@ impl<'a> Parser<'a> {
     /// Supports both block syntax: `fun name() { ... }`
     /// and expression syntax: `fun name() = expr`
     pub(crate) fn parse_function(&mut self) -> Result<Function> {
+        let docs = self.current_docs();
         let start_span = self.expect(&TokenKind::Fun)?;
 
         let name = self.expect_identifier()?;
@@ -44,10 +45,66 @@ impl<'a> Parser<'a> {
             params,
             return_type,
             body,
+            docs,
+            test: None,
             span: start_span.merge(end_span),
         })
     }
 
+    /// Parses a function preceded by an attribute: `@test`, `@test(panics)`
+    /// or `@test(panics = "message")`.
+    pub(crate) fn parse_attributed_function(&mut self) -> Result<Function> {
+        let docs = self.current_docs();
+        let start_span = self.expect(&TokenKind::At)?;
+        let name_span = self.current_span();
+        let name = self.expect_identifier()?;
+        if name != "test" {
+            return Err(Error::Parser(
+                format!("Unknown attribute '@{name}'"),
+                start_span.merge(name_span),
+            ));
+        }
+
+        let mut test = TestAttribute {
+            should_panic: false,
+            expected_message: None,
+            span: start_span.merge(name_span),
+        };
+        if self.match_token(&TokenKind::LeftParen) {
+            let option_span = self.current_span();
+            let option = self.expect_identifier()?;
+            if option != "panics" {
+                return Err(Error::Parser(
+                    format!("Unknown '@test' option '{option}', expected 'panics'"),
+                    option_span,
+                ));
+            }
+            test.should_panic = true;
+            if self.match_token(&TokenKind::Equal) {
+                if !self.check(&TokenKind::String) {
+                    return Err(Error::Parser(
+                        "Expected the expected panic message as a string".to_string(),
+                        self.current_span(),
+                    ));
+                }
+                test.expected_message = Some(self.parse_string_content()?);
+            }
+            let end_span = self.expect(&TokenKind::RightParen)?;
+            test.span = test.span.merge(end_span);
+        }
+
+        if !self.check(&TokenKind::Fun) {
+            return Err(Error::Parser(
+                "'@test' can only be applied to functions".to_string(),
+                self.current_span(),
+            ));
+        }
+        let mut function = self.parse_function()?;
+        function.docs = docs.or(function.docs);
+        function.test = Some(test);
+        Ok(function)
+    }
+
     /// Parses a parameter list.
     fn parse_parameter_list(&mut self) -> Result<Vec<Parameter>> {
         let mut params = Vec::new();
@@ -60,15 +117,26 @@ impl<'a> Parser<'a> {
                 self.expect(&TokenKind::Colon)?;
                 let param_type = self.parse_type()?;
 
+                let default = if self.match_token(&TokenKind::Equal) {
+                    Some(self.parse_expression()?)
+                } else {
+                    None
+                };
+
                 params.push(Parameter {
                     name,
                     param_type,
+                    default,
                     span: name_span,
                 });
 
                 if !self.match_token(&TokenKind::Comma) {
                     break;
                 }
+                // Allow trailing comma
+                if self.check(&TokenKind::RightParen) {
+                    break;
+                }
             }
         }
 
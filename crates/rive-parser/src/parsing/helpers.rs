@@ -1,18 +1,33 @@
 //! Common parsing helper functions.
 
 use super::parser::Parser;
+use crate::ast::Expression;
 use rive_core::{Error, Result};
 use rive_lexer::TokenKind;
 
 impl<'a> Parser<'a> {
     /// Parses a literal value into an i64.
     pub(crate) fn parse_i64_literal(&mut self) -> Result<i64> {
+        self.parse_signed_i64_literal(false)
+    }
+
+    /// Parses an integer literal token into an i64, negated if `negative`.
+    ///
+    /// Literal tokens are unsigned, so the sign is applied before range
+    /// checking to make `-9223372036854775808` representable.
+    pub(crate) fn parse_signed_i64_literal(&mut self, negative: bool) -> Result<i64> {
         let token = self.peek();
-        let value = token
-            .0
-            .text
-            .parse()
-            .map_err(|_| Error::Parser("Invalid integer literal".to_string(), token.1))?;
+        let text = if negative {
+            format!("-{}", token.0.text)
+        } else {
+            token.0.text.clone()
+        };
+        let value = text.parse().map_err(|_| {
+            Error::Parser(
+                format!("Integer literal '{text}' is out of range for Int"),
+                token.1,
+            )
+        })?;
         self.advance();
         Ok(value)
     }
@@ -29,6 +44,38 @@ impl<'a> Parser<'a> {
         Ok(value)
     }
 
+    /// Parses a numeric literal preceded by `-`, folding the sign into the value.
+    ///
+    /// Returns `None` (consuming nothing) unless the current tokens are `-`
+    /// followed by an integer or float literal that isn't the receiver of a
+    /// postfix operation: `-1.abs()` negates the call's result, as in Rust.
+    pub(crate) fn parse_negative_literal(&mut self) -> Result<Option<Expression>> {
+        let is_literal =
+            self.check_ahead(1, &TokenKind::Integer) || self.check_ahead(1, &TokenKind::Float);
+        let has_postfix = [TokenKind::Dot, TokenKind::BangBang, TokenKind::Question]
+            .iter()
+            .any(|kind| self.check_ahead(2, kind));
+        if !self.check(&TokenKind::Minus) || !is_literal || has_postfix {
+            return Ok(None);
+        }
+
+        let start = self.current_span();
+        self.advance(); // consume '-'
+        let span = start.merge(self.current_span());
+
+        let literal = if self.check(&TokenKind::Integer) {
+            let value = self.parse_signed_i64_literal(true)?;
+            Expression::Integer { value, span }
+        } else {
+            let value = self.parse_f64_literal()?;
+            Expression::Float {
+                value: -value,
+                span,
+            }
+        };
+        Ok(Some(literal))
+    }
+
     /// Parses a string literal (removes surrounding quotes).
     pub(crate) fn parse_string_content(&mut self) -> Result<String> {
         let token = self.peek();
@@ -54,7 +101,22 @@ impl<'a> Parser<'a> {
     }
 
     /// Parses a condition expression (with optional parentheses).
+    ///
+    /// Also accepts a null binding `let name = expr` for `if let` / `while let`.
     pub(crate) fn parse_condition(&mut self) -> Result<crate::ast::Expression> {
+        if self.match_token(&TokenKind::Let) {
+            let start_span = self.previous_span();
+            let name = self.expect_identifier()?;
+            self.expect(&TokenKind::Equal)?;
+            let value = self.parse_expression()?;
+            let span = start_span.merge(value.span());
+            return Ok(crate::ast::Expression::LetBinding {
+                name,
+                value: Box::new(value),
+                span,
+            });
+        }
+
         let has_paren = self.match_token(&TokenKind::LeftParen);
         let condition = self.parse_expression()?;
         if has_paren {
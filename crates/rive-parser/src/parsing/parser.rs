@@ -1,24 +1,50 @@
 //! Core parser structure and helper methods.
 
-use crate::ast::{Block, Item, Program};
+use crate::ast::{Block, Expression, Global, Item, Program};
 use rive_core::type_system::TypeRegistry;
 use rive_core::{Error, Result, Span};
 use rive_lexer::{Token, TokenKind};
+use std::borrow::Cow;
+use std::collections::HashMap;
 
 /// Parser for Rive source code.
 pub struct Parser<'a> {
-    tokens: &'a [(Token, Span)],
+    tokens: Cow<'a, [(Token, Span)]>,
+    /// Doc comment text, keyed by the index of the token it documents
+    docs: HashMap<usize, String>,
     current: usize,
     type_registry: TypeRegistry,
+    /// Set when a `>>` token closed a type argument list and its second `>`
+    /// still has to close the enclosing one
+    split_greater: bool,
+    /// Set while parsing inside parentheses, brackets or a map literal, where
+    /// a `-` at the start of a line continues the expression; elsewhere it
+    /// begins the next statement or `when` arm (e.g. `-1 -> ...`)
+    in_delimiters: bool,
 }
 
 impl<'a> Parser<'a> {
     /// Creates a new parser for the given token stream.
+    /// Doc comment tokens are removed from the stream and remembered for the
+    /// item that follows them.
     pub fn new(tokens: &'a [(Token, Span)]) -> Self {
+        let (tokens, docs) = if tokens
+            .iter()
+            .any(|(token, _)| token.kind == TokenKind::DocComment)
+        {
+            let (tokens, docs) = split_doc_comments(tokens);
+            (Cow::Owned(tokens), docs)
+        } else {
+            (Cow::Borrowed(tokens), HashMap::new())
+        };
+
         Self {
             tokens,
+            docs,
             current: 0,
             type_registry: TypeRegistry::new(),
+            split_greater: false,
+            in_delimiters: false,
         }
     }
 
@@ -37,6 +63,49 @@ impl<'a> Parser<'a> {
         self.type_registry
     }
 
+    /// Returns true if the second half of a `>>` token is pending.
+    pub(crate) fn split_greater(&self) -> bool {
+        self.split_greater
+    }
+
+    pub(crate) fn set_split_greater(&mut self, pending: bool) {
+        self.split_greater = pending;
+    }
+
+    /// Runs `parse` with `in_delimiters` set to `in_delimiters`, restoring
+    /// the enclosing setting afterwards.
+    pub(crate) fn with_delimiters<T>(
+        &mut self,
+        in_delimiters: bool,
+        parse: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let outer = std::mem::replace(&mut self.in_delimiters, in_delimiters);
+        let result = parse(self);
+        self.in_delimiters = outer;
+        result
+    }
+
+    /// Parses a single-expression `when` arm body.
+    ///
+    /// A `-` at the start of a line ends the body rather than continuing it
+    /// as a subtraction, so the next arm may start with a negative literal.
+    pub(crate) fn parse_arm_expression(&mut self) -> Result<Expression> {
+        self.with_delimiters(false, Self::parse_expression)
+    }
+
+    /// Returns true if the current `-` begins the next statement or `when`
+    /// arm: it starts a line outside parentheses, brackets and map literals.
+    pub(crate) fn minus_starts_next_statement(&self) -> bool {
+        !self.in_delimiters
+            && self.check(&TokenKind::Minus)
+            && self.current_span().start.line != self.previous_span().end.line
+    }
+
+    /// Returns the doc comment written directly before the current token.
+    pub(crate) fn current_docs(&self) -> Option<String> {
+        self.docs.get(&self.current).cloned()
+    }
+
     /// Returns the previous token.
     pub(crate) fn previous_token(&self) -> &(Token, Span) {
         if self.current > 0 {
@@ -57,22 +126,61 @@ impl<'a> Parser<'a> {
         Ok(Program { items })
     }
 
-    /// Parses a top-level item (currently only functions).
+    /// Parses a top-level item: a function, a global `let`/`const` or a type alias.
     fn parse_item(&mut self) -> Result<Item> {
-        if self.check(&TokenKind::Fun) {
-            Ok(Item::Function(self.parse_function()?))
-        } else {
-            let span = self.current_span();
-            Err(Error::Parser(
-                format!(
-                    "Expected function declaration, found '{}'",
-                    self.peek().0.text
-                ),
-                span,
-            ))
+        match self.peek().0.kind {
+            TokenKind::Fun => Ok(Item::Function(self.parse_function()?)),
+            TokenKind::At => Ok(Item::Function(self.parse_attributed_function()?)),
+            TokenKind::Let | TokenKind::Const => Ok(Item::Global(self.parse_global()?)),
+            TokenKind::Type | TokenKind::Newtype => Ok(Item::TypeAlias(self.parse_type_alias()?)),
+            _ => {
+                let span = self.current_span();
+                Err(Error::Parser(
+                    format!(
+                        "Expected function, global or type declaration, found '{}'",
+                        self.peek().0.text
+                    ),
+                    span,
+                ))
+            }
         }
     }
 
+    /// Parses a global declaration: `let name[: type] = expr` or `const name[: type] = expr`.
+    fn parse_global(&mut self) -> Result<Global> {
+        let docs = self.current_docs();
+        let constant = self.check(&TokenKind::Const);
+        let start_span = self.current_span();
+        self.advance(); // consume `let` or `const`
+
+        if self.check(&TokenKind::Mut) {
+            return Err(Error::Parser(
+                "Global variables cannot be mutable".to_string(),
+                self.current_span(),
+            ));
+        }
+
+        let name = self.expect_identifier()?;
+        let var_type = if self.match_token(&TokenKind::Colon) {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+
+        self.expect(&TokenKind::Equal)?;
+        let initializer = self.parse_expression()?;
+        let span = start_span.merge(initializer.span());
+
+        Ok(Global {
+            name,
+            constant,
+            var_type,
+            initializer,
+            docs,
+            span,
+        })
+    }
+
     /// Parses a block of statements.
     pub(crate) fn parse_block(&mut self) -> Result<Block> {
         let start_span = self.expect(&TokenKind::LeftBrace)?;
@@ -114,6 +222,10 @@ impl<'a> Parser<'a> {
             .is_some_and(|t| &t.0.kind == kind)
     }
 
+    pub(crate) fn kind_ahead(&self, offset: usize) -> Option<&TokenKind> {
+        self.tokens.get(self.current + offset).map(|t| &t.0.kind)
+    }
+
     pub(crate) fn advance(&mut self) {
         if !self.is_at_end() {
             self.current += 1;
@@ -184,3 +296,30 @@ impl<'a> Parser<'a> {
         }
     }
 }
+
+/// Separates doc comments from the other tokens, joining consecutive `///`
+/// lines and keying them by the index of the next remaining token.
+fn split_doc_comments(tokens: &[(Token, Span)]) -> (Vec<(Token, Span)>, HashMap<usize, String>) {
+    let mut remaining = Vec::with_capacity(tokens.len());
+    let mut docs = HashMap::new();
+    let mut pending: Vec<&str> = Vec::new();
+
+    for (token, span) in tokens {
+        if token.kind == TokenKind::DocComment {
+            let line = &token.text[3..];
+            // `////` is an ordinary comment
+            if !line.starts_with('/') {
+                pending.push(line.strip_prefix(' ').unwrap_or(line).trim_end());
+            }
+            continue;
+        }
+
+        if !pending.is_empty() {
+            docs.insert(remaining.len(), pending.join("\n"));
+            pending.clear();
+        }
+        remaining.push((token.clone(), *span));
+    }
+
+    (remaining, docs)
+}
@@ -68,7 +68,7 @@ impl<'a> Parser<'a> {
             // Array literal
             TokenKind::LeftBracket => {
                 self.advance();
-                let elements = self.parse_array_elements()?;
+                let elements = self.with_delimiters(true, Self::parse_array_elements)?;
                 let end_span = self.expect(&TokenKind::RightBracket)?;
                 Ok(Expression::Array {
                     elements,
@@ -80,7 +80,22 @@ impl<'a> Parser<'a> {
             TokenKind::While => Ok(Expression::While(Box::new(self.parse_while(None)?))),
             TokenKind::For => Ok(Expression::For(Box::new(self.parse_for(None)?))),
             TokenKind::Loop => Ok(Expression::Loop(Box::new(self.parse_loop(None)?))),
+            TokenKind::When if self.check_ahead(1, &TokenKind::LeftBrace) => {
+                Ok(Expression::When(Box::new(self.parse_when()?)))
+            }
             TokenKind::When => Ok(Expression::Match(Box::new(self.parse_match()?))),
+            // Diverging control flow (type Never), e.g. `x ?: return`
+            TokenKind::Return => {
+                self.advance();
+                let value = self.parse_return_value()?.map(Box::new);
+                let end_span = value.as_ref().map_or(span, |v| v.span());
+                Ok(Expression::Return {
+                    value,
+                    span: span.merge(end_span),
+                })
+            }
+            TokenKind::Break => Ok(Expression::Break(Box::new(self.parse_break()?))),
+            TokenKind::Continue => Ok(Expression::Continue(self.parse_continue()?)),
             // Dict literal or block expression
             TokenKind::LeftBrace => self.parse_brace_expression(),
             _ => {
@@ -95,14 +110,8 @@ impl<'a> Parser<'a> {
 
     /// Parses an integer literal.
     fn parse_integer_literal(&mut self) -> Result<Expression> {
-        let token = self.peek();
         let span = self.current_span();
-        let value = token
-            .0
-            .text
-            .parse::<i64>()
-            .map_err(|_| Error::Parser(format!("Invalid integer: {}", token.0.text), span))?;
-        self.advance();
+        let value = self.parse_i64_literal()?;
         Ok(Expression::Integer { value, span })
     }
 
@@ -139,6 +148,10 @@ impl<'a> Parser<'a> {
                 if !self.match_token(&TokenKind::Comma) {
                     break;
                 }
+                // Allow trailing comma
+                if self.check(&TokenKind::RightBracket) {
+                    break;
+                }
             }
         }
 
@@ -153,6 +166,11 @@ impl<'a> Parser<'a> {
     /// - `(a, b, c)` - multi-element tuple
     /// - `()` - empty tuple (unit)
     fn parse_paren_or_tuple(&mut self) -> Result<Expression> {
+        self.with_delimiters(true, Self::parse_paren_or_tuple_contents)
+    }
+
+    /// Parses the contents of `parse_paren_or_tuple`, starting at the `(`.
+    fn parse_paren_or_tuple_contents(&mut self) -> Result<Expression> {
         let start_span = self.current_span();
         self.advance(); // consume `(`
 
@@ -222,39 +240,7 @@ impl<'a> Parser<'a> {
         // Dict: first token is String followed by Colon
         if self.check(&TokenKind::String) && self.check_ahead(1, &TokenKind::Colon) {
             // Parse as dict literal
-            let mut entries = Vec::new();
-
-            loop {
-                // Parse key (must be string literal)
-                let key_token = self.peek();
-                let key_span = self.current_span();
-                if key_token.0.kind != TokenKind::String {
-                    return Err(Error::Parser(
-                        "Dictionary keys must be string literals".to_string(),
-                        key_span,
-                    ));
-                }
-                let key = key_token.0.text[1..key_token.0.text.len() - 1].to_string();
-                self.advance();
-
-                // Expect colon
-                self.expect(&TokenKind::Colon)?;
-
-                // Parse value
-                let value = self.parse_expression()?;
-                entries.push((key, value));
-
-                // Check for comma
-                if !self.match_token(&TokenKind::Comma) {
-                    break;
-                }
-
-                // Allow trailing comma
-                if self.check(&TokenKind::RightBrace) {
-                    break;
-                }
-            }
-
+            let entries = self.with_delimiters(true, Self::parse_dict_entries)?;
             let end_span = self.expect(&TokenKind::RightBrace)?;
             Ok(Expression::Dict {
                 entries,
@@ -277,4 +263,42 @@ impl<'a> Parser<'a> {
             })))
         }
     }
+
+    /// Parses the `"key": value` entries of a dict literal.
+    fn parse_dict_entries(&mut self) -> Result<Vec<(String, Expression)>> {
+        let mut entries = Vec::new();
+
+        loop {
+            // Parse key (must be string literal)
+            let key_token = self.peek();
+            let key_span = self.current_span();
+            if key_token.0.kind != TokenKind::String {
+                return Err(Error::Parser(
+                    "Dictionary keys must be string literals".to_string(),
+                    key_span,
+                ));
+            }
+            let key = key_token.0.text[1..key_token.0.text.len() - 1].to_string();
+            self.advance();
+
+            // Expect colon
+            self.expect(&TokenKind::Colon)?;
+
+            // Parse value
+            let value = self.parse_expression()?;
+            entries.push((key, value));
+
+            // Check for comma
+            if !self.match_token(&TokenKind::Comma) {
+                break;
+            }
+
+            // Allow trailing comma
+            if self.check(&TokenKind::RightBrace) {
+                break;
+            }
+        }
+
+        Ok(entries)
+    }
 }
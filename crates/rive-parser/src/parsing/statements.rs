@@ -1,16 +1,25 @@
 //! Statement parsing.
 
 use super::parser::Parser;
-use crate::ast::Statement;
+use crate::ast::{BinaryOperator, BindingPattern, Expression, Statement};
 use rive_core::Result;
 use rive_lexer::TokenKind;
 
 impl<'a> Parser<'a> {
     /// Parses a statement.
+    ///
+    /// A `-` at the start of a line ends the statement even if it is inside
+    /// parentheses, e.g. in a block passed as an argument.
     pub(crate) fn parse_statement(&mut self) -> Result<Statement> {
+        self.with_delimiters(false, Self::parse_statement_kind)
+    }
+
+    /// Parses a statement, dispatching on its first token.
+    fn parse_statement_kind(&mut self) -> Result<Statement> {
         match self.peek().0.kind {
             TokenKind::Let => self.parse_let_statement(),
             TokenKind::Const => self.parse_const_statement(),
+            TokenKind::Fun => Ok(Statement::Function(Box::new(self.parse_function()?))),
             TokenKind::Return => self.parse_return_statement(),
             TokenKind::Break => {
                 let break_stmt = self.parse_break()?;
@@ -29,7 +38,17 @@ impl<'a> Parser<'a> {
     fn parse_let_statement(&mut self) -> Result<Statement> {
         let start_span = self.expect(&TokenKind::Let)?;
 
+        if self.check(&TokenKind::LeftParen) {
+            return self.parse_destructure(start_span);
+        }
+
         let mutable = self.match_token(&TokenKind::Mut);
+        if mutable && self.check(&TokenKind::LeftParen) {
+            return Err(rive_core::Error::Parser(
+                "Cannot use 'mut' before a destructuring pattern. Mark each binding instead, e.g. 'let (mut a, b)'".to_string(),
+                self.current_span(),
+            ));
+        }
         let name = self.expect_identifier()?;
 
         // Check for `?` after variable name (e.g., `let result? = ...`)
@@ -68,6 +87,62 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parses the rest of a destructuring let: `(a, b) = expr`
+    fn parse_destructure(&mut self, start_span: rive_core::Span) -> Result<Statement> {
+        let pattern = self.parse_binding_pattern()?;
+
+        self.expect(&TokenKind::Equal)?;
+        let initializer = self.parse_expression()?;
+        let end_span = initializer.span();
+
+        Ok(Statement::Destructure {
+            pattern,
+            initializer,
+            span: start_span.merge(end_span),
+        })
+    }
+
+    /// Parses a binding pattern: `name`, `mut name`, `_` or `(pattern, ...)`.
+    pub(crate) fn parse_binding_pattern(&mut self) -> Result<BindingPattern> {
+        let start_span = self.current_span();
+
+        if self.match_token(&TokenKind::Underscore) {
+            return Ok(BindingPattern::Wildcard { span: start_span });
+        }
+
+        if self.match_token(&TokenKind::LeftParen) {
+            let mut elements = vec![self.parse_binding_pattern()?];
+            let mut trailing_comma = false;
+            while self.match_token(&TokenKind::Comma) {
+                if self.check(&TokenKind::RightParen) {
+                    trailing_comma = true;
+                    break;
+                }
+                elements.push(self.parse_binding_pattern()?);
+            }
+            let end_span = self.expect(&TokenKind::RightParen)?;
+
+            // `(a)` is a parenthesized pattern, `(a,)` a single-element tuple
+            if elements.len() == 1 && !trailing_comma {
+                return Ok(elements.remove(0));
+            }
+
+            return Ok(BindingPattern::Tuple {
+                elements,
+                span: start_span.merge(end_span),
+            });
+        }
+
+        let mutable = self.match_token(&TokenKind::Mut);
+        let name = self.expect_identifier()?;
+
+        Ok(BindingPattern::Name {
+            name,
+            mutable,
+            span: start_span.merge(self.previous_span()),
+        })
+    }
+
     /// Parses a const statement.
     /// Supports both `const x? = expr` (infer nullable) and `const x: Type? = expr` (explicit nullable)
     fn parse_const_statement(&mut self) -> Result<Statement> {
@@ -113,13 +188,7 @@ impl<'a> Parser<'a> {
     /// Parses a return statement.
     fn parse_return_statement(&mut self) -> Result<Statement> {
         let start_span = self.expect(&TokenKind::Return)?;
-
-        let value = if self.check(&TokenKind::RightBrace) || self.is_at_end() {
-            None
-        } else {
-            Some(self.parse_expression()?)
-        };
-
+        let value = self.parse_return_value()?;
         let end_span = value.as_ref().map_or(start_span, |v| v.span());
 
         Ok(Statement::Return {
@@ -128,6 +197,24 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parses the optional value after `return`.
+    ///
+    /// The value must start on the same line as `return`; a bare `return` is
+    /// followed by a closing delimiter, a comma or a line break.
+    pub(crate) fn parse_return_value(&mut self) -> Result<Option<Expression>> {
+        let has_value = !self.is_at_end()
+            && !self.check(&TokenKind::RightBrace)
+            && !self.check(&TokenKind::RightParen)
+            && !self.check(&TokenKind::Comma)
+            && self.current_span().start.line == self.previous_span().end.line;
+
+        if has_value {
+            Ok(Some(self.parse_expression()?))
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Parses an expression statement or assignment.
     fn parse_expression_or_assignment(&mut self) -> Result<Statement> {
         // Check if this is an assignment (identifier followed by =)
@@ -143,6 +230,26 @@ impl<'a> Parser<'a> {
             return Ok(Statement::Assignment { name, value, span });
         }
 
+        // Compound assignment (identifier followed by +=, -=, ...)
+        if self.check(&TokenKind::Identifier)
+            && let Some(operator) = self.kind_ahead(1).and_then(compound_assignment_operator)
+        {
+            let start_span = self.current_span();
+            let name = self.peek().0.text.clone();
+            self.advance(); // consume identifier
+            self.advance(); // consume operator
+
+            let value = self.parse_expression()?;
+            let span = start_span.merge(value.span());
+
+            return Ok(Statement::CompoundAssignment {
+                name,
+                operator,
+                value,
+                span,
+            });
+        }
+
         // Otherwise, it's an expression statement
         let expression = self.parse_expression()?;
         let span = expression.span();
@@ -150,3 +257,15 @@ impl<'a> Parser<'a> {
         Ok(Statement::Expression { expression, span })
     }
 }
+
+/// Returns the binary operator applied by a compound assignment token.
+fn compound_assignment_operator(kind: &TokenKind) -> Option<BinaryOperator> {
+    match kind {
+        TokenKind::PlusEqual => Some(BinaryOperator::Add),
+        TokenKind::MinusEqual => Some(BinaryOperator::Subtract),
+        TokenKind::StarEqual => Some(BinaryOperator::Multiply),
+        TokenKind::SlashEqual => Some(BinaryOperator::Divide),
+        TokenKind::PercentEqual => Some(BinaryOperator::Modulo),
+        _ => None,
+    }
+}
@@ -1,11 +1,55 @@
 //! Type annotation parsing.
 
 use super::parser::Parser;
+use crate::ast::TypeAlias;
 use rive_core::type_system::TypeId;
-use rive_core::{Error, Result};
+use rive_core::{Error, Result, Span};
 use rive_lexer::TokenKind;
 
+/// Type names built into the parser rather than the registry.
+const BUILTIN_TYPE_NAMES: &[&str] = &[
+    "Int", "Float", "Text", "Bool", "Never", "Result", "List", "Map",
+];
+
 impl<'a> Parser<'a> {
+    /// Parses a type alias: `type Name = T` or `newtype Name = T`.
+    ///
+    /// The alias is registered immediately, so it can be used by any
+    /// declaration that follows it.
+    pub(crate) fn parse_type_alias(&mut self) -> Result<TypeAlias> {
+        let docs = self.current_docs();
+        let distinct = self.check(&TokenKind::Newtype);
+        let start_span = self.current_span();
+        self.advance(); // consume `type` or `newtype`
+
+        let name_span = self.current_span();
+        let name = self.expect_identifier()?;
+        if BUILTIN_TYPE_NAMES.contains(&name.as_str())
+            || self.type_registry().get_by_name(&name).is_some()
+        {
+            return Err(Error::Parser(
+                format!("Type '{name}' is already defined"),
+                name_span,
+            ));
+        }
+
+        self.expect(&TokenKind::Equal)?;
+        let target = self.parse_type()?;
+        let span = start_span.merge(self.previous_span());
+        let type_id = self
+            .type_registry_mut()
+            .register_alias(&name, target, distinct);
+
+        Ok(TypeAlias {
+            name,
+            type_id,
+            target,
+            distinct,
+            docs,
+            span,
+        })
+    }
+
     /// Parses a type annotation and returns a TypeId.
     ///
     /// Supports nullable types with `?` suffix: `Int?`, `Text?`, etc.
@@ -15,6 +59,7 @@ impl<'a> Parser<'a> {
         let base_type = match &token.0.kind {
             TokenKind::Identifier => self.parse_named_type(),
             TokenKind::LeftBracket => self.parse_array_type(),
+            TokenKind::LeftParen => self.parse_tuple_type(),
             _ => {
                 let span = self.current_span();
                 return Err(Error::Parser(
@@ -24,8 +69,8 @@ impl<'a> Parser<'a> {
             }
         }?;
 
-        // Check for nullable type suffix `?`
-        if self.peek().0.kind == TokenKind::Question {
+        // Check for nullable type suffix `?` (unless the type ended inside a `>>`)
+        if !self.split_greater() && self.peek().0.kind == TokenKind::Question {
             self.advance(); // consume `?`
             Ok(self.type_registry_mut().create_optional(base_type))
         } else {
@@ -33,7 +78,8 @@ impl<'a> Parser<'a> {
         }
     }
 
-    /// Parses a named type (Int, Float, Text, Bool).
+    /// Parses a named type (Int, Float, Text, Bool, Result<T, E>, List<T>,
+    /// Map<K, V>) or the name of a type alias.
     ///
     /// Note: Nullable types are handled by `parse_type()` with the `?` suffix.
     fn parse_named_type(&mut self) -> Result<TypeId> {
@@ -46,8 +92,69 @@ impl<'a> Parser<'a> {
             "Float" => Ok(TypeId::FLOAT),
             "Text" => Ok(TypeId::TEXT),
             "Bool" => Ok(TypeId::BOOL),
-            _ => Err(Error::Parser(format!("Unknown type '{type_name}'"), span)),
+            "Never" => Ok(TypeId::NEVER),
+            "Result" => {
+                let arguments = self.parse_type_arguments(2, &type_name)?;
+                Ok(self
+                    .type_registry_mut()
+                    .create_result(arguments[0], arguments[1]))
+            }
+            "List" => {
+                let arguments = self.parse_type_arguments(1, &type_name)?;
+                Ok(self.type_registry_mut().create_list(arguments[0]))
+            }
+            "Map" => {
+                let arguments = self.parse_type_arguments(2, &type_name)?;
+                Ok(self
+                    .type_registry_mut()
+                    .create_map(arguments[0], arguments[1]))
+            }
+            _ => self
+                .type_registry()
+                .get_by_name(&type_name)
+                .ok_or_else(|| Error::Parser(format!("Unknown type '{type_name}'"), span)),
+        }
+    }
+
+    /// Parses a generic argument list `<T, U, ...>` with exactly `count` types.
+    fn parse_type_arguments(&mut self, count: usize, type_name: &str) -> Result<Vec<TypeId>> {
+        let start = self.expect(&TokenKind::Less)?;
+
+        let mut arguments = vec![self.parse_type()?];
+        while !self.split_greater() && self.match_token(&TokenKind::Comma) {
+            arguments.push(self.parse_type()?);
+        }
+
+        let end = self.expect_closing_angle()?;
+
+        if arguments.len() != count {
+            return Err(Error::Parser(
+                format!(
+                    "'{type_name}' expects {count} type arguments, found {}",
+                    arguments.len()
+                ),
+                start.merge(end),
+            ));
         }
+
+        Ok(arguments)
+    }
+
+    /// Consumes the `>` closing a type argument list.
+    ///
+    /// A `>>` token closes two nested lists: its first half is consumed here
+    /// and the second half is left pending for the enclosing list.
+    fn expect_closing_angle(&mut self) -> Result<Span> {
+        if self.split_greater() {
+            self.set_split_greater(false);
+            return Ok(self.previous_span());
+        }
+        if self.check(&TokenKind::GreaterGreater) {
+            self.advance();
+            self.set_split_greater(true);
+            return Ok(self.previous_span());
+        }
+        self.expect(&TokenKind::Greater)
     }
 
     /// Parses an array type [T; N].
@@ -65,4 +172,34 @@ impl<'a> Parser<'a> {
         self.expect(&TokenKind::RightBracket)?;
         Ok(self.type_registry_mut().create_array(element_type, size))
     }
+
+    /// Parses a tuple type (T, U, ...).
+    ///
+    /// `()` is Unit, `(T)` is a parenthesized `T`, and `(T,)` is a one-element tuple.
+    fn parse_tuple_type(&mut self) -> Result<TypeId> {
+        self.advance(); // consume '('
+
+        if self.match_token(&TokenKind::RightParen) {
+            return Ok(TypeId::UNIT);
+        }
+
+        let mut elements = vec![self.parse_type()?];
+        let mut trailing_comma = false;
+        while self.match_token(&TokenKind::Comma) {
+            trailing_comma = true;
+            if self.check(&TokenKind::RightParen) {
+                break;
+            }
+            elements.push(self.parse_type()?);
+            trailing_comma = false;
+        }
+
+        self.expect(&TokenKind::RightParen)?;
+
+        if elements.len() == 1 && !trailing_comma {
+            Ok(elements[0])
+        } else {
+            Ok(self.type_registry_mut().create_tuple(elements))
+        }
+    }
 }
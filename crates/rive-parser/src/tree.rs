@@ -0,0 +1,545 @@
+//! Printable tree view of the AST.
+//!
+//! [`syntax_tree`] converts a program into a uniform tree of [`SyntaxNode`]s
+//! that can be printed one node per line, or walked by tools that render it in
+//! other formats:
+//!
+//! ```text
+//! Program
+//!   Function main() -> Unit
+//!     Let x: Int
+//!       Binary + [Int]
+//!         Integer 1 [Int]
+//!         Integer 2 [Int]
+//! ```
+//!
+//! Expression types (the `[Int]` above) are only shown after
+//! [`SyntaxNode::annotate_types`] fills them in.
+
+use std::fmt;
+
+use rive_core::Span;
+use rive_core::type_system::{TypeId, TypeRegistry};
+
+use crate::ast::{
+    BinaryOperator, BindingPattern, Block, Expression, Function, FunctionBody, Item, Program,
+    Statement, UnaryOperator,
+};
+use crate::control_flow::Pattern;
+
+/// A node of the printable syntax tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxNode {
+    /// Kind of node, e.g. `Function` or `Binary`
+    pub kind: &'static str,
+    /// Name, operator or literal shown after the kind
+    pub detail: Option<String>,
+    pub span: Span,
+    /// Whether the node is an expression, and so has a type
+    pub is_expression: bool,
+    /// Type of the expression, once annotated
+    pub type_name: Option<String>,
+    pub children: Vec<SyntaxNode>,
+}
+
+impl SyntaxNode {
+    fn new(kind: &'static str, span: Span) -> Self {
+        Self {
+            kind,
+            detail: None,
+            span,
+            is_expression: false,
+            type_name: None,
+            children: Vec::new(),
+        }
+    }
+
+    fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Shows a loop label as the detail, if there is one.
+    fn label(mut self, label: &Option<String>) -> Self {
+        self.detail.clone_from(label);
+        self
+    }
+
+    fn child(mut self, child: Self) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    fn children(mut self, children: impl IntoIterator<Item = Self>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// Sets the type of every expression node for which `type_of` returns one.
+    pub fn annotate_types(&mut self, type_of: &dyn Fn(Span) -> Option<String>) {
+        if self.is_expression {
+            self.type_name = type_of(self.span);
+        }
+        for child in &mut self.children {
+            child.annotate_types(type_of);
+        }
+    }
+
+    fn write_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:indent$}{}", "", self.kind, indent = depth * 2)?;
+        if let Some(detail) = &self.detail {
+            write!(f, " {detail}")?;
+        }
+        if let Some(type_name) = &self.type_name {
+            write!(f, " [{type_name}]")?;
+        }
+        writeln!(f)?;
+        for child in &self.children {
+            child.write_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_indented(f, 0)
+    }
+}
+
+/// Builds the printable syntax tree of a program.
+///
+/// `registry` is the parser's type registry, used to name declared types.
+#[must_use]
+pub fn syntax_tree(program: &Program, registry: &TypeRegistry) -> SyntaxNode {
+    let builder = TreeBuilder { registry };
+    let span = match (program.items.first(), program.items.last()) {
+        (Some(first), Some(last)) => item_span(first).merge(item_span(last)),
+        _ => Span::from_range(0, 0),
+    };
+    SyntaxNode::new("Program", span).children(program.items.iter().map(|item| builder.item(item)))
+}
+
+fn item_span(item: &Item) -> Span {
+    match item {
+        Item::Function(function) => function.span,
+        Item::Global(global) => global.span,
+        Item::TypeAlias(alias) => alias.span,
+    }
+}
+
+struct TreeBuilder<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl TreeBuilder<'_> {
+    fn type_name(&self, type_id: TypeId) -> String {
+        self.registry.get_type_name(type_id)
+    }
+
+    /// Formats `name` followed by an optional `: Type` annotation.
+    fn declared(&self, name: &str, var_type: Option<TypeId>) -> String {
+        match var_type {
+            Some(type_id) => format!("{name}: {}", self.type_name(type_id)),
+            None => name.to_string(),
+        }
+    }
+
+    fn item(&self, item: &Item) -> SyntaxNode {
+        match item {
+            Item::Function(function) => self.function(function),
+            Item::Global(global) => {
+                let kind = if global.constant { "Const" } else { "Global" };
+                SyntaxNode::new(kind, global.span)
+                    .detail(self.declared(&global.name, global.var_type))
+                    .child(self.expression(&global.initializer))
+            }
+            Item::TypeAlias(alias) => {
+                let kind = if alias.distinct {
+                    "Newtype"
+                } else {
+                    "TypeAlias"
+                };
+                SyntaxNode::new(kind, alias.span).detail(format!(
+                    "{} = {}",
+                    alias.name,
+                    self.type_name(alias.target)
+                ))
+            }
+        }
+    }
+
+    fn function(&self, function: &Function) -> SyntaxNode {
+        let params = function
+            .params
+            .iter()
+            .map(|param| format!("{}: {}", param.name, self.type_name(param.param_type)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut signature = format!(
+            "{}({params}) -> {}",
+            function.name,
+            self.type_name(function.return_type)
+        );
+        if let Some(test) = &function.test {
+            signature.insert_str(
+                0,
+                if test.should_panic {
+                    "@test(panics) "
+                } else {
+                    "@test "
+                },
+            );
+        }
+
+        let defaults = function.params.iter().filter_map(|param| {
+            let default = param.default.as_ref()?;
+            Some(
+                SyntaxNode::new("Default", param.span)
+                    .detail(&param.name)
+                    .child(self.expression(default)),
+            )
+        });
+        let body = match &function.body {
+            FunctionBody::Block(block) => self.block(block),
+            FunctionBody::Expression(expression) => self.expression(expression),
+        };
+
+        SyntaxNode::new("Function", function.span)
+            .detail(signature)
+            .children(defaults)
+            .child(body)
+    }
+
+    fn block(&self, block: &Block) -> SyntaxNode {
+        SyntaxNode::new("Block", block.span)
+            .children(block.statements.iter().map(|stmt| self.statement(stmt)))
+    }
+
+    fn statement(&self, statement: &Statement) -> SyntaxNode {
+        match statement {
+            Statement::Let {
+                name,
+                mutable,
+                var_type,
+                initializer,
+                span,
+                ..
+            } => {
+                let name = if *mutable {
+                    format!("mut {name}")
+                } else {
+                    name.clone()
+                };
+                SyntaxNode::new("Let", *span)
+                    .detail(self.declared(&name, *var_type))
+                    .child(self.expression(initializer))
+            }
+            Statement::Destructure {
+                pattern,
+                initializer,
+                span,
+            } => SyntaxNode::new("Destructure", *span)
+                .detail(binding_pattern(pattern))
+                .child(self.expression(initializer)),
+            Statement::Const {
+                name,
+                var_type,
+                initializer,
+                span,
+                ..
+            } => SyntaxNode::new("Const", *span)
+                .detail(self.declared(name, *var_type))
+                .child(self.expression(initializer)),
+            Statement::Assignment { name, value, span } => SyntaxNode::new("Assign", *span)
+                .detail(name)
+                .child(self.expression(value)),
+            Statement::CompoundAssignment {
+                name,
+                operator,
+                value,
+                span,
+            } => SyntaxNode::new("Assign", *span)
+                .detail(format!("{name} {}=", binary_operator(*operator)))
+                .child(self.expression(value)),
+            Statement::Expression { expression, .. } => self.expression(expression),
+            Statement::Return { value, span } => SyntaxNode::new("Return", *span)
+                .children(value.iter().map(|value| self.expression(value))),
+            Statement::Break(brk) => SyntaxNode::new("Break", brk.span)
+                .label(&brk.label)
+                .children(brk.value.iter().map(|value| self.expression(value))),
+            Statement::Continue(cont) => SyntaxNode::new("Continue", cont.span).label(&cont.label),
+            Statement::Function(function) => self.function(function),
+        }
+    }
+
+    fn expression(&self, expression: &Expression) -> SyntaxNode {
+        let span = expression.span();
+        let node = match expression {
+            Expression::Integer { value, .. } => {
+                SyntaxNode::new("Integer", span).detail(value.to_string())
+            }
+            Expression::Float { value, .. } => {
+                SyntaxNode::new("Float", span).detail(value.to_string())
+            }
+            Expression::String { value, .. } => {
+                SyntaxNode::new("String", span).detail(format!("\"{value}\""))
+            }
+            Expression::Boolean { value, .. } => {
+                SyntaxNode::new("Boolean", span).detail(value.to_string())
+            }
+            Expression::Null { .. } => SyntaxNode::new("Null", span),
+            Expression::Variable { name, .. } => SyntaxNode::new("Variable", span).detail(name),
+            Expression::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => SyntaxNode::new("Binary", span)
+                .detail(binary_operator(*operator))
+                .child(self.expression(left))
+                .child(self.expression(right)),
+            Expression::Unary {
+                operator, operand, ..
+            } => SyntaxNode::new("Unary", span)
+                .detail(unary_operator(*operator))
+                .child(self.expression(operand)),
+            Expression::Call {
+                callee,
+                arguments,
+                named_arguments,
+                ..
+            } => SyntaxNode::new("Call", span)
+                .detail(callee)
+                .children(arguments.iter().map(|arg| self.expression(arg)))
+                .children(named_arguments.iter().map(|arg| {
+                    SyntaxNode::new("NamedArgument", arg.span)
+                        .detail(&arg.name)
+                        .child(self.expression(&arg.value))
+                })),
+            Expression::Array { elements, .. } => {
+                SyntaxNode::new("Array", span).children(self.expressions(elements))
+            }
+            Expression::If(if_expr) => {
+                let branches = if_expr.else_if_branches.iter().map(|branch| {
+                    SyntaxNode::new("ElseIf", branch.span)
+                        .child(self.expression(&branch.condition))
+                        .child(self.block(&branch.block))
+                });
+                let else_block = if_expr
+                    .else_block
+                    .iter()
+                    .map(|block| SyntaxNode::new("Else", block.span).child(self.block(block)));
+                SyntaxNode::new("If", span)
+                    .child(self.expression(&if_expr.condition))
+                    .child(self.block(&if_expr.then_block))
+                    .children(branches)
+                    .children(else_block)
+            }
+            Expression::While(while_expr) => SyntaxNode::new("While", span)
+                .label(&while_expr.label)
+                .child(self.expression(&while_expr.condition))
+                .child(self.block(&while_expr.body)),
+            Expression::For(for_expr) => {
+                let pattern = binding_pattern(&for_expr.pattern);
+                let detail = match &for_expr.label {
+                    Some(label) => format!("{label}: {pattern}"),
+                    None => pattern,
+                };
+                SyntaxNode::new("For", span)
+                    .detail(detail)
+                    .child(self.expression(&for_expr.iterable))
+                    .child(self.block(&for_expr.body))
+            }
+            Expression::Loop(loop_expr) => SyntaxNode::new("Loop", span)
+                .label(&loop_expr.label)
+                .child(self.block(&loop_expr.body)),
+            Expression::When(when) => {
+                let arms = when.arms.iter().map(|arm| {
+                    SyntaxNode::new("Arm", arm.span)
+                        .child(self.expression(&arm.condition))
+                        .child(self.expression(&arm.body))
+                });
+                let else_arm = when
+                    .else_arm
+                    .iter()
+                    .map(|body| SyntaxNode::new("Else", body.span()).child(self.expression(body)));
+                SyntaxNode::new("When", span)
+                    .children(arms)
+                    .children(else_arm)
+            }
+            Expression::Return { value, .. } => SyntaxNode::new("Return", span)
+                .children(value.iter().map(|value| self.expression(value))),
+            Expression::Break(brk) => SyntaxNode::new("Break", span)
+                .label(&brk.label)
+                .children(brk.value.iter().map(|value| self.expression(value))),
+            Expression::Continue(cont) => SyntaxNode::new("Continue", span).label(&cont.label),
+            Expression::Match(match_expr) => {
+                let arms = match_expr.arms.iter().map(|arm| {
+                    SyntaxNode::new("Arm", arm.span)
+                        .child(self.pattern(&arm.pattern))
+                        .children(arm.guard.iter().map(|guard| {
+                            SyntaxNode::new("Guard", guard.span()).child(self.expression(guard))
+                        }))
+                        .child(self.expression(&arm.body))
+                });
+                SyntaxNode::new("Match", span)
+                    .child(self.expression(&match_expr.scrutinee))
+                    .children(arms)
+            }
+            Expression::Range(range) => SyntaxNode::new("Range", span)
+                .detail(if range.inclusive { "..=" } else { ".." })
+                .child(self.expression(&range.start))
+                .child(self.expression(&range.end)),
+            Expression::Block(block) => self.block(block),
+            Expression::Elvis {
+                value, fallback, ..
+            } => SyntaxNode::new("Elvis", span)
+                .child(self.expression(value))
+                .child(self.expression(fallback)),
+            Expression::SafeCall { object, call, .. } => SyntaxNode::new("SafeCall", span)
+                .child(self.expression(object))
+                .child(self.expression(call)),
+            Expression::NonNullAssert { value, .. } => {
+                SyntaxNode::new("NonNullAssert", span).child(self.expression(value))
+            }
+            Expression::Propagate { value, .. } => {
+                SyntaxNode::new("Propagate", span).child(self.expression(value))
+            }
+            Expression::LetBinding { name, value, .. } => SyntaxNode::new("LetBinding", span)
+                .detail(name)
+                .child(self.expression(value)),
+            Expression::Tuple { elements, .. } => {
+                SyntaxNode::new("Tuple", span).children(self.expressions(elements))
+            }
+            Expression::List { elements, .. } => {
+                SyntaxNode::new("List", span).children(self.expressions(elements))
+            }
+            Expression::Dict { entries, .. } => {
+                SyntaxNode::new("Dict", span).children(entries.iter().map(|(key, value)| {
+                    SyntaxNode::new("Entry", value.span())
+                        .detail(format!("\"{key}\""))
+                        .child(self.expression(value))
+                }))
+            }
+            Expression::MethodCall {
+                object,
+                method,
+                arguments,
+                ..
+            } => SyntaxNode::new("MethodCall", span)
+                .detail(method)
+                .child(self.expression(object))
+                .children(self.expressions(arguments)),
+            Expression::FieldAccess { object, field, .. } => SyntaxNode::new("FieldAccess", span)
+                .detail(field)
+                .child(self.expression(object)),
+        };
+
+        SyntaxNode {
+            is_expression: true,
+            ..node
+        }
+    }
+
+    fn expressions<'e>(
+        &'e self,
+        expressions: &'e [Expression],
+    ) -> impl Iterator<Item = SyntaxNode> + 'e {
+        expressions.iter().map(|expr| self.expression(expr))
+    }
+
+    fn pattern(&self, pattern: &Pattern) -> SyntaxNode {
+        let span = pattern.span();
+        match pattern {
+            Pattern::Integer { value, .. } => {
+                SyntaxNode::new("IntegerPattern", span).detail(value.to_string())
+            }
+            Pattern::Float { value, .. } => {
+                SyntaxNode::new("FloatPattern", span).detail(value.to_string())
+            }
+            Pattern::String { value, .. } => {
+                SyntaxNode::new("StringPattern", span).detail(format!("\"{value}\""))
+            }
+            Pattern::Boolean { value, .. } => {
+                SyntaxNode::new("BooleanPattern", span).detail(value.to_string())
+            }
+            Pattern::Null { .. } => SyntaxNode::new("NullPattern", span),
+            Pattern::Wildcard { .. } => SyntaxNode::new("WildcardPattern", span),
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+                ..
+            } => SyntaxNode::new("RangePattern", span)
+                .detail(if *inclusive { "..=" } else { ".." })
+                .child(self.expression(start))
+                .child(self.expression(end)),
+            Pattern::Ok { binding, .. } => SyntaxNode::new("OkPattern", span)
+                .detail(binding.clone().unwrap_or_else(|| "_".to_string())),
+            Pattern::Err { binding, .. } => SyntaxNode::new("ErrPattern", span)
+                .detail(binding.clone().unwrap_or_else(|| "_".to_string())),
+            Pattern::Binding { name, .. } => SyntaxNode::new("BindingPattern", span).detail(name),
+            Pattern::Tuple { elements, .. } => SyntaxNode::new("TuplePattern", span)
+                .children(elements.iter().map(|element| self.pattern(element))),
+            Pattern::Or { alternatives, .. } => SyntaxNode::new("OrPattern", span).children(
+                alternatives
+                    .iter()
+                    .map(|alternative| self.pattern(alternative)),
+            ),
+        }
+    }
+}
+
+/// Formats a binding pattern as written in source: `(a, mut b, _)`.
+fn binding_pattern(pattern: &BindingPattern) -> String {
+    match pattern {
+        BindingPattern::Name { name, mutable, .. } => {
+            if *mutable {
+                format!("mut {name}")
+            } else {
+                name.clone()
+            }
+        }
+        BindingPattern::Wildcard { .. } => "_".to_string(),
+        BindingPattern::Tuple { elements, .. } => {
+            let elements = elements.iter().map(binding_pattern).collect::<Vec<_>>();
+            if elements.len() == 1 {
+                format!("({},)", elements[0])
+            } else {
+                format!("({})", elements.join(", "))
+            }
+        }
+    }
+}
+
+fn binary_operator(operator: BinaryOperator) -> &'static str {
+    match operator {
+        BinaryOperator::Add => "+",
+        BinaryOperator::Subtract => "-",
+        BinaryOperator::Multiply => "*",
+        BinaryOperator::Divide => "/",
+        BinaryOperator::Modulo => "%",
+        BinaryOperator::Equal => "==",
+        BinaryOperator::NotEqual => "!=",
+        BinaryOperator::Less => "<",
+        BinaryOperator::LessEqual => "<=",
+        BinaryOperator::Greater => ">",
+        BinaryOperator::GreaterEqual => ">=",
+        BinaryOperator::And => "&&",
+        BinaryOperator::Or => "||",
+        BinaryOperator::BitAnd => "&",
+        BinaryOperator::BitOr => "|",
+        BinaryOperator::BitXor => "^",
+        BinaryOperator::ShiftLeft => "<<",
+        BinaryOperator::ShiftRight => ">>",
+    }
+}
+
+fn unary_operator(operator: UnaryOperator) -> &'static str {
+    match operator {
+        UnaryOperator::Negate => "-",
+        UnaryOperator::Not => "!",
+        UnaryOperator::BitNot => "~",
+    }
+}
@@ -1,8 +1,18 @@
 //! Tests for the Rive parser.
 
 use rive_lexer::tokenize;
-use rive_parser::ast::FunctionBody;
-use rive_parser::{BinaryOperator, Expression, Item, Statement, parse};
+use rive_parser::ast::{Function, FunctionBody};
+use rive_parser::{
+    BinaryOperator, BindingPattern, Expression, Item, Pattern, Statement, UnaryOperator, parse,
+};
+
+/// Helper function to get the function declared by an item
+fn as_function(item: &Item) -> &Function {
+    match item {
+        Item::Function(func) => func,
+        other => panic!("Expected function, got {other:?}"),
+    }
+}
 
 /// Helper function to get statements from a function body
 fn get_statements(body: &FunctionBody) -> &[Statement] {
@@ -20,7 +30,7 @@ fn test_parse_simple_function() {
 
     assert_eq!(program.items.len(), 1);
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(func.name, "main");
     assert_eq!(func.params.len(), 0);
     assert_eq!(get_statements(&func.body).len(), 0);
@@ -34,7 +44,7 @@ fn test_parse_function_with_parameters() {
 
     assert_eq!(program.items.len(), 1);
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(func.name, "add");
     assert_eq!(func.params.len(), 2);
     assert_eq!(func.params[0].name, "x");
@@ -47,7 +57,7 @@ fn test_parse_let_statement() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(get_statements(&func.body).len(), 1);
 
     if let Statement::Let { name, mutable, .. } = &get_statements(&func.body)[0] {
@@ -64,7 +74,7 @@ fn test_parse_mutable_variable() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     if let Statement::Let { name, mutable, .. } = &get_statements(&func.body)[0] {
         assert_eq!(name, "count");
         assert!(mutable);
@@ -79,7 +89,7 @@ fn test_parse_binary_expression() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     if let Statement::Let { initializer, .. } = &get_statements(&func.body)[0] {
         if let Expression::Binary { operator, .. } = initializer {
             assert_eq!(operator, &BinaryOperator::Add);
@@ -95,7 +105,7 @@ fn test_parse_function_call() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     if let Statement::Expression { expression, .. } = &get_statements(&func.body)[0] {
         if let Expression::Call {
             callee, arguments, ..
@@ -115,7 +125,7 @@ fn test_parse_return_statement() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     if let Statement::Return { value, .. } = &get_statements(&func.body)[0] {
         assert!(value.is_some());
         if let Some(Expression::Integer { value, .. }) = value {
@@ -132,7 +142,7 @@ fn test_parse_array_literal() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     if let Statement::Let { initializer, .. } = &get_statements(&func.body)[0] {
         if let Expression::Array { elements, .. } = initializer {
             assert_eq!(elements.len(), 3);
@@ -155,7 +165,7 @@ fn test_parse_comparison_operators() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(get_statements(&func.body).len(), 6);
 }
 
@@ -167,7 +177,7 @@ fn test_parse_logical_operators() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(get_statements(&func.body).len(), 1);
 }
 
@@ -180,7 +190,7 @@ fn test_parse_unary_operators() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(get_statements(&func.body).len(), 2);
 }
 
@@ -192,7 +202,7 @@ fn test_parse_complex_expression() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(get_statements(&func.body).len(), 1);
 }
 
@@ -208,7 +218,7 @@ fn test_parse_hello_world() {
 
     assert_eq!(program.items.len(), 1);
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(func.name, "main");
     assert_eq!(get_statements(&func.body).len(), 1);
 }
@@ -221,7 +231,7 @@ fn test_parse_nullable_type() {
 
     assert_eq!(program.items.len(), 1);
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(func.name, "test");
     assert_eq!(func.params.len(), 1);
 
@@ -253,7 +263,7 @@ fn test_parse_multiple_nullable_types() {
     let tokens = tokenize(source).unwrap();
     let (program, type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(func.params.len(), 4);
 
     // All parameters should be nullable
@@ -275,7 +285,7 @@ fn test_parse_array_of_nullable_types() {
     let tokens = tokenize(source).unwrap();
     let (program, type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     let param_type = func.params[0].param_type;
     let param_meta = type_registry.get(param_type).unwrap();
 
@@ -314,7 +324,7 @@ fn test_parse_elvis_operator() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(get_statements(&func.body).len(), 2);
 
     // Check the Elvis operator expression
@@ -335,7 +345,7 @@ fn test_parse_chained_elvis() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     // Should parse successfully with nested Elvis
     assert_eq!(get_statements(&func.body).len(), 3);
 }
@@ -349,7 +359,7 @@ fn test_parse_safe_call() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(get_statements(&func.body).len(), 2);
 
     // Check the SafeCall expression
@@ -368,7 +378,7 @@ fn test_parse_chained_safe_call() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     // Should parse successfully with chained safe calls
     assert_eq!(get_statements(&func.body).len(), 1);
 }
@@ -381,7 +391,7 @@ fn test_parse_elvis_with_safe_call() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(get_statements(&func.body).len(), 1);
 
     // Should have Elvis with SafeCall inside
@@ -407,7 +417,7 @@ fn test_parse_block_expression() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(get_statements(&func.body).len(), 1);
 
     // Check block expression
@@ -430,7 +440,7 @@ fn test_parse_elvis_with_block() {
     let tokens = tokenize(source).unwrap();
     let (program, _type_registry) = parse(&tokens).unwrap();
 
-    let Item::Function(func) = &program.items[0];
+    let func = as_function(&program.items[0]);
     assert_eq!(get_statements(&func.body).len(), 2);
 
     // Elvis with block fallback
@@ -444,3 +454,943 @@ fn test_parse_elvis_with_block() {
         panic!("Expected let statement");
     }
 }
+
+#[test]
+fn test_parse_non_null_assert() {
+    let source = r#"fun test() {
+        let x: Int? = 1
+        let y = x!! + !!true
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    if let Statement::Let { initializer, .. } = &get_statements(&func.body)[1] {
+        let Expression::Binary { left, right, .. } = initializer else {
+            panic!("Expected binary expression");
+        };
+        assert!(matches!(&**left, Expression::NonNullAssert { .. }));
+        // Prefix `!!` is a double negation
+        assert!(matches!(
+            &**right,
+            Expression::Unary {
+                operator: UnaryOperator::Not,
+                ..
+            }
+        ));
+    } else {
+        panic!("Expected let statement");
+    }
+}
+
+#[test]
+fn test_parse_if_let() {
+    let source = r#"fun test() {
+        let x: Int? = 1
+        if let v = x {
+            print(v)
+        }
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    let Statement::Expression {
+        expression: Expression::If(if_expr),
+        ..
+    } = &get_statements(&func.body)[1]
+    else {
+        panic!("Expected if statement");
+    };
+    let Expression::LetBinding { name, value, .. } = &*if_expr.condition else {
+        panic!("Expected let binding condition");
+    };
+    assert_eq!(name, "v");
+    assert!(matches!(&**value, Expression::Variable { .. }));
+}
+
+#[test]
+fn test_parse_result_type_and_propagate() {
+    let source = r#"fun test(): Result<Int, Text> {
+        let v = other()?
+        return ok(v)
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    assert_eq!(
+        type_registry.get_type_name(func.return_type),
+        "Result<Int, Text>"
+    );
+    let Statement::Let { initializer, .. } = &get_statements(&func.body)[0] else {
+        panic!("Expected let statement");
+    };
+    assert!(matches!(initializer, Expression::Propagate { .. }));
+}
+
+#[test]
+fn test_parse_result_patterns() {
+    let source = r#"fun test() {
+        when r {
+            ok(v) -> print(v)
+            err(_) -> print(0)
+        }
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    let Statement::Expression {
+        expression: Expression::Match(match_expr),
+        ..
+    } = &get_statements(&func.body)[0]
+    else {
+        panic!("Expected when statement");
+    };
+    assert!(matches!(
+        &match_expr.arms[0].pattern,
+        Pattern::Ok { binding: Some(name), .. } if name == "v"
+    ));
+    assert!(matches!(
+        &match_expr.arms[1].pattern,
+        Pattern::Err { binding: None, .. }
+    ));
+}
+
+#[test]
+fn test_parse_return_in_expression_position() {
+    let source = r#"fun test(x: Int?): Int {
+        let y = x ?: return 0
+        let z = x ?: return
+        y
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    let statements = get_statements(&func.body);
+    let Statement::Let { initializer, .. } = &statements[0] else {
+        panic!("Expected let statement");
+    };
+    let Expression::Elvis { fallback, .. } = initializer else {
+        panic!("Expected elvis expression");
+    };
+    assert!(matches!(
+        &**fallback,
+        Expression::Return { value: Some(_), .. }
+    ));
+
+    // A bare `return` does not take the next line as its value
+    let Statement::Let { initializer, .. } = &statements[1] else {
+        panic!("Expected let statement");
+    };
+    let Expression::Elvis { fallback, .. } = initializer else {
+        panic!("Expected elvis expression");
+    };
+    assert!(matches!(
+        &**fallback,
+        Expression::Return { value: None, .. }
+    ));
+    assert_eq!(statements.len(), 3);
+}
+
+#[test]
+fn test_parse_break_and_continue_in_when_arms() {
+    let source = r#"fun test() {
+        loop {
+            let v = when 1 {
+                0 -> break
+                1 -> continue
+                _ -> 2
+            }
+        }
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    let Statement::Expression {
+        expression: Expression::Loop(loop_expr),
+        ..
+    } = &get_statements(&func.body)[0]
+    else {
+        panic!("Expected loop");
+    };
+    let Statement::Let {
+        initializer: Expression::Match(match_expr),
+        ..
+    } = &loop_expr.body.statements[0]
+    else {
+        panic!("Expected when expression");
+    };
+    assert!(matches!(&*match_expr.arms[0].body, Expression::Break(_)));
+    assert!(matches!(&*match_expr.arms[1].body, Expression::Continue(_)));
+}
+
+#[test]
+fn test_parse_destructuring_let() {
+    let source = r#"fun test() {
+        let (a, mut b) = (1, 2)
+        let (x, (y, _),) = t
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    let statements = get_statements(&func.body);
+    let Statement::Destructure {
+        pattern: BindingPattern::Tuple { elements, .. },
+        ..
+    } = &statements[0]
+    else {
+        panic!("Expected destructuring let");
+    };
+    assert!(matches!(
+        &elements[..],
+        [
+            BindingPattern::Name { mutable: false, .. },
+            BindingPattern::Name { mutable: true, .. }
+        ]
+    ));
+
+    let Statement::Destructure {
+        pattern: BindingPattern::Tuple { elements, .. },
+        ..
+    } = &statements[1]
+    else {
+        panic!("Expected destructuring let");
+    };
+    assert_eq!(elements.len(), 2);
+    let BindingPattern::Tuple {
+        elements: inner, ..
+    } = &elements[1]
+    else {
+        panic!("Expected nested tuple pattern");
+    };
+    assert!(matches!(inner[1], BindingPattern::Wildcard { .. }));
+}
+
+#[test]
+fn test_parse_for_with_tuple_pattern() {
+    let source = r#"fun test() {
+        for (i, v) in pairs { print(i) }
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    let Statement::Expression {
+        expression: Expression::For(for_loop),
+        ..
+    } = &get_statements(&func.body)[0]
+    else {
+        panic!("Expected for loop");
+    };
+    let BindingPattern::Tuple { elements, .. } = &for_loop.pattern else {
+        panic!("Expected tuple pattern");
+    };
+    assert_eq!(elements.len(), 2);
+    assert!(matches!(&*for_loop.iterable, Expression::Variable { .. }));
+}
+
+#[test]
+fn test_parse_mut_before_destructuring_pattern_fails() {
+    let source = r#"fun test() { let mut (a, b) = (1, 2) }"#;
+    let tokens = tokenize(source).unwrap();
+    assert!(parse(&tokens).is_err());
+}
+
+#[test]
+fn test_parse_rich_when_patterns() {
+    let source = r#"fun test() {
+        when t {
+            null -> 0
+            1 | 2 -> 1
+            (n, _) if n > 10 -> n
+            (a, (b, "x")) -> a
+            other -> 2
+        }
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    let Statement::Expression {
+        expression: Expression::Match(match_expr),
+        ..
+    } = &get_statements(&func.body)[0]
+    else {
+        panic!("Expected when expression");
+    };
+    let arms = &match_expr.arms;
+    assert_eq!(arms.len(), 5);
+    assert!(matches!(arms[0].pattern, Pattern::Null { .. }));
+    assert!(
+        matches!(&arms[1].pattern, Pattern::Or { alternatives, .. } if alternatives.len() == 2)
+    );
+    assert!(matches!(arms[2].pattern, Pattern::Tuple { .. }));
+    assert!(arms[2].guard.is_some());
+    let Pattern::Tuple { elements, .. } = &arms[3].pattern else {
+        panic!("Expected tuple pattern");
+    };
+    assert!(matches!(elements[1], Pattern::Tuple { .. }));
+    assert!(arms[3].guard.is_none());
+    assert!(matches!(&arms[4].pattern, Pattern::Binding { name, .. } if name == "other"));
+}
+
+#[test]
+fn test_parse_subjectless_when() {
+    let source = r#"fun test() {
+        let s = when { x < 0 -> "neg"; x == 0 -> "zero"; else -> "pos" }
+        when {
+            x > 10 -> print(x)
+            x > 5 -> { print(0) }
+        }
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    let statements = get_statements(&func.body);
+    let Statement::Let {
+        initializer: Expression::When(when),
+        ..
+    } = &statements[0]
+    else {
+        panic!("Expected when expression");
+    };
+    assert_eq!(when.arms.len(), 2);
+    assert!(matches!(*when.arms[0].condition, Expression::Binary { .. }));
+    assert!(matches!(
+        when.else_arm.as_deref(),
+        Some(Expression::String { .. })
+    ));
+
+    let Statement::Expression {
+        expression: Expression::When(when),
+        ..
+    } = &statements[1]
+    else {
+        panic!("Expected when statement");
+    };
+    assert_eq!(when.arms.len(), 2);
+    assert!(when.else_arm.is_none());
+    assert!(matches!(*when.arms[1].body, Expression::Block(_)));
+}
+
+#[test]
+fn test_parse_when_else_arm_must_be_last() {
+    let source = r#"fun test() { when { else -> 1, x > 0 -> 2 } }"#;
+    let tokens = tokenize(source).unwrap();
+    assert!(parse(&tokens).is_err());
+}
+
+#[test]
+fn test_parse_bitwise_precedence() {
+    // `|` < `^` < `&` < shifts < `+`, all below comparisons
+    let source = r#"fun test() { let x = a | b ^ c & d << 1 + 2 == e }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    let Statement::Let { initializer, .. } = &get_statements(&func.body)[0] else {
+        panic!("Expected let statement");
+    };
+    let Expression::Binary {
+        operator: BinaryOperator::Equal,
+        left,
+        ..
+    } = initializer
+    else {
+        panic!("Expected == at the root");
+    };
+    let Expression::Binary {
+        operator: BinaryOperator::BitOr,
+        right,
+        ..
+    } = &**left
+    else {
+        panic!("Expected | below ==");
+    };
+    let Expression::Binary {
+        operator: BinaryOperator::BitXor,
+        right,
+        ..
+    } = &**right
+    else {
+        panic!("Expected ^ below |");
+    };
+    let Expression::Binary {
+        operator: BinaryOperator::BitAnd,
+        right,
+        ..
+    } = &**right
+    else {
+        panic!("Expected & below ^");
+    };
+    let Expression::Binary {
+        operator: BinaryOperator::ShiftLeft,
+        right,
+        ..
+    } = &**right
+    else {
+        panic!("Expected << below &");
+    };
+    assert!(matches!(
+        **right,
+        Expression::Binary {
+            operator: BinaryOperator::Add,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_parse_bitwise_not() {
+    let source = r#"fun test() { let x = ~a }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    let Statement::Let { initializer, .. } = &get_statements(&func.body)[0] else {
+        panic!("Expected let statement");
+    };
+    assert!(matches!(
+        initializer,
+        Expression::Unary {
+            operator: UnaryOperator::BitNot,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_parse_compound_assignment() {
+    let source = r#"fun test() {
+        x += 1
+        x -= 2
+        x *= 3
+        x /= 4
+        x %= 5
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    let operators: Vec<_> = get_statements(&func.body)
+        .iter()
+        .map(|statement| match statement {
+            Statement::CompoundAssignment { name, operator, .. } => {
+                assert_eq!(name, "x");
+                *operator
+            }
+            other => panic!("Expected compound assignment, found {other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        operators,
+        vec![
+            BinaryOperator::Add,
+            BinaryOperator::Subtract,
+            BinaryOperator::Multiply,
+            BinaryOperator::Divide,
+            BinaryOperator::Modulo,
+        ]
+    );
+}
+
+#[test]
+fn test_parse_nested_type_arguments_closed_by_shift_token() {
+    let source = r#"fun test(): Result<Int, Result<Int, Text>>? { return null }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    assert_eq!(
+        type_registry.get_type_name(func.return_type),
+        "Result<Int, Result<Int, Text>>?"
+    );
+}
+
+/// Helper to parse the initializer of the first `let` in `fun test() { ... }`.
+fn parse_let_initializer(body: &str) -> rive_core::Result<Expression> {
+    let source = format!("fun test() {{ {body} }}");
+    let tokens = tokenize(&source)?;
+    let (program, _type_registry) = parse(&tokens)?;
+
+    let func = as_function(&program.items[0]);
+    let Statement::Let { initializer, .. } = &get_statements(&func.body)[0] else {
+        panic!("Expected let statement");
+    };
+    Ok(initializer.clone())
+}
+
+#[test]
+fn test_parse_subtraction_without_spaces() {
+    let initializer = parse_let_initializer("let x = y-1").unwrap();
+    let Expression::Binary {
+        operator: BinaryOperator::Subtract,
+        right,
+        ..
+    } = initializer
+    else {
+        panic!("Expected subtraction, found {initializer:?}");
+    };
+    assert!(matches!(*right, Expression::Integer { value: 1, .. }));
+}
+
+#[test]
+fn test_parse_negative_literals_are_folded() {
+    let initializer = parse_let_initializer("let x = -42").unwrap();
+    assert!(matches!(
+        initializer,
+        Expression::Integer { value: -42, .. }
+    ));
+
+    let initializer = parse_let_initializer("let x = -2.5").unwrap();
+    assert!(matches!(initializer, Expression::Float { value, .. } if value == -2.5));
+
+    let initializer = parse_let_initializer("let x = 3 * -2").unwrap();
+    let Expression::Binary { right, .. } = initializer else {
+        panic!("Expected multiplication");
+    };
+    assert!(matches!(*right, Expression::Integer { value: -2, .. }));
+}
+
+#[test]
+fn test_parse_min_int_literal() {
+    let initializer = parse_let_initializer("let x = -9223372036854775808").unwrap();
+    assert!(matches!(
+        initializer,
+        Expression::Integer {
+            value: i64::MIN,
+            ..
+        }
+    ));
+
+    let err = parse_let_initializer("let x = 9223372036854775808").unwrap_err();
+    assert!(err.to_string().contains("out of range"), "got: {err}");
+}
+
+#[test]
+fn test_parse_negated_method_receiver_is_not_folded() {
+    // As in Rust, `-1.abs()` negates the result of the call
+    let initializer = parse_let_initializer("let x = -1.abs()").unwrap();
+    assert!(matches!(
+        initializer,
+        Expression::Unary {
+            operator: UnaryOperator::Negate,
+            ..
+        }
+    ));
+
+    let initializer = parse_let_initializer("let x = -y").unwrap();
+    assert!(matches!(
+        initializer,
+        Expression::Unary {
+            operator: UnaryOperator::Negate,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_parse_negative_literal_patterns() {
+    let source = r#"fun test() {
+        when x {
+            -1 -> 0
+            in -5..-2 -> 1
+            -2.5 -> 2
+            _ -> 3
+        }
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    let Statement::Expression {
+        expression: Expression::Match(match_expr),
+        ..
+    } = &get_statements(&func.body)[0]
+    else {
+        panic!("Expected when expression");
+    };
+    let arms = &match_expr.arms;
+    assert!(matches!(
+        arms[0].pattern,
+        Pattern::Integer { value: -1, .. }
+    ));
+    let Pattern::Range { start, end, .. } = &arms[1].pattern else {
+        panic!("Expected range pattern");
+    };
+    assert!(matches!(**start, Expression::Integer { value: -5, .. }));
+    assert!(matches!(**end, Expression::Integer { value: -2, .. }));
+    assert!(matches!(arms[2].pattern, Pattern::Float { value, .. } if value == -2.5));
+}
+
+#[test]
+fn test_parse_minus_at_line_start_begins_statement() {
+    let source = "fun test() {\n let a = b\n -c\n}";
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+    let statements = get_statements(&as_function(&program.items[0]).body);
+    assert_eq!(
+        statements.len(),
+        2,
+        "Expected two statements, found {statements:?}"
+    );
+    assert!(matches!(
+        &statements[0],
+        Statement::Let {
+            initializer: Expression::Variable { .. },
+            ..
+        }
+    ));
+    assert!(matches!(
+        &statements[1],
+        Statement::Expression {
+            expression: Expression::Unary {
+                operator: UnaryOperator::Negate,
+                ..
+            },
+            ..
+        }
+    ));
+
+    // Inside parentheses, brackets and map literals a leading `-` continues the line
+    for body in [
+        "let x = (a\n - b)",
+        "let x = f(a\n - b)",
+        "let x = List(a\n - b)",
+        "let x = [a\n - b]",
+        "let x = {\"k\": a\n - b}",
+    ] {
+        let tokens = tokenize(&format!("fun test() {{ {body} }}")).unwrap();
+        let (program, _type_registry) = parse(&tokens).unwrap();
+        let statements = get_statements(&as_function(&program.items[0]).body);
+        assert_eq!(statements.len(), 1, "Expected one statement for {body:?}");
+    }
+
+    // A statement inside a parenthesized block still ends at the `-`, and so
+    // does a `when` arm body
+    let initializer =
+        parse_let_initializer("let x = when y {\n 1 -> { a\n - b }\n -1 -> 0\n _ -> 1\n }")
+            .unwrap();
+    let Expression::Match(match_expr) = initializer else {
+        panic!("Expected when expression");
+    };
+    assert_eq!(match_expr.arms.len(), 3);
+    let Expression::Block(block) = &*match_expr.arms[0].body else {
+        panic!("Expected block arm body");
+    };
+    assert_eq!(block.statements.len(), 2);
+}
+
+#[test]
+fn test_parse_doc_comments() {
+    let source = r#"
+        /// Adds two numbers.
+        ///
+        ///Returns their sum.
+        fun add(a: Int, b: Int): Int = a + b
+
+        // plain comment
+        fun main() {
+            /// ignored inside bodies
+            let x = add(1, 2)
+        }
+    "#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let add = as_function(&program.items[0]);
+    assert_eq!(
+        add.docs.as_deref(),
+        Some("Adds two numbers.\n\nReturns their sum.")
+    );
+
+    let main = as_function(&program.items[1]);
+    assert_eq!(main.docs, None);
+    assert_eq!(get_statements(&main.body).len(), 1);
+}
+
+#[test]
+fn test_parse_default_parameters_and_named_arguments() {
+    let source = r#"fun connect(host: Text, port: Int = 8080) {
+        connect("x", port = 9000)
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    assert!(func.params[0].default.is_none());
+    assert!(matches!(
+        func.params[1].default,
+        Some(Expression::Integer { value: 8080, .. })
+    ));
+
+    let statements = get_statements(&func.body);
+    let Statement::Expression {
+        expression:
+            Expression::Call {
+                arguments,
+                named_arguments,
+                ..
+            },
+        ..
+    } = &statements[0]
+    else {
+        panic!("Expected call");
+    };
+    assert_eq!(arguments.len(), 1);
+    assert_eq!(named_arguments.len(), 1);
+    assert_eq!(named_arguments[0].name, "port");
+}
+
+#[test]
+fn test_parse_trailing_commas() {
+    let source = r#"fun add(
+        a: Int,
+        b: Int,
+    ): Int {
+        a + b
+    }
+
+    fun main() {
+        let values = [1, 2, 3,]
+        print(add(1, 2,), values.len(),)
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    assert_eq!(as_function(&program.items[0]).params.len(), 2);
+    let statements = get_statements(&as_function(&program.items[1]).body);
+    let Statement::Let {
+        initializer: Expression::Array { elements, .. },
+        ..
+    } = &statements[0]
+    else {
+        panic!("Expected array literal");
+    };
+    assert_eq!(elements.len(), 3);
+}
+
+#[test]
+fn test_parse_positional_after_named_argument() {
+    let source = r#"fun main() {
+        connect(port = 9000, "x")
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let err = parse(&tokens).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Positional arguments must come before named arguments")
+    );
+}
+
+#[test]
+fn test_parse_globals() {
+    let source = r#"
+        /// Largest allowed size
+        const LIMIT: Int = 10
+        let greeting = "hi"
+        fun main() {}
+    "#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let Item::Global(limit) = &program.items[0] else {
+        panic!("Expected global");
+    };
+    assert_eq!(limit.name, "LIMIT");
+    assert!(limit.constant);
+    assert!(limit.var_type.is_some());
+    assert_eq!(limit.docs.as_deref(), Some("Largest allowed size"));
+
+    let Item::Global(greeting) = &program.items[1] else {
+        panic!("Expected global");
+    };
+    assert!(!greeting.constant);
+    assert!(greeting.var_type.is_none());
+}
+
+#[test]
+fn test_parse_mutable_global() {
+    let tokens = tokenize("let mut count = 0").unwrap();
+    let err = parse(&tokens).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Global variables cannot be mutable")
+    );
+}
+
+#[test]
+fn test_parse_local_function() {
+    let source = r#"fun main() {
+        fun double(x: Int): Int = x * 2
+        double(2)
+    }"#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let func = as_function(&program.items[0]);
+    let statements = get_statements(&func.body);
+    let Statement::Function(local) = &statements[0] else {
+        panic!("Expected local function");
+    };
+    assert_eq!(local.name, "double");
+    assert!(matches!(statements[1], Statement::Expression { .. }));
+}
+
+#[test]
+fn test_parse_test_attributes() {
+    let source = r#"
+        /// Checks addition
+        @test
+        fun adds() { assert(1 + 1 == 2) }
+
+        @test(panics)
+        fun fails() { panic("boom") }
+
+        @test(panics = "overflow")
+        fun overflows() { panic("overflow") }
+
+        fun helper() {}
+    "#;
+    let tokens = tokenize(source).unwrap();
+    let (program, _type_registry) = parse(&tokens).unwrap();
+
+    let adds = as_function(&program.items[0]);
+    let test = adds.test.as_ref().unwrap();
+    assert!(!test.should_panic);
+    assert_eq!(adds.docs.as_deref(), Some("Checks addition"));
+
+    let test = as_function(&program.items[1]).test.as_ref().unwrap();
+    assert!(test.should_panic);
+    assert_eq!(test.expected_message, None);
+
+    let test = as_function(&program.items[2]).test.as_ref().unwrap();
+    assert!(test.should_panic);
+    assert_eq!(test.expected_message.as_deref(), Some("overflow"));
+
+    assert!(as_function(&program.items[3]).test.is_none());
+}
+
+#[test]
+fn test_parse_invalid_attributes() {
+    for (source, expected) in [
+        ("@inline fun f() {}", "Unknown attribute '@inline'"),
+        (
+            "@test(ignore) fun f() {}",
+            "Unknown '@test' option 'ignore'",
+        ),
+        (
+            "@test const X = 1",
+            "'@test' can only be applied to functions",
+        ),
+    ] {
+        let tokens = tokenize(source).unwrap();
+        match parse(&tokens) {
+            Err(rive_core::Error::Parser(message, _)) => {
+                assert!(message.contains(expected), "{message}");
+            }
+            other => panic!("Expected a parser error, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_parse_type_aliases() {
+    let source = r#"
+        /// Identifies a user
+        type UserId = Int
+        newtype Meters = Float
+        fun find(id: UserId): Meters = Meters(1.0)
+    "#;
+    let tokens = tokenize(source).unwrap();
+    let (program, type_registry) = parse(&tokens).unwrap();
+
+    let Item::TypeAlias(user_id) = &program.items[0] else {
+        panic!("Expected type alias");
+    };
+    assert_eq!(user_id.name, "UserId");
+    assert!(!user_id.distinct);
+    assert_eq!(user_id.docs.as_deref(), Some("Identifies a user"));
+    assert_eq!(type_registry.get_by_name("UserId"), Some(user_id.type_id));
+    assert_eq!(type_registry.get_type_name(user_id.type_id), "UserId");
+
+    let Item::TypeAlias(meters) = &program.items[1] else {
+        panic!("Expected type alias");
+    };
+    assert!(meters.distinct);
+
+    let func = as_function(&program.items[2]);
+    assert_eq!(func.params[0].param_type, user_id.type_id);
+    assert_eq!(func.return_type, meters.type_id);
+}
+
+#[test]
+fn test_parse_collection_type_alias() {
+    let tokens = tokenize("type Grid = List<List<Int>>").unwrap();
+    let (program, type_registry) = parse(&tokens).unwrap();
+
+    let Item::TypeAlias(grid) = &program.items[0] else {
+        panic!("Expected type alias");
+    };
+    assert_eq!(type_registry.get_type_name(grid.target), "List<List<Int>>");
+}
+
+#[test]
+fn test_parse_type_alias_errors() {
+    let tokens = tokenize("fun f(id: UserId) {}\ntype UserId = Int").unwrap();
+    let err = parse(&tokens).unwrap_err();
+    assert!(err.to_string().contains("Unknown type 'UserId'"));
+
+    let tokens = tokenize("type Int = Float").unwrap();
+    let err = parse(&tokens).unwrap_err();
+    assert!(err.to_string().contains("Type 'Int' is already defined"));
+
+    let tokens = tokenize("type Id = Int\nnewtype Id = Int").unwrap();
+    let err = parse(&tokens).unwrap_err();
+    assert!(err.to_string().contains("Type 'Id' is already defined"));
+}
+
+#[test]
+fn test_syntax_tree() {
+    let source = r#"
+        fun main() {
+            let mut total: Int = 1 + 2
+            if total > 2 { print("big") }
+        }
+    "#;
+    let tokens = tokenize(source).unwrap();
+    let (program, registry) = parse(&tokens).unwrap();
+    let mut tree = rive_parser::syntax_tree(&program, &registry);
+
+    let expected = "\
+Program
+  Function main() -> Unit
+    Block
+      Let mut total: Int
+        Binary +
+          Integer 1
+          Integer 2
+      If
+        Binary >
+          Variable total
+          Integer 2
+        Block
+          Call print
+            String \"big\"
+";
+    assert_eq!(tree.to_string(), expected);
+
+    tree.annotate_types(&|_| Some("Int".to_string()));
+    let annotated = tree.to_string();
+    assert!(annotated.contains("      Let mut total: Int\n        Binary + [Int]\n"));
+    assert!(
+        annotated.contains("    Block\n"),
+        "only expressions are typed"
+    );
+}
@@ -1,45 +1,66 @@
 //! Control flow type checking (if, while, for, loop, break, continue, range).
 
 use crate::checker::core::TypeChecker;
-use rive_core::type_system::TypeId;
+use crate::checker::helpers::unify_branch_types;
+use rive_core::type_system::{TypeId, TypeKind};
 use rive_core::{Error, Result};
-use rive_parser::control_flow::{Break, Continue, For, If, Loop, While};
+use rive_parser::Expression;
+use rive_parser::control_flow::{Break, Continue, For, If, Loop, When, While};
 
 impl TypeChecker {
     /// Checks an if expression/statement.
-    pub(crate) fn check_if(&mut self, if_expr: &If, needs_value: bool) -> Result<TypeId> {
-        self.check_bool_condition(&if_expr.condition, "If", if_expr.span)?;
+    ///
+    /// The value of each branch is checked against `expected`, if given.
+    pub(crate) fn check_if(
+        &mut self,
+        if_expr: &If,
+        needs_value: bool,
+        expected: Option<TypeId>,
+    ) -> Result<TypeId> {
+        let scoped = self.check_condition(&if_expr.condition, "If", if_expr.span)?;
 
         // Check then block
-        let then_type = self.check_block_with_value(&if_expr.then_block)?;
+        let then_type = self.check_block_with_value(&if_expr.then_block, expected)?;
+        if scoped {
+            self.symbols.exit_scope();
+        }
+
+        // Branches that diverge (type Never) adopt the type of the others
+        let mut result_type = then_type;
 
         // Check else-if branches
         for else_if in &if_expr.else_if_branches {
-            self.check_bool_condition(&else_if.condition, "Else-if", else_if.span)?;
+            let scoped = self.check_condition(&else_if.condition, "Else-if", else_if.span)?;
 
-            let else_if_type = self.check_block_with_value(&else_if.block)?;
-            if else_if_type != then_type {
-                return Err(self.type_mismatch_error(
-                    "All if branches must have same type",
-                    then_type,
-                    else_if_type,
-                    else_if.span,
-                ));
+            let else_if_type = self.check_block_with_value(&else_if.block, expected)?;
+            if scoped {
+                self.symbols.exit_scope();
             }
+            result_type =
+                unify_branch_types(self.symbols.type_registry(), result_type, else_if_type)
+                    .ok_or_else(|| {
+                        self.type_mismatch_error(
+                            "All if branches must have same type",
+                            result_type,
+                            else_if_type,
+                            else_if.span,
+                        )
+                    })?;
         }
 
         // Check else block
         if let Some(else_block) = &if_expr.else_block {
-            let else_type = self.check_block_with_value(else_block)?;
-            if else_type != then_type {
-                return Err(self.type_mismatch_error(
-                    "All if branches must have same type",
-                    then_type,
-                    else_type,
-                    if_expr.span,
-                ));
-            }
-            Ok(then_type)
+            let else_type = self.check_block_with_value(else_block, expected)?;
+            unify_branch_types(self.symbols.type_registry(), result_type, else_type).ok_or_else(
+                || {
+                    self.type_mismatch_error(
+                        "All if branches must have same type",
+                        result_type,
+                        else_type,
+                        if_expr.span,
+                    )
+                },
+            )
         } else {
             // No else block
             if needs_value {
@@ -53,6 +74,68 @@ impl TypeChecker {
         }
     }
 
+    /// Checks a subject-less when expression/statement.
+    ///
+    /// Arms are unified like the branches of an if/else-if chain; the `else`
+    /// arm is required when the when is used as a value. Arm values are
+    /// checked against `expected`, if given.
+    pub(crate) fn check_when(
+        &mut self,
+        when: &When,
+        needs_value: bool,
+        expected: Option<TypeId>,
+    ) -> Result<TypeId> {
+        let mut result_type = TypeId::NEVER;
+
+        for arm in &when.arms {
+            self.check_bool_condition(&arm.condition, "When", arm.condition.span())?;
+            let arm_type = self.check_branch_value(&arm.body, expected)?;
+            result_type = self.unify_when_arm(result_type, arm_type, arm.span, needs_value)?;
+        }
+
+        let Some(else_arm) = &when.else_arm else {
+            if needs_value {
+                return Err(Error::SemanticWithSpan(
+                    "When expression must have an 'else' arm when used as a value".to_string(),
+                    when.span,
+                ));
+            }
+            return Ok(TypeId::UNIT);
+        };
+
+        let else_type = self.check_branch_value(else_arm, expected)?;
+        let result_type =
+            self.unify_when_arm(result_type, else_type, else_arm.span(), needs_value)?;
+        Ok(if needs_value {
+            result_type
+        } else {
+            TypeId::UNIT
+        })
+    }
+
+    /// Unifies the type of a when arm with the arms before it.
+    ///
+    /// Arm types only have to agree when the when produces a value.
+    fn unify_when_arm(
+        &self,
+        result_type: TypeId,
+        arm_type: TypeId,
+        span: rive_core::Span,
+        needs_value: bool,
+    ) -> Result<TypeId> {
+        if !needs_value {
+            return Ok(result_type);
+        }
+        unify_branch_types(self.symbols.type_registry(), result_type, arm_type).ok_or_else(|| {
+            self.type_mismatch_error(
+                "All when arms must have same type",
+                result_type,
+                arm_type,
+                span,
+            )
+        })
+    }
+
     /// Checks a break statement.
     pub(crate) fn check_break(&mut self, break_stmt: &Break) -> Result<TypeId> {
         // Validate we're in a loop
@@ -145,19 +228,20 @@ impl TypeChecker {
     /// Returns Optional<T> where T is the break value type, or Optional<Unit> if no break with value.
     pub(crate) fn check_while_expr(&mut self, while_loop: &While) -> Result<TypeId> {
         // Check condition
-        self.check_bool_condition(&while_loop.condition, "While", while_loop.span)?;
+        let scoped = self.check_condition(&while_loop.condition, "While", while_loop.span)?;
 
         // Enter loop context
         let loop_ctx = crate::checker::loops::LoopContext::new(while_loop.label.clone());
         self.loop_stack.push(loop_ctx);
 
         // Check body statements
-        for stmt in &while_loop.body.statements {
-            self.check_statement(stmt)?;
-        }
+        self.check_statements(&while_loop.body.statements)?;
 
         // Exit loop context and get result type
         let loop_ctx = self.loop_stack.pop().unwrap();
+        if scoped {
+            self.symbols.exit_scope();
+        }
         let result_type = if let Some(break_type) = loop_ctx.break_type {
             // Has break with value: return Optional<T>
             self.get_or_create_nullable(break_type)
@@ -172,25 +256,18 @@ impl TypeChecker {
     /// Checks a for loop expression.
     /// Returns Optional<T> where T is the break value type, or Optional<Unit> if no break with value.
     pub(crate) fn check_for_expr(&mut self, for_loop: &For) -> Result<TypeId> {
-        // Check iterable (should be a range)
-        self.check_expression(&for_loop.iterable)?;
+        let element_type = self.check_iterable(&for_loop.iterable)?;
 
-        // Enter new scope for loop variable
+        // Enter new scope for loop variables
         self.symbols.enter_scope();
-
-        // Define loop variable (Int type for ranges, immutable)
-        let symbol =
-            crate::symbol_table::Symbol::new(for_loop.variable.clone(), TypeId::INT, false);
-        self.symbols.define(symbol)?;
+        self.define_binding_pattern(&for_loop.pattern, element_type)?;
 
         // Enter loop context
         let loop_ctx = crate::checker::loops::LoopContext::new(for_loop.label.clone());
         self.loop_stack.push(loop_ctx);
 
         // Check body statements
-        for stmt in &for_loop.body.statements {
-            self.check_statement(stmt)?;
-        }
+        self.check_statements(&for_loop.body.statements)?;
 
         // Exit loop context and get result type
         let loop_ctx = self.loop_stack.pop().unwrap();
@@ -208,6 +285,47 @@ impl TypeChecker {
         Ok(result_type)
     }
 
+    /// Checks the iterable of a for loop and returns the type of its elements.
+    /// Ranges yield Int, arrays and lists their element type, and maps (key, value) tuples.
+    fn check_iterable(&mut self, iterable: &Expression) -> Result<TypeId> {
+        if let Expression::Range(range) = iterable {
+            for bound in [&range.start, &range.end] {
+                let bound_type = self.check_expression(bound)?;
+                if !self.types_compatible(TypeId::INT, bound_type) {
+                    return Err(self.type_mismatch_error(
+                        "Range bound type mismatch",
+                        TypeId::INT,
+                        bound_type,
+                        bound.span(),
+                    ));
+                }
+            }
+            return Ok(TypeId::INT);
+        }
+
+        let iterable_type = self.check_expression(iterable)?;
+        let kind = self
+            .symbols
+            .type_registry()
+            .get(iterable_type)
+            .map(|meta| meta.kind.clone());
+
+        match kind {
+            Some(TypeKind::Array { element, .. } | TypeKind::List { element }) => Ok(element),
+            Some(TypeKind::Map { key, value }) => Ok(self
+                .symbols
+                .type_registry_mut()
+                .create_tuple(vec![key, value])),
+            _ => {
+                let type_name = self.symbols.type_registry().get_type_name(iterable_type);
+                Err(Error::SemanticWithSpan(
+                    format!("Cannot iterate over '{type_name}'"),
+                    iterable.span(),
+                ))
+            }
+        }
+    }
+
     /// Checks an infinite loop expression.
     /// Returns Optional<T> where T is the break value type, or Optional<Unit> if no break with value.
     pub(crate) fn check_loop_expr(&mut self, loop_expr: &Loop) -> Result<TypeId> {
@@ -216,13 +334,14 @@ impl TypeChecker {
         self.loop_stack.push(loop_ctx);
 
         // Check body statements
-        for stmt in &loop_expr.body.statements {
-            self.check_statement(stmt)?;
-        }
+        self.check_statements(&loop_expr.body.statements)?;
 
         // Exit loop context and get result type
         let loop_ctx = self.loop_stack.pop().unwrap();
-        let result_type = if let Some(break_type) = loop_ctx.break_type {
+        let result_type = if !loop_ctx.has_break {
+            // Nothing leaves the loop except return, panic or exit
+            TypeId::NEVER
+        } else if let Some(break_type) = loop_ctx.break_type {
             // Has break with value: return Optional<T>
             self.get_or_create_nullable(break_type)
         } else {
@@ -3,6 +3,8 @@
 use crate::checker::loops::LoopContext;
 use crate::symbol_table::SymbolTable;
 use rive_core::type_system::TypeId;
+use rive_core::{Span, Warning};
+use std::collections::{HashMap, HashSet};
 
 /// Type checker for Rive programs.
 ///
@@ -15,6 +17,14 @@ pub struct TypeChecker {
     pub(crate) current_function_return_type: Option<TypeId>,
     /// Stack of loop contexts for break/continue validation
     pub(crate) loop_stack: Vec<LoopContext>,
+    /// Warnings collected during checking
+    pub(crate) warnings: Vec<Warning>,
+    /// Local functions being checked, innermost last, with the scope depth of their parameters
+    pub(crate) local_functions: Vec<(String, usize)>,
+    /// Globals whose declaration hasn't been checked yet
+    pub(crate) pending_globals: HashSet<String>,
+    /// Type of every checked expression, by span
+    pub(crate) expression_types: HashMap<Span, TypeId>,
 }
 
 impl TypeChecker {
@@ -24,6 +34,10 @@ impl TypeChecker {
             symbols: SymbolTable::new(),
             current_function_return_type: None,
             loop_stack: Vec::new(),
+            warnings: Vec::new(),
+            local_functions: Vec::new(),
+            pending_globals: HashSet::new(),
+            expression_types: HashMap::new(),
         }
     }
 
@@ -36,9 +50,35 @@ impl TypeChecker {
             symbols,
             current_function_return_type: None,
             loop_stack: Vec::new(),
+            warnings: Vec::new(),
+            local_functions: Vec::new(),
+            pending_globals: HashSet::new(),
+            expression_types: HashMap::new(),
         }
     }
 
+    /// Returns the warnings collected so far.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Takes the collected warnings, leaving none behind.
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Takes the type of every checked expression, keyed by the expression's span.
+    ///
+    /// When several expressions share a span, the outermost one's type is kept.
+    pub fn take_expression_types(&mut self) -> HashMap<Span, TypeId> {
+        std::mem::take(&mut self.expression_types)
+    }
+
+    /// Returns the symbol table, with the declarations and uses recorded so far.
+    pub fn symbols(&self) -> &SymbolTable {
+        &self.symbols
+    }
+
     /// Consumes the type checker and returns the type registry.
     ///
     /// This is useful for extracting the type registry after semantic analysis
@@ -0,0 +1,370 @@
+//! Pattern-space analysis for `when` expressions.
+//!
+//! Arms are checked with the usefulness algorithm: a pattern is useful if it
+//! matches some value that no earlier pattern matches. An arm whose pattern is
+//! not useful is unreachable, and a match is exhaustive when a wildcard added
+//! after the last arm would not be useful.
+//!
+//! Patterns are deconstructed into the constructors of their type: Bool values,
+//! Int intervals, Text and Float literals, `null`/non-null, `ok`/`err` and tuples.
+//! Wildcards and bindings match every constructor.
+
+use rive_core::Span;
+use rive_core::type_system::{TypeId, TypeKind, TypeRegistry};
+use rive_parser::Expression;
+use rive_parser::ast::UnaryOperator;
+use rive_parser::control_flow::{MatchArm, Pattern};
+use std::collections::BTreeSet;
+
+/// Result of analyzing the arms of a `when`.
+pub(crate) struct MatchAnalysis {
+    /// Patterns of arms that can never be reached
+    pub unreachable: Vec<Span>,
+    /// Whether every value of the scrutinee type is matched by an unguarded arm
+    pub exhaustive: bool,
+}
+
+/// Analyzes the arms of a `when` over a scrutinee of type `scrutinee_type`.
+pub(crate) fn analyze_match(
+    registry: &TypeRegistry,
+    scrutinee_type: TypeId,
+    arms: &[MatchArm],
+) -> MatchAnalysis {
+    let analyzer = Analyzer { registry };
+    let types = [scrutinee_type];
+
+    let mut matrix: Vec<Vec<Deconstructed>> = Vec::new();
+    let mut unreachable = Vec::new();
+    for arm in arms {
+        let row = vec![analyzer.deconstruct(&arm.pattern, scrutinee_type)];
+        if !analyzer.is_useful(&matrix, &row, &types) {
+            unreachable.push(arm.pattern.span());
+        }
+        // Guarded arms may not match, so they cover nothing
+        if arm.guard.is_none() {
+            matrix.push(row);
+        }
+    }
+
+    let exhaustive = !analyzer.is_useful(&matrix, &[Deconstructed::Wild], &types);
+    MatchAnalysis {
+        unreachable,
+        exhaustive,
+    }
+}
+
+/// A pattern deconstructed for analysis.
+#[derive(Debug, Clone)]
+enum Deconstructed {
+    /// Matches every value (`_` or a binding)
+    Wild,
+    /// Matches values built by a constructor whose fields match the sub-patterns
+    Ctor(Constructor, Vec<Deconstructed>),
+    /// Matches if any alternative matches
+    Or(Vec<Deconstructed>),
+    /// Matches values the analysis cannot determine (e.g. a range with
+    /// non-literal bounds); never unreachable and never counted as covering
+    Opaque,
+}
+
+/// A constructor of a value.
+#[derive(Debug, Clone, PartialEq)]
+enum Constructor {
+    Bool(bool),
+    /// Inclusive interval of integers
+    Int(i128, i128),
+    Text(String),
+    /// Float literal, compared by bit pattern
+    Float(u64),
+    Null,
+    NonNull,
+    Ok,
+    Err,
+    Tuple,
+}
+
+impl Constructor {
+    /// Returns true if every value built by `other` is also built by `self`.
+    fn covers(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Int(lo, hi), Self::Int(other_lo, other_hi)) => lo <= other_lo && other_hi <= hi,
+            _ => self == other,
+        }
+    }
+}
+
+struct Analyzer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl Analyzer<'_> {
+    /// Deconstructs a pattern matched against a value of type `type_id`.
+    fn deconstruct(&self, pattern: &Pattern, type_id: TypeId) -> Deconstructed {
+        match pattern {
+            Pattern::Wildcard { .. } | Pattern::Binding { .. } => return Deconstructed::Wild,
+            Pattern::Or { alternatives, .. } => {
+                return Deconstructed::Or(
+                    alternatives
+                        .iter()
+                        .map(|alternative| self.deconstruct(alternative, type_id))
+                        .collect(),
+                );
+            }
+            Pattern::Null { .. } => return Deconstructed::Ctor(Constructor::Null, Vec::new()),
+            _ => {}
+        }
+
+        let kind = self.registry.get(type_id).map(|meta| &meta.kind);
+
+        // Other patterns match the contents of a non-null value
+        if let Some(TypeKind::Optional { inner }) = kind {
+            let inner = self.deconstruct(pattern, *inner);
+            return Deconstructed::Ctor(Constructor::NonNull, vec![inner]);
+        }
+
+        match pattern {
+            Pattern::Integer { value, .. } => {
+                let value = i128::from(*value);
+                Deconstructed::Ctor(Constructor::Int(value, value), Vec::new())
+            }
+            Pattern::Boolean { value, .. } => {
+                Deconstructed::Ctor(Constructor::Bool(*value), Vec::new())
+            }
+            Pattern::String { value, .. } => {
+                Deconstructed::Ctor(Constructor::Text(value.clone()), Vec::new())
+            }
+            Pattern::Float { value, .. } => {
+                Deconstructed::Ctor(Constructor::Float(value.to_bits()), Vec::new())
+            }
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+                ..
+            } => match (integer_literal(start), integer_literal(end)) {
+                (Some(start), Some(end)) => {
+                    let end = if *inclusive { end } else { end - 1 };
+                    Deconstructed::Ctor(Constructor::Int(start, end), Vec::new())
+                }
+                _ => Deconstructed::Opaque,
+            },
+            Pattern::Ok { .. } => Deconstructed::Ctor(Constructor::Ok, vec![Deconstructed::Wild]),
+            Pattern::Err { .. } => Deconstructed::Ctor(Constructor::Err, vec![Deconstructed::Wild]),
+            Pattern::Tuple { elements, .. } => match kind {
+                Some(TypeKind::Tuple {
+                    elements: element_types,
+                }) => Deconstructed::Ctor(
+                    Constructor::Tuple,
+                    elements
+                        .iter()
+                        .zip(element_types)
+                        .map(|(element, element_type)| self.deconstruct(element, *element_type))
+                        .collect(),
+                ),
+                _ => Deconstructed::Opaque,
+            },
+            Pattern::Wildcard { .. }
+            | Pattern::Binding { .. }
+            | Pattern::Or { .. }
+            | Pattern::Null { .. } => unreachable!("handled above"),
+        }
+    }
+
+    /// Returns true if `row` matches a value that no row of `matrix` matches.
+    /// `types` holds the type of each column.
+    fn is_useful(
+        &self,
+        matrix: &[Vec<Deconstructed>],
+        row: &[Deconstructed],
+        types: &[TypeId],
+    ) -> bool {
+        let Some((head, tail)) = row.split_first() else {
+            return matrix.is_empty();
+        };
+        let matrix = expand_or_heads(matrix);
+
+        match head {
+            Deconstructed::Or(alternatives) => alternatives.iter().any(|alternative| {
+                let row: Vec<_> = std::iter::once(alternative.clone())
+                    .chain(tail.iter().cloned())
+                    .collect();
+                self.is_useful(&matrix, &row, types)
+            }),
+            Deconstructed::Opaque => true,
+            Deconstructed::Ctor(constructor, _) => {
+                let constructors = match constructor {
+                    Constructor::Int(lo, hi) => split_int_range(&matrix, *lo, *hi),
+                    _ => vec![constructor.clone()],
+                };
+                constructors
+                    .iter()
+                    .any(|constructor| self.is_useful_for(&matrix, row, types, constructor))
+            }
+            Deconstructed::Wild => match self.all_constructors(&matrix, types[0]) {
+                Some(constructors) => constructors
+                    .iter()
+                    .any(|constructor| self.is_useful_for(&matrix, row, types, constructor)),
+                None => {
+                    let default: Vec<_> = matrix
+                        .iter()
+                        .filter(|matrix_row| matches!(matrix_row[0], Deconstructed::Wild))
+                        .map(|matrix_row| matrix_row[1..].to_vec())
+                        .collect();
+                    self.is_useful(&default, tail, &types[1..])
+                }
+            },
+        }
+    }
+
+    /// Checks usefulness restricted to values built by `constructor`.
+    fn is_useful_for(
+        &self,
+        matrix: &[Vec<Deconstructed>],
+        row: &[Deconstructed],
+        types: &[TypeId],
+        constructor: &Constructor,
+    ) -> bool {
+        let field_types = self.field_types(constructor, types[0]);
+        let arity = field_types.len();
+
+        let specialized: Vec<_> = matrix
+            .iter()
+            .filter_map(|matrix_row| specialize(matrix_row, constructor, arity))
+            .collect();
+        let Some(row) = specialize(row, constructor, arity) else {
+            return false;
+        };
+        let types: Vec<_> = field_types
+            .into_iter()
+            .chain(types[1..].iter().copied())
+            .collect();
+
+        self.is_useful(&specialized, &row, &types)
+    }
+
+    /// Returns every constructor of a type, or `None` if the type has
+    /// infinitely many (Text, Float) or none that patterns can name.
+    /// Int is split into the intervals distinguished by the ranges in `matrix`.
+    fn all_constructors(
+        &self,
+        matrix: &[Vec<Deconstructed>],
+        type_id: TypeId,
+    ) -> Option<Vec<Constructor>> {
+        let type_id = self.registry.resolve(type_id);
+        if type_id == TypeId::BOOL {
+            return Some(vec![Constructor::Bool(true), Constructor::Bool(false)]);
+        }
+        if type_id == TypeId::INT {
+            return Some(split_int_range(
+                matrix,
+                i128::from(i64::MIN),
+                i128::from(i64::MAX),
+            ));
+        }
+
+        match self.registry.get(type_id)?.kind {
+            TypeKind::Optional { .. } => Some(vec![Constructor::Null, Constructor::NonNull]),
+            TypeKind::Result { .. } => Some(vec![Constructor::Ok, Constructor::Err]),
+            TypeKind::Tuple { .. } => Some(vec![Constructor::Tuple]),
+            _ => None,
+        }
+    }
+
+    /// Returns the types of the fields of a constructor of `type_id`.
+    fn field_types(&self, constructor: &Constructor, type_id: TypeId) -> Vec<TypeId> {
+        let Some(meta) = self.registry.get(type_id) else {
+            return Vec::new();
+        };
+        match (constructor, &meta.kind) {
+            (Constructor::NonNull, TypeKind::Optional { inner }) => vec![*inner],
+            (Constructor::Ok, TypeKind::Result { ok, .. }) => vec![*ok],
+            (Constructor::Err, TypeKind::Result { err, .. }) => vec![*err],
+            (Constructor::Tuple, TypeKind::Tuple { elements }) => elements.clone(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Replaces rows whose first pattern is an or-pattern with one row per alternative.
+fn expand_or_heads(matrix: &[Vec<Deconstructed>]) -> Vec<Vec<Deconstructed>> {
+    let mut expanded = Vec::new();
+    for row in matrix {
+        match row.first() {
+            Some(Deconstructed::Or(alternatives)) => {
+                let alternatives: Vec<_> = alternatives
+                    .iter()
+                    .map(|alternative| {
+                        std::iter::once(alternative.clone())
+                            .chain(row[1..].iter().cloned())
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+                expanded.extend(expand_or_heads(&alternatives));
+            }
+            _ => expanded.push(row.clone()),
+        }
+    }
+    expanded
+}
+
+/// Specializes a row to values built by `constructor`, replacing its first
+/// pattern by the patterns of the constructor's fields. Returns `None` if
+/// the row cannot match such values.
+fn specialize(
+    row: &[Deconstructed],
+    constructor: &Constructor,
+    arity: usize,
+) -> Option<Vec<Deconstructed>> {
+    let (head, tail) = row.split_first()?;
+    let mut fields = match head {
+        Deconstructed::Wild => vec![Deconstructed::Wild; arity],
+        Deconstructed::Ctor(head_constructor, fields) if head_constructor.covers(constructor) => {
+            fields.clone()
+        }
+        _ => return None,
+    };
+    fields.extend(tail.iter().cloned());
+    Some(fields)
+}
+
+/// Splits the interval `lo..=hi` at the bounds of every Int range in the first
+/// column of `matrix`, so each part is either inside or outside of each range.
+fn split_int_range(matrix: &[Vec<Deconstructed>], lo: i128, hi: i128) -> Vec<Constructor> {
+    if lo > hi {
+        return Vec::new();
+    }
+
+    let mut bounds = BTreeSet::from([lo]);
+    for row in matrix {
+        if let Some(Deconstructed::Ctor(Constructor::Int(start, end), _)) = row.first() {
+            for bound in [*start, end + 1] {
+                if lo < bound && bound <= hi {
+                    bounds.insert(bound);
+                }
+            }
+        }
+    }
+
+    let bounds: Vec<_> = bounds.into_iter().collect();
+    bounds
+        .iter()
+        .enumerate()
+        .map(|(index, &start)| {
+            let end = bounds.get(index + 1).map_or(hi, |next| next - 1);
+            Constructor::Int(start, end)
+        })
+        .collect()
+}
+
+/// Returns the value of an integer literal, possibly negated.
+fn integer_literal(expression: &Expression) -> Option<i128> {
+    match expression {
+        Expression::Integer { value, .. } => Some(i128::from(*value)),
+        Expression::Unary {
+            operator: UnaryOperator::Negate,
+            operand,
+            ..
+        } => integer_literal(operand).map(|value| -value),
+        _ => None,
+    }
+}
@@ -0,0 +1,136 @@
+//! Built-in function type checking.
+//!
+//! Covers the diagnostic functions `panic`, `assert`, `assert_eq`, `todo` and
+//! `unreachable`, and the process functions `args` and `exit`.
+//! The diverging builtins have type `Never`, so they can be used wherever a
+//! value is expected (e.g. in a `when` arm).
+
+use crate::checker::core::TypeChecker;
+use rive_core::type_system::TypeId;
+use rive_core::{Error, Result, Span};
+use rive_parser::ast::Expression;
+
+/// Returns true if `name` is a built-in function other than `print`.
+pub(crate) fn is_builtin_function(name: &str) -> bool {
+    matches!(
+        name,
+        "panic" | "assert" | "assert_eq" | "todo" | "unreachable" | "args" | "exit"
+    )
+}
+
+impl TypeChecker {
+    /// Checks a call to a built-in function and returns its type.
+    pub(super) fn check_builtin_call(
+        &mut self,
+        callee: &str,
+        arguments: &[Expression],
+        span: Span,
+    ) -> Result<TypeId> {
+        match callee {
+            // panic(message)
+            "panic" => {
+                self.check_builtin_arity(callee, arguments, 1, 1, span)?;
+                self.check_builtin_message(callee, &arguments[0])?;
+                Ok(TypeId::NEVER)
+            }
+
+            // assert(condition) or assert(condition, message)
+            "assert" => {
+                self.check_builtin_arity(callee, arguments, 1, 2, span)?;
+                self.check_bool_condition(&arguments[0], "assert", arguments[0].span())?;
+                if let Some(message) = arguments.get(1) {
+                    self.check_builtin_message(callee, message)?;
+                }
+                Ok(TypeId::UNIT)
+            }
+
+            // assert_eq(left, right)
+            "assert_eq" => {
+                self.check_builtin_arity(callee, arguments, 2, 2, span)?;
+                let left = self.check_expression(&arguments[0])?;
+                let right = self.check_expression(&arguments[1])?;
+                let registry = self.symbols.type_registry();
+                if !registry.are_compatible(left, right) && !registry.are_compatible(right, left) {
+                    return Err(self.type_mismatch_error(
+                        "assert_eq operands must have the same type",
+                        left,
+                        right,
+                        arguments[1].span(),
+                    ));
+                }
+                Ok(TypeId::UNIT)
+            }
+
+            // args(): the command-line arguments, without the program name
+            "args" => {
+                self.check_builtin_arity(callee, arguments, 0, 0, span)?;
+                Ok(self.symbols.type_registry_mut().create_list(TypeId::TEXT))
+            }
+
+            // exit(status)
+            "exit" => {
+                self.check_builtin_arity(callee, arguments, 1, 1, span)?;
+                let status_type = self.check_expression(&arguments[0])?;
+                if !self.types_compatible(TypeId::INT, status_type) {
+                    return Err(self.type_mismatch_error(
+                        "'exit' status must be Int",
+                        TypeId::INT,
+                        status_type,
+                        arguments[0].span(),
+                    ));
+                }
+                Ok(TypeId::NEVER)
+            }
+
+            // todo() / unreachable(), each with an optional message
+            _ => {
+                self.check_builtin_arity(callee, arguments, 0, 1, span)?;
+                if let Some(message) = arguments.first() {
+                    self.check_builtin_message(callee, message)?;
+                }
+                Ok(TypeId::NEVER)
+            }
+        }
+    }
+
+    /// Checks that a builtin received between `min` and `max` arguments.
+    fn check_builtin_arity(
+        &self,
+        callee: &str,
+        arguments: &[Expression],
+        min: usize,
+        max: usize,
+        span: Span,
+    ) -> Result<()> {
+        if (min..=max).contains(&arguments.len()) {
+            return Ok(());
+        }
+
+        let expected = if min == max {
+            format!("{min}")
+        } else {
+            format!("{min} to {max}")
+        };
+        Err(Error::SemanticWithSpan(
+            format!(
+                "'{callee}' expects {expected} arguments, but {} were provided",
+                arguments.len()
+            ),
+            span,
+        ))
+    }
+
+    /// Checks that a builtin message argument is Text.
+    fn check_builtin_message(&mut self, callee: &str, message: &Expression) -> Result<()> {
+        let message_type = self.check_expression(message)?;
+        if !self.types_compatible(TypeId::TEXT, message_type) {
+            return Err(self.type_mismatch_error(
+                &format!("'{callee}' message must be Text"),
+                TypeId::TEXT,
+                message_type,
+                message.span(),
+            ));
+        }
+        Ok(())
+    }
+}
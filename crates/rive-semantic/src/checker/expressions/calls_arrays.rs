@@ -3,7 +3,7 @@
 use crate::checker::core::TypeChecker;
 use rive_core::type_system::TypeId;
 use rive_core::{Error, Result, Span};
-use rive_parser::ast::Expression;
+use rive_parser::ast::{Expression, NamedArgument, Parameter, is_result_constructor};
 
 impl TypeChecker {
     /// Checks a function call.
@@ -11,8 +11,25 @@ impl TypeChecker {
         &mut self,
         callee: &str,
         arguments: &[Expression],
+        named_arguments: &[NamedArgument],
         span: Span,
     ) -> Result<TypeId> {
+        let params = self
+            .symbols
+            .lookup(callee)
+            .and_then(|symbol| symbol.params.clone());
+        // User functions shadow every builtin except `print`
+        let is_builtin_function = params.is_none() && super::builtins::is_builtin_function(callee);
+        let is_builtin = callee == "print" || is_builtin_function || is_result_constructor(callee);
+        if let Some(named) = named_arguments.first()
+            && (is_builtin || params.is_none())
+        {
+            return Err(Error::SemanticWithSpan(
+                format!("'{callee}' does not accept named arguments"),
+                named.span,
+            ));
+        }
+
         // Special handling for built-in print function
         if callee == "print" {
             if arguments.is_empty() {
@@ -28,7 +45,28 @@ impl TypeChecker {
             return Ok(TypeId::UNIT);
         }
 
+        // Built-in diagnostic functions (panic, assert, ...)
+        if is_builtin_function {
+            return self.check_builtin_call(callee, arguments, span);
+        }
+
+        // Result constructors need an expected type from their context
+        if is_result_constructor(callee) {
+            return self.check_result_constructor(callee, arguments, None, span);
+        }
+
+        // A distinct alias is constructed by calling it with a value of its target type
+        if self.symbols.lookup(callee).is_none()
+            && let Some(alias_type) = self.symbols.type_registry().get_by_name(callee)
+            && let Some(alias) = self.symbols.type_registry().get_alias(alias_type)
+            && alias.distinct
+        {
+            let target = alias.target;
+            return self.check_newtype_constructor(callee, alias_type, target, arguments, span);
+        }
+
         // Look up function symbol
+        self.symbols.record_reference(callee, span);
         let func_type_id = self
             .symbols
             .lookup(callee)
@@ -55,6 +93,71 @@ impl TypeChecker {
             }
         };
 
+        let Some(params) = params else {
+            return self
+                .check_positional_arguments(callee, &param_types, arguments, span)
+                .map(|()| return_type);
+        };
+
+        let bound = bind_arguments(callee, &params, arguments, named_arguments, span)?;
+        for (i, (param, argument)) in params.iter().zip(bound).enumerate() {
+            let Some(argument) = argument else {
+                continue;
+            };
+            let arg_type = self.check_expression_expecting(argument, param.param_type)?;
+            if !self.types_compatible(param.param_type, arg_type) {
+                let label = if i < arguments.len() {
+                    format!("Argument {} type mismatch", i + 1)
+                } else {
+                    format!("Argument '{}' type mismatch", param.name)
+                };
+                return Err(self.type_mismatch_error(&label, param.param_type, arg_type, span));
+            }
+        }
+
+        Ok(return_type)
+    }
+
+    /// Checks `Name(value)`, which wraps a value of its target type in a distinct alias.
+    fn check_newtype_constructor(
+        &mut self,
+        callee: &str,
+        alias_type: TypeId,
+        target: TypeId,
+        arguments: &[Expression],
+        span: Span,
+    ) -> Result<TypeId> {
+        let [argument] = arguments else {
+            return Err(Error::SemanticWithSpan(
+                format!(
+                    "Type '{callee}' expects 1 argument, but {} were provided",
+                    arguments.len()
+                ),
+                span,
+            ));
+        };
+
+        let arg_type = self.check_expression_expecting(argument, target)?;
+        if !self.types_compatible(target, arg_type) {
+            return Err(self.type_mismatch_error(
+                "Argument 1 type mismatch",
+                target,
+                arg_type,
+                span,
+            ));
+        }
+
+        Ok(alias_type)
+    }
+
+    /// Checks a call to a function value that only has a type, not declared parameters.
+    fn check_positional_arguments(
+        &mut self,
+        callee: &str,
+        param_types: &[TypeId],
+        arguments: &[Expression],
+        span: Span,
+    ) -> Result<()> {
         // Check argument count
         if arguments.len() != param_types.len() {
             return Err(Error::SemanticWithSpan(
@@ -67,13 +170,9 @@ impl TypeChecker {
             ));
         }
 
-        // Clone arguments to avoid borrow issues during checking
-        let args_clone = arguments.to_vec();
-
         // Check argument types
-        for (i, (expected_type, arg)) in param_types.iter().zip(args_clone.iter()).enumerate() {
-            let arg_type = self.check_expression(arg)?;
-            // Check if arg_type can be assigned to expected_type
+        for (i, (expected_type, arg)) in param_types.iter().zip(arguments).enumerate() {
+            let arg_type = self.check_expression_expecting(arg, *expected_type)?;
             if !self.types_compatible(*expected_type, arg_type) {
                 return Err(self.type_mismatch_error(
                     &format!("Argument {} type mismatch", i + 1),
@@ -84,7 +183,7 @@ impl TypeChecker {
             }
         }
 
-        Ok(return_type)
+        Ok(())
     }
 
     /// Checks an array literal.
@@ -324,3 +423,78 @@ impl TypeChecker {
         }
     }
 }
+
+/// Matches a call's arguments to the callee's parameters.
+///
+/// Returns the argument supplied for each parameter, or `None` where the
+/// parameter's default value is used.
+fn bind_arguments<'a>(
+    callee: &str,
+    params: &[Parameter],
+    arguments: &'a [Expression],
+    named_arguments: &'a [NamedArgument],
+    span: Span,
+) -> Result<Vec<Option<&'a Expression>>> {
+    let required = params.iter().filter(|p| p.default.is_none()).count();
+    let arity_error = || {
+        let expected = if required == params.len() {
+            params.len().to_string()
+        } else {
+            format!("{required} to {}", params.len())
+        };
+        Error::SemanticWithSpan(
+            format!(
+                "Function '{callee}' expects {expected} arguments, but {} were provided",
+                arguments.len() + named_arguments.len()
+            ),
+            span,
+        )
+    };
+
+    if arguments.len() > params.len() {
+        return Err(arity_error());
+    }
+
+    let mut bound: Vec<Option<&Expression>> = vec![None; params.len()];
+    for (slot, argument) in bound.iter_mut().zip(arguments) {
+        *slot = Some(argument);
+    }
+
+    for named in named_arguments {
+        let Some(index) = params.iter().position(|p| p.name == named.name) else {
+            return Err(Error::SemanticWithSpan(
+                format!(
+                    "Function '{callee}' has no parameter named '{}'",
+                    named.name
+                ),
+                named.span,
+            ));
+        };
+        if bound[index].is_some() {
+            return Err(Error::SemanticWithSpan(
+                format!("Argument '{}' is provided more than once", named.name),
+                named.span,
+            ));
+        }
+        bound[index] = Some(&named.value);
+    }
+
+    if let Some((param, _)) = params
+        .iter()
+        .zip(&bound)
+        .find(|(param, argument)| argument.is_none() && param.default.is_none())
+    {
+        if named_arguments.is_empty() {
+            return Err(arity_error());
+        }
+        return Err(Error::SemanticWithSpan(
+            format!(
+                "Missing argument for parameter '{}' of function '{callee}'",
+                param.name
+            ),
+            span,
+        ));
+    }
+
+    Ok(bound)
+}
@@ -3,13 +3,18 @@
 //! This module is split into focused submodules:
 //! - `operators`: Binary and unary operator checking
 //! - `calls_arrays`: Function calls and array literals
-//! - `nullable`: Elvis and safe call operators
+//! - `builtins`: Built-in functions (`panic`, `assert`, `args`, `exit`, ...)
+//! - `nullable`: Elvis, safe call, non-null assertion and null binding
+//! - `results`: Result constructors and error propagation
 
+mod builtins;
 mod calls_arrays;
 mod nullable;
 mod operators;
+mod results;
 
 use crate::checker::core::TypeChecker;
+use crate::checker::helpers::is_diverging_statement;
 use rive_core::type_system::TypeId;
 use rive_core::{Error, Result};
 use rive_parser::ast::Expression;
@@ -18,8 +23,15 @@ impl TypeChecker {
     /// Checks an expression and returns its type.
     ///
     /// This is the main entry point for expression type checking.
-    /// It delegates to specialized methods for different expression types.
+    /// The type is also recorded by span for [`TypeChecker::expression_types`].
     pub(crate) fn check_expression(&mut self, expr: &Expression) -> Result<TypeId> {
+        let type_id = self.check_expression_kind(expr)?;
+        self.expression_types.insert(expr.span(), type_id);
+        Ok(type_id)
+    }
+
+    /// Checks an expression by delegating to the method for its kind.
+    fn check_expression_kind(&mut self, expr: &Expression) -> Result<TypeId> {
         match expr {
             // Literals
             Expression::Integer { .. } => Ok(TypeId::INT),
@@ -29,12 +41,7 @@ impl TypeChecker {
             Expression::Null { .. } => Ok(TypeId::NULL),
 
             // Variables
-            Expression::Variable { name, span } => {
-                let symbol = self.symbols.lookup(name).ok_or_else(|| {
-                    Error::SemanticWithSpan(format!("Undefined variable '{name}'"), *span)
-                })?;
-                Ok(symbol.symbol_type)
-            }
+            Expression::Variable { name, span } => self.check_variable(name, *span),
 
             // Operators
             Expression::Binary {
@@ -54,24 +61,40 @@ impl TypeChecker {
             Expression::Call {
                 callee,
                 arguments,
+                named_arguments,
                 span,
-            } => self.check_call(callee, arguments, *span),
+            } => self.check_call(callee, arguments, named_arguments, *span),
 
             Expression::Array { elements, span } => self.check_array(elements, *span),
 
             // Control flow expressions
-            Expression::If(if_expr) => self.check_if(if_expr, true),
+            Expression::If(if_expr) => self.check_if(if_expr, true, None),
             Expression::While(while_loop) => self.check_while_expr(while_loop),
             Expression::For(for_loop) => self.check_for_expr(for_loop),
             Expression::Loop(loop_expr) => self.check_loop_expr(loop_expr),
-            Expression::Match(match_expr) => self.check_match(match_expr, true),
+            Expression::Match(match_expr) => self.check_match(match_expr, true, None),
+            Expression::When(when) => self.check_when(when, true, None),
+
+            // Diverging control flow never produces a value
+            Expression::Return { value, span } => {
+                self.check_return(value.as_deref(), *span)?;
+                Ok(TypeId::NEVER)
+            }
+            Expression::Break(break_expr) => {
+                self.check_break(break_expr)?;
+                Ok(TypeId::NEVER)
+            }
+            Expression::Continue(continue_expr) => {
+                self.check_continue(continue_expr)?;
+                Ok(TypeId::NEVER)
+            }
             Expression::Range(_) => {
                 // Range expressions are used in for loops - return Unit for now
                 Ok(TypeId::UNIT)
             }
 
             // Block expressions
-            Expression::Block(block) => self.check_block_expression(block),
+            Expression::Block(block) => self.check_block_expression(block, None),
 
             // Null safety operators
             Expression::Elvis {
@@ -84,6 +107,15 @@ impl TypeChecker {
                 self.check_safe_call(object, call, *span)
             }
 
+            Expression::NonNullAssert { value, span } => self.check_non_null_assert(value, *span),
+
+            Expression::Propagate { value, span } => self.check_propagate(value, *span),
+
+            Expression::LetBinding { span, .. } => Err(Error::SemanticWithSpan(
+                "'let' bindings are only allowed in if and while conditions".to_string(),
+                *span,
+            )),
+
             // New collection literals
             Expression::Tuple { elements, span } => self.check_tuple(elements, *span),
             Expression::List { elements, span } => self.check_list(elements, *span),
@@ -106,16 +138,28 @@ impl TypeChecker {
     }
 
     /// Checks a block expression and returns its type.
-    fn check_block_expression(&mut self, block: &rive_parser::Block) -> Result<TypeId> {
-        // Check all statements in the block
-        for statement in &block.statements {
-            self.check_statement(statement)?;
+    pub(crate) fn check_block_expression(
+        &mut self,
+        block: &rive_parser::Block,
+        expected: Option<TypeId>,
+    ) -> Result<TypeId> {
+        // A final expression is the value of the block
+        if let Some((rive_parser::Statement::Expression { expression, .. }, rest)) =
+            block.statements.split_last()
+        {
+            for statement in rest {
+                self.check_statement(statement)?;
+            }
+            let type_id = self.check_branch_value(expression, expected)?;
+            self.warn_unreachable(&block.statements);
+            return Ok(type_id);
         }
 
-        // Check if there's a final expression
-        if let Some(rive_parser::Statement::Expression { expression, .. }) = block.statements.last()
-        {
-            return self.check_expression(expression);
+        self.check_statements(&block.statements)?;
+
+        // A block that ends by leaving it never produces a value
+        if block.statements.last().is_some_and(is_diverging_statement) {
+            return Ok(TypeId::NEVER);
         }
 
         // No final expression, block has Unit type
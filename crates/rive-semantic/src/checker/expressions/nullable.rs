@@ -1,8 +1,9 @@
-//! Elvis and safe call operator type checking.
+//! Elvis, safe call, non-null assertion and null binding type checking.
 
 use crate::checker::core::TypeChecker;
+use crate::symbol_table::Symbol;
 use rive_core::type_system::TypeId;
-use rive_core::{Result, Span};
+use rive_core::{Error, Result, Span};
 use rive_parser::ast::Expression;
 
 impl TypeChecker {
@@ -106,4 +107,56 @@ impl TypeChecker {
             Ok(self.symbols.type_registry_mut().create_optional(call_type))
         }
     }
+
+    /// Checks a non-null assertion: `value!!`
+    ///
+    /// # Type Rules
+    /// - If `value: T?`, result is `T`
+    /// - If `value: T` (non-nullable), the assertion is redundant and result is `T`
+    /// - Asserting the `null` literal is an error since it always fails
+    pub(super) fn check_non_null_assert(
+        &mut self,
+        value: &Expression,
+        span: Span,
+    ) -> Result<TypeId> {
+        let value_type = self.check_expression(value)?;
+
+        if value_type == TypeId::NULL {
+            return Err(Error::SemanticWithSpan(
+                "Non-null assertion on 'null' always fails".to_string(),
+                span,
+            ));
+        }
+
+        Ok(self.get_nullable_inner(value_type).unwrap_or(value_type))
+    }
+
+    /// Checks a null binding condition: `let name = value`
+    ///
+    /// Enters a new scope and defines `name` with the non-null inner type of `value`.
+    /// The caller is responsible for exiting the scope after checking the guarded block.
+    ///
+    /// # Type Rules
+    /// - `value` must be nullable (`T?`)
+    /// - `name` is bound immutably with type `T`
+    pub(crate) fn check_let_binding(
+        &mut self,
+        name: &str,
+        value: &Expression,
+        span: Span,
+    ) -> Result<()> {
+        let value_type = self.check_expression(value)?;
+
+        let Some(inner_type) = self.get_nullable_inner(value_type) else {
+            let type_name = self.symbols.type_registry().get_type_name(value_type);
+            return Err(Error::SemanticWithSpan(
+                format!("'let' condition requires a nullable value, found '{type_name}'"),
+                span,
+            ));
+        };
+
+        self.symbols.enter_scope();
+        self.symbols
+            .define(Symbol::new(name.to_string(), inner_type, false).with_span(span))
+    }
 }
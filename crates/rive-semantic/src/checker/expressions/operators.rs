@@ -14,10 +14,20 @@ impl TypeChecker {
         right: &Expression,
         span: Span,
     ) -> Result<TypeId> {
-        use rive_parser::BinaryOperator;
-
         let left_type = self.check_expression(left)?;
         let right_type = self.check_expression(right)?;
+        self.binary_result_type(left_type, operator, right_type, span)
+    }
+
+    /// Returns the type of `left operator right` given the operand types.
+    pub(crate) fn binary_result_type(
+        &self,
+        left_type: TypeId,
+        operator: &rive_parser::BinaryOperator,
+        right_type: TypeId,
+        span: Span,
+    ) -> Result<TypeId> {
+        use rive_parser::BinaryOperator;
 
         // Type compatibility check
         if !self.types_compatible(left_type, right_type) {
@@ -37,6 +47,22 @@ impl TypeChecker {
             | BinaryOperator::Divide
             | BinaryOperator::Modulo => left_type,
 
+            BinaryOperator::BitAnd
+            | BinaryOperator::BitOr
+            | BinaryOperator::BitXor
+            | BinaryOperator::ShiftLeft
+            | BinaryOperator::ShiftRight => {
+                let registry = self.symbols.type_registry();
+                if registry.resolve(left_type) != TypeId::INT {
+                    let type_str = registry.get_type_name(left_type);
+                    return Err(Error::SemanticWithSpan(
+                        format!("Bitwise operators require 'Int' operands, found '{type_str}'"),
+                        span,
+                    ));
+                }
+                left_type
+            }
+
             BinaryOperator::Equal
             | BinaryOperator::NotEqual
             | BinaryOperator::Less
@@ -63,10 +89,9 @@ impl TypeChecker {
 
         match operator {
             UnaryOperator::Negate => {
-                if !self.types_compatible(operand_type, TypeId::INT)
-                    && !self.types_compatible(operand_type, TypeId::FLOAT)
-                {
-                    let registry = self.symbols.type_registry();
+                let registry = self.symbols.type_registry();
+                let numeric = registry.resolve(operand_type);
+                if numeric != TypeId::INT && numeric != TypeId::FLOAT {
                     let type_str = registry.get_type_name(operand_type);
                     return Err(Error::SemanticWithSpan(
                         format!("Cannot negate type '{type_str}'"),
@@ -86,6 +111,17 @@ impl TypeChecker {
                 }
                 Ok(TypeId::BOOL)
             }
+            UnaryOperator::BitNot => {
+                let registry = self.symbols.type_registry();
+                if registry.resolve(operand_type) != TypeId::INT {
+                    let type_str = registry.get_type_name(operand_type);
+                    return Err(Error::SemanticWithSpan(
+                        format!("Cannot apply bitwise NOT to type '{type_str}'"),
+                        span,
+                    ));
+                }
+                Ok(operand_type)
+            }
         }
     }
 }
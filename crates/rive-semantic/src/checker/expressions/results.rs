@@ -0,0 +1,155 @@
+//! Result constructor and error propagation type checking.
+
+use crate::checker::core::TypeChecker;
+use rive_core::type_system::{TypeId, TypeKind};
+use rive_core::{Error, Result, Span};
+use rive_parser::ast::{Expression, is_result_constructor};
+
+impl TypeChecker {
+    /// Checks an expression against the type expected by its context.
+    ///
+    /// Result constructors (`ok(..)`/`err(..)`) only know one side of their type,
+    /// so they take the full `Result<T, E>` from the context (a typed `let`,
+    /// `return`, assignment or argument). The branches of an `if`, `when` or
+    /// block pass the expected type on to their values. All other expressions
+    /// are checked normally.
+    pub(crate) fn check_expression_expecting(
+        &mut self,
+        expr: &Expression,
+        expected: TypeId,
+    ) -> Result<TypeId> {
+        let type_id = match expr {
+            Expression::Call {
+                callee,
+                arguments,
+                named_arguments,
+                span,
+            } if named_arguments.is_empty() && is_result_constructor(callee) => {
+                return self.check_result_constructor(callee, arguments, Some(expected), *span);
+            }
+            Expression::If(if_expr) => self.check_if(if_expr, true, Some(expected))?,
+            Expression::Match(match_expr) => self.check_match(match_expr, true, Some(expected))?,
+            Expression::When(when) => self.check_when(when, true, Some(expected))?,
+            Expression::Block(block) => self.check_block_expression(block, Some(expected))?,
+            _ => return self.check_expression(expr),
+        };
+
+        self.expression_types.insert(expr.span(), type_id);
+        Ok(type_id)
+    }
+
+    /// Checks the value of a branch, against the type expected by the
+    /// enclosing expression if there is one.
+    pub(crate) fn check_branch_value(
+        &mut self,
+        expr: &Expression,
+        expected: Option<TypeId>,
+    ) -> Result<TypeId> {
+        match expected {
+            Some(expected) => self.check_expression_expecting(expr, expected),
+            None => self.check_expression(expr),
+        }
+    }
+
+    /// Checks a result constructor: `ok(value)` or `err(error)`
+    ///
+    /// # Type Rules
+    /// - The expected type must be `Result<T, E>`
+    /// - `ok(value)` requires `value: T`, `err(error)` requires `error: E`
+    /// - Result type is the expected `Result<T, E>`
+    pub(crate) fn check_result_constructor(
+        &mut self,
+        callee: &str,
+        arguments: &[Expression],
+        expected: Option<TypeId>,
+        span: Span,
+    ) -> Result<TypeId> {
+        if arguments.len() != 1 {
+            return Err(Error::SemanticWithSpan(
+                format!(
+                    "'{callee}' expects 1 argument, but {} were provided",
+                    arguments.len()
+                ),
+                span,
+            ));
+        }
+
+        let Some(expected) = expected else {
+            return Err(Error::SemanticWithSpan(
+                format!(
+                    "Cannot infer the type of '{callee}(..)': use it where a Result type is expected (e.g. a typed 'let' or a 'return')"
+                ),
+                span,
+            ));
+        };
+
+        let Some((ok_type, err_type)) = self.get_result_parts(expected) else {
+            let type_name = self.symbols.type_registry().get_type_name(expected);
+            return Err(Error::SemanticWithSpan(
+                format!("'{callee}(..)' creates a Result, but '{type_name}' is expected"),
+                span,
+            ));
+        };
+
+        let inner_expected = if callee == "ok" { ok_type } else { err_type };
+        let value_type = self.check_expression_expecting(&arguments[0], inner_expected)?;
+        if !self.types_compatible(inner_expected, value_type) {
+            return Err(self.type_mismatch_error(
+                &format!("'{callee}' value type mismatch"),
+                inner_expected,
+                value_type,
+                arguments[0].span(),
+            ));
+        }
+
+        Ok(expected)
+    }
+
+    /// Checks an error propagation: `value?`
+    ///
+    /// # Type Rules
+    /// - `value` must be `Result<T, E>`
+    /// - The enclosing function must return `Result<U, F>` where `E` is compatible with `F`
+    /// - Result type is `T`
+    pub(super) fn check_propagate(&mut self, value: &Expression, span: Span) -> Result<TypeId> {
+        let value_type = self.check_expression(value)?;
+
+        let Some((ok_type, err_type)) = self.get_result_parts(value_type) else {
+            let type_name = self.symbols.type_registry().get_type_name(value_type);
+            return Err(Error::SemanticWithSpan(
+                format!("'?' can only be applied to a Result, found '{type_name}'"),
+                span,
+            ));
+        };
+
+        let return_type = self.current_function_return_type.unwrap_or(TypeId::UNIT);
+        let Some((_, return_err_type)) = self.get_result_parts(return_type) else {
+            let type_name = self.symbols.type_registry().get_type_name(return_type);
+            return Err(Error::SemanticWithSpan(
+                format!(
+                    "'?' can only be used in a function that returns a Result, found '{type_name}'"
+                ),
+                span,
+            ));
+        };
+
+        if !self.types_compatible(return_err_type, err_type) {
+            return Err(self.type_mismatch_error(
+                "'?' error type does not match the function's error type",
+                return_err_type,
+                err_type,
+                span,
+            ));
+        }
+
+        Ok(ok_type)
+    }
+
+    /// Returns the `(ok, err)` types if `type_id` is a `Result<T, E>`.
+    pub(crate) fn get_result_parts(&self, type_id: TypeId) -> Option<(TypeId, TypeId)> {
+        match self.symbols.type_registry().get(type_id)?.kind {
+            TypeKind::Result { ok, err } => Some((ok, err)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Local function declarations and the variables they capture.
+
+use crate::checker::core::TypeChecker;
+use crate::symbol_table::Symbol;
+use rive_core::type_system::TypeId;
+use rive_core::{Error, Result, Span};
+use rive_parser::ast::Function;
+
+impl TypeChecker {
+    /// Checks a local function declaration.
+    ///
+    /// The function is visible in its own body and in the rest of the enclosing
+    /// scope. Its body may read the enclosing function's immutable variables.
+    pub(crate) fn check_local_function(&mut self, func: &Function) -> Result<()> {
+        self.check_parameter_order(func)?;
+
+        let param_types: Vec<_> = func.params.iter().map(|p| p.param_type).collect();
+        let func_type_id = self
+            .symbols
+            .type_registry_mut()
+            .create_function(param_types, func.return_type);
+        let symbol = Symbol::function(func.name.clone(), func_type_id, func.params.clone())
+            .with_span(func.span);
+        self.symbols.define(symbol)?;
+        self.check_parameter_defaults(func)?;
+
+        // The body is checked as a function of its own: `return` and loop
+        // control don't reach the enclosing function
+        let enclosing_return_type = self.current_function_return_type.take();
+        let enclosing_loops = std::mem::take(&mut self.loop_stack);
+        self.local_functions
+            .push((func.name.clone(), self.symbols.depth() + 1));
+
+        let result = self.check_function(func);
+
+        self.local_functions.pop();
+        self.loop_stack = enclosing_loops;
+        self.current_function_return_type = enclosing_return_type;
+        result
+    }
+
+    /// Checks a variable read and returns its type.
+    pub(crate) fn check_variable(&mut self, name: &str, span: Span) -> Result<TypeId> {
+        let Some((depth, symbol)) = self.symbols.lookup_with_depth(name) else {
+            if self.pending_globals.contains(name) {
+                return Err(Error::SemanticWithSpan(
+                    format!("Global '{name}' is used before its declaration"),
+                    span,
+                ));
+            }
+            return Err(Error::SemanticWithSpan(
+                format!("Undefined variable '{name}'"),
+                span,
+            ));
+        };
+
+        // Variables of enclosing functions live between the global scope and
+        // the innermost local function's own scopes
+        if let Some((function, floor)) = self.local_functions.last()
+            && depth > 0
+            && depth < *floor
+            && symbol.mutable
+        {
+            return Err(Error::SemanticWithSpan(
+                format!("Local function '{function}' cannot capture mutable variable '{name}'"),
+                span,
+            ));
+        }
+
+        let symbol_type = symbol.symbol_type;
+        self.symbols.record_reference(name, span);
+        Ok(symbol_type)
+    }
+}
@@ -1,7 +1,7 @@
 //! Helper functions to reduce code duplication.
 
 use crate::checker::core::TypeChecker;
-use rive_core::type_system::TypeId;
+use rive_core::type_system::{TypeId, TypeRegistry};
 use rive_core::{Error, Result, Span};
 
 impl TypeChecker {
@@ -31,7 +31,7 @@ impl TypeChecker {
     ) -> Result<()> {
         let condition_type = self.check_expression(condition)?;
 
-        if condition_type != TypeId::BOOL {
+        if !self.types_compatible(TypeId::BOOL, condition_type) {
             let registry = self.symbols.type_registry();
             let cond_str = registry.get_type_name(condition_type);
             return Err(Error::SemanticWithSpan(
@@ -43,6 +43,25 @@ impl TypeChecker {
         Ok(())
     }
 
+    /// Validates an `if`/`while` condition, which may be a null binding.
+    ///
+    /// Returns `true` if a `let` binding opened a new scope that the caller
+    /// must exit once the guarded block has been checked.
+    pub(crate) fn check_condition(
+        &mut self,
+        condition: &rive_parser::Expression,
+        context: &str,
+        span: Span,
+    ) -> Result<bool> {
+        if let rive_parser::Expression::LetBinding { name, value, span } = condition {
+            self.check_let_binding(name, value, *span)?;
+            return Ok(true);
+        }
+
+        self.check_bool_condition(condition, context, span)?;
+        Ok(false)
+    }
+
     /// Checks if two types are compatible for assignment.
     ///
     /// This delegates to the TypeRegistry's compatibility checking,
@@ -55,3 +74,34 @@ impl TypeChecker {
         self.symbols.type_registry().are_compatible(target, source)
     }
 }
+
+/// Unifies the types of two branches of an `if` or `when`.
+///
+/// A diverging branch (`Never`) takes the type of the other branch.
+/// Returns `None` if the branch types differ.
+pub(crate) fn unify_branch_types(
+    registry: &TypeRegistry,
+    first: TypeId,
+    second: TypeId,
+) -> Option<TypeId> {
+    if first == TypeId::NEVER {
+        Some(second)
+    } else if second == TypeId::NEVER
+        || first == second
+        || (registry.are_compatible(first, second) && registry.are_compatible(second, first))
+    {
+        Some(first)
+    } else {
+        None
+    }
+}
+
+/// Returns true if a statement unconditionally leaves the enclosing block.
+pub(crate) fn is_diverging_statement(statement: &rive_parser::Statement) -> bool {
+    matches!(
+        statement,
+        rive_parser::Statement::Return { .. }
+            | rive_parser::Statement::Break(_)
+            | rive_parser::Statement::Continue(_)
+    )
+}
@@ -2,7 +2,9 @@
 
 mod control_flow;
 mod core;
+mod exhaustiveness;
 mod expressions;
+mod functions;
 mod helpers;
 mod loops;
 mod patterns;
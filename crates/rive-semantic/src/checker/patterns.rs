@@ -1,16 +1,22 @@
 //! Pattern matching type checking.
 
 use crate::checker::core::TypeChecker;
-use rive_core::type_system::TypeId;
-use rive_core::{Error, Result};
+use crate::checker::exhaustiveness::analyze_match;
+use crate::checker::helpers::unify_branch_types;
+use crate::symbol_table::Symbol;
+use rive_core::type_system::{TypeId, TypeKind};
+use rive_core::{Error, Result, Warning};
 use rive_parser::control_flow::{Match, Pattern};
 
 impl TypeChecker {
     /// Checks a match expression.
+    ///
+    /// Arm values are checked against `expected`, if given.
     pub(crate) fn check_match(
         &mut self,
         match_expr: &Match,
         is_expression: bool,
+        expected: Option<TypeId>,
     ) -> Result<TypeId> {
         let scrutinee_type = self.check_expression(&match_expr.scrutinee)?;
 
@@ -22,31 +28,48 @@ impl TypeChecker {
         }
 
         let mut arm_types = Vec::new();
-        let mut has_wildcard = false;
-        let mut has_true = false;
-        let mut has_false = false;
+        let mut null_covered = false;
 
         for arm in &match_expr.arms {
-            // Check pattern matches scrutinee type
-            self.check_pattern(&arm.pattern, scrutinee_type)?;
-
-            match arm.pattern {
-                Pattern::Wildcard { .. } => has_wildcard = true,
-                Pattern::Boolean { value: true, .. } => has_true = true,
-                Pattern::Boolean { value: false, .. } => has_false = true,
-                _ => {}
+            // Pattern bindings are scoped to the arm
+            self.symbols.enter_scope();
+
+            // Check pattern matches scrutinee type. Once an earlier arm has
+            // caught null, a binding only sees the non-null value.
+            let pattern_type = match (&arm.pattern, null_covered) {
+                (Pattern::Binding { .. }, true) => self
+                    .get_nullable_inner(scrutinee_type)
+                    .unwrap_or(scrutinee_type),
+                _ => scrutinee_type,
+            };
+            self.check_pattern(&arm.pattern, pattern_type)?;
+            null_covered |= arm.covers_null();
+
+            if let Some(guard) = &arm.guard {
+                self.check_bool_condition(guard, "Guard", guard.span())?;
             }
 
             // Check arm body type
-            let arm_type = self.check_expression(&arm.body)?;
+            let arm_type = self.check_branch_value(&arm.body, expected)?;
             arm_types.push(arm_type);
+
+            self.symbols.exit_scope();
         }
 
-        // Check exhaustiveness
-        let is_exhaustive =
-            has_wildcard || (scrutinee_type == TypeId::BOOL && has_true && has_false);
+        // Check reachability and exhaustiveness
+        let analysis = analyze_match(
+            self.symbols.type_registry(),
+            scrutinee_type,
+            &match_expr.arms,
+        );
+        for span in analysis.unreachable {
+            self.warnings.push(Warning::new(
+                "Unreachable pattern: this arm is covered by earlier arms",
+                span,
+            ));
+        }
 
-        if !is_exhaustive {
+        if !analysis.exhaustive {
             return Err(Error::SemanticWithSpan(
                 "Match must be exhaustive (add a wildcard '_' pattern or cover all cases)"
                     .to_string(),
@@ -54,46 +77,86 @@ impl TypeChecker {
             ));
         }
 
-        // When used as an expression, all arms must return same type
+        // When used as an expression, all arms must return same type.
+        // Diverging arms (type Never) adopt the type of the other arms.
         if is_expression {
-            let first_type = arm_types[0];
-            for (i, &arm_type) in arm_types[1..].iter().enumerate() {
-                if arm_type != first_type {
-                    return Err(self.type_mismatch_error(
-                        &format!("Match arm {} type mismatch", i + 2),
-                        first_type,
-                        arm_type,
-                        match_expr.arms[i + 1].span,
-                    ));
-                }
+            let mut result_type = arm_types[0];
+            for (i, &arm_type) in arm_types.iter().enumerate().skip(1) {
+                result_type =
+                    unify_branch_types(self.symbols.type_registry(), result_type, arm_type)
+                        .ok_or_else(|| {
+                            self.type_mismatch_error(
+                                &format!("Match arm {} type mismatch", i + 1),
+                                result_type,
+                                arm_type,
+                                match_expr.arms[i].span,
+                            )
+                        })?;
             }
-            Ok(first_type)
+            Ok(result_type)
         } else {
             // When used as a statement, return Unit
             Ok(TypeId::UNIT)
         }
     }
 
-    /// Checks a pattern against expected type.
+    /// Checks a pattern against expected type, defining any bindings it introduces.
     pub(crate) fn check_pattern(&mut self, pattern: &Pattern, expected_type: TypeId) -> Result<()> {
+        // Wildcards and bindings match any value, including null
+        match pattern {
+            Pattern::Wildcard { .. } => return Ok(()),
+            Pattern::Binding { name, span } => {
+                return self
+                    .symbols
+                    .define(Symbol::new(name.clone(), expected_type, false).with_span(*span));
+            }
+            Pattern::Null { span } => {
+                if !self.is_nullable(expected_type) {
+                    let type_name = self.symbols.type_registry().get_type_name(expected_type);
+                    return Err(Error::SemanticWithSpan(
+                        format!("Null pattern used on a non-nullable value of type '{type_name}'"),
+                        *span,
+                    ));
+                }
+                return Ok(());
+            }
+            Pattern::Or { alternatives, .. } => {
+                return self.check_or_pattern(alternatives, expected_type);
+            }
+            _ => {}
+        }
+
+        // Other patterns match the non-null value of a nullable
+        let expected_type = self
+            .get_nullable_inner(expected_type)
+            .unwrap_or(expected_type);
+
         let pattern_type = match pattern {
             Pattern::Integer { .. } => TypeId::INT,
             Pattern::Float { .. } => TypeId::FLOAT,
             Pattern::String { .. } => TypeId::TEXT,
             Pattern::Boolean { .. } => TypeId::BOOL,
-            Pattern::Null { .. } => {
-                return Err(Error::SemanticWithSpan(
-                    "Null patterns not yet supported".to_string(),
-                    pattern.span(),
-                ));
-            }
-            Pattern::Wildcard { .. } => return Ok(()), // Wildcard matches any type
             Pattern::Range { start, end, .. } => {
                 return self.check_pattern_range(start, end, expected_type);
             }
+            Pattern::Ok { binding, span } | Pattern::Err { binding, span } => {
+                return self.check_result_pattern(
+                    pattern,
+                    binding.as_deref(),
+                    expected_type,
+                    *span,
+                );
+            }
+            Pattern::Tuple { elements, span } => {
+                return self.check_tuple_pattern(elements, expected_type, *span);
+            }
+            Pattern::Wildcard { .. }
+            | Pattern::Binding { .. }
+            | Pattern::Null { .. }
+            | Pattern::Or { .. } => unreachable!("handled above"),
         };
 
-        if pattern_type != expected_type {
+        if !self.types_compatible(expected_type, pattern_type) {
             return Err(self.type_mismatch_error(
                 "Pattern type mismatch",
                 expected_type,
@@ -105,6 +168,97 @@ impl TypeChecker {
         Ok(())
     }
 
+    /// Checks a tuple pattern element by element.
+    fn check_tuple_pattern(
+        &mut self,
+        elements: &[Pattern],
+        expected_type: TypeId,
+        span: rive_core::Span,
+    ) -> Result<()> {
+        let registry = self.symbols.type_registry();
+        let element_types = match registry.get(expected_type).map(|meta| &meta.kind) {
+            Some(TypeKind::Tuple { elements }) => elements.clone(),
+            _ => {
+                let type_name = registry.get_type_name(expected_type);
+                return Err(Error::SemanticWithSpan(
+                    format!("Tuple pattern used on a non-tuple value of type '{type_name}'"),
+                    span,
+                ));
+            }
+        };
+
+        if element_types.len() != elements.len() {
+            let type_name = registry.get_type_name(expected_type);
+            return Err(Error::SemanticWithSpan(
+                format!(
+                    "Tuple pattern has {} elements but '{type_name}' has {}",
+                    elements.len(),
+                    element_types.len()
+                ),
+                span,
+            ));
+        }
+
+        for (element, element_type) in elements.iter().zip(element_types) {
+            self.check_pattern(element, element_type)?;
+        }
+        Ok(())
+    }
+
+    /// Checks an or-pattern. Every alternative must bind the same names.
+    fn check_or_pattern(&mut self, alternatives: &[Pattern], expected_type: TypeId) -> Result<()> {
+        let mut expected_bindings = pattern_bindings(&alternatives[0]);
+        expected_bindings.sort();
+
+        for alternative in &alternatives[1..] {
+            // Later alternatives are checked in their own scope; their bindings
+            // are the same names as the first alternative's
+            self.symbols.enter_scope();
+            let checked = self.check_pattern(alternative, expected_type);
+            self.symbols.exit_scope();
+            checked?;
+
+            let mut bindings = pattern_bindings(alternative);
+            bindings.sort();
+            if bindings != expected_bindings {
+                return Err(Error::SemanticWithSpan(
+                    "All alternatives of an or-pattern must bind the same names".to_string(),
+                    alternative.span(),
+                ));
+            }
+        }
+
+        self.check_pattern(&alternatives[0], expected_type)
+    }
+
+    /// Checks an `ok(..)`/`err(..)` pattern and defines its binding.
+    fn check_result_pattern(
+        &mut self,
+        pattern: &Pattern,
+        binding: Option<&str>,
+        expected_type: TypeId,
+        span: rive_core::Span,
+    ) -> Result<()> {
+        let Some((ok_type, err_type)) = self.get_result_parts(expected_type) else {
+            let type_name = self.symbols.type_registry().get_type_name(expected_type);
+            return Err(Error::SemanticWithSpan(
+                format!("Result pattern used on a non-Result value of type '{type_name}'"),
+                span,
+            ));
+        };
+
+        let binding_type = match pattern {
+            Pattern::Ok { .. } => ok_type,
+            _ => err_type,
+        };
+        if let Some(name) = binding {
+            self.symbols
+                .define(Symbol::new(name.to_string(), binding_type, false).with_span(span))?;
+        }
+
+        Ok(())
+    }
+
     /// Checks a range pattern.
     fn check_pattern_range(
         &mut self,
@@ -115,7 +269,7 @@ impl TypeChecker {
         let start_type = self.check_expression(start)?;
         let end_type = self.check_expression(end)?;
 
-        if start_type != expected_type {
+        if !self.types_compatible(expected_type, start_type) {
             return Err(self.type_mismatch_error(
                 "Range start type mismatch",
                 expected_type,
@@ -124,7 +278,7 @@ impl TypeChecker {
             ));
         }
 
-        if end_type != expected_type {
+        if !self.types_compatible(expected_type, end_type) {
             return Err(self.type_mismatch_error(
                 "Range end type mismatch",
                 expected_type,
@@ -136,3 +290,24 @@ impl TypeChecker {
         Ok(())
     }
 }
+
+/// Returns the names bound by a pattern.
+fn pattern_bindings(pattern: &Pattern) -> Vec<&str> {
+    match pattern {
+        Pattern::Binding { name, .. } => vec![name.as_str()],
+        Pattern::Ok {
+            binding: Some(name),
+            ..
+        }
+        | Pattern::Err {
+            binding: Some(name),
+            ..
+        } => vec![name.as_str()],
+        Pattern::Tuple { elements, .. } => elements.iter().flat_map(pattern_bindings).collect(),
+        Pattern::Or { alternatives, .. } => alternatives
+            .first()
+            .map(pattern_bindings)
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
@@ -1,45 +1,227 @@
 //! Program and function type checking.
 
 use crate::checker::core::TypeChecker;
+use crate::checker::helpers::is_diverging_statement;
 use crate::symbol_table::Symbol;
 use rive_core::type_system::TypeId;
-use rive_core::{Error, Result};
-use rive_parser::ast::{Function, FunctionBody, Item, Program};
+use rive_core::{Error, Result, Span, Warning};
+use rive_parser::ast::{Block, Function, FunctionBody, Global, Item, Program, Statement};
+use std::collections::{HashMap, HashSet};
 
 impl TypeChecker {
     /// Checks a complete program.
     pub fn check_program(&mut self, program: &Program) -> Result<()> {
         // Check that a main function exists
-        let has_main = program.items.iter().any(|item| {
-            let Item::Function(func) = item;
-            func.name == "main"
-        });
-
-        if !has_main {
+        let Some(main) = functions(program).find(|func| func.name == "main") else {
             return Err(Error::Semantic(
                 "Program must have a 'main' function".to_string(),
             ));
-        }
+        };
+        self.check_main_signature(main)?;
 
         // First pass: register all function signatures
-        for item in &program.items {
-            let Item::Function(func) = item;
+        for func in functions(program) {
+            self.check_test_signature(func)?;
+            self.check_parameter_order(func)?;
             let param_types: Vec<_> = func.params.iter().map(|p| p.param_type).collect();
             let func_type_id = self
                 .symbols
                 .type_registry_mut()
                 .create_function(param_types, func.return_type);
 
-            let symbol = Symbol::new(func.name.clone(), func_type_id, false);
+            let symbol = Symbol::function(func.name.clone(), func_type_id, func.params.clone())
+                .with_span(func.span);
             self.symbols.define(symbol)?;
         }
 
-        // Second pass: type check each function body
+        // Globals are checked in declaration order, so an initializer only
+        // sees the globals declared before it
+        self.pending_globals = program
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Global(global) => Some(global.name.clone()),
+                _ => None,
+            })
+            .collect();
+        let mut constants = HashSet::new();
         for item in &program.items {
-            let Item::Function(func) = item;
+            if let Item::Global(global) = item {
+                self.check_global(global, &constants)?;
+                if global.constant {
+                    constants.insert(global.name.as_str());
+                }
+            }
+        }
+
+        // Default values may call any function, so they're checked once all are known
+        for func in functions(program) {
+            self.check_parameter_defaults(func)?;
+        }
+
+        // Second pass: type check each function body
+        for func in functions(program) {
             self.check_function(func)?;
         }
 
+        // Function bodies are checked, so every call made by an initializer is known
+        self.check_global_initialization_order(program)?;
+
+        Ok(())
+    }
+
+    /// Checks that no global initializer reaches itself or a later global
+    /// through the functions it calls.
+    ///
+    /// Direct uses are reported while checking the initializer; this catches
+    /// indirect ones such as `let a = f()` where `f` reads `a`.
+    fn check_global_initialization_order(&self, program: &Program) -> Result<()> {
+        let globals: Vec<&Global> = program
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Global(global) => Some(global),
+                _ => None,
+            })
+            .collect();
+        if globals.is_empty() {
+            return Ok(());
+        }
+
+        // Top-level items by declaration span: `Ok(index)` for a global,
+        // `Err(name)` for a function
+        let mut items: HashMap<Span, std::result::Result<usize, &str>> = HashMap::new();
+        for (index, global) in globals.iter().enumerate() {
+            items.insert(global.span, Ok(index));
+        }
+        for func in functions(program) {
+            items.insert(func.span, Err(func.name.as_str()));
+        }
+
+        // The globals and functions used within each top-level item
+        let mut uses: HashMap<Span, Vec<Span>> = HashMap::new();
+        for reference in self.symbols.references() {
+            if !items.contains_key(&reference.declaration) {
+                continue;
+            }
+            let owner = items
+                .keys()
+                .find(|span| span.contains(reference.span.start));
+            if let Some(&owner) = owner {
+                uses.entry(owner).or_default().push(reference.declaration);
+            }
+        }
+
+        for (index, global) in globals.iter().enumerate() {
+            // Walk the functions reachable from the initializer, remembering
+            // the call path that first reached each of them
+            let mut visited = HashSet::new();
+            let mut stack = vec![(global.span, Vec::new())];
+            while let Some((item, path)) = stack.pop() {
+                for &used in uses.get(&item).into_iter().flatten() {
+                    match items[&used] {
+                        Ok(used_index) if used_index >= index && !path.is_empty() => {
+                            let via = path.join("' -> '");
+                            let message = if used_index == index {
+                                format!(
+                                    "Initializer of global '{}' depends on itself through '{via}'",
+                                    global.name
+                                )
+                            } else {
+                                format!(
+                                    "Global '{}' is used before its initialization by global '{}' through '{via}'",
+                                    globals[used_index].name, global.name
+                                )
+                            };
+                            return Err(Error::SemanticWithSpan(
+                                message,
+                                global.initializer.span(),
+                            ));
+                        }
+                        Ok(_) => {}
+                        Err(name) => {
+                            if visited.insert(used) {
+                                let mut path = path.clone();
+                                path.push(name);
+                                stack.push((used, path));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks a global declaration and defines it in the global scope.
+    ///
+    /// A `const` may only combine literals and earlier constants.
+    fn check_global(&mut self, global: &Global, constants: &HashSet<&str>) -> Result<()> {
+        if global.constant
+            && !global
+                .initializer
+                .is_constant(&|name| constants.contains(name))
+        {
+            return Err(Error::SemanticWithSpan(
+                format!(
+                    "Constant '{}' must be initialized with a constant expression",
+                    global.name
+                ),
+                global.initializer.span(),
+            ));
+        }
+
+        self.check_let(
+            &global.name,
+            false,
+            &global.var_type,
+            false,
+            &global.initializer,
+            global.span,
+        )?;
+        self.pending_globals.remove(&global.name);
+        Ok(())
+    }
+
+    /// Checks that parameters with default values come after those without.
+    pub(crate) fn check_parameter_order(&self, func: &Function) -> Result<()> {
+        let mut seen_default = false;
+        for param in &func.params {
+            if param.default.is_some() {
+                seen_default = true;
+            } else if seen_default {
+                return Err(Error::SemanticWithSpan(
+                    format!(
+                        "Parameter '{}' must have a default value because it follows a parameter with one",
+                        param.name
+                    ),
+                    param.span,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that each default value matches its parameter's type.
+    ///
+    /// Defaults are evaluated in the scope the function is declared in, so they
+    /// never see the caller's variables.
+    pub(crate) fn check_parameter_defaults(&mut self, func: &Function) -> Result<()> {
+        for param in &func.params {
+            let Some(default) = &param.default else {
+                continue;
+            };
+            let default_type = self.check_expression_expecting(default, param.param_type)?;
+            if !self.types_compatible(param.param_type, default_type) {
+                return Err(self.type_mismatch_error(
+                    &format!("Default value of parameter '{}' type mismatch", param.name),
+                    param.param_type,
+                    default_type,
+                    default.span(),
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -51,18 +233,19 @@ impl TypeChecker {
 
         // Register parameters in the function scope
         for param in &func.params {
-            let symbol = Symbol::new(param.name.clone(), param.param_type, false);
+            let symbol =
+                Symbol::new(param.name.clone(), param.param_type, false).with_span(param.span);
             self.symbols.define(symbol)?;
         }
 
         // Check function body based on its type
         match &func.body {
             FunctionBody::Block(block) => {
-                self.check_block(block)?;
+                self.check_function_block(block, func)?;
             }
             FunctionBody::Expression(expr) => {
                 // For expression bodies, check that the expression type matches the return type
-                let expr_type = self.check_expression(expr)?;
+                let expr_type = self.check_expression_expecting(expr, func.return_type)?;
                 if !self.types_compatible(func.return_type, expr_type) {
                     return Err(self.type_mismatch_error(
                         &format!("Function '{}' expression body type mismatch", func.name),
@@ -81,43 +264,202 @@ impl TypeChecker {
         Ok(())
     }
 
+    /// Checks a function body block.
+    ///
+    /// A function that returns a value must end with that value or leave
+    /// before reaching its end (`return`, `panic(..)`, a `loop` without a
+    /// `break`, ...). The trailing expression is checked against the return
+    /// type, so result constructors and the branches of a trailing `if` or
+    /// `when` take their type from it.
+    fn check_function_block(&mut self, block: &Block, func: &Function) -> Result<()> {
+        let return_type = func.return_type;
+        if self.symbols.type_registry().resolve(return_type) == TypeId::UNIT {
+            return self.check_block(block);
+        }
+
+        let Some((last, rest)) = block.statements.split_last() else {
+            return Err(self.missing_return_error(func, block.span));
+        };
+        for statement in rest {
+            self.check_statement(statement)?;
+        }
+
+        match last {
+            Statement::Expression { expression, span } if expression.produces_value() => {
+                let expr_type = self.check_expression_expecting(expression, return_type)?;
+                if expr_type == TypeId::UNIT {
+                    return Err(self.missing_return_error(func, *span));
+                }
+                if !self.types_compatible(return_type, expr_type) {
+                    return Err(self.type_mismatch_error(
+                        "Implicit return type mismatch",
+                        return_type,
+                        expr_type,
+                        *span,
+                    ));
+                }
+            }
+            _ => {
+                self.check_statement(last)?;
+                if !self.statement_diverges(last) {
+                    return Err(self.missing_return_error(func, last.span()));
+                }
+            }
+        }
+
+        self.warn_unreachable(&block.statements);
+        Ok(())
+    }
+
+    /// Creates the error for a function body that can end without a value.
+    fn missing_return_error(&self, func: &Function, span: Span) -> Error {
+        let type_name = self.symbols.type_registry().get_type_name(func.return_type);
+        Error::SemanticWithSpan(
+            format!(
+                "Function '{}' must return a value of type '{type_name}'",
+                func.name
+            ),
+            span,
+        )
+    }
+
     /// Checks a block of statements.
-    pub(crate) fn check_block(&mut self, block: &rive_parser::Block) -> Result<()> {
-        for statement in &block.statements {
+    pub(crate) fn check_block(&mut self, block: &Block) -> Result<()> {
+        self.check_statements(&block.statements)
+    }
+
+    /// Checks a sequence of statements, warning about any that follow a
+    /// statement which always leaves the block.
+    pub(crate) fn check_statements(&mut self, statements: &[Statement]) -> Result<()> {
+        for statement in statements {
             self.check_statement(statement)?;
         }
+        self.warn_unreachable(statements);
         Ok(())
     }
 
+    /// Warns about the first statement after one that always leaves the
+    /// block. The statements must already be checked.
+    pub(crate) fn warn_unreachable(&mut self, statements: &[Statement]) {
+        let Some(index) = statements
+            .iter()
+            .position(|statement| self.statement_diverges(statement))
+        else {
+            return;
+        };
+        if let Some(unreachable) = statements.get(index + 1) {
+            self.warnings.push(Warning::new(
+                "Unreachable code: the statement before it never completes",
+                unreachable.span(),
+            ));
+        }
+    }
+
+    /// Returns true if a checked statement never completes: it leaves the
+    /// block, or is an expression of type `Never` such as `panic(..)`.
+    fn statement_diverges(&self, statement: &Statement) -> bool {
+        match statement {
+            Statement::Expression { expression, .. } => {
+                self.expression_types.get(&expression.span()) == Some(&TypeId::NEVER)
+            }
+            _ => is_diverging_statement(statement),
+        }
+    }
+
     /// Checks a block and returns its type (considering implicit return).
     pub(crate) fn check_block_with_value(
         &mut self,
-        block: &rive_parser::Block,
+        block: &Block,
+        expected: Option<TypeId>,
     ) -> Result<rive_core::type_system::TypeId> {
-        if block.statements.is_empty() {
+        let Some((last_stmt, rest)) = block.statements.split_last() else {
             return Ok(TypeId::UNIT);
-        }
-
-        let num_stmts = block.statements.len();
+        };
 
         // Check all but last
-        for stmt in &block.statements[..num_stmts - 1] {
+        for stmt in rest {
             self.check_statement(stmt)?;
         }
 
         // Check last statement
-        let last_stmt = &block.statements[num_stmts - 1];
-
-        match last_stmt {
-            rive_parser::Statement::Expression { expression, .. } => {
+        let block_type = match last_stmt {
+            Statement::Expression { expression, .. } => {
                 // Last expression is implicit return
-                self.check_expression(expression)
+                self.check_branch_value(expression, expected)?
             }
             _ => {
                 // Last statement is not expression, block returns Unit
+                // (or Never if it leaves the block)
                 self.check_statement(last_stmt)?;
-                Ok(TypeId::UNIT)
+                if is_diverging_statement(last_stmt) {
+                    TypeId::NEVER
+                } else {
+                    TypeId::UNIT
+                }
             }
+        };
+
+        self.warn_unreachable(&block.statements);
+        Ok(block_type)
+    }
+
+    /// Checks that `main` takes no parameters and returns nothing or an
+    /// Int, which becomes the exit status of the program.
+    fn check_main_signature(&self, main: &Function) -> Result<()> {
+        if let Some(param) = main.params.first() {
+            return Err(Error::SemanticWithSpan(
+                "'main' cannot take parameters; use args() to read command-line arguments"
+                    .to_string(),
+                param.span,
+            ));
         }
+
+        let return_type = self.symbols.type_registry().resolve(main.return_type);
+        if !matches!(return_type, TypeId::UNIT | TypeId::INT | TypeId::NEVER) {
+            let type_name = self.symbols.type_registry().get_type_name(main.return_type);
+            return Err(Error::SemanticWithSpan(
+                format!("'main' must return Unit or Int, found '{type_name}'"),
+                main.span,
+            ));
+        }
+
+        Ok(())
     }
+
+    /// Checks that an `@test` function can be called by the test runner.
+    fn check_test_signature(&self, func: &Function) -> Result<()> {
+        let Some(test) = &func.test else {
+            return Ok(());
+        };
+
+        if func.name == "main" {
+            return Err(Error::SemanticWithSpan(
+                "'main' cannot be a test".to_string(),
+                test.span,
+            ));
+        }
+        if let Some(param) = func.params.first() {
+            return Err(Error::SemanticWithSpan(
+                format!("Test '{}' cannot take parameters", func.name),
+                param.span,
+            ));
+        }
+        if self.symbols.type_registry().resolve(func.return_type) != TypeId::UNIT {
+            let type_name = self.symbols.type_registry().get_type_name(func.return_type);
+            return Err(Error::SemanticWithSpan(
+                format!("Test '{}' must return Unit, found '{type_name}'", func.name),
+                func.span,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the top-level functions of a program.
+fn functions(program: &Program) -> impl Iterator<Item = &Function> {
+    program.items.iter().filter_map(|item| match item {
+        Item::Function(func) => Some(func),
+        _ => None,
+    })
 }
@@ -2,9 +2,9 @@
 
 use crate::checker::core::TypeChecker;
 use crate::symbol_table::Symbol;
-use rive_core::type_system::TypeId;
+use rive_core::type_system::{TypeId, TypeKind};
 use rive_core::{Error, Result};
-use rive_parser::ast::{Expression, Statement};
+use rive_parser::ast::{BindingPattern, Expression, Statement};
 
 impl TypeChecker {
     /// Checks a statement.
@@ -41,10 +41,26 @@ impl TypeChecker {
                 *span,
             ),
 
+            Statement::Destructure {
+                pattern,
+                initializer,
+                ..
+            } => {
+                let init_type = self.check_expression(initializer)?;
+                self.define_binding_pattern(pattern, init_type)
+            }
+
             Statement::Assignment { name, value, span } => {
                 self.check_assignment(name, value, *span)
             }
 
+            Statement::CompoundAssignment {
+                name,
+                operator,
+                value,
+                span,
+            } => self.check_compound_assignment(name, operator, value, *span),
+
             Statement::Expression { expression, .. } => self.check_expression_statement(expression),
 
             Statement::Return { value, span } => self.check_return(value.as_ref(), *span),
@@ -58,11 +74,13 @@ impl TypeChecker {
                 self.check_continue(continue_stmt)?;
                 Ok(())
             }
+
+            Statement::Function(func) => self.check_local_function(func),
         }
     }
 
     /// Checks a let statement.
-    fn check_let(
+    pub(crate) fn check_let(
         &mut self,
         name: &str,
         mutable: bool,
@@ -71,7 +89,12 @@ impl TypeChecker {
         initializer: &Expression,
         span: rive_core::Span,
     ) -> Result<()> {
-        let init_type = self.check_expression(initializer)?;
+        let init_type = match var_type {
+            Some(annotated_type) => {
+                self.check_expression_expecting(initializer, *annotated_type)?
+            }
+            None => self.check_expression(initializer)?,
+        };
 
         // Determine the final variable type
         let var_type_id = if let Some(annotated_type) = var_type {
@@ -93,11 +116,63 @@ impl TypeChecker {
             init_type
         };
 
-        let symbol = Symbol::new(name.to_string(), var_type_id, mutable);
+        let symbol = Symbol::new(name.to_string(), var_type_id, mutable).with_span(span);
         self.symbols.define(symbol)?;
         Ok(())
     }
 
+    /// Defines the variables bound by a pattern matched against a value of `value_type`.
+    /// Tuple patterns must match the arity of the tuple they destructure.
+    pub(crate) fn define_binding_pattern(
+        &mut self,
+        pattern: &BindingPattern,
+        value_type: TypeId,
+    ) -> Result<()> {
+        match pattern {
+            BindingPattern::Name {
+                name,
+                mutable,
+                span,
+            } => {
+                let symbol = Symbol::new(name.clone(), value_type, *mutable).with_span(*span);
+                self.symbols.define(symbol)
+            }
+
+            BindingPattern::Wildcard { .. } => Ok(()),
+
+            BindingPattern::Tuple { elements, span } => {
+                let registry = self.symbols.type_registry();
+                let element_types = match registry.get(value_type).map(|meta| &meta.kind) {
+                    Some(TypeKind::Tuple { elements }) => elements.clone(),
+                    _ => {
+                        let type_name = registry.get_type_name(value_type);
+                        return Err(Error::SemanticWithSpan(
+                            format!("Cannot destructure '{type_name}' with a tuple pattern"),
+                            *span,
+                        ));
+                    }
+                };
+
+                if element_types.len() != elements.len() {
+                    let type_name = registry.get_type_name(value_type);
+                    return Err(Error::SemanticWithSpan(
+                        format!(
+                            "Tuple pattern has {} elements but '{type_name}' has {}",
+                            elements.len(),
+                            element_types.len()
+                        ),
+                        *span,
+                    ));
+                }
+
+                for (element, element_type) in elements.iter().zip(element_types) {
+                    self.define_binding_pattern(element, element_type)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
     /// Checks an assignment statement.
     fn check_assignment(
         &mut self,
@@ -105,28 +180,39 @@ impl TypeChecker {
         value: &Expression,
         span: rive_core::Span,
     ) -> Result<()> {
-        // Clone symbol data to avoid borrow conflicts
-        let (is_mutable, expected_type) = {
-            let var_symbol = self.symbols.lookup(name).ok_or_else(|| {
-                Error::SemanticWithSpan(format!("Undefined variable '{name}'"), span)
-            })?;
-            (var_symbol.mutable, var_symbol.symbol_type)
-        };
+        let expected_type = self.assignable_variable_type(name, span)?;
 
-        if !is_mutable {
-            return Err(Error::SemanticWithSpan(
-                format!("Cannot assign to immutable variable '{name}'"),
+        let value_type = self.check_expression_expecting(value, expected_type)?;
+        // Check if value_type can be assigned to expected_type
+        if !self.types_compatible(expected_type, value_type) {
+            return Err(self.type_mismatch_error(
+                &format!("Cannot assign to variable '{name}'"),
+                expected_type,
+                value_type,
                 span,
             ));
         }
 
+        Ok(())
+    }
+
+    /// Checks a compound assignment statement (`name op= value`).
+    fn check_compound_assignment(
+        &mut self,
+        name: &str,
+        operator: &rive_parser::BinaryOperator,
+        value: &Expression,
+        span: rive_core::Span,
+    ) -> Result<()> {
+        let variable_type = self.assignable_variable_type(name, span)?;
         let value_type = self.check_expression(value)?;
-        // Check if value_type can be assigned to expected_type
-        if !self.types_compatible(expected_type, value_type) {
+        let result_type = self.binary_result_type(variable_type, operator, value_type, span)?;
+
+        if !self.types_compatible(variable_type, result_type) {
             return Err(self.type_mismatch_error(
                 &format!("Cannot assign to variable '{name}'"),
-                expected_type,
-                value_type,
+                variable_type,
+                result_type,
                 span,
             ));
         }
@@ -134,18 +220,48 @@ impl TypeChecker {
         Ok(())
     }
 
+    /// Returns the type of a variable that is about to be assigned,
+    /// checking that it exists and is mutable.
+    fn assignable_variable_type(
+        &mut self,
+        name: &str,
+        span: rive_core::Span,
+    ) -> Result<rive_core::type_system::TypeId> {
+        let var_type = self.check_variable(name, span)?;
+        let var_symbol = self
+            .symbols
+            .lookup(name)
+            .ok_or_else(|| Error::SemanticWithSpan(format!("Undefined variable '{name}'"), span))?;
+
+        if !var_symbol.mutable {
+            return Err(Error::SemanticWithSpan(
+                format!("Cannot assign to immutable variable '{name}'"),
+                span,
+            ));
+        }
+
+        Ok(var_type)
+    }
+
     /// Checks an expression statement.
     fn check_expression_statement(&mut self, expression: &Expression) -> Result<()> {
         // Special handling for control flow structures that can be both expressions and statements
         match expression {
             Expression::If(if_expr) => {
-                // If used as statement, doesn't require else branch
-                self.check_if(if_expr, false)?;
+                // If used as statement, doesn't require else branch. Its type
+                // is still recorded, since it is Never when every branch diverges
+                let type_id = self.check_if(if_expr, false, None)?;
+                self.expression_types.insert(expression.span(), type_id);
                 Ok(())
             }
             Expression::Match(match_expr) => {
                 // Match used as statement
-                self.check_match(match_expr, false)?;
+                self.check_match(match_expr, false, None)?;
+                Ok(())
+            }
+            Expression::When(when) => {
+                // When used as statement, doesn't require an else arm
+                self.check_when(when, false, None)?;
                 Ok(())
             }
             _ => {
@@ -156,13 +272,17 @@ impl TypeChecker {
     }
 
     /// Checks a return statement.
-    fn check_return(&mut self, value: Option<&Expression>, span: rive_core::Span) -> Result<()> {
+    pub(crate) fn check_return(
+        &mut self,
+        value: Option<&Expression>,
+        span: rive_core::Span,
+    ) -> Result<()> {
         let return_type_id = self.current_function_return_type.ok_or_else(|| {
             Error::SemanticWithSpan("Return statement outside of function".to_string(), span)
         })?;
 
         let value_type = if let Some(expr) = value {
-            self.check_expression(expr)?
+            self.check_expression_expecting(expr, return_type_id)?
         } else {
             TypeId::UNIT
         };
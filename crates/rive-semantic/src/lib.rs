@@ -8,11 +8,23 @@ mod checker;
 mod symbol_table;
 
 pub use checker::TypeChecker;
-pub use symbol_table::{Symbol, SymbolTable};
+pub use symbol_table::{Symbol, SymbolReference, SymbolTable};
 
-use rive_core::Result;
-use rive_core::type_system::TypeRegistry;
+use rive_core::type_system::{TypeId, TypeRegistry};
+use rive_core::{Result, Span, Warning};
 use rive_parser::ast::Program;
+use std::collections::HashMap;
+
+/// Everything semantic analysis learns about a program.
+#[derive(Debug)]
+pub struct Analysis {
+    /// The type registry, including types created during analysis
+    pub type_registry: TypeRegistry,
+    /// Warnings that don't stop compilation
+    pub warnings: Vec<Warning>,
+    /// Type of every expression, keyed by the expression's span
+    pub expression_types: HashMap<Span, TypeId>,
+}
 
 /// Performs semantic analysis on a Rive program.
 ///
@@ -46,11 +58,37 @@ pub fn analyze_with_registry(
     program: &Program,
     type_registry: TypeRegistry,
 ) -> Result<TypeRegistry> {
+    analyze_with_warnings(program, type_registry).map(|(registry, _)| registry)
+}
+
+/// Performs semantic analysis and also returns the warnings found.
+///
+/// Warnings (such as unreachable `when` arms) don't stop compilation;
+/// callers decide how to report them.
+pub fn analyze_with_warnings(
+    program: &Program,
+    type_registry: TypeRegistry,
+) -> Result<(TypeRegistry, Vec<Warning>)> {
+    analyze_program(program, type_registry)
+        .map(|analysis| (analysis.type_registry, analysis.warnings))
+}
+
+/// Performs semantic analysis and returns everything it found, including
+/// the type of every expression.
+///
+/// # Errors
+/// Returns the first semantic error found.
+pub fn analyze_program(program: &Program, type_registry: TypeRegistry) -> Result<Analysis> {
     let symbols = SymbolTable::with_registry(type_registry);
     let mut checker = TypeChecker::with_symbols(symbols);
     checker.check_program(program)?;
-    // Extract and return the type registry
-    Ok(checker.into_type_registry())
+    let warnings = checker.take_warnings();
+    let expression_types = checker.take_expression_types();
+    Ok(Analysis {
+        type_registry: checker.into_type_registry(),
+        warnings,
+        expression_types,
+    })
 }
 
 /// Performs semantic analysis on a Rive program (for backward compatibility).
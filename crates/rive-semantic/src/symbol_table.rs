@@ -1,6 +1,8 @@
 //! Symbol table for tracking variables and functions during semantic analysis.
 
+use rive_core::Span;
 use rive_core::type_system::{TypeId, TypeRegistry};
+use rive_parser::ast::Parameter;
 use std::collections::HashMap;
 
 /// Represents a symbol in the symbol table.
@@ -14,6 +16,10 @@ pub struct Symbol {
     pub mutable: bool,
     /// Whether the symbol has been initialized
     pub initialized: bool,
+    /// Declared parameters, if the symbol is a function
+    pub params: Option<Vec<Parameter>>,
+    /// Span of the declaration, for symbols written in the source
+    pub span: Option<Span>,
 }
 
 impl Symbol {
@@ -24,20 +30,52 @@ impl Symbol {
             symbol_type,
             mutable,
             initialized: true,
+            params: None,
+            span: None,
         }
     }
+
+    /// Sets the span of the symbol's declaration.
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Creates a symbol for a declared function.
+    pub fn function(name: String, symbol_type: TypeId, params: Vec<Parameter>) -> Self {
+        Self {
+            params: Some(params),
+            ..Self::new(name, symbol_type, false)
+        }
+    }
+}
+
+/// A use of a symbol declared in the source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolReference {
+    pub name: String,
+    /// Span of the expression or statement that uses the symbol, which starts with its name
+    pub span: Span,
+    /// Span of the symbol's declaration
+    pub declaration: Span,
 }
 
 /// Symbol table for managing variable and function scopes.
 ///
 /// The symbol table uses a stack of scopes to handle nested blocks.
-/// Each scope maps symbol names to their definitions.
+/// Each scope maps symbol names to their definitions. Symbols declared in
+/// the source are also remembered after their scope ends, along with every
+/// recorded use, so tools can map uses back to declarations.
 #[derive(Debug)]
 pub struct SymbolTable {
     /// Stack of scopes, with the current scope at the top
     scopes: Vec<HashMap<String, Symbol>>,
     /// Type registry for managing types
     type_registry: TypeRegistry,
+    /// Every symbol defined with a span, in definition order
+    declarations: Vec<Symbol>,
+    /// Every recorded use of a declared symbol
+    references: Vec<SymbolReference>,
 }
 
 impl SymbolTable {
@@ -46,6 +84,8 @@ impl SymbolTable {
         Self {
             scopes: vec![HashMap::new()],
             type_registry: TypeRegistry::new(),
+            declarations: Vec::new(),
+            references: Vec::new(),
         }
     }
 
@@ -54,6 +94,8 @@ impl SymbolTable {
         Self {
             scopes: vec![HashMap::new()],
             type_registry,
+            declarations: Vec::new(),
+            references: Vec::new(),
         }
     }
 
@@ -100,16 +142,41 @@ impl SymbolTable {
         let current_scope = self.scopes.last_mut().unwrap();
 
         if current_scope.contains_key(&symbol.name) {
-            return Err(rive_core::Error::Semantic(format!(
-                "Symbol '{}' is already defined in this scope",
-                symbol.name
-            )));
+            let message = format!("Symbol '{}' is already defined in this scope", symbol.name);
+            return Err(match symbol.span {
+                Some(span) => rive_core::Error::SemanticWithSpan(message, span),
+                None => rive_core::Error::Semantic(message),
+            });
         }
 
+        if symbol.span.is_some() {
+            self.declarations.push(symbol.clone());
+        }
         current_scope.insert(symbol.name.clone(), symbol);
         Ok(())
     }
 
+    /// Records a use of `name` at `span`, if it resolves to a symbol declared in the source.
+    pub fn record_reference(&mut self, name: &str, span: Span) {
+        if let Some(declaration) = self.lookup(name).and_then(|symbol| symbol.span) {
+            self.references.push(SymbolReference {
+                name: name.to_string(),
+                span,
+                declaration,
+            });
+        }
+    }
+
+    /// Returns every symbol defined with a span, including those whose scope has ended.
+    pub fn declarations(&self) -> &[Symbol] {
+        &self.declarations
+    }
+
+    /// Returns every recorded use of a declared symbol.
+    pub fn references(&self) -> &[SymbolReference] {
+        &self.references
+    }
+
     /// Looks up a symbol by name, searching from the current scope upwards.
     ///
     /// # Arguments
@@ -127,6 +194,15 @@ impl SymbolTable {
         None
     }
 
+    /// Looks up a symbol by name, also returning the depth of the scope it was found in.
+    pub fn lookup_with_depth(&self, name: &str) -> Option<(usize, &Symbol)> {
+        self.scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(depth, scope)| scope.get(name).map(|symbol| (depth, symbol)))
+    }
+
     /// Looks up a symbol by name (mutable version).
     pub fn lookup_mut(&mut self, name: &str) -> Option<&mut Symbol> {
         for scope in self.scopes.iter_mut().rev() {
@@ -210,6 +286,35 @@ mod tests {
         assert!(table.define(symbol2).is_err());
     }
 
+    #[test]
+    fn test_symbol_table_references() {
+        let mut table = SymbolTable::new();
+        let declared = Span::from_range(0, 5);
+        let used = Span::from_range(10, 11);
+
+        table.enter_scope();
+        table
+            .define(Symbol::new("x".to_string(), TypeId::INT, false).with_span(declared))
+            .unwrap();
+        table
+            .define(Symbol::new("y".to_string(), TypeId::INT, false))
+            .unwrap();
+        table.record_reference("x", used);
+        table.record_reference("y", used);
+        table.exit_scope();
+
+        assert_eq!(table.declarations().len(), 1);
+        assert_eq!(table.declarations()[0].name, "x");
+        assert_eq!(
+            table.references(),
+            &[SymbolReference {
+                name: "x".to_string(),
+                span: used,
+                declaration: declared,
+            }]
+        );
+    }
+
     #[test]
     fn test_symbol_table_undefined() {
         let table = SymbolTable::new();
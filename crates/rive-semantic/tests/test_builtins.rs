@@ -0,0 +1,215 @@
+//! Built-in function (`panic`, `assert`, `assert_eq`, `todo`, `unreachable`, `args`, `exit`) tests.
+
+use rive_core::Result;
+use rive_lexer::tokenize;
+use rive_parser::parse;
+use rive_semantic::analyze_with_registry;
+
+/// Helper to compile and analyze Rive source code.
+fn compile_and_analyze(source: &str) -> Result<()> {
+    let tokens = tokenize(source)?;
+    let (ast, type_registry) = parse(&tokens)?;
+    analyze_with_registry(&ast, type_registry)?;
+    Ok(())
+}
+
+/// Helper to check if source should fail.
+fn should_fail(source: &str) -> bool {
+    compile_and_analyze(source).is_err()
+}
+
+#[test]
+fn test_builtin_statements() {
+    let source = r#"
+        fun main() {
+            let x = 1
+            assert(x > 0)
+            assert(x > 0, "x must be positive")
+            assert_eq(x, 1)
+            assert_eq("a", "a")
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_diverging_builtins_in_when_arms() {
+    let source = r#"
+        fun main() {
+            let x = 2
+            let a: Int = when x {
+                1 -> 10
+                _ -> panic("unexpected value")
+            }
+            let b: Text = when x {
+                1 -> unreachable()
+                2 -> "two"
+                _ -> todo("other values")
+            }
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "Diverging builtins should fit any arm type"
+    );
+}
+
+#[test]
+fn test_diverging_builtin_as_value() {
+    let source = r#"
+        fun later(): Int {
+            return todo()
+        }
+
+        fun main() {
+            let x: Int = later()
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_panic_requires_text_message() {
+    let source = r#"
+        fun main() {
+            panic(42)
+        }
+    "#;
+    assert!(should_fail(source), "panic message must be Text");
+}
+
+#[test]
+fn test_panic_requires_message() {
+    let source = r#"
+        fun main() {
+            panic()
+        }
+    "#;
+    assert!(should_fail(source), "panic requires a message");
+}
+
+#[test]
+fn test_assert_requires_bool() {
+    let source = r#"
+        fun main() {
+            assert(1)
+        }
+    "#;
+    assert!(should_fail(source), "assert condition must be Bool");
+}
+
+#[test]
+fn test_assert_eq_type_mismatch_fails() {
+    let source = r#"
+        fun main() {
+            assert_eq(1, "one")
+        }
+    "#;
+    assert!(should_fail(source), "assert_eq operands must match");
+}
+
+#[test]
+fn test_todo_too_many_arguments_fails() {
+    let source = r#"
+        fun main() {
+            todo("a", "b")
+        }
+    "#;
+    assert!(should_fail(source), "todo accepts at most one message");
+}
+
+#[test]
+fn test_args_and_exit() {
+    let source = r#"
+        fun main(): Int {
+            let arguments: List<Text> = args()
+            if arguments.is_empty() {
+                exit(2)
+            }
+            let code: Int = when arguments.len() {
+                1 -> 0
+                _ -> exit(1)
+            }
+            return code
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_user_functions_shadow_diagnostic_builtins() {
+    let source = r#"
+        fun assert(x: Int): Int {
+            return x * 2
+        }
+
+        fun main() {
+            let doubled: Int = assert(5)
+            fun panic(message: Text): Text = message
+            print(panic("not a panic"))
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "user functions named like builtins should be called instead"
+    );
+}
+
+#[test]
+fn test_exit_requires_int_status() {
+    let source = r#"
+        fun main() {
+            exit("failure")
+        }
+    "#;
+    assert!(should_fail(source), "exit status must be Int");
+}
+
+#[test]
+fn test_args_takes_no_arguments() {
+    let source = r#"
+        fun main() {
+            let a = args(1)
+        }
+    "#;
+    assert!(should_fail(source), "args accepts no arguments");
+}
+
+#[test]
+fn test_user_functions_shadow_process_builtins() {
+    let source = r#"
+        fun exit(code: Int): Int {
+            return code + 1
+        }
+
+        fun args(): Int = 0
+
+        fun main() {
+            let next: Int = exit(3)
+            let arguments: Int = args()
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "user functions named exit and args should be called instead"
+    );
+}
+
+#[test]
+fn test_main_cannot_take_parameters() {
+    let source = r#"
+        fun main(arguments: List<Text>) {
+        }
+    "#;
+    assert!(should_fail(source), "main must not take parameters");
+}
+
+#[test]
+fn test_main_must_return_unit_or_int() {
+    let source = r#"
+        fun main(): Text {
+            return "done"
+        }
+    "#;
+    assert!(should_fail(source), "main must return Unit or Int");
+}
@@ -0,0 +1,166 @@
+//! Destructuring tests for tuple patterns in `let` and `for`.
+
+use rive_core::Result;
+use rive_lexer::tokenize;
+use rive_parser::parse;
+use rive_semantic::analyze_with_registry;
+
+/// Helper to compile and analyze Rive source code.
+fn compile_and_analyze(source: &str) -> Result<()> {
+    let tokens = tokenize(source)?;
+    let (ast, type_registry) = parse(&tokens)?;
+    analyze_with_registry(&ast, type_registry)?;
+    Ok(())
+}
+
+/// Helper to check if source should fail.
+fn should_fail(source: &str) -> bool {
+    compile_and_analyze(source).is_err()
+}
+
+#[test]
+fn test_destructure_function_result() {
+    let source = r#"
+        fun pair(): (Int, Text) {
+            return (1, "one")
+        }
+
+        fun main() {
+            let (n, name) = pair()
+            let total: Int = n + 1
+            let label: Text = name
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "tuple elements should bind with their own types"
+    );
+}
+
+#[test]
+fn test_destructure_nested_tuple() {
+    let source = r#"
+        fun main() {
+            let t = (1, (2.5, true))
+            let (x, (y, _)) = t
+            let a: Int = x
+            let b: Float = y
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_destructure_element_type_mismatch() {
+    let source = r#"
+        fun main() {
+            let (a, b) = (1, "two")
+            let c: Int = b
+        }
+    "#;
+    assert!(should_fail(source), "second element is Text, not Int");
+}
+
+#[test]
+fn test_destructure_arity_mismatch() {
+    let source = r#"
+        fun main() {
+            let (a, b) = (1, 2, 3)
+        }
+    "#;
+    assert!(should_fail(source), "pattern arity must match the tuple");
+}
+
+#[test]
+fn test_destructure_non_tuple() {
+    let source = r#"
+        fun main() {
+            let (a, b) = 5
+        }
+    "#;
+    assert!(should_fail(source), "Int cannot be destructured");
+}
+
+#[test]
+fn test_destructure_mutability_is_per_binding() {
+    let source = r#"
+        fun main() {
+            let (mut a, b) = (1, 2)
+            a = a + b
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+
+    let source = r#"
+        fun main() {
+            let (mut a, b) = (1, 2)
+            b = a
+        }
+    "#;
+    assert!(should_fail(source), "b is immutable");
+}
+
+#[test]
+fn test_destructure_duplicate_binding() {
+    let source = r#"
+        fun main() {
+            let (a, a) = (1, 2)
+        }
+    "#;
+    assert!(
+        should_fail(source),
+        "a pattern cannot bind the same name twice"
+    );
+}
+
+#[test]
+fn test_for_tuple_pattern_over_array() {
+    let source = r#"
+        fun main() {
+            let pairs = [(1, "a"), (2, "b")]
+            for (i, name) in pairs {
+                let n: Int = i
+                let s: Text = name
+            }
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_for_tuple_pattern_over_map() {
+    let source = r#"
+        fun main() {
+            let scores = {"a": 1, "b": 2}
+            for (key, score) in scores {
+                let k: Text = key
+                let s: Int = score
+            }
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_for_tuple_pattern_over_range() {
+    let source = r#"
+        fun main() {
+            for (a, b) in 0..3 {
+                print(a)
+            }
+        }
+    "#;
+    assert!(should_fail(source), "range elements are Int, not tuples");
+}
+
+#[test]
+fn test_for_over_non_iterable() {
+    let source = r#"
+        fun main() {
+            for x in 5 {
+                print(x)
+            }
+        }
+    "#;
+    assert!(should_fail(source), "Int is not iterable");
+}
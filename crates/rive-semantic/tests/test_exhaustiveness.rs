@@ -0,0 +1,260 @@
+//! `when` exhaustiveness and unreachable arm tests.
+
+use rive_core::{Result, Warning};
+use rive_lexer::tokenize;
+use rive_parser::parse;
+use rive_semantic::analyze_with_warnings;
+
+/// Helper to compile and analyze Rive source code, returning the warnings.
+fn compile_and_analyze(source: &str) -> Result<Vec<Warning>> {
+    let tokens = tokenize(source)?;
+    let (ast, type_registry) = parse(&tokens)?;
+    let (_, warnings) = analyze_with_warnings(&ast, type_registry)?;
+    Ok(warnings)
+}
+
+/// Helper to check if source should fail.
+fn should_fail(source: &str) -> bool {
+    compile_and_analyze(source).is_err()
+}
+
+/// Helper to count the unreachable arms reported for source that must analyze.
+fn unreachable_arms(source: &str) -> usize {
+    compile_and_analyze(source)
+        .expect("analysis should succeed")
+        .iter()
+        .filter(|warning| warning.message.contains("Unreachable pattern"))
+        .count()
+}
+
+#[test]
+fn test_arm_after_wildcard_is_unreachable() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            when x {
+                _ -> print("any")
+                1 -> print("one")
+            }
+        }
+    "#;
+    assert_eq!(unreachable_arms(source), 1);
+}
+
+#[test]
+fn test_duplicate_literal_is_unreachable() {
+    let source = r#"
+        fun main() {
+            let s = "a"
+            when s {
+                "a" -> print(1)
+                "b" -> print(2)
+                "a" -> print(3)
+                _ -> print(4)
+            }
+        }
+    "#;
+    assert_eq!(unreachable_arms(source), 1);
+}
+
+#[test]
+fn test_bool_arm_after_both_values_is_unreachable() {
+    let source = r#"
+        fun main() {
+            let b = true
+            when b {
+                true -> print(1)
+                false -> print(2)
+                _ -> print(3)
+            }
+        }
+    "#;
+    assert_eq!(unreachable_arms(source), 1);
+}
+
+#[test]
+fn test_value_inside_earlier_range_is_unreachable() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            when x {
+                in 1..10 -> print(1)
+                5 -> print(2)
+                in 0..=20 -> print(3)
+                _ -> print(4)
+            }
+        }
+    "#;
+    assert_eq!(unreachable_arms(source), 1);
+}
+
+#[test]
+fn test_range_covered_by_adjacent_ranges_is_unreachable() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            when x {
+                in 0..5 -> print(1)
+                in 5..=9 -> print(2)
+                in 0..10 -> print(3)
+                _ -> print(4)
+            }
+        }
+    "#;
+    assert_eq!(unreachable_arms(source), 1);
+}
+
+#[test]
+fn test_empty_range_is_unreachable() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            when x {
+                in 5..5 -> print(1)
+                _ -> print(2)
+            }
+        }
+    "#;
+    assert_eq!(unreachable_arms(source), 1);
+}
+
+#[test]
+fn test_null_and_binding_cover_nullable() {
+    let source = r#"
+        fun main() {
+            let x: Int? = 5
+            let y: Int = when x {
+                null -> 0
+                n -> 1
+            }
+        }
+    "#;
+    assert_eq!(unreachable_arms(source), 0);
+}
+
+#[test]
+fn test_second_null_arm_is_unreachable() {
+    let source = r#"
+        fun main() {
+            let x: Int? = 5
+            when x {
+                null -> print(0)
+                1 -> print(1)
+                null -> print(2)
+                _ -> print(3)
+            }
+        }
+    "#;
+    assert_eq!(unreachable_arms(source), 1);
+}
+
+#[test]
+fn test_nullable_without_null_arm_is_not_exhaustive() {
+    let source = r#"
+        fun main() {
+            let x: Bool? = true
+            let y: Int = when x {
+                true -> 1
+                false -> 0
+            }
+        }
+    "#;
+    assert!(should_fail(source), "null is not covered");
+}
+
+#[test]
+fn test_nullable_bool_all_cases_exhaustive() {
+    let source = r#"
+        fun main() {
+            let x: Bool? = true
+            let y: Int = when x {
+                true -> 1
+                false -> 0
+                null -> -1
+            }
+        }
+    "#;
+    assert_eq!(unreachable_arms(source), 0);
+}
+
+#[test]
+fn test_text_without_wildcard_is_not_exhaustive() {
+    let source = r#"
+        fun main() {
+            let s = "a"
+            let n: Int = when s {
+                "a" -> 1
+                "b" -> 2
+            }
+        }
+    "#;
+    assert!(should_fail(source));
+}
+
+#[test]
+fn test_tuple_of_bools_exhaustive() {
+    let source = r#"
+        fun main() {
+            let pair = (true, false)
+            let n: Int = when pair {
+                (true, _) -> 1
+                (false, true) -> 2
+                (false, false) -> 3
+            }
+        }
+    "#;
+    assert_eq!(unreachable_arms(source), 0);
+}
+
+#[test]
+fn test_tuple_missing_case_is_not_exhaustive() {
+    let source = r#"
+        fun main() {
+            let pair = (true, false)
+            let n: Int = when pair {
+                (true, _) -> 1
+                (false, true) -> 2
+            }
+        }
+    "#;
+    assert!(should_fail(source));
+}
+
+#[test]
+fn test_guarded_arm_does_not_cover_later_arms() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            when x {
+                n if n > 0 -> print(1)
+                n -> print(2)
+            }
+        }
+    "#;
+    assert_eq!(unreachable_arms(source), 0);
+}
+
+#[test]
+fn test_or_pattern_alternatives_cover_later_arm() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            when x {
+                1 | 2 -> print(1)
+                2 -> print(2)
+                _ -> print(3)
+            }
+        }
+    "#;
+    assert_eq!(unreachable_arms(source), 1);
+}
+
+#[test]
+fn test_warning_points_at_pattern() {
+    let source =
+        "fun main() {\n    when 1 {\n        _ -> print(1)\n        2 -> print(2)\n    }\n}";
+    let warnings = compile_and_analyze(source).expect("analysis should succeed");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].span.start.line, 4);
+    assert_eq!(warnings[0].span.start.column, 9);
+}
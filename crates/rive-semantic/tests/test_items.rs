@@ -0,0 +1,250 @@
+//! Local function, global declaration and test function tests.
+
+use rive_core::{Error, Result};
+use rive_lexer::tokenize;
+use rive_parser::parse;
+use rive_semantic::analyze_with_registry;
+
+/// Helper to compile and analyze Rive source code.
+fn compile_and_analyze(source: &str) -> Result<()> {
+    let tokens = tokenize(source)?;
+    let (ast, type_registry) = parse(&tokens)?;
+    analyze_with_registry(&ast, type_registry)?;
+    Ok(())
+}
+
+/// Helper to get the error message for source that should fail.
+fn error_message(source: &str) -> String {
+    match compile_and_analyze(source) {
+        Err(Error::SemanticWithSpan(message, _) | Error::Semantic(message)) => message,
+        other => panic!("Expected a semantic error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_local_functions() {
+    let source = r#"
+        fun main() {
+            let base = 10
+
+            fun add(x: Int): Int = x + base
+
+            fun fact(n: Int): Int {
+                if n <= 1 {
+                    return 1
+                }
+                return n * fact(n - 1)
+            }
+
+            let total: Int = add(1) + fact(3)
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_local_function_cannot_capture_mutable_variable() {
+    let source = r#"
+        fun main() {
+            let mut count = 0
+            fun bump(): Int = count + 1
+        }
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Local function 'bump' cannot capture mutable variable 'count'"
+    );
+}
+
+#[test]
+fn test_local_function_used_before_declaration() {
+    let source = r#"
+        fun main() {
+            let x = helper()
+            fun helper(): Int = 1
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_err());
+}
+
+#[test]
+fn test_local_function_return_type() {
+    let source = r#"
+        fun main() {
+            fun name(): Int {
+                return true
+            }
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_err());
+}
+
+#[test]
+fn test_globals() {
+    let source = r#"
+        const LIMIT = 10
+        const HALF: Int = LIMIT / 2
+        let offset = compute(4)
+
+        fun compute(x: Int): Int = x + LIMIT
+
+        fun main() {
+            let total: Int = HALF + offset
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_global_used_before_declaration() {
+    let source = r#"
+        let a = b + 1
+        let b = 2
+        fun main() {}
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Global 'b' is used before its declaration"
+    );
+}
+
+#[test]
+fn test_global_initialization_cycle_through_functions() {
+    let source = r#"
+        let a = f()
+        fun f(): Int { a }
+        fun main() {}
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Initializer of global 'a' depends on itself through 'f'"
+    );
+
+    let source = r#"
+        let a = g()
+        fun f(): Int = a + 1
+        fun g(): Int = f()
+        fun main() {}
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Initializer of global 'a' depends on itself through 'g' -> 'f'"
+    );
+}
+
+#[test]
+fn test_global_used_before_initialization_through_function() {
+    let source = r#"
+        let a = f()
+        let b = 2
+        fun f(): Int = b * 2
+        fun main() {}
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Global 'b' is used before its initialization by global 'a' through 'f'"
+    );
+
+    // Functions may read globals initialized before the caller
+    let source = r#"
+        let b = 2
+        let a = f()
+        fun f(): Int = b * 2
+        fun main() {
+            print(a, f())
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_const_requires_constant_expression() {
+    let source = r#"
+        fun compute(): Int = 1
+        const A = compute()
+        fun main() {}
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Constant 'A' must be initialized with a constant expression"
+    );
+
+    let source = r#"
+        let a = 1
+        const B = a + 1
+        fun main() {}
+    "#;
+    assert!(compile_and_analyze(source).is_err());
+}
+
+#[test]
+fn test_globals_are_immutable() {
+    let source = r#"
+        let count = 0
+        fun main() {
+            count = 1
+        }
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Cannot assign to immutable variable 'count'"
+    );
+}
+
+#[test]
+fn test_global_type_mismatch() {
+    let source = r#"
+        let limit: Int = "ten"
+        fun main() {}
+    "#;
+    assert!(compile_and_analyze(source).is_err());
+}
+
+#[test]
+fn test_test_functions() {
+    let source = r#"
+        fun main() {}
+
+        @test
+        fun adds() {
+            assert_eq(1 + 1, 2)
+        }
+
+        @test(panics = "boom")
+        fun explodes() {
+            panic("boom")
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_test_function_signatures() {
+    let with_parameter = r#"
+        fun main() {}
+
+        @test
+        fun adds(x: Int) {}
+    "#;
+    assert_eq!(
+        error_message(with_parameter),
+        "Test 'adds' cannot take parameters"
+    );
+
+    let with_result = r#"
+        fun main() {}
+
+        @test
+        fun adds(): Int = 2
+    "#;
+    assert_eq!(
+        error_message(with_result),
+        "Test 'adds' must return Unit, found 'Int'"
+    );
+
+    let main_test = r#"
+        @test
+        fun main() {}
+    "#;
+    assert_eq!(error_message(main_test), "'main' cannot be a test");
+}
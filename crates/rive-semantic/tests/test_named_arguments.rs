@@ -0,0 +1,168 @@
+//! Default parameter value and named argument tests.
+
+use rive_core::{Error, Result};
+use rive_lexer::tokenize;
+use rive_parser::parse;
+use rive_semantic::analyze_with_registry;
+
+/// Helper to compile and analyze Rive source code.
+fn compile_and_analyze(source: &str) -> Result<()> {
+    let tokens = tokenize(source)?;
+    let (ast, type_registry) = parse(&tokens)?;
+    analyze_with_registry(&ast, type_registry)?;
+    Ok(())
+}
+
+/// Helper to get the error message for source that should fail.
+fn error_message(source: &str) -> String {
+    match compile_and_analyze(source) {
+        Err(Error::SemanticWithSpan(message, _)) => message,
+        other => panic!("Expected a semantic error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_defaults_and_named_arguments() {
+    let source = r#"
+        fun connect(host: Int, port: Int = 8080, retries: Int = 3): Int = host + port + retries
+
+        fun main() {
+            let a = connect(1)
+            let b = connect(1, 2)
+            let c = connect(1, retries = 5)
+            let d = connect(host = 1, retries = 5, port = 2)
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_default_may_call_functions() {
+    let source = r#"
+        fun default_port(): Int = 8080
+        fun connect(port: Int = default_port()): Int = port
+
+        fun main() {
+            let p = connect()
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_default_type_mismatch() {
+    let source = r#"
+        fun connect(port: Int = true) {}
+        fun main() {}
+    "#;
+    assert!(error_message(source).contains("Default value of parameter 'port' type mismatch"));
+}
+
+#[test]
+fn test_default_cannot_see_other_parameters() {
+    let source = r#"
+        fun range(start: Int, end: Int = start) {}
+        fun main() {}
+    "#;
+    assert!(compile_and_analyze(source).is_err());
+}
+
+#[test]
+fn test_required_parameter_after_default() {
+    let source = r#"
+        fun connect(port: Int = 8080, host: Int) {}
+        fun main() {}
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Parameter 'host' must have a default value because it follows a parameter with one"
+    );
+}
+
+#[test]
+fn test_unknown_named_argument() {
+    let source = r#"
+        fun connect(host: Int, port: Int = 8080) {}
+        fun main() {
+            connect(1, prot = 9000)
+        }
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Function 'connect' has no parameter named 'prot'"
+    );
+}
+
+#[test]
+fn test_duplicate_named_argument() {
+    let source = r#"
+        fun connect(host: Int, port: Int = 8080) {}
+        fun main() {
+            connect(1, port = 1, port = 2)
+        }
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Argument 'port' is provided more than once"
+    );
+
+    let source = r#"
+        fun connect(host: Int, port: Int = 8080) {}
+        fun main() {
+            connect(1, host = 2)
+        }
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Argument 'host' is provided more than once"
+    );
+}
+
+#[test]
+fn test_missing_arguments() {
+    let source = r#"
+        fun connect(host: Int, port: Int = 8080) {}
+        fun main() {
+            connect()
+        }
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Function 'connect' expects 1 to 2 arguments, but 0 were provided"
+    );
+
+    let source = r#"
+        fun connect(host: Int, port: Int = 8080) {}
+        fun main() {
+            connect(port = 1)
+        }
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Missing argument for parameter 'host' of function 'connect'"
+    );
+}
+
+#[test]
+fn test_named_argument_type_mismatch() {
+    let source = r#"
+        fun connect(host: Int, port: Int = 8080) {}
+        fun main() {
+            connect(1, port = "x")
+        }
+    "#;
+    assert!(error_message(source).contains("Argument 'port' type mismatch"));
+}
+
+#[test]
+fn test_builtins_reject_named_arguments() {
+    let source = r#"
+        fun main() {
+            print(value = 1)
+        }
+    "#;
+    assert_eq!(
+        error_message(source),
+        "'print' does not accept named arguments"
+    );
+}
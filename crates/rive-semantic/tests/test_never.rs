@@ -0,0 +1,247 @@
+//! Never type tests for diverging expressions (`return`, `break`, `continue`).
+
+use rive_core::{Result, Warning};
+use rive_lexer::tokenize;
+use rive_parser::parse;
+use rive_semantic::analyze_with_warnings;
+
+/// Helper to compile and analyze Rive source code, returning the warnings.
+fn compile_and_analyze(source: &str) -> Result<Vec<Warning>> {
+    let tokens = tokenize(source)?;
+    let (ast, type_registry) = parse(&tokens)?;
+    let (_, warnings) = analyze_with_warnings(&ast, type_registry)?;
+    Ok(warnings)
+}
+
+/// Helper to check if source should fail.
+fn should_fail(source: &str) -> bool {
+    compile_and_analyze(source).is_err()
+}
+
+#[test]
+fn test_elvis_with_return() {
+    let source = r#"
+        fun get(x: Int?): Int {
+            let value: Int = x ?: return 0
+            return value
+        }
+
+        fun main() {}
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "x ?: return should have the non-null type"
+    );
+}
+
+#[test]
+fn test_return_expression_checks_return_type() {
+    let source = r#"
+        fun get(x: Int?): Int {
+            let value = x ?: return "none"
+            return value
+        }
+
+        fun main() {}
+    "#;
+    assert!(should_fail(source), "return value must match return type");
+}
+
+#[test]
+fn test_when_arm_with_return() {
+    let source = r#"
+        fun classify(x: Int): Text {
+            let label: Text = when x {
+                0 -> return "zero"
+                _ -> "other"
+            }
+            return label
+        }
+
+        fun main() {}
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_break_and_continue_in_when_arms() {
+    let source = r#"
+        fun main() {
+            let mut i = 0
+            while i < 10 {
+                i = i + 1
+                let v: Int = when i {
+                    3 -> continue
+                    8 -> break
+                    _ -> i
+                }
+            }
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_break_expression_outside_loop_fails() {
+    let source = r#"
+        fun main() {
+            let x: Int? = null
+            let y = x ?: break
+        }
+    "#;
+    assert!(should_fail(source), "break must be inside a loop");
+}
+
+#[test]
+fn test_if_branch_with_return_unifies() {
+    let source = r#"
+        fun clamp(x: Int): Int {
+            let y: Int = if x > 10 { return 10 } else { x }
+            let z: Int = if x < 0 { 0 } else { return x }
+            return y + z
+        }
+
+        fun main() {}
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "Diverging if branches should take the other branch's type"
+    );
+}
+
+#[test]
+fn test_if_branch_mismatch_still_fails() {
+    let source = r#"
+        fun main() {
+            let y = if true { 1 } else { "one" }
+        }
+    "#;
+    assert!(should_fail(source));
+}
+
+#[test]
+fn test_never_return_type() {
+    let source = r#"
+        fun fail(message: Text): Never {
+            panic(message)
+        }
+
+        fun main() {
+            let x: Int = when 1 {
+                1 -> 10
+                _ -> fail("unexpected")
+            }
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "Calls to Never functions should fit any type"
+    );
+}
+
+#[test]
+fn test_missing_return_value_fails() {
+    let unit_tail = r#"
+        fun k(x: Int): Int { print(x) }
+
+        fun main() {}
+    "#;
+    assert!(should_fail(unit_tail), "print(x) is not an Int");
+
+    let if_without_else = r#"
+        fun sign(x: Int): Int {
+            if x > 0 { return 1 }
+        }
+
+        fun main() {}
+    "#;
+    assert!(
+        should_fail(if_without_else),
+        "The function can end without a value"
+    );
+
+    let empty_body = r#"
+        fun zero(): Int {}
+
+        fun main() {}
+    "#;
+    assert!(should_fail(empty_body));
+}
+
+#[test]
+fn test_diverging_function_bodies() {
+    let source = r#"
+        fun next(x: Int): Int { x + 1 }
+
+        fun call(x: Int): Int { next(x) }
+
+        fun sign(x: Int): Int {
+            if x >= 0 { return 1 } else { panic("negative") }
+        }
+
+        fun first(x: Int): Int {
+            loop {
+                if x > 0 { return x }
+            }
+        }
+
+        fun main() {
+            print(call(1), sign(2), first(3))
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "Bodies that end in a value or never complete should be accepted"
+    );
+}
+
+#[test]
+fn test_unreachable_code_warning() {
+    let source = r#"
+        fun get(x: Int): Int {
+            return x
+            print("after return")
+            x
+        }
+
+        fun main() {
+            let mut i = 0
+            while i < 3 {
+                i += 1
+                continue
+                print("after continue")
+            }
+            if i > 5 { return } else { panic("small") }
+            print("after if")
+            exit(0)
+        }
+
+        fun stop() {
+            exit(1)
+            print("after exit")
+        }
+    "#;
+    let warnings = compile_and_analyze(source).expect("analysis should succeed");
+    let unreachable = warnings
+        .iter()
+        .filter(|warning| warning.message.contains("Unreachable code"))
+        .count();
+    assert_eq!(unreachable, 4, "{warnings:?}");
+}
+
+#[test]
+fn test_reachable_code_has_no_warning() {
+    let source = r#"
+        fun get(x: Int): Int {
+            if x > 0 { return x }
+            print("reachable")
+            0
+        }
+
+        fun main() {
+            print(get(1))
+        }
+    "#;
+    let warnings = compile_and_analyze(source).expect("analysis should succeed");
+    assert!(warnings.is_empty(), "{warnings:?}");
+}
@@ -0,0 +1,153 @@
+//! Non-null assertion (`!!`) and null binding (`if let`/`while let`) semantic analysis tests.
+
+use rive_core::Result;
+use rive_lexer::tokenize;
+use rive_parser::parse;
+use rive_semantic::analyze_with_registry;
+
+/// Helper to compile and analyze Rive source code.
+fn compile_and_analyze(source: &str) -> Result<()> {
+    let tokens = tokenize(source)?;
+    let (ast, type_registry) = parse(&tokens)?;
+    analyze_with_registry(&ast, type_registry)?;
+    Ok(())
+}
+
+/// Helper to check if source should fail.
+fn should_fail(source: &str) -> bool {
+    compile_and_analyze(source).is_err()
+}
+
+#[test]
+fn test_non_null_assert_unwraps_nullable() {
+    let source = r#"
+        fun main() {
+            let x: Int? = 42
+            let y: Int = x!!
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "x!! should have the non-null type Int"
+    );
+}
+
+#[test]
+fn test_non_null_assert_on_non_nullable_is_allowed() {
+    let source = r#"
+        fun main() {
+            let x: Int = 42
+            let y: Int = x!!
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "Redundant !! on a non-nullable value is valid"
+    );
+}
+
+#[test]
+fn test_non_null_assert_on_null_literal_fails() {
+    let source = r#"
+        fun main() {
+            let x: Int = null!!
+        }
+    "#;
+    assert!(should_fail(source), "null!! always fails");
+}
+
+#[test]
+fn test_if_let_binds_non_null_value() {
+    let source = r#"
+        fun main() {
+            let x: Int? = 42
+            if let v = x {
+                let y: Int = v + 1
+            }
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "if let should bind the inner type"
+    );
+}
+
+#[test]
+fn test_if_let_binding_is_scoped_to_then_block() {
+    let source = r#"
+        fun main() {
+            let x: Int? = 42
+            if let v = x {
+                print(v)
+            } else {
+                print(v)
+            }
+        }
+    "#;
+    assert!(
+        should_fail(source),
+        "if let binding should not be visible in the else block"
+    );
+}
+
+#[test]
+fn test_else_if_let() {
+    let source = r#"
+        fun main() {
+            let x: Int? = null
+            let y: Text? = "hi"
+            if let a = x {
+                print(a)
+            } else if let b = y {
+                print(b)
+            }
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "else if let should work"
+    );
+}
+
+#[test]
+fn test_if_let_requires_nullable_value() {
+    let source = r#"
+        fun main() {
+            let x: Int = 42
+            if let v = x {
+                print(v)
+            }
+        }
+    "#;
+    assert!(
+        should_fail(source),
+        "if let on a non-nullable value should fail"
+    );
+}
+
+#[test]
+fn test_while_let() {
+    let source = r#"
+        fun main() {
+            let mut cur: Int? = 3
+            while let x = cur {
+                print(x)
+                cur = null
+            }
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok(), "while let should work");
+}
+
+#[test]
+fn test_let_binding_outside_condition_fails() {
+    let source = r#"
+        fun main() {
+            let x: Int? = 42
+            if (let v = x) == true {
+                print(v)
+            }
+        }
+    "#;
+    assert!(should_fail(source));
+}
@@ -0,0 +1,125 @@
+//! Compound assignment, bitwise and shift operator tests.
+
+use rive_core::Result;
+use rive_lexer::tokenize;
+use rive_parser::parse;
+use rive_semantic::analyze_with_registry;
+
+/// Helper to compile and analyze Rive source code.
+fn compile_and_analyze(source: &str) -> Result<()> {
+    let tokens = tokenize(source)?;
+    let (ast, type_registry) = parse(&tokens)?;
+    analyze_with_registry(&ast, type_registry)?;
+    Ok(())
+}
+
+/// Helper to check if source should fail.
+fn should_fail(source: &str) -> bool {
+    compile_and_analyze(source).is_err()
+}
+
+#[test]
+fn test_bitwise_operators_on_int() {
+    let source = r#"
+        fun main() {
+            let a = 12
+            let b = 10
+            let c: Int = (a & b) | (a ^ b)
+            let d: Int = (1 << 4) >> 2
+            let e: Int = ~a
+            let f: Bool = a & b == 8
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_bitwise_operators_reject_float() {
+    let source = r#"
+        fun main() {
+            let x = 1.5 & 2.5
+        }
+    "#;
+    let err = compile_and_analyze(source).unwrap_err();
+    assert!(err.to_string().contains("Bitwise operators require 'Int'"));
+}
+
+#[test]
+fn test_bitwise_operators_reject_bool() {
+    let source = r#"
+        fun main() {
+            let x = true | false
+        }
+    "#;
+    assert!(should_fail(source));
+}
+
+#[test]
+fn test_bitwise_not_rejects_bool() {
+    let source = r#"
+        fun main() {
+            let x = ~true
+        }
+    "#;
+    assert!(should_fail(source));
+}
+
+#[test]
+fn test_shift_by_float_fails() {
+    let source = r#"
+        fun main() {
+            let x = 1 << 2.0
+        }
+    "#;
+    assert!(should_fail(source));
+}
+
+#[test]
+fn test_compound_assignment() {
+    let source = r#"
+        fun main() {
+            let mut x = 10
+            x += 5
+            x -= 3
+            x *= 2
+            x /= 4
+            x %= 5
+            let mut y = 1.5
+            y *= 2.0
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_compound_assignment_requires_mutable() {
+    let source = r#"
+        fun main() {
+            let x = 10
+            x += 1
+        }
+    "#;
+    let err = compile_and_analyze(source).unwrap_err();
+    assert!(err.to_string().contains("immutable"));
+}
+
+#[test]
+fn test_compound_assignment_type_mismatch() {
+    let source = r#"
+        fun main() {
+            let mut x = 10
+            x += 1.5
+        }
+    "#;
+    assert!(should_fail(source));
+}
+
+#[test]
+fn test_compound_assignment_undefined_variable() {
+    let source = r#"
+        fun main() {
+            x += 1
+        }
+    "#;
+    assert!(should_fail(source));
+}
@@ -0,0 +1,235 @@
+//! `when` pattern tests: bindings, tuples, or-patterns, guards and nullable values.
+
+use rive_core::Result;
+use rive_lexer::tokenize;
+use rive_parser::parse;
+use rive_semantic::analyze_with_registry;
+
+/// Helper to compile and analyze Rive source code.
+fn compile_and_analyze(source: &str) -> Result<()> {
+    let tokens = tokenize(source)?;
+    let (ast, type_registry) = parse(&tokens)?;
+    analyze_with_registry(&ast, type_registry)?;
+    Ok(())
+}
+
+/// Helper to check if source should fail.
+fn should_fail(source: &str) -> bool {
+    compile_and_analyze(source).is_err()
+}
+
+#[test]
+fn test_binding_pattern_is_exhaustive() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            let y: Int = when x {
+                0 -> 0
+                n -> n * 2
+            }
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "a binding matches every value"
+    );
+}
+
+#[test]
+fn test_binding_is_scoped_to_arm() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            when x {
+                n -> print(n)
+            }
+            print(n)
+        }
+    "#;
+    assert!(should_fail(source), "n is only defined inside its arm");
+}
+
+#[test]
+fn test_guard_must_be_bool() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            when x {
+                n if n -> print(n)
+                _ -> print(0)
+            }
+        }
+    "#;
+    assert!(should_fail(source), "guard conditions must be Bool");
+}
+
+#[test]
+fn test_guarded_binding_is_not_exhaustive() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            let y = when x {
+                n if n > 3 -> n
+            }
+        }
+    "#;
+    assert!(should_fail(source), "a guarded arm may not match");
+}
+
+#[test]
+fn test_or_pattern() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            let small: Bool = when x {
+                1 | 2 | 3 -> true
+                _ -> false
+            }
+            let b = true
+            let s: Int = when b {
+                true | false -> 1
+            }
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_or_pattern_must_bind_same_names() {
+    let source = r#"
+        fun main() {
+            let t = (1, 2)
+            when t {
+                (a, 0) | (0, b) -> print(0)
+                _ -> print(1)
+            }
+        }
+    "#;
+    assert!(should_fail(source), "alternatives bind different names");
+}
+
+#[test]
+fn test_tuple_pattern() {
+    let source = r#"
+        fun main() {
+            let t = (1, ("a", true))
+            let n: Int = when t {
+                (0, (_, true)) -> 0
+                (k, ("a", _)) -> k
+                (k, (label, flag)) -> k + label.len()
+            }
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_tuple_pattern_type_mismatch() {
+    let source = r#"
+        fun main() {
+            let t = (1, 2)
+            when t {
+                (0, "a") -> print(0)
+                _ -> print(1)
+            }
+        }
+    "#;
+    assert!(should_fail(source), "second element is Int, not Text");
+}
+
+#[test]
+fn test_tuple_pattern_arity_mismatch() {
+    let source = r#"
+        fun main() {
+            let t = (1, 2)
+            when t {
+                (a, b, c) -> print(a)
+                _ -> print(1)
+            }
+        }
+    "#;
+    assert!(should_fail(source), "pattern arity must match the tuple");
+}
+
+#[test]
+fn test_nullable_patterns() {
+    let source = r#"
+        fun describe(x: Int?): Int {
+            return when x {
+                null -> -1
+                0 -> 0
+                in 1..10 -> 1
+                n -> 2
+            }
+        }
+
+        fun main() {
+            let pair: (Int?, Text?) = (1, "a")
+            when pair {
+                (null, null) -> print(0)
+                (5, name) -> print(name)
+                _ -> print(1)
+            }
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_binding_after_null_arm_is_non_null() {
+    let source = r#"
+        fun pick(n: Int?): Int {
+            when n {
+                null -> 0
+                m if m > 2 -> m * 10
+                m -> m + 1
+            }
+        }
+
+        fun main() {}
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "Bindings after a null arm should have type Int"
+    );
+
+    let without_null_arm = r#"
+        fun pick(n: Int?): Int {
+            when n {
+                m -> m + 1
+            }
+        }
+
+        fun main() {}
+    "#;
+    assert!(should_fail(without_null_arm), "m may still be null");
+
+    let guarded_null_arm = r#"
+        fun pick(n: Int?, skip: Bool): Int {
+            when n {
+                null if skip -> 0
+                m -> m + 1
+            }
+        }
+
+        fun main() {}
+    "#;
+    assert!(
+        should_fail(guarded_null_arm),
+        "A guarded null arm doesn't catch every null"
+    );
+}
+
+#[test]
+fn test_null_pattern_on_non_nullable_fails() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            when x {
+                null -> print(0)
+                _ -> print(1)
+            }
+        }
+    "#;
+    assert!(should_fail(source), "Int can never be null");
+}
@@ -0,0 +1,230 @@
+//! Result type (`ok`/`err` constructors, `?` propagation, ok/err patterns) semantic analysis tests.
+
+use rive_core::Result;
+use rive_lexer::tokenize;
+use rive_parser::parse;
+use rive_semantic::analyze_with_registry;
+
+/// Helper to compile and analyze Rive source code.
+fn compile_and_analyze(source: &str) -> Result<()> {
+    let tokens = tokenize(source)?;
+    let (ast, type_registry) = parse(&tokens)?;
+    analyze_with_registry(&ast, type_registry)?;
+    Ok(())
+}
+
+/// Helper to check if source should fail.
+fn should_fail(source: &str) -> bool {
+    compile_and_analyze(source).is_err()
+}
+
+#[test]
+fn test_result_constructors_with_annotation() {
+    let source = r#"
+        fun main() {
+            let a: Result<Int, Text> = ok(1)
+            let b: Result<Int, Text> = err("bad")
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "ok/err should take their type from the annotation"
+    );
+}
+
+#[test]
+fn test_result_constructor_type_mismatch_fails() {
+    let source = r#"
+        fun main() {
+            let a: Result<Int, Text> = ok("one")
+        }
+    "#;
+    assert!(should_fail(source), "ok payload must match the ok type");
+}
+
+#[test]
+fn test_result_constructor_without_context_fails() {
+    let source = r#"
+        fun main() {
+            let a = ok(1)
+        }
+    "#;
+    assert!(should_fail(source), "ok(1) alone cannot infer the err type");
+}
+
+#[test]
+fn test_result_return_and_implicit_return() {
+    let source = r#"
+        fun check(x: Int): Result<Int, Text> {
+            if x < 0 {
+                return err("negative")
+            }
+            ok(x)
+        }
+
+        fun main() {
+            let r = check(1)
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_result_constructors_in_branches() {
+    let source = r#"
+        fun parse(c: Bool): Result<Int, Text> {
+            if c { ok(1) } else { err("bad") }
+        }
+
+        fun main() {
+            let c = true
+            let a: Result<Int, Text> = if c { ok(1) } else { err("x") }
+            let b: Result<Int, Text> = when a {
+                ok(v) -> ok(v + 1)
+                err(e) -> { err(e) }
+            }
+            let d: Result<Int, Text> = when {
+                c -> ok(2)
+                else -> b
+            }
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "if and when branches should take their type from the context"
+    );
+}
+
+#[test]
+fn test_result_constructor_in_branch_type_mismatch_fails() {
+    let source = r#"
+        fun main() {
+            let c = true
+            let a: Result<Int, Text> = if c { ok("one") } else { err("x") }
+        }
+    "#;
+    assert!(
+        should_fail(source),
+        "ok value must match the branch's expected type"
+    );
+}
+
+#[test]
+fn test_propagate_unwraps_ok_type() {
+    let source = r#"
+        fun check(x: Int): Result<Int, Text> {
+            return ok(x)
+        }
+
+        fun twice(x: Int): Result<Int, Text> {
+            let v: Int = check(x)?
+            return ok(v * 2)
+        }
+
+        fun main() {
+            let r = twice(1)
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "? should produce the ok type"
+    );
+}
+
+#[test]
+fn test_propagate_outside_result_function_fails() {
+    let source = r#"
+        fun check(x: Int): Result<Int, Text> {
+            return ok(x)
+        }
+
+        fun main() {
+            let v = check(1)?
+        }
+    "#;
+    assert!(
+        should_fail(source),
+        "? requires the enclosing function to return a Result"
+    );
+}
+
+#[test]
+fn test_propagate_err_type_mismatch_fails() {
+    let source = r#"
+        fun check(x: Int): Result<Int, Text> {
+            return ok(x)
+        }
+
+        fun twice(x: Int): Result<Int, Int> {
+            let v = check(x)?
+            return ok(v)
+        }
+
+        fun main() {}
+    "#;
+    assert!(should_fail(source), "? requires compatible err types");
+}
+
+#[test]
+fn test_propagate_on_non_result_fails() {
+    let source = r#"
+        fun twice(x: Int): Result<Int, Text> {
+            let v = x?
+            return ok(v)
+        }
+
+        fun main() {}
+    "#;
+    assert!(should_fail(source), "? requires a Result value");
+}
+
+#[test]
+fn test_when_ok_err_patterns() {
+    let source = r#"
+        fun main() {
+            let r: Result<Int, Text> = ok(1)
+            let v: Int = when r {
+                ok(n) -> n
+                err(_) -> 0
+            }
+            when r {
+                ok(n) -> print(n)
+                err(e) -> print(e)
+            }
+        }
+    "#;
+    assert!(
+        compile_and_analyze(source).is_ok(),
+        "ok/err patterns should bind their payloads"
+    );
+}
+
+#[test]
+fn test_when_result_missing_arm_fails() {
+    let source = r#"
+        fun main() {
+            let r: Result<Int, Text> = ok(1)
+            let v: Int = when r {
+                ok(n) -> n
+            }
+        }
+    "#;
+    assert!(should_fail(source), "when on a Result must cover err");
+}
+
+#[test]
+fn test_ok_pattern_on_non_result_fails() {
+    let source = r#"
+        fun main() {
+            let x = 1
+            when x {
+                ok(n) -> print(n)
+                _ -> print(0)
+            }
+        }
+    "#;
+    assert!(
+        should_fail(source),
+        "ok pattern requires a Result scrutinee"
+    );
+}
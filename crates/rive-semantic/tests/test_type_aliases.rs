@@ -0,0 +1,138 @@
+//! Type alias and newtype tests.
+
+use rive_core::{Error, Result};
+use rive_lexer::tokenize;
+use rive_parser::parse;
+use rive_semantic::analyze_with_registry;
+
+/// Helper to compile and analyze Rive source code.
+fn compile_and_analyze(source: &str) -> Result<()> {
+    let tokens = tokenize(source)?;
+    let (ast, type_registry) = parse(&tokens)?;
+    analyze_with_registry(&ast, type_registry)?;
+    Ok(())
+}
+
+/// Helper to get the error message for source that should fail.
+fn error_message(source: &str) -> String {
+    match compile_and_analyze(source) {
+        Err(Error::SemanticWithSpan(message, _) | Error::Semantic(message)) => message,
+        other => panic!("Expected a semantic error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_alias_is_interchangeable_with_target() {
+    let source = r#"
+        type UserId = Int
+        type Grid = List<List<Int>>
+
+        fun next(id: UserId): UserId = id + 1
+
+        fun main() {
+            let id: UserId = 41
+            let plain: Int = next(id) * 2
+            let other: UserId = -plain & 7
+            let grid: Grid = List(List(1, 2), List(3, 4))
+            let size: Int = grid.len()
+            let label = when id {
+                41 -> "yes"
+                _ -> "no"
+            }
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_alias_named_in_diagnostics() {
+    let source = r#"
+        type UserId = Int
+        fun main() {
+            let id: UserId = "admin"
+        }
+    "#;
+    assert!(
+        error_message(source).contains("expected 'UserId', found 'Text'"),
+        "got: {}",
+        error_message(source)
+    );
+
+    let source = r#"
+        type Name = Text
+        fun main() {
+            let name: Name? = 1
+        }
+    "#;
+    assert!(error_message(source).contains("expected 'Name?'"));
+}
+
+#[test]
+fn test_newtype_is_distinct() {
+    let source = r#"
+        newtype Meters = Float
+
+        fun total(a: Meters, b: Meters): Meters = a + b
+
+        fun main() {
+            let walked: Meters = total(Meters(1.5), Meters(2.0))
+            let back: Meters = -walked
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+
+    let source = r#"
+        newtype Meters = Float
+        fun main() {
+            let walked: Meters = 1.5
+        }
+    "#;
+    assert!(error_message(source).contains("expected 'Meters', found 'Float'"));
+
+    let source = r#"
+        newtype Meters = Float
+        fun main() {
+            let walked: Float = Meters(1.5)
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_err());
+
+    let source = r#"
+        newtype Meters = Float
+        newtype Seconds = Float
+        fun main() {
+            let walked = Meters(1.5) + Seconds(2.0)
+        }
+    "#;
+    assert!(error_message(source).contains("Binary operation type mismatch"));
+}
+
+#[test]
+fn test_newtype_constructor_arguments() {
+    let source = r#"
+        newtype Meters = Float
+        fun main() {
+            let walked = Meters("far")
+        }
+    "#;
+    assert!(error_message(source).contains("Argument 1 type mismatch"));
+
+    let source = r#"
+        newtype Meters = Float
+        fun main() {
+            let walked = Meters(1.0, 2.0)
+        }
+    "#;
+    assert_eq!(
+        error_message(source),
+        "Type 'Meters' expects 1 argument, but 2 were provided"
+    );
+
+    let source = r#"
+        type UserId = Int
+        fun main() {
+            let id = UserId(1)
+        }
+    "#;
+    assert_eq!(error_message(source), "Undefined function 'UserId'");
+}
@@ -0,0 +1,107 @@
+//! Subject-less `when` tests: condition chains with an `else` arm.
+
+use rive_core::Result;
+use rive_lexer::tokenize;
+use rive_parser::parse;
+use rive_semantic::analyze_with_registry;
+
+/// Helper to compile and analyze Rive source code.
+fn compile_and_analyze(source: &str) -> Result<()> {
+    let tokens = tokenize(source)?;
+    let (ast, type_registry) = parse(&tokens)?;
+    analyze_with_registry(&ast, type_registry)?;
+    Ok(())
+}
+
+/// Helper to check if source should fail.
+fn should_fail(source: &str) -> bool {
+    compile_and_analyze(source).is_err()
+}
+
+#[test]
+fn test_when_chain_as_value() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            let s: Text = when {
+                x < 0 -> "neg"
+                x == 0 -> "zero"
+                else -> "pos"
+            }
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_when_chain_as_value_requires_else() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            let s = when {
+                x < 0 -> "neg"
+                x == 0 -> "zero"
+            }
+        }
+    "#;
+    let err = compile_and_analyze(source).unwrap_err();
+    assert!(err.to_string().contains("'else' arm"), "got: {err}");
+}
+
+#[test]
+fn test_when_chain_statement_without_else() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            when {
+                x > 3 -> print("big")
+                x > 1 -> print(x)
+            }
+        }
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_when_chain_arm_types_must_match() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            let s = when {
+                x < 0 -> "neg"
+                else -> 1
+            }
+        }
+    "#;
+    assert!(should_fail(source));
+}
+
+#[test]
+fn test_when_chain_diverging_arm() {
+    let source = r#"
+        fun check(x: Int): Int {
+            let y = when {
+                x < 0 -> return 0
+                else -> x * 2
+            }
+            return y
+        }
+
+        fun main() {}
+    "#;
+    assert!(compile_and_analyze(source).is_ok());
+}
+
+#[test]
+fn test_when_chain_condition_must_be_bool() {
+    let source = r#"
+        fun main() {
+            let x = 5
+            let s = when {
+                x -> 1
+                else -> 2
+            }
+        }
+    "#;
+    assert!(should_fail(source));
+}
@@ -1,7 +1,9 @@
 //! Configuration file handling for Rive projects.
 
+use crate::profile::Profile;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
@@ -9,6 +11,9 @@ use std::path::Path;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub package: Package,
+    /// Build profiles from `[profile.<name>]` tables
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profile: BTreeMap<String, Profile>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,18 +38,77 @@ impl Config {
                 version: "0.1.0".to_string(),
                 edition: default_edition(),
             },
+            profile: BTreeMap::new(),
         }
     }
 
     /// Loads configuration from a rive.toml file.
     ///
     /// # Errors
-    /// Returns an error if the file cannot be read or parsed.
+    /// Returns an error if the file cannot be read or parsed, or if a build
+    /// profile is invalid.
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
         let contents = fs::read_to_string(path.as_ref())
             .with_context(|| format!("Failed to read {}", path.as_ref().display()))?;
 
-        toml::from_str(&contents).with_context(|| "Failed to parse rive.toml")
+        let config: Self =
+            toml::from_str(&contents).with_context(|| "Failed to parse rive.toml")?;
+        for name in config.profile.keys() {
+            config.resolve_profile(name)?;
+        }
+        Ok(config)
+    }
+
+    /// Returns the settings of a build profile with inherited and default
+    /// settings filled in.
+    ///
+    /// `dev` and `release` always exist; custom profiles must be declared and
+    /// inherit from `dev` unless they name another profile.
+    ///
+    /// # Errors
+    /// Returns an error if the profile is undefined, inherits from an
+    /// undefined profile or from itself, or has an invalid setting.
+    pub fn resolve_profile(&self, name: &str) -> Result<Profile> {
+        self.resolve_profile_chain(name, &mut Vec::new())
+    }
+
+    /// Resolves a profile, tracking the profiles being resolved to detect cycles.
+    fn resolve_profile_chain<'a>(
+        &'a self,
+        name: &'a str,
+        chain: &mut Vec<&'a str>,
+    ) -> Result<Profile> {
+        if chain.contains(&name) {
+            anyhow::bail!("Profile '{name}' inherits from itself");
+        }
+        chain.push(name);
+
+        let declared = self.profile.get(name);
+        let base = match name {
+            "dev" => Profile::dev(),
+            "release" => Profile::release(),
+            _ => {
+                let declared = declared
+                    .with_context(|| format!("Profile '{name}' is not defined in rive.toml"))?;
+                let parent = declared.inherits.as_deref().unwrap_or("dev");
+                self.resolve_profile_chain(parent, chain)?
+            }
+        };
+
+        let Some(declared) = declared else {
+            return Ok(base);
+        };
+        if declared.inherits.is_some() && Profile::BUILTIN.contains(&name) {
+            anyhow::bail!("Built-in profile '{name}' cannot inherit from another profile");
+        }
+        if let Some(level) = declared.opt_level
+            && !level.is_valid()
+        {
+            anyhow::bail!(
+                "Invalid opt-level {level} in profile '{name}', expected 0 to 3, \"s\" or \"z\""
+            );
+        }
+        Ok(declared.merged_over(&base))
     }
 
     /// Saves configuration to a rive.toml file.
@@ -3,5 +3,7 @@
 //! This crate provides shared utilities used across multiple compiler components.
 
 pub mod config;
+pub mod profile;
 
 pub use config::Config;
+pub use profile::{Lto, LtoMode, OptLevel, PanicStrategy, Profile, SizeLevel};
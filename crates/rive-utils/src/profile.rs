@@ -0,0 +1,150 @@
+//! Build profiles declared in `[profile.<name>]` tables of rive.toml.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Settings of a build profile.
+///
+/// Unset fields fall back to the profile named by `inherits`, or to the
+/// defaults of the built-in `dev` and `release` profiles.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Profile {
+    /// Profile this one starts from; only custom profiles may set it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inherits: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opt_level: Option<OptLevel>,
+    /// Whether to include debug info
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debug: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overflow_checks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lto: Option<Lto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub panic: Option<PanicStrategy>,
+}
+
+impl Profile {
+    /// Names of the profiles that exist without being declared.
+    pub const BUILTIN: [&'static str; 2] = ["dev", "release"];
+
+    /// Default settings of the `dev` profile.
+    #[must_use]
+    pub fn dev() -> Self {
+        Self {
+            inherits: None,
+            opt_level: Some(OptLevel::Level(0)),
+            debug: Some(true),
+            overflow_checks: Some(true),
+            lto: Some(Lto::Enabled(false)),
+            panic: Some(PanicStrategy::Unwind),
+        }
+    }
+
+    /// Default settings of the `release` profile.
+    #[must_use]
+    pub fn release() -> Self {
+        Self {
+            inherits: None,
+            opt_level: Some(OptLevel::Level(3)),
+            debug: Some(false),
+            overflow_checks: Some(false),
+            lto: Some(Lto::Enabled(false)),
+            panic: Some(PanicStrategy::Unwind),
+        }
+    }
+
+    /// Returns these settings with unset fields taken from `base`.
+    #[must_use]
+    pub fn merged_over(&self, base: &Self) -> Self {
+        Self {
+            inherits: self.inherits.clone(),
+            opt_level: self.opt_level.or(base.opt_level),
+            debug: self.debug.or(base.debug),
+            overflow_checks: self.overflow_checks.or(base.overflow_checks),
+            lto: self.lto.or(base.lto),
+            panic: self.panic.or(base.panic),
+        }
+    }
+}
+
+/// Optimization level: `0` to `3`, or `"s"`/`"z"` to optimize for size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OptLevel {
+    Level(u8),
+    Size(SizeLevel),
+}
+
+/// How aggressively to optimize for size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SizeLevel {
+    S,
+    Z,
+}
+
+impl OptLevel {
+    /// Returns true if the level is one rustc accepts.
+    pub fn is_valid(self) -> bool {
+        !matches!(self, Self::Level(level) if level > 3)
+    }
+}
+
+impl fmt::Display for OptLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Level(level) => write!(f, "{level}"),
+            Self::Size(SizeLevel::S) => write!(f, "s"),
+            Self::Size(SizeLevel::Z) => write!(f, "z"),
+        }
+    }
+}
+
+/// Link-time optimization: `true`/`false`, or `"thin"`, `"fat"` or `"off"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Lto {
+    Enabled(bool),
+    Mode(LtoMode),
+}
+
+/// Kind of link-time optimization named by a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LtoMode {
+    Thin,
+    Fat,
+    /// No link-time optimization at all, not even within the crate
+    Off,
+}
+
+impl fmt::Display for Lto {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Enabled(enabled) => write!(f, "{enabled}"),
+            Self::Mode(LtoMode::Thin) => write!(f, "thin"),
+            Self::Mode(LtoMode::Fat) => write!(f, "fat"),
+            Self::Mode(LtoMode::Off) => write!(f, "off"),
+        }
+    }
+}
+
+/// What a panic does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PanicStrategy {
+    Unwind,
+    Abort,
+}
+
+impl fmt::Display for PanicStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unwind => write!(f, "unwind"),
+            Self::Abort => write!(f, "abort"),
+        }
+    }
+}
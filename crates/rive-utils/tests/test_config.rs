@@ -54,3 +54,129 @@ fn test_config_find() {
     assert_eq!(found_config.package.name, "find-test");
     assert_eq!(found_dir, project_dir);
 }
+
+#[test]
+fn test_config_profiles() {
+    use rive_utils::{Lto, OptLevel, PanicStrategy, Profile, SizeLevel};
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("rive.toml");
+    fs::write(
+        &config_path,
+        r#"
+[package]
+name = "profiles"
+version = "0.1.0"
+
+[profile.release]
+panic = "abort"
+
+[profile.small]
+inherits = "release"
+opt-level = "z"
+lto = true
+
+[profile.checked]
+overflow-checks = true
+opt-level = 2
+"#,
+    )
+    .unwrap();
+
+    let config = Config::load(&config_path).unwrap();
+    assert_eq!(config.profile.len(), 3);
+
+    let dev = config.resolve_profile("dev").unwrap();
+    assert_eq!(dev, Profile::dev());
+
+    let release = config.resolve_profile("release").unwrap();
+    assert_eq!(release.panic, Some(PanicStrategy::Abort));
+    assert_eq!(release.opt_level, Some(OptLevel::Level(3)));
+
+    let small = config.resolve_profile("small").unwrap();
+    assert_eq!(small.opt_level, Some(OptLevel::Size(SizeLevel::Z)));
+    assert_eq!(small.lto, Some(Lto::Enabled(true)));
+    assert_eq!(small.panic, Some(PanicStrategy::Abort));
+    assert_eq!(small.debug, Some(false));
+
+    // Custom profiles inherit from dev by default
+    let checked = config.resolve_profile("checked").unwrap();
+    assert_eq!(checked.opt_level, Some(OptLevel::Level(2)));
+    assert_eq!(checked.debug, Some(true));
+
+    let err = config.resolve_profile("missing").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Profile 'missing' is not defined in rive.toml"
+    );
+
+    // Profiles survive a save and load
+    config.save(&config_path).unwrap();
+    let reloaded = Config::load(&config_path).unwrap();
+    assert_eq!(reloaded.profile, config.profile);
+}
+
+#[test]
+fn test_config_lto_forms() {
+    use rive_utils::{Lto, LtoMode};
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("rive.toml");
+    let lto = |value: &str| {
+        fs::write(
+            &config_path,
+            format!(
+                "[package]\nname = \"p\"\nversion = \"0.1.0\"\n\n[profile.release]\nlto = {value}\n"
+            ),
+        )
+        .unwrap();
+        let config = Config::load(&config_path).map_err(|err| format!("{err:#}"))?;
+        Ok::<_, String>(config.resolve_profile("release").unwrap().lto)
+    };
+
+    assert_eq!(lto("true"), Ok(Some(Lto::Enabled(true))));
+    assert_eq!(lto("false"), Ok(Some(Lto::Enabled(false))));
+    assert_eq!(lto("\"thin\""), Ok(Some(Lto::Mode(LtoMode::Thin))));
+    assert_eq!(lto("\"fat\""), Ok(Some(Lto::Mode(LtoMode::Fat))));
+    assert_eq!(lto("\"off\""), Ok(Some(Lto::Mode(LtoMode::Off))));
+    assert!(lto("\"full\"").is_err());
+
+    // A string mode is saved back as a string
+    lto("\"thin\"").unwrap();
+    let config = Config::load(&config_path).unwrap();
+    config.save(&config_path).unwrap();
+    let saved = fs::read_to_string(&config_path).unwrap();
+    assert!(saved.contains("lto = \"thin\""), "{saved}");
+}
+
+#[test]
+fn test_config_invalid_profiles() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("rive.toml");
+    let load = |profiles: &str| {
+        fs::write(
+            &config_path,
+            format!("[package]\nname = \"p\"\nversion = \"0.1.0\"\n\n{profiles}"),
+        )
+        .unwrap();
+        Config::load(&config_path).map_err(|err| format!("{err:#}"))
+    };
+
+    let err = load("[profile.fast]\nopt-level = 4\n").unwrap_err();
+    assert!(
+        err.contains("Invalid opt-level 4 in profile 'fast'"),
+        "{err}"
+    );
+
+    let err = load("[profile.a]\ninherits = \"b\"\n[profile.b]\ninherits = \"a\"\n").unwrap_err();
+    assert!(err.contains("inherits from itself"), "{err}");
+
+    let err = load("[profile.fast]\ninherits = \"turbo\"\n").unwrap_err();
+    assert!(err.contains("Profile 'turbo' is not defined"), "{err}");
+
+    let err = load("[profile.release]\ninherits = \"dev\"\n").unwrap_err();
+    assert!(err.contains("cannot inherit"), "{err}");
+
+    let err = load("[profile.dev]\nspeed = 3\n").unwrap_err();
+    assert!(err.contains("unknown field"), "{err}");
+}